@@ -94,8 +94,9 @@ class TextElement {
     var textStyle: TextStyle
     var depth: UInt?
     var style_link: TextStyle?
+    var opacity: Float
     
-    init(id_chain: [UInt64], clipping_ids: [[UInt64]], content: String, transform: [Float], size_x: Float, size_y: Float, textStyle: TextStyle, depth: UInt?, style_link: TextStyle?) {
+    init(id_chain: [UInt64], clipping_ids: [[UInt64]], content: String, transform: [Float], size_x: Float, size_y: Float, textStyle: TextStyle, depth: UInt?, style_link: TextStyle?, opacity: Float) {
         self.id_chain = id_chain
         self.clipping_ids = clipping_ids
         self.content = content
@@ -105,11 +106,12 @@ class TextElement {
         self.textStyle = textStyle
         self.depth = depth
         self.style_link = style_link
+        self.opacity = opacity
     }
     
     static func makeDefault(id_chain: [UInt64], clipping_ids: [[UInt64]]) -> TextElement {
         let defaultTextStyle = TextStyle(font: PaxFont.makeDefault(), fill: Color(.black), alignmentMultiline: .leading, alignment: .topLeading, font_size: 5.0, underline: false)
-        return TextElement(id_chain: id_chain, clipping_ids: clipping_ids, content: "", transform: [1,0,0,1,0,0], size_x: 0.0, size_y: 0.0, textStyle: defaultTextStyle, depth: nil, style_link: nil)
+        return TextElement(id_chain: id_chain, clipping_ids: clipping_ids, content: "", transform: [1,0,0,1,0,0], size_x: 0.0, size_y: 0.0, textStyle: defaultTextStyle, depth: nil, style_link: nil, opacity: 1.0)
     }
     
     func applyPatch(patch: TextUpdatePatch) {
@@ -130,6 +132,9 @@ class TextElement {
         if let depth = patch.depth {
             self.depth = depth
         }
+        if let opacity = patch.opacity {
+            self.opacity = opacity
+        }
         
         // Apply new TextStyle
         if let styleBuffer = patch.style {
@@ -198,12 +203,16 @@ func toAlignment(horizontalAlignment: TextAlignHorizontal, verticalAlignment: Te
 class ImageLoadPatch {
     var id_chain: [UInt64]
     var path: String?
+    var size_x: Float?
+    var size_y: Float?
     
     init(fb:FlxbReference) {
         self.id_chain = fb["id_chain"]!.asVector!.makeIterator().map({ fb in
             fb.asUInt64!
         })
         self.path = fb["path"]?.asString
+        self.size_x = fb["size_x"]?.asFloat
+        self.size_y = fb["size_y"]?.asFloat
     }
 }
 
@@ -278,6 +287,7 @@ class TextUpdatePatch {
     var depth: UInt?
     var style: TextStyleMessage?
     var style_link: TextStyleMessage?
+    var opacity: Float?
 
     init(fb: FlxbReference) {
         self.id_chain = fb["id_chain"]!.asVector!.makeIterator().map({ fb in
@@ -290,6 +300,7 @@ class TextUpdatePatch {
         self.size_x = fb["size_x"]?.asFloat
         self.size_y = fb["size_y"]?.asFloat
         self.depth = fb["depth"]?.asUInt
+        self.opacity = fb["opacity"]?.asFloat
         
         if let styleBuffer = fb["style"], !styleBuffer.isNull {
             self.style = TextStyleMessage(styleBuffer)
@@ -166,6 +166,7 @@ struct PaxView: View {
                 .frame(width: CGFloat(textElement.size_x), height: CGFloat(textElement.size_y), alignment: textElement.textStyle.alignment)
                 .position(x: CGFloat(textElement.size_x / 2.0), y: CGFloat(textElement.size_y / 2.0))
                 .transformEffect(transform)
+                .opacity(Double(textElement.opacity))
                 .textSelection(.enabled)
 
 //
@@ -462,9 +463,18 @@ struct PaxView: View {
         override func scrollWheel(with event: NSEvent){
             let deltaX = event.scrollingDeltaX
             let deltaY = -event.scrollingDeltaY
-            let x = event.locationInWindow.x;
-            let y = event.locationInWindow.y;
-            let json = String(format: "{\"Scroll\": {\"x\": %f, \"y\": %f, \"delta_x\": %f, \"delta_y\": %f} }", x, y, deltaX, deltaY);
+            //`locationInWindow` is measured from the window's bottom-left, whereas the engine measures from this view's top-left
+            let location = self.convert(event.locationInWindow, from: nil)
+            let x = location.x
+            let y = self.isFlipped ? location.y : self.bounds.height - location.y
+            //Trackpads report precise (pixel) deltas; mouse wheels report line deltas
+            let deltaMode = event.hasPreciseScrollingDeltas ? "Pixel" : "Line"
+            var modifiers: [String] = []
+            if event.modifierFlags.contains(.shift) { modifiers.append("\"Shift\"") }
+            if event.modifierFlags.contains(.control) { modifiers.append("\"Control\"") }
+            if event.modifierFlags.contains(.option) { modifiers.append("\"Alt\"") }
+            if event.modifierFlags.contains(.command) { modifiers.append("\"Command\"") }
+            let json = String(format: "{\"Scroll\": {\"x\": %f, \"y\": %f, \"delta_x\": %f, \"delta_y\": %f, \"delta_mode\": \"%@\", \"modifiers\": [%@]} }", x, y, deltaX, deltaY, deltaMode, modifiers.joined(separator: ","));
             let buffer = try! FlexBufferBuilder.fromJSON(json)
 
             //Send `Scroll` interrupt
@@ -476,5 +486,38 @@ struct PaxView: View {
             })
         }
 
+        //Receive key events, e.g. for the debug overlay's shortcut (Ctrl + Shift + D)
+        override var acceptsFirstResponder: Bool { return true }
+
+        override func viewDidMoveToWindow() {
+            super.viewDidMoveToWindow()
+            self.window?.makeFirstResponder(self)
+        }
+
+        override func keyDown(with event: NSEvent) {
+            var modifiers: [FlxbValue] = []
+            if event.modifierFlags.contains(.shift) { modifiers.append("Shift") }
+            if event.modifierFlags.contains(.control) { modifiers.append("Control") }
+            if event.modifierFlags.contains(.option) { modifiers.append("Alt") }
+            if event.modifierFlags.contains(.command) { modifiers.append("Command") }
+
+            //Unlike the web's `KeyboardEvent.code`, `code` is the macOS virtual key code of the key pressed
+            let buffer = try! FlexBufferBuilder.encode(
+                [ "KeyDown": [
+                    "key": event.charactersIgnoringModifiers ?? "",
+                    "code": String(event.keyCode),
+                    "modifiers": FlxbValueVector.init(values: modifiers),
+                    "is_repeat": event.isARepeat,
+                ] as FlxbValueMap ] as FlxbValueMap)
+
+            //Send `KeyDown` interrupt
+            buffer.data.withUnsafeBytes({ptr in
+                var ffi_container = InterruptBuffer( data_ptr: ptr.baseAddress!, length: UInt64(ptr.count) )
+                withUnsafePointer(to: &ffi_container) {ffi_container_ptr in
+                    pax_interrupt(PaxEngineContainer.paxEngineContainer!, ffi_container_ptr)
+                }
+            })
+        }
+
     }
 }
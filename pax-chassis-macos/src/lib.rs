@@ -6,6 +6,7 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::ffi::c_void;
 use std::rc::Rc;
+use std::time::Instant;
 
 use std::mem::{transmute, ManuallyDrop};
 use std::os::raw::c_char;
@@ -24,13 +25,17 @@ use pax_core::{InstanceRegistry, PaxEngine};
 //Note that any types exposed by pax_message must ALSO be added to `paxchassismacos.h`
 //in order to be visible to Swift
 pub use pax_message::*;
-use pax_runtime_api::{ArgsClick, ArgsScroll, ModifierKey, MouseButton, MouseEventArgs};
+use pax_runtime_api::{
+    ArgsClick, ArgsKeyDown, ArgsScroll, KeyboardEventArgs, ModifierKey, MouseButton,
+    MouseEventArgs, ScrollDeltaMode,
+};
 
 /// Container data structure for PaxEngine, aggregated to support passing across C bridge
 #[repr(C)] //Exposed to Swift via paxchassismacos.h
 pub struct PaxEngineContainer {
     _engine: *mut PaxEngine<CoreGraphicsContext<'static>>,
-    //NOTE: since that has become a single field, this data structure may be be retired and `*mut PaxEngine` could be passed directly.
+    /// Moment at which the engine was initialized, used to report elapsed time to the engine
+    _start_time: Instant,
 }
 
 /// Allocate an instance of the Pax engine, with a specified root/main component from the loaded `pax_cartridge`.
@@ -56,6 +61,7 @@ pub extern "C" fn pax_init(logger: extern "C" fn(*const c_char)) -> *mut PaxEngi
 
     let container = ManuallyDrop::new(Box::new(PaxEngineContainer {
         _engine: Box::into_raw(ManuallyDrop::into_inner(engine)),
+        _start_time: Instant::now(),
     }));
 
     Box::into_raw(ManuallyDrop::into_inner(container))
@@ -109,6 +115,8 @@ pub extern "C" fn pax_interrupt(
                         mouse: MouseEventArgs {
                             x: args.x,
                             y: args.y,
+                            local_x: args.x,
+                            local_y: args.y,
                             button: MouseButton::from(args.button),
                             modifiers,
                         },
@@ -120,18 +128,48 @@ pub extern "C" fn pax_interrupt(
         }
 
         NativeInterrupt::Scroll(args) => {
-            let prospective_hit = engine.get_focused_element();
+            let prospective_hit = match (args.x, args.y) {
+                (Some(x), Some(y)) => engine.get_topmost_element_beneath_ray((x, y)),
+                _ => engine.get_focused_element(),
+            };
             match prospective_hit {
                 Some(topmost_node) => {
+                    let modifiers = args
+                        .modifiers
+                        .iter()
+                        .map(|x| ModifierKey::from(x))
+                        .collect();
                     let args_scroll = ArgsScroll {
                         delta_x: args.delta_x,
                         delta_y: args.delta_y,
+                        delta_mode: ScrollDeltaMode::from(&args.delta_mode),
+                        modifiers,
                     };
                     topmost_node.dispatch_scroll(args_scroll);
                 }
                 _ => {}
             };
         }
+        NativeInterrupt::KeyDown(args) => {
+            let modifiers = args
+                .modifiers
+                .iter()
+                .map(|x| ModifierKey::from(x))
+                .collect();
+            let args_key_down = ArgsKeyDown {
+                keyboard: KeyboardEventArgs {
+                    key: args.key,
+                    code: args.code,
+                    modifiers,
+                    is_repeat: args.is_repeat,
+                },
+            };
+            if !engine.handle_debug_overlay_shortcut(&args_key_down.keyboard) {
+                if let Some(topmost_node) = engine.get_focused_element() {
+                    topmost_node.dispatch_key_down(args_key_down);
+                }
+            }
+        }
         NativeInterrupt::Image(args) => match args {
             ImageLoadInterruptArgs::Reference(ref_args) => {
                 let ptr = ref_args.image_data as *const u8;
@@ -169,6 +207,8 @@ pub extern "C" fn pax_tick(
     let ctx = unsafe { &mut *will_cast_cgContext };
     let render_context = CoreGraphicsContext::new_y_up(ctx, height as f64, None);
     (*engine).set_viewport_size((width as f64, height as f64));
+    (*engine)
+        .set_seconds_elapsed(unsafe { (*engine_container)._start_time.elapsed() }.as_secs_f64());
 
     let mut render_contexts = HashMap::new();
     render_contexts.insert(format!("{}", 0), render_context);
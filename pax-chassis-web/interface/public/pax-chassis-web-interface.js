@@ -116,6 +116,7 @@ var Pax = (() => {
       this.size_x = jsonMessage["size_x"];
       this.size_y = jsonMessage["size_y"];
       this.transform = jsonMessage["transform"];
+      this.opacity = jsonMessage["opacity"];
       this.depth = jsonMessage["depth"];
       const styleMessage = jsonMessage["style"];
       if (styleMessage) {
@@ -134,6 +135,7 @@ var Pax = (() => {
       this.size_x = 0;
       this.size_y = 0;
       this.transform = [];
+      this.opacity = void 0;
       this.objectManager.returnToPool(TEXT_STYLE, this.style);
       this.style = void 0;
       this.objectManager.returnToPool(TEXT_STYLE, this.style_link);
@@ -654,6 +656,8 @@ var Pax = (() => {
           const deltas = this.objectManager.getFromPool(OBJECT);
           deltas["delta_x"] = deltaX;
           deltas["delta_y"] = deltaY;
+          deltas["delta_mode"] = "Pixel";
+          deltas["modifiers"] = [];
           scrollEvent.Scroll = deltas;
           const scrollEventStringified = JSON.stringify(scrollEvent);
           this.messageList.push(scrollEventStringified);
@@ -775,6 +779,9 @@ var Pax = (() => {
       if (patch.transform != null) {
         leaf.style.transform = packAffineCoeffsIntoMatrix3DString(patch.transform);
       }
+      if (patch.opacity != null) {
+        leaf.style.opacity = String(patch.opacity);
+      }
     }
     textDelete(id_chain) {
       let oldNode = this.textNodes[id_chain];
@@ -1208,6 +1215,16 @@ var Pax = (() => {
       modifiers.push("Command");
     return modifiers;
   }
+  function getScrollDeltaMode(event) {
+    switch (event.deltaMode) {
+      case WheelEvent.DOM_DELTA_LINE:
+        return "Line";
+      case WheelEvent.DOM_DELTA_PAGE:
+        return "Page";
+      default:
+        return "Pixel";
+    }
+  }
   function getMouseButton(event) {
     switch (event.button) {
       case 0:
@@ -1288,6 +1305,17 @@ var Pax = (() => {
         }
       };
       chassis.interrupt(JSON.stringify(event), []);
+      let scrollEvent = {
+        "Scroll": {
+          "x": evt.clientX,
+          "y": evt.clientY,
+          "delta_x": evt.deltaX,
+          "delta_y": evt.deltaY,
+          "delta_mode": getScrollDeltaMode(evt),
+          "modifiers": convertModifiers(evt)
+        }
+      };
+      chassis.interrupt(JSON.stringify(scrollEvent), []);
     }, { "passive": true, "capture": true });
     layer.addEventListener("mousedown", (evt) => {
       let event = {
@@ -1386,6 +1414,7 @@ var Pax = (() => {
       let event = {
         "KeyDown": {
           "key": evt.key,
+          "code": evt.code,
           "modifiers": convertModifiers(evt),
           "is_repeat": evt.repeat
         }
@@ -1396,6 +1425,7 @@ var Pax = (() => {
       let event = {
         "KeyUp": {
           "key": evt.key,
+          "code": evt.code,
           "modifiers": convertModifiers(evt),
           "is_repeat": evt.repeat
         }
@@ -1406,6 +1436,7 @@ var Pax = (() => {
       let event = {
         "KeyPress": {
           "key": evt.key,
+          "code": evt.code,
           "modifiers": convertModifiers(evt),
           "is_repeat": evt.repeat
         }
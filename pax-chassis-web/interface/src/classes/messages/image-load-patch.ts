@@ -1,14 +1,20 @@
 export class ImageLoadPatch {
     public id_chain?: number[];
     public path?: string;
+    public size_x?: number;
+    public size_y?: number;
 
     fromPatch(jsonMessage: any) {
         this.id_chain = jsonMessage["id_chain"];
         this.path = jsonMessage["path"];
+        this.size_x = jsonMessage["size_x"];
+        this.size_y = jsonMessage["size_y"];
     }
 
     cleanUp(){
         this.id_chain = [];
         this.path = '';
+        this.size_x = 0;
+        this.size_y = 0;
     }
 }
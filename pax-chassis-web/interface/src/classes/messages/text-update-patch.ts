@@ -10,6 +10,7 @@ export class TextUpdatePatch {
     public transform?: number[];
     public style?: TextStyle;
     public style_link?: TextStyle;
+    public opacity?: number;
     public depth?: number;
     objectManager: ObjectManager;
 
@@ -23,6 +24,7 @@ export class TextUpdatePatch {
         this.size_x = jsonMessage["size_x"];
         this.size_y = jsonMessage["size_y"];
         this.transform = jsonMessage["transform"];
+        this.opacity = jsonMessage["opacity"];
         this.depth = jsonMessage["depth"];
 
         const styleMessage = jsonMessage["style"];
@@ -44,6 +46,7 @@ export class TextUpdatePatch {
         this.size_x = 0;
         this.size_y = 0;
         this.transform = [];
+        this.opacity = undefined;
         this.objectManager.returnToPool(TEXT_STYLE, this.style);
         this.style = undefined;
         this.objectManager.returnToPool(TEXT_STYLE, this.style_link);
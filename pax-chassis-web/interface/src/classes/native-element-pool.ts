@@ -81,6 +81,12 @@ export class NativeElementPool {
                 deltas['delta_x'] = deltaX;
                 // @ts-ignore
                 deltas['delta_y'] = deltaY;
+                // Scroller deltas are derived from the native (vertical) scroll position, and thus always in pixels,
+                // with no modifier keys; wheel events send their own `Scroll`s (see `setupEventListeners`)
+                // @ts-ignore
+                deltas['delta_mode'] = 'Pixel';
+                // @ts-ignore
+                deltas['modifiers'] = [];
                 // @ts-ignore
                 scrollEvent.Scroll = deltas;
                 const scrollEventStringified = JSON.stringify(scrollEvent);
@@ -221,6 +227,10 @@ export class NativeElementPool {
         if (patch.transform != null) {
             leaf.style.transform = packAffineCoeffsIntoMatrix3DString(patch.transform);
         }
+
+        if (patch.opacity != null) {
+            leaf.style.opacity = String(patch.opacity);
+        }
     }
 
     textDelete(id_chain: number[]) {
@@ -9,6 +9,14 @@ function convertModifiers(event: MouseEvent | KeyboardEvent) {
     return modifiers;
 }
 
+function getScrollDeltaMode(event: WheelEvent) {
+    switch (event.deltaMode) {
+        case WheelEvent.DOM_DELTA_LINE: return 'Line';
+        case WheelEvent.DOM_DELTA_PAGE: return 'Page';
+        default: return 'Pixel';
+    }
+}
+
 function getMouseButton(event: MouseEvent) {
     switch (event.button) {
         case 0: return 'Left';
@@ -94,6 +102,17 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
             }
         };
         chassis.interrupt(JSON.stringify(event), []);
+        let scrollEvent = {
+            "Scroll": {
+                "x": evt.clientX,
+                "y": evt.clientY,
+                "delta_x": evt.deltaX,
+                "delta_y": evt.deltaY,
+                "delta_mode": getScrollDeltaMode(evt),
+                "modifiers": convertModifiers(evt)
+            }
+        };
+        chassis.interrupt(JSON.stringify(scrollEvent), []);
     }, {"passive": true, "capture": true});
     // @ts-ignore
     layer.addEventListener('mousedown', (evt) => {
@@ -203,6 +222,7 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
         let event = {
             "KeyDown": {
                 "key": evt.key,
+                "code": evt.code,
                 "modifiers": convertModifiers(evt),
                 "is_repeat": evt.repeat
             }
@@ -214,6 +234,7 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
         let event = {
             "KeyUp": {
                 "key": evt.key,
+                "code": evt.code,
                 "modifiers": convertModifiers(evt),
                 "is_repeat": evt.repeat
             }
@@ -225,6 +246,7 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
         let event = {
             "KeyPress": {
                 "key": evt.key,
+                "code": evt.code,
                 "modifiers": convertModifiers(evt),
                 "is_repeat": evt.repeat
             }
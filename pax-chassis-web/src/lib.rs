@@ -17,7 +17,7 @@ use pax_runtime_api::{
     ArgsClick, ArgsContextMenu, ArgsDoubleClick, ArgsJab, ArgsKeyDown, ArgsKeyPress, ArgsKeyUp,
     ArgsMouseDown, ArgsMouseMove, ArgsMouseOut, ArgsMouseOver, ArgsMouseUp, ArgsScroll,
     ArgsTouchEnd, ArgsTouchMove, ArgsTouchStart, ArgsWheel, KeyboardEventArgs, ModifierKey,
-    MouseButton, MouseEventArgs, Touch,
+    MouseButton, MouseEventArgs, ScrollDeltaMode, Touch,
 };
 use serde_json;
 
@@ -57,6 +57,8 @@ pub fn wasm_memory() -> JsValue {
 pub struct PaxChassisWeb {
     engine: Rc<RefCell<PaxEngine<WebRenderContext<'static>>>>,
     drawing_contexts: HashMap<String, WebRenderContext<'static>>,
+    /// Timestamp (ms since epoch) at which this chassis was initialized, used to report elapsed time to the engine
+    start_time_ms: f64,
 }
 
 #[wasm_bindgen]
@@ -75,13 +77,14 @@ impl PaxChassisWeb {
             pax_cartridge::instantiate_main_component(Rc::clone(&instance_registry));
         let expression_table = pax_cartridge::instantiate_expression_table();
 
-        let engine = pax_core::PaxEngine::new(
+        let mut engine = pax_core::PaxEngine::new(
             main_component_instance,
             expression_table,
             pax_runtime_api::PlatformSpecificLogger::Web(log_wrapper),
             (width, height),
             instance_registry,
         );
+        engine.set_device_pixel_ratio(window.device_pixel_ratio());
 
         let engine_container: Rc<RefCell<PaxEngine<WebRenderContext>>> =
             Rc::new(RefCell::new(engine));
@@ -89,6 +92,7 @@ impl PaxChassisWeb {
         Self {
             engine: engine_container,
             drawing_contexts: HashMap::new(),
+            start_time_ms: js_sys::Date::now(),
         }
     }
 
@@ -121,7 +125,16 @@ impl PaxChassisWeb {
     }
 
     pub fn send_viewport_update(&mut self, width: f64, height: f64) {
-        self.engine.borrow_mut().set_viewport_size((width, height));
+        let dpr = window().unwrap().device_pixel_ratio();
+        let mut engine = self.engine.borrow_mut();
+        engine.set_device_pixel_ratio(dpr);
+        engine.set_viewport_size((width, height));
+    }
+    /// Outlines every node's bounding box, e.g. `chassis.set_debug_overlay_enabled(true)` from the devtools console
+    pub fn set_debug_overlay_enabled(&mut self, is_debug_overlay_enabled: bool) {
+        self.engine
+            .borrow_mut()
+            .set_debug_overlay_enabled(is_debug_overlay_enabled);
     }
     pub fn remove_context(&mut self, id: String) {
         self.drawing_contexts.remove(&id);
@@ -152,6 +165,8 @@ impl PaxChassisWeb {
                         mouse: MouseEventArgs {
                             x: args.x,
                             y: args.y,
+                            local_x: args.x,
+                            local_y: args.y,
                             button: MouseButton::from(args.button),
                             modifiers: args
                                 .modifiers
@@ -164,11 +179,22 @@ impl PaxChassisWeb {
                 }
             }
             NativeInterrupt::Scroll(args) => {
-                let prospective_hit = (*self.engine).borrow().get_focused_element();
+                let prospective_hit = match (args.x, args.y) {
+                    (Some(x), Some(y)) => (*self.engine)
+                        .borrow()
+                        .get_topmost_element_beneath_ray((x, y)),
+                    _ => (*self.engine).borrow().get_focused_element(),
+                };
                 if let Some(topmost_node) = prospective_hit {
                     let args_scroll = ArgsScroll {
                         delta_x: args.delta_x,
                         delta_y: args.delta_y,
+                        delta_mode: ScrollDeltaMode::from(&args.delta_mode),
+                        modifiers: args
+                            .modifiers
+                            .iter()
+                            .map(|x| ModifierKey::from(x))
+                            .collect(),
                     };
                     topmost_node.dispatch_scroll(args_scroll);
                 }
@@ -219,21 +245,27 @@ impl PaxChassisWeb {
                 }
             }
             NativeInterrupt::KeyDown(args) => {
-                let prospective_hit = (*self.engine).borrow().get_focused_element();
-                if let Some(topmost_node) = prospective_hit {
-                    let modifiers = args
-                        .modifiers
-                        .iter()
-                        .map(|x| ModifierKey::from(x))
-                        .collect();
-                    let args_key_down = ArgsKeyDown {
-                        keyboard: KeyboardEventArgs {
-                            key: args.key,
-                            modifiers,
-                            is_repeat: args.is_repeat,
-                        },
-                    };
-                    topmost_node.dispatch_key_down(args_key_down);
+                let modifiers = args
+                    .modifiers
+                    .iter()
+                    .map(|x| ModifierKey::from(x))
+                    .collect();
+                let args_key_down = ArgsKeyDown {
+                    keyboard: KeyboardEventArgs {
+                        key: args.key,
+                        code: args.code,
+                        modifiers,
+                        is_repeat: args.is_repeat,
+                    },
+                };
+                let is_consumed = (*self.engine)
+                    .borrow_mut()
+                    .handle_debug_overlay_shortcut(&args_key_down.keyboard);
+                if !is_consumed {
+                    let prospective_hit = (*self.engine).borrow().get_focused_element();
+                    if let Some(topmost_node) = prospective_hit {
+                        topmost_node.dispatch_key_down(args_key_down);
+                    }
                 }
             }
             NativeInterrupt::KeyUp(args) => {
@@ -247,6 +279,7 @@ impl PaxChassisWeb {
                     let args_key_up = ArgsKeyUp {
                         keyboard: KeyboardEventArgs {
                             key: args.key,
+                            code: args.code,
                             modifiers,
                             is_repeat: args.is_repeat,
                         },
@@ -265,6 +298,7 @@ impl PaxChassisWeb {
                     let args_key_press = ArgsKeyPress {
                         keyboard: KeyboardEventArgs {
                             key: args.key,
+                            code: args.code,
                             modifiers,
                             is_repeat: args.is_repeat,
                         },
@@ -281,6 +315,8 @@ impl PaxChassisWeb {
                         mouse: MouseEventArgs {
                             x: args.x,
                             y: args.y,
+                            local_x: args.x,
+                            local_y: args.y,
                             button: MouseButton::from(args.button),
                             modifiers: args
                                 .modifiers
@@ -301,6 +337,8 @@ impl PaxChassisWeb {
                         mouse: MouseEventArgs {
                             x: args.x,
                             y: args.y,
+                            local_x: args.x,
+                            local_y: args.y,
                             button: MouseButton::from(args.button),
                             modifiers: args
                                 .modifiers
@@ -341,6 +379,8 @@ impl PaxChassisWeb {
                         mouse: MouseEventArgs {
                             x: args.x,
                             y: args.y,
+                            local_x: args.x,
+                            local_y: args.y,
                             button: MouseButton::from(args.button),
                             modifiers: args
                                 .modifiers
@@ -361,6 +401,8 @@ impl PaxChassisWeb {
                         mouse: MouseEventArgs {
                             x: args.x,
                             y: args.y,
+                            local_x: args.x,
+                            local_y: args.y,
                             button: MouseButton::from(args.button),
                             modifiers: args
                                 .modifiers
@@ -381,6 +423,8 @@ impl PaxChassisWeb {
                         mouse: MouseEventArgs {
                             x: args.x,
                             y: args.y,
+                            local_x: args.x,
+                            local_y: args.y,
                             button: MouseButton::from(args.button),
                             modifiers: args
                                 .modifiers
@@ -401,6 +445,8 @@ impl PaxChassisWeb {
                         mouse: MouseEventArgs {
                             x: args.x,
                             y: args.y,
+                            local_x: args.x,
+                            local_y: args.y,
                             button: MouseButton::from(args.button),
                             modifiers: args
                                 .modifiers
@@ -421,6 +467,8 @@ impl PaxChassisWeb {
                         mouse: MouseEventArgs {
                             x: args.x,
                             y: args.y,
+                            local_x: args.x,
+                            local_y: args.y,
                             button: MouseButton::from(args.button),
                             modifiers: args
                                 .modifiers
@@ -443,6 +491,10 @@ impl PaxChassisWeb {
     }
 
     pub fn tick(&mut self) -> MemorySlice {
+        let seconds_elapsed = (js_sys::Date::now() - self.start_time_ms) / 1000.0;
+        self.engine
+            .borrow_mut()
+            .set_seconds_elapsed(seconds_elapsed);
         let message_queue = self.engine.borrow_mut().tick(&mut self.drawing_contexts);
 
         // Serialize data to a JSON string
@@ -1,12 +1,12 @@
 use clap::{crate_version, App, AppSettings, Arg, ArgMatches};
 use colored::{ColoredString, Colorize};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::{fs, process, thread};
 
-use pax_compiler::{CreateContext, RunContext, RunTarget};
+use pax_compiler::{CreateContext, FormatContext, RunContext, RunTarget};
 mod http;
 
 use signal_hook::consts::{SIGINT, SIGTERM};
@@ -34,6 +34,7 @@ fn main() -> Result<(), ()> {
             perform_cleanup(
                 Arc::clone(&cloned_version_info),
                 Arc::clone(&cloned_process_child_ids),
+                0,
             );
         }
     });
@@ -69,6 +70,81 @@ fn main() -> Result<(), ()> {
         .help("Signal to the compiler to run certain operations in libdev mode, offering certain ergonomic affordances for Pax library developers.")
         .hidden(true); //hidden because this is of negative value to end-users; things are expected to break when invoked outside of the pax monorepo
 
+    #[allow(non_snake_case)]
+    let ARG_BUILD_REPORT = Arg::with_name("build-report")
+        .long("build-report")
+        .takes_value(false)
+        .help("Also write a machine-readable JSON report of the build to `.pax/build-report.json`");
+
+    #[allow(non_snake_case)]
+    let ARG_OUTPUT_DIR = Arg::with_name("output-dir")
+        .short("o")
+        .long("output-dir")
+        .takes_value(true)
+        .help("Directory into which built artifacts are written.  Defaults to `.pax/build/<target>` within the project.");
+
+    #[allow(non_snake_case)]
+    let ARG_OFFLINE = Arg::with_name("offline")
+        .long("offline")
+        .takes_value(false)
+        .help("Never access the network.  Fails if any required package has not already been fetched into `.pax/pkg`");
+
+    #[allow(non_snake_case)]
+    let ARG_VENDOR_DIR = Arg::with_name("vendor-dir")
+        .long("vendor-dir")
+        .takes_value(true)
+        .help("Directory containing a local checkout of each `pax-*` package, e.g. `<vendor-dir>/pax-core`.  Used instead of downloading packages from crates.io.");
+
+    #[allow(non_snake_case)]
+    let ARG_DRY_RUN = Arg::with_name("dry-run")
+        .long("dry-run")
+        .takes_value(false)
+        .help("Parse, compile expressions and generate code, then stop before building the chassis.  A fast check that the project compiles.");
+
+    #[allow(non_snake_case)]
+    let ARG_WASM_SIZE_WARNING_KB = Arg::with_name("wasm-size-warning-kb")
+        .long("wasm-size-warning-kb")
+        .takes_value(true)
+        .help("For web builds, warn if the gzipped `.wasm` bundle exceeds this many kilobytes.  Defaults to 1024.");
+
+    #[allow(non_snake_case)]
+    let ARG_POST_BUILD = Arg::with_name("post-build")
+        .long("post-build")
+        .takes_value(true)
+        .help("Shell command to run from the project directory after a successful build, e.g. to deploy.  `PAX_DIR` and `PAX_OUTPUT_DIR` are set in its environment.");
+
+    #[allow(non_snake_case)]
+    let ARG_MANIFEST_PATH = Arg::with_name("manifest-path")
+        .long("manifest-path")
+        .takes_value(true)
+        .help("Path to the project's `Cargo.toml`, to build a project without `cd`-ing into it.  Defaults to `<path>/Cargo.toml`.");
+
+    #[allow(non_snake_case)]
+    let ARG_SERVER_WORKERS = Arg::with_name("server-workers")
+        .long("server-workers")
+        .takes_value(true)
+        .help("Number of worker threads for the dev server that serves the built project.  Defaults to 2; at least 1 is used.");
+
+    #[allow(non_snake_case)]
+    let ARG_FEATURES = Arg::with_name("features")
+        .long("features")
+        .takes_value(true)
+        .multiple(true)
+        .use_delimiter(true)
+        .help("Comma-separated cargo features to enable when building the chassis and cartridge, e.g. `pax-cartridge/debug-telemetry`.  Enabled in addition to the chassis' default features.");
+
+    #[allow(non_snake_case)]
+    let ARG_PARSER_NO_DEFAULT_FEATURES = Arg::with_name("parser-no-default-features")
+        .long("parser-no-default-features")
+        .takes_value(false)
+        .help("Build the project's parser binary with `--no-default-features`, for crates whose default features conflict with the `parser` feature.");
+
+    #[allow(non_snake_case)]
+    let ARG_DUMP_TYPES = Arg::with_name("dump-types")
+        .long("dump-types")
+        .takes_value(false)
+        .help("Print each type in the project's type table, with its escaped id, import path, iterable type and properties, once parsed.  For debugging type resolution.");
+
     let matches = App::new("pax")
         .name("pax")
         .bin_name("pax")
@@ -83,6 +159,15 @@ fn main() -> Result<(), ()> {
                 .arg( ARG_TARGET.clone() )
                 .arg( ARG_VERBOSE.clone() )
                 .arg( ARG_LIBDEV.clone() )
+                .arg( ARG_BUILD_REPORT.clone() )
+                .arg( ARG_OFFLINE.clone() )
+                .arg( ARG_VENDOR_DIR.clone() )
+                .arg( ARG_WASM_SIZE_WARNING_KB.clone() )
+                .arg( ARG_MANIFEST_PATH.clone() )
+                .arg( ARG_SERVER_WORKERS.clone() )
+                .arg( ARG_FEATURES.clone() )
+                .arg( ARG_PARSER_NO_DEFAULT_FEATURES.clone() )
+                .arg( ARG_DUMP_TYPES.clone() )
         )
         .subcommand(
             App::new("build")
@@ -91,6 +176,17 @@ fn main() -> Result<(), ()> {
                 .arg( ARG_TARGET.clone() )
                 .arg( ARG_VERBOSE.clone() )
                 .arg( ARG_LIBDEV.clone() )
+                .arg( ARG_BUILD_REPORT.clone() )
+                .arg( ARG_OUTPUT_DIR.clone() )
+                .arg( ARG_OFFLINE.clone() )
+                .arg( ARG_VENDOR_DIR.clone() )
+                .arg( ARG_DRY_RUN.clone() )
+                .arg( ARG_POST_BUILD.clone() )
+                .arg( ARG_WASM_SIZE_WARNING_KB.clone() )
+                .arg( ARG_MANIFEST_PATH.clone() )
+                .arg( ARG_FEATURES.clone() )
+                .arg( ARG_PARSER_NO_DEFAULT_FEATURES.clone() )
+                .arg( ARG_DUMP_TYPES.clone() )
         )
         .subcommand(
             App::new("clean")
@@ -98,14 +194,43 @@ fn main() -> Result<(), ()> {
                 .arg( ARG_LIBDEV.clone() )
                 .about("Cleans the temporary files associated with the Pax project in the current working directory — notably, the temporary files generated into the .pax directory")
         )
+        .subcommand(
+            App::new("check")
+                .arg( ARG_PATH.clone() )
+                .about("Parses the Pax project at the specified `path` and reports every error found by compiling its expressions and validating its templates, without generating code or building.  Exits with a non-zero status if there are any errors.")
+        )
+        .subcommand(
+            App::new("doctor")
+                .arg( ARG_PATH.clone() )
+                .about("Checks that the environment can build the Pax project at the specified `path` — that `cargo`, `wasm-pack` and the `wasm32-unknown-unknown` target are installed, that the project depends on `pax-*` packages and that its .pax directory is writable — reporting how to fix each problem found")
+        )
+        .subcommand(
+            App::new("graph")
+                .arg( ARG_PATH.clone() )
+                .about("Parses the Pax project at the specified `path` and writes a GraphViz DOT graph of its component templates to `.pax/graph.dot`, for visualizing the structure of the project")
+        )
+        .subcommand(
+            App::new("fmt")
+                .arg( ARG_PATH.clone() )
+                .arg(Arg::with_name("check")
+                    .long("check")
+                    .takes_value(false)
+                    .help("Report unformatted files without rewriting them, exiting with a non-zero status if there are any"))
+                .about("Formats every `.pax` file and every `#[inlined(...)]` template within the specified `path` into canonical style")
+        )
         .subcommand(
             App::new("create")
                 .alias("new")
+                .about("Creates a new Pax project from the built-in template, or from the template directory at `PAX_TEMPLATE_DIR` if set.")
                 .arg(Arg::with_name("path")
                     .help("File system path where the new project should be created. If not provided with --path, it should directly follow 'create'")
                     .takes_value(true)
                     .index(1))  // Positional arg, `pax create positional_arg_here`
                 .arg( ARG_LIBDEV.clone())
+                .arg(Arg::with_name("workspace")
+                    .long("workspace")
+                    .takes_value(false)
+                    .help("If the new project is created inside a Cargo workspace, add it to the workspace's `members`"))
         )
         .subcommand(
             App::new("libdev")
@@ -120,12 +245,20 @@ fn main() -> Result<(), ()> {
                         .arg( ARG_PATH.clone() )
                         .about("Parses the Pax program at the specified path and prints the manifest object, serialized to string. Also prints error messages if parsing fails.")
                 )
+                .subcommand(
+                    App::new("dump-manifest")
+                        .arg( ARG_PATH.clone() )
+                        .about("Parses the Pax program at the specified path, compiles its expressions, and prints the resulting manifest as pretty-printed JSON.  Useful for debugging codegen.")
+                )
                 .about("Collection of tools for internal library development")
         )
         .get_matches();
 
-    let _ = perform_nominal_action(matches, Arc::clone(&process_child_ids));
-    perform_cleanup(new_version_info, process_child_ids);
+    let exit_code = match perform_nominal_action(matches, Arc::clone(&process_child_ids)) {
+        Ok(()) => 0,
+        Err(()) => 1,
+    };
+    perform_cleanup(new_version_info, process_child_ids, exit_code);
 
     Ok(())
 }
@@ -140,6 +273,26 @@ fn perform_nominal_action(
             let path = args.value_of("path").unwrap().to_string(); //default value "."
             let verbose = args.is_present("verbose");
             let is_libdev_mode = args.is_present("libdev");
+            let should_write_build_report = args.is_present("build-report");
+            let offline = args.is_present("offline");
+            let vendor_dir = args.value_of("vendor-dir").map(PathBuf::from);
+            let manifest_path = args.value_of("manifest-path").map(PathBuf::from);
+            let wasm_size_warning_bytes = args.value_of("wasm-size-warning-kb").map(|kb| {
+                kb.parse::<u64>()
+                    .expect("Expected an integer number of kilobytes")
+                    * 1024
+            });
+            let server_workers = args.value_of("server-workers").map(|workers| {
+                workers
+                    .parse::<usize>()
+                    .expect("Expected an integer number of workers")
+            });
+            let chassis_features: Vec<String> = args
+                .values_of("features")
+                .map(|features| features.map(str::to_string).collect())
+                .unwrap_or_default();
+            let parser_no_default_features = args.is_present("parser-no-default-features");
+            let dump_types = args.is_present("dump-types");
 
             pax_compiler::perform_build(&RunContext {
                 target: RunTarget::from(target.as_str()),
@@ -148,6 +301,19 @@ fn perform_nominal_action(
                 should_also_run: true,
                 is_libdev_mode,
                 process_child_ids,
+                should_write_build_report,
+                output_dir: None,
+                offline,
+                vendor_dir,
+                dry_run: false,
+                wasm_size_warning_bytes,
+                post_build_command: None,
+                manifest_path,
+                server_workers,
+                chassis_features,
+                ready_tx: None,
+                parser_no_default_features,
+                dump_types,
             })
         }
         ("build", Some(args)) => {
@@ -155,6 +321,24 @@ fn perform_nominal_action(
             let path = args.value_of("path").unwrap().to_string(); //default value "."
             let verbose = args.is_present("verbose");
             let is_libdev_mode = args.is_present("libdev");
+            let should_write_build_report = args.is_present("build-report");
+            let offline = args.is_present("offline");
+            let vendor_dir = args.value_of("vendor-dir").map(PathBuf::from);
+            let manifest_path = args.value_of("manifest-path").map(PathBuf::from);
+            let wasm_size_warning_bytes = args.value_of("wasm-size-warning-kb").map(|kb| {
+                kb.parse::<u64>()
+                    .expect("Expected an integer number of kilobytes")
+                    * 1024
+            });
+            let output_dir = args.value_of("output-dir").map(PathBuf::from);
+            let dry_run = args.is_present("dry-run");
+            let post_build_command = args.value_of("post-build").map(str::to_string);
+            let chassis_features: Vec<String> = args
+                .values_of("features")
+                .map(|features| features.map(str::to_string).collect())
+                .unwrap_or_default();
+            let parser_no_default_features = args.is_present("parser-no-default-features");
+            let dump_types = args.is_present("dump-types");
 
             pax_compiler::perform_build(&RunContext {
                 target: RunTarget::from(target.as_str()),
@@ -163,6 +347,19 @@ fn perform_nominal_action(
                 verbose,
                 is_libdev_mode,
                 process_child_ids,
+                should_write_build_report,
+                output_dir,
+                offline,
+                vendor_dir,
+                dry_run,
+                wasm_size_warning_bytes,
+                post_build_command,
+                manifest_path,
+                server_workers: None,
+                chassis_features,
+                ready_tx: None,
+                parser_no_default_features,
+                dump_types,
             })
         }
         ("clean", Some(args)) => {
@@ -175,15 +372,42 @@ fn perform_nominal_action(
             println!("Done.");
             Ok(())
         }
+        ("check", Some(args)) => {
+            let path = args.value_of("path").unwrap().to_string(); //default value "."
+
+            //each diagnostic has already been printed
+            pax_compiler::check::perform_check(&path).map_err(|_| ())
+        }
+        ("doctor", Some(args)) => {
+            let path = args.value_of("path").unwrap().to_string(); //default value "."
+
+            //each failure has already been printed
+            pax_compiler::doctor::perform_doctor(&path).map_err(|_| ())
+        }
+        ("graph", Some(args)) => {
+            let path = args.value_of("path").unwrap().to_string(); //default value "."
+
+            //the error, if any, has already been printed
+            pax_compiler::graph::perform_graph(&path).map_err(|_| ())
+        }
+        ("fmt", Some(args)) => {
+            let path = args.value_of("path").unwrap().to_string(); //default value "."
+            let check = args.is_present("check");
+
+            //each file has already been printed
+            pax_compiler::formatting::perform_format(&FormatContext { path, check }).map_err(|_| ())
+        }
         ("create", Some(args)) => {
             let path = args.value_of("path").unwrap().to_string(); //default value "."
             let is_libdev_mode = args.is_present("libdev");
+            let should_add_to_workspace = args.is_present("workspace");
             let version = crate_version!().to_string(); // Note: this could also be parameterized, but an easy default is to clamp to the CLI version
 
             pax_compiler::perform_create(&CreateContext {
                 path,
                 is_libdev_mode,
                 version,
+                should_add_to_workspace,
             });
             Ok(())
         }
@@ -191,7 +415,8 @@ fn perform_nominal_action(
             match args.subcommand() {
                 ("parse", Some(args)) => {
                     let path = args.value_of("path").unwrap().to_string(); //default value "."
-                    let output = &pax_compiler::run_parser_binary(&path, process_child_ids);
+                    let output =
+                        &pax_compiler::run_parser_binary(&path, false, false, process_child_ids);
 
                     // Forward both stdout and stderr
                     std::io::stderr()
@@ -203,6 +428,13 @@ fn perform_nominal_action(
 
                     Ok(())
                 }
+                ("dump-manifest", Some(args)) => {
+                    let path = args.value_of("path").unwrap().to_string(); //default value "."
+
+                    pax_compiler::dump_manifest(&path, &mut std::io::stdout()).map_err(|err| {
+                        eprintln!("{}", err);
+                    })
+                }
                 ("build-chassis", Some(args)) => {
                     let target = args.value_of("target").unwrap().to_lowercase();
                     let path = args.value_of("path").unwrap().to_string(); //default value "."
@@ -217,6 +449,19 @@ fn perform_nominal_action(
                         should_also_run: false,
                         is_libdev_mode: true,
                         process_child_ids: Arc::new(Mutex::new(vec![])),
+                        should_write_build_report: false,
+                        output_dir: None,
+                        offline: false,
+                        vendor_dir: None,
+                        dry_run: false,
+                        wasm_size_warning_bytes: None,
+                        post_build_command: None,
+                        manifest_path: None,
+                        server_workers: None,
+                        chassis_features: vec![],
+                        ready_tx: None,
+                        parser_no_default_features: false,
+                        dump_types: false,
                     };
 
                     let output = pax_compiler::build_chassis_with_cartridge(
@@ -247,6 +492,7 @@ fn perform_nominal_action(
 fn perform_cleanup(
     new_version_info: Arc<Mutex<Option<String>>>,
     process_child_ids: Arc<Mutex<Vec<u64>>>,
+    exit_code: i32,
 ) {
     //1. kill any running child processes
     if let Ok(process_child_ids_lock) = process_child_ids.lock() {
@@ -320,7 +566,7 @@ fn perform_cleanup(
         }
     }
 
-    process::exit(0);
+    process::exit(exit_code);
 }
 
 #[cfg(unix)]
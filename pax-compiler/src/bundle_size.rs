@@ -0,0 +1,102 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use log::{info, warn};
+use serde::Serialize;
+
+use crate::PAX_DIR_PKG_PATH;
+
+/// Size on disk of a single generated bundle file, and its size after gzip compression
+/// (approximating its over-the-wire size)
+#[derive(Serialize, Default)]
+pub struct BundleFileSize {
+    pub file_name: String,
+    pub bytes: u64,
+    pub gzipped_bytes: u64,
+}
+
+/// Gzipped `.wasm` size above which a web build warns of a heavy bundle, unless
+/// `RunContext::wasm_size_warning_bytes` specifies otherwise
+pub const DEFAULT_WASM_SIZE_WARNING_BYTES: u64 = 1024 * 1024;
+
+/// Stats the `.wasm` and `.js` generated by `wasm-pack` into `interface/public`, printing the raw and
+/// gzipped size of each and warning if the gzipped `.wasm` exceeds `wasm_size_warning_bytes`
+pub(crate) fn report_web_bundle_sizes(
+    pax_dir: &Path,
+    wasm_size_warning_bytes: u64,
+) -> Vec<BundleFileSize> {
+    let public_path = pax_dir
+        .join(PAX_DIR_PKG_PATH)
+        .join("pax-chassis-web")
+        .join("interface")
+        .join("public");
+
+    ["pax-chassis-web_bg.wasm", "pax-chassis-web.js"]
+        .iter()
+        .filter_map(|file_name| {
+            let contents = fs::read(public_path.join(file_name)).ok()?;
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&contents).ok()?;
+            let gzipped_bytes = encoder.finish().ok()?.len() as u64;
+            let bundle_file_size = BundleFileSize {
+                file_name: file_name.to_string(),
+                bytes: contents.len() as u64,
+                gzipped_bytes,
+            };
+
+            info!(
+                "📦 {}: {} ({} gzipped)", file_name,
+                format_byte_size(bundle_file_size.bytes),
+                format_byte_size(bundle_file_size.gzipped_bytes)
+            );
+            if file_name.ends_with(".wasm") && gzipped_bytes > wasm_size_warning_bytes {
+                warn!(
+                    "⚠️  {} exceeds the {} (gzipped) size warning threshold — check for heavy dependencies or components", file_name,
+                    format_byte_size(wasm_size_warning_bytes)
+                );
+            }
+            Some(bundle_file_size)
+        })
+        .collect()
+}
+
+fn format_byte_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.2} MiB", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_byte_size, report_web_bundle_sizes};
+    use crate::PAX_DIR_PKG_PATH;
+
+    #[test]
+    fn test_report_web_bundle_sizes() {
+        let pax_dir = std::env::temp_dir().join(format!("pax-bundle-{}", uuid::Uuid::new_v4()));
+        let public_path = pax_dir
+            .join(PAX_DIR_PKG_PATH)
+            .join("pax-chassis-web")
+            .join("interface")
+            .join("public");
+        std::fs::create_dir_all(&public_path).unwrap();
+        std::fs::write(public_path.join("pax-chassis-web_bg.wasm"), vec![0u8; 4096]).unwrap();
+
+        let sizes = report_web_bundle_sizes(&pax_dir, 1);
+
+        //the `.js` is absent, so only the `.wasm` is reported
+        assert_eq!(sizes.len(), 1);
+        assert_eq!(sizes[0].file_name, "pax-chassis-web_bg.wasm");
+        assert_eq!(sizes[0].bytes, 4096);
+        assert!(sizes[0].gzipped_bytes > 0 && sizes[0].gzipped_bytes < 4096);
+        assert_eq!(format_byte_size(4096), "4.0 KiB");
+
+        std::fs::remove_dir_all(&pax_dir).unwrap();
+    }
+}
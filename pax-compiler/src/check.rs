@@ -0,0 +1,59 @@
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use log::{error, info};
+
+use crate::{
+    find_expression_compilation_errors, find_literal_type_mismatches,
+    find_out_of_range_slot_indices, find_readonly_property_bindings, init_logging, parse_manifest,
+    warn_missing_event_handlers, PaxBuildError,
+};
+
+/// Runs the parser and every semantic validation of `perform_build` for the project at `path` —
+/// unresolved symbols, mismatched literal types and out-of-range slot indices — printing each diagnostic.
+/// Event handlers that may be missing are warned of, as by `perform_build`.
+/// Never generates code or builds the chassis; returns `Err` with the diagnostics, if any were reported.
+/// Like `dump_manifest`, dependencies must already be resolvable by `cargo`.
+pub fn perform_check(path: &str) -> Result<(), Vec<PaxBuildError>> {
+    init_logging();
+    info!("🔍 Checking Pax project...");
+    let diagnostics = check_project(path);
+
+    for diagnostic in diagnostics.iter() {
+        error!("❌ {}", diagnostic);
+    }
+    if diagnostics.is_empty() {
+        info!("✅ No errors found");
+        Ok(())
+    } else {
+        error!(
+            "Found {} error{}",
+            diagnostics.len(),
+            if diagnostics.len() == 1 { "" } else { "s" }
+        );
+        Err(diagnostics)
+    }
+}
+
+fn check_project(path: &str) -> Vec<PaxBuildError> {
+    let (manifest, host_crate_info) = match parse_manifest(
+        &Path::new(path).join("Cargo.toml"),
+        false,
+        false,
+        Arc::new(Mutex::new(vec![])),
+    ) {
+        Ok(parsed) => parsed,
+        Err(err) => return vec![err],
+    };
+
+    warn_missing_event_handlers(&manifest, &Path::new(path).join("src"));
+
+    let mut diagnostics = find_literal_type_mismatches(&manifest);
+    diagnostics.extend(find_readonly_property_bindings(&manifest));
+    diagnostics.extend(find_expression_compilation_errors(
+        &manifest,
+        &host_crate_info.expression_functions,
+    ));
+    diagnostics.extend(find_out_of_range_slot_indices(&manifest));
+    diagnostics
+}
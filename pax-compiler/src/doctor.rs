@@ -0,0 +1,152 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use log::{error, info};
+
+use crate::{
+    get_version_of_whitelisted_packages, init_logging, require_tool_on_path, CARGO_INSTALL_HINT,
+    WASM_PACK_INSTALL_HINT,
+};
+
+/// Checks that the environment can build the Pax project at `path`: that `cargo` and `wasm-pack` are
+/// on `PATH`, that the `wasm32-unknown-unknown` target is installed for web builds, that the project
+/// depends on a `pax-*` package and that its `.pax` directory is writable.  Prints each check as it
+/// passes or fails, along with how to remedy each failure; returns `Err` with each failure, if any.
+pub fn perform_doctor(path: &str) -> Result<(), Vec<String>> {
+    init_logging();
+    info!("🩺 Checking environment and project...");
+    let checks = run_doctor_checks(path);
+
+    let mut failures = vec![];
+    for check in checks.iter() {
+        match &check.result {
+            Ok(detail) => info!("✅ {}{}", check.description, detail),
+            Err(remediation) => {
+                let failure = format!("{} — {}", check.description, remediation);
+                error!("❌ {}", failure);
+                failures.push(failure);
+            }
+        }
+    }
+    if failures.is_empty() {
+        info!("✅ Ready to build");
+        Ok(())
+    } else {
+        error!(
+            "Found {} problem{}",
+            failures.len(),
+            if failures.len() == 1 { "" } else { "s" }
+        );
+        Err(failures)
+    }
+}
+
+/// The outcome of one of the checks made by `perform_doctor`
+struct DoctorCheck {
+    /// What is checked, e.g. "`cargo` is on PATH"
+    description: &'static str,
+    /// Details to append to `description` if the check passed, or otherwise how to remedy its failure
+    result: Result<String, String>,
+}
+
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+fn run_doctor_checks(path: &str) -> Vec<DoctorCheck> {
+    vec![
+        DoctorCheck {
+            description: "`cargo` is on PATH",
+            result: require_tool_on_path("cargo", CARGO_INSTALL_HINT)
+                .map(|_| String::new())
+                .map_err(|err| err.to_string()),
+        },
+        DoctorCheck {
+            description: "`wasm-pack` is on PATH, for web builds",
+            result: require_tool_on_path("wasm-pack", WASM_PACK_INSTALL_HINT)
+                .map(|_| String::new())
+                .map_err(|err| err.to_string()),
+        },
+        DoctorCheck {
+            description: "The `wasm32-unknown-unknown` target is installed, for web builds",
+            result: check_rust_target_installed(path, WASM_TARGET).map(|_| String::new()),
+        },
+        DoctorCheck {
+            description: "The project depends on `pax-*` packages",
+            result: get_version_of_whitelisted_packages(path, false)
+                .map(|version| format!(", at version {}", version)),
+        },
+        DoctorCheck {
+            description: "The `.pax` directory is writable",
+            result: check_pax_dir_writable(path).map(|_| String::new()),
+        },
+    ]
+}
+
+/// Checks that the standard library for `target` is installed in the sysroot of the toolchain that
+/// `rustc` resolves to from `path`, which respects e.g. a `rust-toolchain.toml`
+fn check_rust_target_installed(path: &str, target: &str) -> Result<(), String> {
+    let output = Command::new("rustc")
+        .arg("--print")
+        .arg("sysroot")
+        .current_dir(path)
+        .output()
+        .map_err(|_| format!("`rustc` not found; {}", CARGO_INSTALL_HINT))?;
+    if !output.status.success() {
+        return Err(format!(
+            "`rustc --print sysroot` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let sysroot = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    if sysroot.join("lib").join("rustlib").join(target).is_dir() {
+        Ok(())
+    } else {
+        Err(format!(
+            "`{}` is not installed; install via `rustup target add {}`",
+            target, target
+        ))
+    }
+}
+
+/// Checks that `.pax` within `path` exists or can be created, and that files can be written to it
+fn check_pax_dir_writable(path: &str) -> Result<(), String> {
+    let pax_dir = Path::new(path).join(".pax");
+    let probe_path = pax_dir.join(".doctor");
+    fs::create_dir_all(&pax_dir)
+        .and_then(|_| fs::write(&probe_path, ""))
+        .and_then(|_| fs::remove_file(&probe_path))
+        .map_err(|err| {
+            format!(
+                "cannot write to {} ({}); check its permissions, or run `pax clean` to recreate it",
+                pax_dir.display(),
+                err
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_doctor_checks;
+
+    #[test]
+    fn test_doctor_checks() {
+        let tmp_dir = std::env::temp_dir().join(format!("pax-doctor-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&tmp_dir).unwrap();
+
+        let checks = run_doctor_checks(tmp_dir.to_str().unwrap());
+        let get_result = |description_prefix: &str| {
+            &checks
+                .iter()
+                .find(|check| check.description.starts_with(description_prefix))
+                .unwrap()
+                .result
+        };
+        //No Cargo.toml, so no `pax-*` dependency can be found
+        let dependency_result = get_result("The project depends on");
+        assert!(dependency_result.as_ref().unwrap_err().contains("Cargo"));
+        assert_eq!(get_result("The `.pax` directory"), &Ok(String::new()));
+        assert!(tmp_dir.join(".pax").is_dir());
+        assert!(!tmp_dir.join(".pax").join(".doctor").exists());
+        std::fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+}
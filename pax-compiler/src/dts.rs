@@ -0,0 +1,122 @@
+use std::fs;
+use std::path::Path;
+
+use crate::manifest::{self, PaxManifest};
+use crate::templating::{
+    press_template_codegen_web_interface_dts, TemplateArgsCodegenWebInterfaceDts,
+};
+use crate::PAX_DIR_PKG_PATH;
+
+//relative to the web interface's `public` directory
+pub const WEB_INTERFACE_DTS_PATH: &str = "pax.d.ts";
+
+/// Writes a TypeScript declaration of the web interface's public API — `mount`, plus the properties
+/// and events of the main component — to `interface/public`, alongside the built wasm and js
+pub(crate) fn generate_web_interface_dts(pax_dir: &Path, manifest: &PaxManifest) {
+    let path = pax_dir
+        .join(PAX_DIR_PKG_PATH)
+        .join("pax-chassis-web")
+        .join("interface")
+        .join("public")
+        .join(WEB_INTERFACE_DTS_PATH);
+    fs::write(path, press_web_interface_dts(manifest)).unwrap();
+}
+
+pub(crate) fn press_web_interface_dts(manifest: &PaxManifest) -> String {
+    let main_component_info = manifest
+        .get_component_info(&manifest.main_component_type_id)
+        .expect("main component not found in manifest");
+    press_template_codegen_web_interface_dts(TemplateArgsCodegenWebInterfaceDts {
+        pascal_identifier: main_component_info.pascal_identifier,
+        type_id: main_component_info.type_id,
+        properties: main_component_info
+            .properties
+            .into_iter()
+            .map(|pi| {
+                (
+                    pi.name,
+                    get_typescript_type(&pi.type_display_name),
+                    pi.type_display_name,
+                )
+            })
+            .collect(),
+        events: main_component_info
+            .events
+            .into_iter()
+            .map(|ei| ei.name)
+            .collect(),
+    })
+}
+
+/// Maps the display name of a Rust property type (see `PropertyInfo::type_display_name`) to the
+/// TypeScript type of its values, e.g. `f64` => `number` and `Vec<String>` => `string[]`.
+/// Types without an obvious JS representation map to `unknown`.
+fn get_typescript_type(type_display_name: &str) -> String {
+    if let Some(element) = type_display_name
+        .strip_prefix("Vec<")
+        .and_then(|tail| tail.strip_suffix('>'))
+    {
+        return format!("{}[]", get_typescript_type(element));
+    }
+    match type_display_name {
+        "bool" => "boolean",
+        "String" => "string",
+        "Numeric" => "number",
+        _ if manifest::SUPPORTED_NUMERIC_PRIMITIVES.contains(&type_display_name) => "number",
+        _ => "unknown",
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::press_web_interface_dts;
+    use crate::manifest::PropertyDefinition;
+    use crate::parsing::ParsingContext;
+    use crate::tests::build_test_manifest;
+
+    #[test]
+    fn test_press_web_interface_dts() {
+        let pax = r#"
+            <Rectangle />
+
+            @handlers {
+                will_render: handle_will_render,
+                did_mount: handle_did_mount,
+            }
+        "#;
+
+        let manifest = build_test_manifest(
+            ParsingContext::default(),
+            pax,
+            "Example",
+            true,
+            vec![
+                PropertyDefinition::primitive_with_name("usize", "total"),
+                PropertyDefinition::primitive_with_name("bool", "is_open"),
+                PropertyDefinition::primitive_with_name("std::vec::Vec<String>", "tags"),
+                PropertyDefinition::primitive_with_name("pax_std::types::Color", "fill"),
+            ],
+            vec![("pax_std::primitives::Rectangle", vec![])],
+        );
+
+        let dts = press_web_interface_dts(&manifest);
+        assert!(dts.contains(
+            "export declare function mount(selectorOrElement: string | Element, extensionlessUrl: string): void;"
+        ));
+        assert!(dts.contains("export type RootComponent = \"Example\";"));
+        assert!(dts.contains(
+            "export interface ExampleProperties {
+    /** `usize` */
+    total: number;
+    /** `bool` */
+    is_open: boolean;
+    /** `Vec<String>` */
+    tags: string[];
+    /** `Color` */
+    fill: unknown;
+}"
+        ));
+        assert!(dts.contains("export type ExampleEvent = \"did_mount\" | \"will_render\";"));
+    }
+}
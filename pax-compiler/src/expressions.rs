@@ -1,20 +1,154 @@
 use super::manifest::{
     ComponentDefinition, ControlFlowRepeatPredicateDefinition, ExpressionSpec,
-    ExpressionSpecInvocation, PaxManifest, PropertyDefinition, SettingsSelectorBlockDefinition,
-    TemplateNodeDefinition, ValueDefinition,
+    ExpressionSpecInvocation, ExpressionSpecKey, PaxManifest, PropertyDefinition,
+    SettingsSelectorBlockDefinition, TemplateNodeDefinition, ValueDefinition,
 };
-use std::collections::HashMap;
-use std::ops::{IndexMut, RangeFrom};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::ops::{IndexMut, Range, RangeFrom};
+use std::path::{Path, PathBuf};
 use std::slice::IterMut;
+use std::time::{Duration, Instant};
 
-use crate::manifest::{PropertyDefinitionFlags, TypeDefinition, TypeTable};
-use crate::parsing::escape_identifier;
+use crate::manifest::{
+    DestructuredRepeatElemDefinition, PropertyDefinitionFlags, TypeDefinition, TypeTable,
+};
+use crate::parsing::{escape_identifier, PaxParser, Rule};
 use itertools::Itertools;
 use lazy_static::lazy_static;
+use pest::Parser;
+use serde_derive::{Deserialize, Serialize};
+
+/// Reserved symbol bound to the length of the iterable inside every `for` body, e.g. `i / len`.
+/// It shadows any property of the same name on the containing component, so `self.len` inside a
+/// `for` body also refers to the length; a predicate binding like `for (len, i) in ...` takes precedence.
+pub const REPEAT_LEN_SYMBOL: &str = "len";
+
+/// Types of the properties common to every element, e.g. the `width` in `<Rectangle width=50% />`,
+/// which are not declared on the elements' own components
+const BUILTIN_PROPERTY_TYPES: [(&str, &str); 18] = [
+    ("transform", "Transform2D"),
+    ("width", "Size"),
+    ("height", "Size"),
+    ("min_width", "Size"),
+    ("max_width", "Size"),
+    ("min_height", "Size"),
+    ("max_height", "Size"),
+    ("opacity", "f64"),
+    ("z_index", "isize"),
+    ("x", "Size"),
+    ("y", "Size"),
+    ("anchor_x", "Size"),
+    ("anchor_y", "Size"),
+    ("skew_x", "Numeric"),
+    ("skew_y", "Numeric"),
+    ("scale_x", "Size"),
+    ("scale_y", "Size"),
+    ("rotate", "Rotation"),
+];
+
+pub fn get_builtin_property_type(property_name: &str) -> Option<&'static str> {
+    BUILTIN_PROPERTY_TYPES
+        .iter()
+        .find(|(name, _)| *name == property_name)
+        .map(|(_, type_string)| *type_string)
+}
+
+/// A host Rust function callable by name from expressions, like `lerp` in `{lerp(0, 100, self.progress)}`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExpressionFunction {
+    /// Path to the function as seen from the cartridge, e.g. `pax_example::math::lerp`
+    pub path: String,
+    /// type_ids of the function's arguments, in order.  Arguments of numeric types are converted from
+    /// the `Numeric`s that numbers are lowered to, while others are passed by value as they are.
+    pub arg_type_ids: Vec<String>,
+    /// type_id of the function's return value.  Numeric return values are wrapped in `Numeric`, so
+    /// that results compose with the rest of the expression, e.g. `lerp(0, 100, self.progress) * 2`
+    pub return_type_id: String,
+}
 
-pub fn compile_all_expressions<'a>(manifest: &'a mut PaxManifest) {
+impl ExpressionFunction {
+    /// Lowers a call to this function with the RIL `args`, which must match `arg_type_ids` in number
+    pub fn lower_call_to_ril(&self, args: &[String]) -> String {
+        let args = args
+            .iter()
+            .zip(self.arg_type_ids.iter())
+            .map(|(arg, arg_type_id)| match arg_type_id.as_str() {
+                "Numeric" => format!("Numeric::from({})", arg),
+                "f64" => format!("Numeric::from({}).get_as_float()", arg),
+                _ if ExpressionSpecInvocation::is_numeric(arg_type_id) => {
+                    format!("(Numeric::from({}).get_as_int() as {})", arg, arg_type_id)
+                }
+                _ => format!("({})", arg),
+            })
+            .join(",");
+        let ril = format!("{}({})", self.path, args);
+        if ExpressionSpecInvocation::is_numeric(&self.return_type_id) {
+            format!("Numeric::from({})", ril)
+        } else {
+            ril
+        }
+    }
+}
+
+/// Host functions callable from expressions beyond the built-ins, by the name that expressions call them by.
+/// Calls to any other unqualified function are rejected at compile time, while qualified calls like
+/// `Color::rgb(...)` are lowered as they are.
+#[derive(Default, Debug, Clone, Hash)]
+pub struct ExpressionFunctionRegistry {
+    functions: BTreeMap<String, ExpressionFunction>,
+}
+
+impl ExpressionFunctionRegistry {
+    /// Makes `function` callable from expressions as `name`, replacing any function previously registered as `name`
+    pub fn register(&mut self, name: &str, function: ExpressionFunction) {
+        self.functions.insert(name.to_string(), function);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ExpressionFunction> {
+        self.functions.get(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.functions.keys().map(String::as_str)
+    }
+}
+
+/// Compiles all expressions in the manifest, populating `manifest.expression_specs`.
+/// Returns the time spent compiling each component's expressions, keyed by component `type_id`,
+/// or every unresolved symbol and function found among the expressions of every component.
+pub fn compile_all_expressions(
+    manifest: &mut PaxManifest,
+) -> Result<HashMap<String, Duration>, Vec<UnresolvedSymbolError>> {
+    compile_all_expressions_with_cache(manifest, &mut ExpressionCache::default())
+}
+
+/// Same as `compile_all_expressions`, but reuses the compiled RIL of any expression found in `expression_cache`
+/// and records each newly compiled expression there, so that unchanged expressions are not recompiled by the next build
+pub fn compile_all_expressions_with_cache(
+    manifest: &mut PaxManifest,
+    expression_cache: &mut ExpressionCache,
+) -> Result<HashMap<String, Duration>, Vec<UnresolvedSymbolError>> {
+    compile_all_expressions_with_functions(
+        manifest,
+        expression_cache,
+        &ExpressionFunctionRegistry::default(),
+    )
+}
+
+/// Same as `compile_all_expressions_with_cache`, but also lowers calls to the host functions in `expression_functions`
+pub fn compile_all_expressions_with_functions(
+    manifest: &mut PaxManifest,
+    expression_cache: &mut ExpressionCache,
+    expression_functions: &ExpressionFunctionRegistry,
+) -> Result<HashMap<String, Duration>, Vec<UnresolvedSymbolError>> {
+    let mut component_durations: HashMap<String, Duration> = HashMap::new();
+    let mut unresolved_symbol_errors: Vec<UnresolvedSymbolError> = vec![];
     let mut swap_expression_specs: HashMap<usize, ExpressionSpec> = HashMap::new();
     let mut all_expression_specs: HashMap<usize, ExpressionSpec> = HashMap::new();
+    let mut expression_spec_ids: HashMap<ExpressionSpecKey, usize> = HashMap::new();
 
     let mut new_components = manifest.components.clone();
     let mut uid_track = 0;
@@ -22,6 +156,7 @@ pub fn compile_all_expressions<'a>(manifest: &'a mut PaxManifest) {
     new_components
         .values_mut()
         .for_each(|component_def: &mut ComponentDefinition| {
+            let component_start = Instant::now();
             let mut new_component_def = component_def.clone();
             let read_only_component_def = component_def.clone();
 
@@ -40,36 +175,51 @@ pub fn compile_all_expressions<'a>(manifest: &'a mut PaxManifest) {
                     uid_gen: uid_track..,
                     all_components: manifest.components.clone(),
                     expression_specs: &mut swap_expression_specs,
+                    expression_spec_ids: &mut expression_spec_ids,
                     component_def: &read_only_component_def,
                     type_table: &manifest.type_table,
+                    expression_cache: &mut *expression_cache,
+                    expression_functions,
+                    scope_fingerprint: None,
+                    unresolved_symbol_errors: vec![],
                 };
 
                 ctx = recurse_compile_expressions(ctx);
+                unresolved_symbol_errors.append(&mut ctx.unresolved_symbol_errors);
                 uid_track = ctx.uid_gen.next().unwrap();
                 all_expression_specs.extend(ctx.expression_specs.to_owned());
                 std::mem::swap(&mut ctx.active_node_def, template.index_mut(0));
             }
 
             std::mem::swap(component_def, &mut new_component_def);
+            component_durations.insert(component_def.type_id.clone(), component_start.elapsed());
         });
+    if !unresolved_symbol_errors.is_empty() {
+        unresolved_symbol_errors.sort_by(|err0, err1| err0.component.cmp(&err1.component));
+        return Err(unresolved_symbol_errors);
+    }
     manifest.components = new_components;
     manifest.expression_specs = Some(swap_expression_specs);
+    Ok(component_durations)
 }
 
-fn pull_matched_identifiers_from_inline(
-    inline_settings: &Option<Vec<(String, ValueDefinition)>>,
-    s: String,
-) -> Vec<String> {
-    let mut ret = Vec::new();
-    if let Some(val) = inline_settings {
-        for (_, matched) in val.iter().filter(|avd| avd.0 == s.as_str()) {
-            match matched {
-                ValueDefinition::Identifier(s, _) => ret.push(s.clone()),
-                _ => {}
-            };
-        }
+/// Registers `spec` in `ctx.expression_specs` under a new id from `ctx.uid_gen`, and returns the vtable id
+/// to bind to; the `id` `spec` is built with is ignored.  If a structurally-equivalent spec (see
+/// `ExpressionSpec::is_equivalent`) was already registered, e.g. by a copy-pasted expression, `spec` is
+/// discarded and the existing id is returned instead, so that both share one vtable entry.
+fn register_expression_spec(
+    ctx: &mut ExpressionCompilationContext,
+    mut spec: ExpressionSpec,
+) -> usize {
+    let key = spec.get_key();
+    if let Some(id) = ctx.expression_spec_ids.get(&key) {
+        return *id;
     }
-    ret
+    let id = ctx.uid_gen.next().unwrap();
+    spec.id = id;
+    ctx.expression_spec_ids.insert(key, id);
+    ctx.expression_specs.insert(id, spec);
+    id
 }
 
 fn pull_settings_with_selector(
@@ -94,28 +244,46 @@ fn pull_settings_with_selector(
     }
 }
 
-fn merge_inline_settings_with_settings_block(
-    inline_settings: &Option<Vec<(String, ValueDefinition)>>,
+/// Computes a node's effective settings by merging the `@settings` blocks whose selectors match the node
+/// with the node's inline settings.  Selectors are applied in increasing order of specificity, with
+/// later (more specific) sources overriding earlier ones key-by-key:
+///
+/// 1. type selector, matching the node's pascal identifier: `Rectangle`
+/// 2. class selectors, for each of the node's `classes` (from `class="..."`): `.some-class`
+/// 3. id selector, for the node's `element_id` (from `id="..."`): `#some-id`
+/// 4. inline settings, e.g. `<Rectangle fill=... />`
+///
+/// Within a single level, blocks are applied in declaration order, so a later block wins on conflict.
+pub(crate) fn merge_inline_settings_with_settings_block(
+    node: &TemplateNodeDefinition,
     settings_block: &Option<Vec<SettingsSelectorBlockDefinition>>,
+    pascal_identifier: Option<&str>,
 ) -> Option<Vec<(String, ValueDefinition)>> {
-    // collect id settings
-    let ids = pull_matched_identifiers_from_inline(&inline_settings, "id".to_string());
+    let inline_settings = &node.settings;
 
+    // collect type settings
+    let mut type_settings = Vec::new();
+    if let Some(pascal_identifier) = pascal_identifier {
+        if let Some(settings) =
+            pull_settings_with_selector(settings_block, pascal_identifier.to_string())
+        {
+            type_settings.extend(settings);
+        }
+    }
+
+    // collect id settings
     let mut id_settings = Vec::new();
-    if ids.len() == 1 {
-        if let Some(settings) = pull_settings_with_selector(&settings_block, format!("#{}", ids[0]))
+    if let Some(element_id) = &node.element_id {
+        if let Some(settings) =
+            pull_settings_with_selector(settings_block, format!("#{}", element_id))
         {
             id_settings.extend(settings.clone());
         }
-    } else if ids.len() > 1 {
-        panic!("Specified more than one id inline!");
     }
 
     // collect all class settings
-    let classes = pull_matched_identifiers_from_inline(&inline_settings, "class".to_string());
-
     let mut class_settings = Vec::new();
-    for class in classes {
+    for class in node.classes.iter() {
         if let Some(settings) = pull_settings_with_selector(&settings_block, format!(".{}", class))
         {
             class_settings.extend(settings.clone());
@@ -124,7 +292,11 @@ fn merge_inline_settings_with_settings_block(
 
     let mut map = HashMap::new();
 
-    // Iterate in reverse order of priority (class, then id, then inline)
+    // Iterate in reverse order of priority (type, then class, then id, then inline)
+    for (key, value) in type_settings.into_iter() {
+        map.insert(key, value);
+    }
+
     for (key, value) in class_settings.into_iter() {
         map.insert(key, value);
     }
@@ -155,7 +327,7 @@ fn recurse_compile_literal_block<'a>(
 ) {
     settings_pairs.for_each(|pair| {
         match &mut pair.1 {
-            ValueDefinition::LiteralValue(_) => {
+            ValueDefinition::LiteralValue(_) | ValueDefinition::LiteralEnumVariant(_) => {
                 //no need to compile literal values
             }
             ValueDefinition::EventBindingTarget(_) => {
@@ -177,50 +349,47 @@ fn recurse_compile_literal_block<'a>(
                     type_def.type_id_escaped.clone(),
                 );
             }
+            ValueDefinition::LiteralList(elements) => {
+                let type_def = current_property_definitions
+                    .iter()
+                    .find(|property_def| property_def.name == pair.0)
+                    .unwrap_or_else(|| panic!("Property `{}` not found on `{}`", &pair.0, type_id))
+                    .get_type_definition(ctx.type_table);
+                recurse_compile_literal_list(elements.iter_mut(), ctx, type_def);
+            }
             ValueDefinition::Expression(input, manifest_id) => {
                 // e.g. the `self.num_clicks + 5` in `<SomeNode some_property={self.num_clicks + 5} />`
-                let id = ctx.uid_gen.next().unwrap();
-
-                let (output_statement, invocations) = compile_paxel_to_ril(&input, &ctx);
-
-                let builtin_types = HashMap::from([
-                    ("transform", "Transform2D".to_string()),
-                    ("width", "Size".to_string()),
-                    ("height", "Size".to_string()),
-                    ("x", "Size".to_string()),
-                    ("y", "Size".to_string()),
-                    ("anchor_x", "Size".to_string()),
-                    ("anchor_y", "Size".to_string()),
-                    ("skew_x", "Numeric".to_string()),
-                    ("skew_y", "Numeric".to_string()),
-                    ("scale_x", "Size".to_string()),
-                    ("scale_y", "Size".to_string()),
-                    ("rotate", "Rotation".to_string()),
-                ]);
-
-                let pascalized_return_type = if let Some(type_string) = builtin_types.get(&*pair.0)
-                {
-                    type_string.to_string()
-                } else {
-                    (current_property_definitions
-                        .iter()
-                        .find(|property_def| property_def.name == pair.0)
-                        .expect(&format!(
-                            "Property `{}` not found on component `{}`",
-                            &pair.0, type_id
-                        ))
-                        .get_type_definition(ctx.type_table)
-                        .type_id_escaped)
-                        .clone()
-                };
+                let (output_statement, invocations) = compile_paxel_to_ril(input, ctx);
+
+                let pascalized_return_type =
+                    if let Some(type_string) = get_builtin_property_type(&pair.0) {
+                        type_string.to_string()
+                    } else if let Some(element_type_id_escaped) =
+                        resolve_index_access_return_type(input, ctx)
+                    {
+                        element_type_id_escaped
+                    } else {
+                        (current_property_definitions
+                            .iter()
+                            .find(|property_def| property_def.name == pair.0)
+                            .unwrap_or_else(|| {
+                                panic!(
+                                    "Property `{}` not found on component `{}`",
+                                    &pair.0, type_id
+                                )
+                            })
+                            .get_type_definition(ctx.type_table)
+                            .type_id_escaped)
+                            .clone()
+                    };
 
                 let mut whitespace_removed_input = input.clone();
                 whitespace_removed_input.retain(|c| !c.is_whitespace());
 
-                ctx.expression_specs.insert(
-                    id,
+                let id = register_expression_spec(
+                    ctx,
                     ExpressionSpec {
-                        id,
+                        id: 0,
                         pascalized_return_type,
                         invocations,
                         output_statement,
@@ -241,15 +410,9 @@ fn recurse_compile_literal_block<'a>(
                     //No-op -- special-case `id=some_identifier` and `class=some_identifier` — we DON'T want to compile an expression {some_identifier},
                     //so we skip the case where `id` is the key
                 } else {
-                    let id = ctx.uid_gen.next().unwrap();
-
-                    //Write this id back to the manifest, for downstream use by RIL component tree generator
-                    let mut manifest_id_insert: Option<usize> = Some(id);
-                    std::mem::swap(manifest_id, &mut manifest_id_insert);
-
                     //a single identifier binding is the same as an expression returning that identifier, `{self.some_identifier}`
                     //thus, we can compile it as PAXEL and make use of any shared logic, e.g. `self`/`this` handling
-                    let (output_statement, invocations) = compile_paxel_to_ril(&identifier, &ctx);
+                    let (output_statement, invocations) = compile_paxel_to_ril(identifier, ctx);
 
                     let pascalized_return_type = (&ctx
                         .component_def
@@ -261,10 +424,10 @@ fn recurse_compile_literal_block<'a>(
                         .type_id_escaped)
                         .clone();
 
-                    ctx.expression_specs.insert(
-                        id,
+                    let id = register_expression_spec(
+                        ctx,
                         ExpressionSpec {
-                            id,
+                            id: 0,
                             pascalized_return_type,
                             invocations,
                             output_statement,
@@ -273,6 +436,10 @@ fn recurse_compile_literal_block<'a>(
                             repeat_source_iterable_type_id_escaped: "".to_string(),
                         },
                     );
+
+                    //Write this id back to the manifest, for downstream use by RIL component tree generator
+                    let mut manifest_id_insert: Option<usize> = Some(id);
+                    std::mem::swap(manifest_id, &mut manifest_id_insert);
                 }
             }
             _ => {
@@ -282,15 +449,52 @@ fn recurse_compile_literal_block<'a>(
     })
 }
 
+/// Compiles the expressions within the blocks of a literal list, like the `{self.stop}` in
+/// `stops=[{position: {self.stop}, color: ...}]`, against the properties of the list's element type
+fn recurse_compile_literal_list(
+    elements: IterMut<ValueDefinition>,
+    ctx: &mut ExpressionCompilationContext,
+    type_def: &TypeDefinition,
+) {
+    let inner_iterable_type_id = match &type_def.inner_iterable_type_id {
+        Some(inner_iterable_type_id) => inner_iterable_type_id,
+        //Reported with context during codegen
+        None => return,
+    };
+    let inner_type_def = ctx.type_table.get(inner_iterable_type_id);
+    elements.for_each(|element| match (element, inner_type_def) {
+        (ValueDefinition::Block(block), Some(inner_type_def)) => {
+            recurse_compile_literal_block(
+                block.settings_key_value_pairs.iter_mut(),
+                ctx,
+                inner_type_def.property_definitions.clone(),
+                inner_type_def.type_id_escaped.clone(),
+            );
+        }
+        (ValueDefinition::LiteralList(inner_elements), Some(inner_type_def)) => {
+            recurse_compile_literal_list(inner_elements.iter_mut(), ctx, inner_type_def);
+        }
+        _ => {
+            //no need to compile literal values
+        }
+    });
+}
+
 fn recurse_compile_expressions<'a>(
     mut ctx: ExpressionCompilationContext<'a>,
 ) -> ExpressionCompilationContext<'a> {
-    let incremented = false;
+    let mut incremented = false;
 
     let cloned_settings_block = ctx.component_def.settings.clone();
-    let cloned_inline_settings = ctx.active_node_def.settings.clone();
-    let mut merged_settings =
-        merge_inline_settings_with_settings_block(&cloned_inline_settings, &cloned_settings_block);
+    let active_pascal_identifier = ctx
+        .all_components
+        .get(&ctx.active_node_def.type_id)
+        .map(|component| component.pascal_identifier.clone());
+    let mut merged_settings = merge_inline_settings_with_settings_block(
+        &ctx.active_node_def,
+        &cloned_settings_block,
+        active_pascal_identifier.as_deref(),
+    );
     let mut cloned_control_flow_settings = ctx.active_node_def.control_flow_settings.clone();
 
     if let Some(ref mut inline_settings) = merged_settings {
@@ -322,7 +526,7 @@ fn recurse_compile_expressions<'a>(
 
         // Definitions are stored modally as `Option<T>`s in ControlFlowAttributeValueDefinition,
         // so: iff `repeat_source_definition` is present, then we can assume this is a Repeat element
-        if let Some(ref mut repeat_source_definition) = &mut cfa.repeat_source_definition {
+        if cfa.repeat_source_definition.is_some() {
             // Examples:
             // for (elem, i) in self.elements
             //  - must be a symbolic identifier, such as `elements` or `self.elements`
@@ -330,9 +534,6 @@ fn recurse_compile_expressions<'a>(
             //  - may use an integer literal or symbolic identifier in either position
             //  - must use an exclusive (..) range operator (inclusive could be supported; effort required)
 
-            let id = ctx.uid_gen.next().unwrap();
-            repeat_source_definition.vtable_id = Some(id);
-
             // Handle the `self.some_data_source` in `for (elem, i) in self.some_data_source`
             let repeat_source_definition = cfa.repeat_source_definition.as_ref().unwrap();
             // todo!("map 'this is a source' into a flag for codegen, so we can rewrap Rc<>s");
@@ -348,6 +549,14 @@ fn recurse_compile_expressions<'a>(
                     TypeDefinition::builtin_range_isize(),
                 )
             } else if let Some(symbolic_binding) = &repeat_source_definition.symbolic_binding {
+                //Without a source, the predicate's symbols can't be typed, and neither can the expressions
+                //of descendants that read them, so they are skipped rather than reported as unresolved too
+                if let Some(err) =
+                    find_unresolved_symbol(symbolic_binding.trim(), symbolic_binding, &ctx)
+                {
+                    ctx.unresolved_symbol_errors.push(err);
+                    return ctx;
+                }
                 let inner_iterable_type_id = ctx
                     .resolve_symbol_as_prop_def(symbolic_binding)
                     .unwrap()
@@ -376,7 +585,7 @@ fn recurse_compile_expressions<'a>(
             //with the parser that we are only binding to a simple symbolic id, like `self.foo`.
             //This is because we are inferring the return type of this expression based on the declared-and-known
             //type of property `self.foo`
-            let (output_statement, invocations) = compile_paxel_to_ril(&paxel, &ctx);
+            let (output_statement, invocations) = compile_paxel_to_ril(&paxel, &mut ctx);
 
             // Attach shadowed property symbols to the scope_stack, so e.g. `elem` can be
             // referred to with the symbol `elem` in PAXEL
@@ -395,11 +604,15 @@ fn recurse_compile_expressions<'a>(
                         flags: PropertyDefinitionFlags {
                             is_binding_repeat_i: false,
                             is_binding_repeat_elem: true,
+                            is_binding_repeat_len: false,
+                            destructured_repeat_elem: None,
                             is_repeat_source_range,
                             is_repeat_source_iterable,
                             is_property_wrapped: true,
+                            is_readonly: false,
                         },
                         type_id: "isize".to_string(),
+                        default_value: None,
                     };
 
                     let scope = HashMap::from([
@@ -437,10 +650,14 @@ fn recurse_compile_expressions<'a>(
                         flags: PropertyDefinitionFlags {
                             is_binding_repeat_elem: true,
                             is_binding_repeat_i: false,
+                            is_binding_repeat_len: false,
+                            destructured_repeat_elem: None,
                             is_repeat_source_range,
                             is_repeat_source_iterable,
                             is_property_wrapped: true,
+                            is_readonly: false,
                         },
+                        default_value: None,
                     };
 
                     let mut i_property_definition =
@@ -448,9 +665,12 @@ fn recurse_compile_expressions<'a>(
                     i_property_definition.flags = PropertyDefinitionFlags {
                         is_binding_repeat_i: true,
                         is_binding_repeat_elem: false,
+                        is_binding_repeat_len: false,
+                        destructured_repeat_elem: None,
                         is_repeat_source_range,
                         is_repeat_source_iterable,
                         is_property_wrapped: true,
+                        is_readonly: false,
                     };
 
                     ctx.scope_stack.push(HashMap::from([
@@ -460,8 +680,103 @@ fn recurse_compile_expressions<'a>(
                         (index_id.clone(), i_property_definition),
                     ]));
                 }
+                ControlFlowRepeatPredicateDefinition::ElemTupleIndexId(elem_ids, index_id) => {
+                    //for ((name, count), i) in self.pairs
+                    //each of `name` and `count` is bound to its member of the tuple `elem`, which
+                    //is the iterable type T of the symbolic binding `self.pairs` : Property<Vec<T>>
+                    let symbolic_binding = repeat_source_definition
+                        .symbolic_binding
+                        .as_ref()
+                        .expect("Tuple destructuring in `for` requires a symbolic source, like `self.pairs`");
+                    let iterable_type = ctx
+                        .resolve_symbol_as_prop_def(symbolic_binding)
+                        .unwrap_or_else(|| panic!("Property not found: {}", symbolic_binding))
+                        .last()
+                        .unwrap()
+                        .get_inner_iterable_type_definition(ctx.type_table)
+                        .unwrap()
+                        .clone();
+
+                    if !iterable_type.type_id.starts_with('(')
+                        || iterable_type.property_definitions.len() != elem_ids.len()
+                    {
+                        panic!(
+                            "Unable to destructure `({})` from elements of `{}`, of type `{}`",
+                            elem_ids.join(", "),
+                            symbolic_binding,
+                            iterable_type.type_id
+                        );
+                    }
+
+                    let mut scope: HashMap<String, PropertyDefinition> = elem_ids
+                        .iter()
+                        .zip(iterable_type.property_definitions.iter())
+                        .enumerate()
+                        .map(|(index, (elem_id, member_property_definition))| {
+                            let member_property_definition = PropertyDefinition {
+                                name: elem_id.clone(),
+                                type_id: member_property_definition.type_id.clone(),
+                                flags: PropertyDefinitionFlags {
+                                    is_binding_repeat_elem: true,
+                                    is_binding_repeat_i: false,
+                                    is_binding_repeat_len: false,
+                                    destructured_repeat_elem: Some(
+                                        DestructuredRepeatElemDefinition {
+                                            tuple_type_id: iterable_type.type_id.clone(),
+                                            index,
+                                        },
+                                    ),
+                                    is_repeat_source_range,
+                                    is_repeat_source_iterable,
+                                    is_property_wrapped: true,
+                                    is_readonly: false,
+                                },
+                                default_value: None,
+                            };
+                            (elem_id.clone(), member_property_definition)
+                        })
+                        .collect();
+
+                    let mut i_property_definition =
+                        PropertyDefinition::primitive_with_name("usize", index_id);
+                    i_property_definition.flags = PropertyDefinitionFlags {
+                        is_binding_repeat_i: true,
+                        is_binding_repeat_elem: false,
+                        is_binding_repeat_len: false,
+                        destructured_repeat_elem: None,
+                        is_repeat_source_range,
+                        is_repeat_source_iterable,
+                        is_property_wrapped: true,
+                        is_readonly: false,
+                    };
+                    //`i` property (by specified name)
+                    scope.insert(index_id.clone(), i_property_definition);
+
+                    ctx.scope_stack.push(scope);
+                }
             };
 
+            //`len` property, unless the predicate already binds a symbol of that name
+            let mut len_property_definition =
+                PropertyDefinition::primitive_with_name("usize", REPEAT_LEN_SYMBOL);
+            len_property_definition.flags = PropertyDefinitionFlags {
+                is_binding_repeat_i: false,
+                is_binding_repeat_elem: false,
+                is_binding_repeat_len: true,
+                destructured_repeat_elem: None,
+                is_repeat_source_range,
+                is_repeat_source_iterable,
+                is_property_wrapped: true,
+                is_readonly: false,
+            };
+            ctx.scope_stack
+                .last_mut()
+                .unwrap()
+                .entry(REPEAT_LEN_SYMBOL.to_string())
+                .or_insert(len_property_definition);
+            ctx.scope_fingerprint = None;
+            incremented = true;
+
             // The return type for a repeat source expression will either be:
             //   1. isize, for ranges (including ranges with direct symbolic references as either operand, like `self.x..10`)
             //   2. T for a direct symbolic reference to `self.x` for x : Property<Vec<T>>
@@ -473,10 +788,10 @@ fn recurse_compile_expressions<'a>(
             let mut whitespace_removed_input = paxel.clone();
             whitespace_removed_input.retain(|c| !c.is_whitespace());
 
-            ctx.expression_specs.insert(
-                id,
+            let id = register_expression_spec(
+                &mut ctx,
                 ExpressionSpec {
-                    id,
+                    id: 0,
                     pascalized_return_type: return_type.type_id_escaped,
                     invocations,
                     output_statement,
@@ -485,44 +800,69 @@ fn recurse_compile_expressions<'a>(
                     repeat_source_iterable_type_id_escaped,
                 },
             );
-        } else if let Some(condition_expression_paxel) = &cfa.condition_expression_paxel {
-            //Handle `if` boolean expression, e.g. the `num_clicks > 5` in `if num_clicks > 5 { ... }`
-            let (output_statement, invocations) =
-                compile_paxel_to_ril(&condition_expression_paxel, &ctx);
-            let id = ctx.uid_gen.next().unwrap();
+            if let Some(repeat_source_definition) = &mut cfa.repeat_source_definition {
+                repeat_source_definition.vtable_id = Some(id);
 
-            cfa.condition_expression_vtable_id = Some(id);
+                // Handle the optional `item.id` in `for item in self.items key={item.id}`.  This is compiled
+                // after the predicate's symbols are pushed to the scope_stack, so that it may refer to them.
+                // Keys are normalized to their `Debug` representation, so that any key type can be compared.
+                if let Some(key_expression_paxel) = &repeat_source_definition.key_expression_paxel {
+                    let (output_statement, invocations) =
+                        compile_paxel_to_ril(key_expression_paxel, &mut ctx);
 
-            let mut whitespace_removed_input = condition_expression_paxel.clone();
-            whitespace_removed_input.retain(|c| !c.is_whitespace());
+                    let mut whitespace_removed_input = key_expression_paxel.clone();
+                    whitespace_removed_input.retain(|c| !c.is_whitespace());
 
-            ctx.expression_specs.insert(
-                id,
-                ExpressionSpec {
-                    id,
-                    pascalized_return_type: "bool".to_string(),
-                    invocations,
-                    output_statement,
-                    input_statement: whitespace_removed_input,
-                    is_repeat_source_iterable_expression: false,
-                    repeat_source_iterable_type_id_escaped: "".to_string(),
-                },
-            );
+                    let id = register_expression_spec(
+                        &mut ctx,
+                        ExpressionSpec {
+                            id: 0,
+                            pascalized_return_type: "String".to_string(),
+                            invocations,
+                            output_statement: format!("format!(\"{{:?}}\", {})", output_statement),
+                            input_statement: whitespace_removed_input,
+                            is_repeat_source_iterable_expression: false,
+                            repeat_source_iterable_type_id_escaped: "".to_string(),
+                        },
+                    );
+                    repeat_source_definition.key_vtable_id = Some(id);
+                }
+            }
+        } else if let Some(conditional_arms) = &mut cfa.conditional_arms {
+            //Handle `if` / `else if` boolean expressions, e.g. the `num_clicks > 5` in `if num_clicks > 5 { ... }`
+            for arm in conditional_arms.iter_mut() {
+                let (output_statement, invocations) =
+                    compile_paxel_to_ril(&arm.condition_expression_paxel, &mut ctx);
+
+                let mut whitespace_removed_input = arm.condition_expression_paxel.clone();
+                whitespace_removed_input.retain(|c| !c.is_whitespace());
+
+                let id = register_expression_spec(
+                    &mut ctx,
+                    ExpressionSpec {
+                        id: 0,
+                        pascalized_return_type: "bool".to_string(),
+                        invocations,
+                        output_statement,
+                        input_statement: whitespace_removed_input,
+                        is_repeat_source_iterable_expression: false,
+                        repeat_source_iterable_type_id_escaped: "".to_string(),
+                    },
+                );
+                arm.condition_expression_vtable_id = Some(id);
+            }
         } else if let Some(slot_index_expression_paxel) = &cfa.slot_index_expression_paxel {
             //Handle `if` boolean expression, e.g. the `num_clicks > 5` in `if num_clicks > 5 { ... }`
             let (output_statement, invocations) =
-                compile_paxel_to_ril(&slot_index_expression_paxel, &ctx);
-            let id = ctx.uid_gen.next().unwrap();
-
-            cfa.slot_index_expression_vtable_id = Some(id);
+                compile_paxel_to_ril(slot_index_expression_paxel, &mut ctx);
 
             let mut whitespace_removed_input = slot_index_expression_paxel.clone();
             whitespace_removed_input.retain(|c| !c.is_whitespace());
 
-            ctx.expression_specs.insert(
-                id,
+            let id = register_expression_spec(
+                &mut ctx,
                 ExpressionSpec {
-                    id,
+                    id: 0,
                     pascalized_return_type: "Numeric".to_string(),
                     invocations,
                     output_statement,
@@ -531,6 +871,7 @@ fn recurse_compile_expressions<'a>(
                     repeat_source_iterable_type_id_escaped: "".to_string(),
                 },
             );
+            cfa.slot_index_expression_vtable_id = Some(id);
         } else {
             unreachable!("encountered invalid control flow definition")
         }
@@ -572,18 +913,55 @@ fn recurse_compile_expressions<'a>(
 
     if incremented {
         ctx.scope_stack.pop();
+        ctx.scope_fingerprint = None;
     }
     ctx
 }
 
+/// For an expression consisting solely of an index access, like `self.items[i]`, returns the
+/// escaped type_id of the accessed element, i.e. the inner iterable type of the indexed `Vec`
+fn resolve_index_access_return_type(
+    paxel: &str,
+    ctx: &ExpressionCompilationContext,
+) -> Option<String> {
+    let paxel = paxel.trim();
+    let xo_symbol = PaxParser::parse(Rule::xo_symbol, paxel).ok()?.next()?;
+    if xo_symbol.as_str() != paxel || !clean_and_split_symbols(paxel).last()?.ends_with(']') {
+        return None;
+    }
+    let prop_def_chain = ctx.resolve_symbol_as_prop_def(paxel)?;
+    Some(
+        prop_def_chain
+            .last()?
+            .get_type_definition(ctx.type_table)
+            .type_id_escaped
+            .clone(),
+    )
+}
+
 /// From a symbol like `num_clicks` or `self.num_clicks`, populate an ExpressionSpecInvocation
 fn resolve_symbol_as_invocation(
     sym: &str,
     ctx: &ExpressionCompilationContext,
 ) -> ExpressionSpecInvocation {
-    //Handle built-ins, like $container
-    if BUILTIN_MAP.contains_key(sym) {
-        unimplemented!("Built-ins like $bounds are not yet supported")
+    //Handle built-ins, like $frames_elapsed
+    if let Some((builtin_type_id, builtin_accessor_literal)) = resolve_builtin(sym, ctx) {
+        let escaped_identifier = escape_identifier(clean_and_split_symbols(sym).join("."));
+        ExpressionSpecInvocation {
+            root_identifier: sym.to_string(),
+            is_numeric: ExpressionSpecInvocation::is_numeric(builtin_type_id),
+            is_primitive_nonnumeric: ExpressionSpecInvocation::is_primitive_nonnumeric(
+                builtin_type_id,
+            ),
+            escaped_identifier,
+            stack_offset: 0,
+            iterable_type_id_escaped: "".to_string(),
+            properties_coproduct_type: "".to_string(),
+            property_flags: PropertyDefinitionFlags::default(),
+            nested_symbol_tail_literal: "".to_string(),
+            is_nested_numeric: false,
+            builtin_accessor_literal: Some(builtin_accessor_literal.to_string()),
+        }
     } else {
         let prop_def_chain = ctx
             .resolve_symbol_as_prop_def(&sym)
@@ -595,19 +973,24 @@ fn resolve_symbol_as_invocation(
         let split_symbols = clean_and_split_symbols(&sym);
         let escaped_identifier = escape_identifier(split_symbols.join("."));
 
-        let mut split_symbols = split_symbols.into_iter();
-        let root_identifier = split_symbols.next().unwrap().to_string();
+        let root_identifier = split_index_accesses(&split_symbols[0]).0.to_string();
         let root_prop_def = prop_def_chain.first().unwrap();
 
         let properties_coproduct_type = ctx.component_def.type_id_escaped.clone();
 
-        let iterable_type_id_escaped = if root_prop_def.flags.is_binding_repeat_elem {
-            escape_identifier(root_prop_def.type_id.clone())
-        } else if root_prop_def.flags.is_binding_repeat_i {
-            "usize".to_string()
-        } else {
-            "".to_string()
-        };
+        let iterable_type_id_escaped =
+            if let Some(destructured_repeat_elem) = &root_prop_def.flags.destructured_repeat_elem {
+                //the datum is the whole tuple, from which this member is accessed by position
+                escape_identifier(destructured_repeat_elem.tuple_type_id.clone())
+            } else if root_prop_def.flags.is_binding_repeat_elem {
+                escape_identifier(root_prop_def.type_id.clone())
+            } else if root_prop_def.flags.is_binding_repeat_i
+                || root_prop_def.flags.is_binding_repeat_len
+            {
+                "usize".to_string()
+            } else {
+                "".to_string()
+            };
 
         let mut found_depth: Option<usize> = None;
         let mut current_depth = 0;
@@ -636,16 +1019,29 @@ fn resolve_symbol_as_invocation(
             .last()
             .unwrap();
 
+        //walk the nested symbols and index accesses alongside their PropertyDefinitions, skipping the root
         let mut nested_symbol_tail_literal = "".to_string();
-        prop_def_chain.iter().enumerate().for_each(|(i, elem)| {
-            if i > 0 && i < prop_def_chain.len() {
-                nested_symbol_tail_literal += &if elem.flags.is_property_wrapped {
-                    format!(".{}.get()", elem.name)
-                } else {
-                    format!(".{}", elem.name)
-                };
+        let mut nested_prop_defs = prop_def_chain.iter().skip(1);
+        for (depth, atomic_symbol) in split_symbols.iter().enumerate() {
+            let (name, indices) = split_index_accesses(atomic_symbol);
+            if depth > 0 {
+                nested_symbol_tail_literal +=
+                    &if nested_prop_defs.next().unwrap().flags.is_property_wrapped {
+                        format!(".{}.get()", name)
+                    } else {
+                        format!(".{}", name)
+                    };
             }
-        });
+            for index in indices {
+                nested_prop_defs.next();
+                //out-of-bounds (including negative) indices evaluate to the element type's default
+                let (index_ril, _) = crate::parsing::run_pratt_parser(index);
+                nested_symbol_tail_literal += &format!(
+                    ".get(({}).get_as_int() as usize).cloned().unwrap_or_default()",
+                    index_ril
+                );
+            }
+        }
         if nested_symbol_tail_literal != "" {
             nested_symbol_tail_literal += ".clone()"
         }
@@ -663,30 +1059,340 @@ fn resolve_symbol_as_invocation(
             property_flags,
             nested_symbol_tail_literal,
             is_nested_numeric,
+            builtin_accessor_literal: None,
         }
     }
 }
 
-/// Returns (RIL string, list of invocation specs for any symbols used)
+/// Returns (RIL string, list of invocation specs for any symbols used), from `ctx.expression_cache` if
+/// this expression was already compiled in an identical scope
 fn compile_paxel_to_ril<'a>(
     paxel: &str,
-    ctx: &ExpressionCompilationContext<'a>,
+    ctx: &mut ExpressionCompilationContext<'a>,
 ) -> (String, Vec<ExpressionSpecInvocation>) {
-    //1. run Pratt parser; generate output RIL and collected symbolic_ids
-    let (output_string, symbolic_ids) = crate::parsing::run_pratt_parser(paxel);
+    let cache_key = ctx.get_expression_cache_key(paxel);
+    if let Some(cached) = ctx.expression_cache.get(&cache_key) {
+        return (cached.output_statement, cached.invocations);
+    }
+
+    //1. run Pratt parser; generate output RIL and collected symbolic_ids, rejecting calls to unknown functions
+    //   and unresolved symbols.  These are recorded in `ctx`, to be reported once every expression is compiled;
+    //   since compilation then fails, the RIL of an expression with errors is never used.
+    let (output_string, symbolic_ids, function_calls) =
+        crate::parsing::run_pratt_parser_with_functions(paxel, ctx.expression_functions);
+    let unresolved_symbol_errors = function_calls
+        .iter()
+        .filter_map(|(name, arg_count)| find_unresolved_function(name, *arg_count, paxel, ctx))
+        .chain(
+            symbolic_ids
+                .iter()
+                .filter_map(|sym| find_unresolved_symbol(sym.trim(), paxel, ctx)),
+        )
+        .collect::<Vec<_>>();
+    if !unresolved_symbol_errors.is_empty() {
+        ctx.unresolved_symbol_errors
+            .extend(unresolved_symbol_errors);
+        return (output_string, vec![]);
+    }
 
     //2. for each symbolic id discovered during parsing, resolve that id through scope_stack and populate an ExpressionSpecInvocation
+    //   symbols used in index expressions, like `i` in `self.items[i]`, are ordered before the indexed symbols that read them
     let invocations = symbolic_ids
         .iter()
-        .map(|sym| resolve_symbol_as_invocation(&sym.trim(), ctx))
-        .unique_by(|esi| esi.escaped_identifier.clone())
-        .sorted_by(|esi0, esi1| esi0.escaped_identifier.cmp(&esi1.escaped_identifier))
+        .map(|sym| {
+            (
+                sym.matches('[').count(),
+                resolve_symbol_as_invocation(sym.trim(), ctx),
+            )
+        })
+        .unique_by(|(_, esi)| esi.escaped_identifier.clone())
+        .sorted_by(|(depth0, esi0), (depth1, esi1)| {
+            (depth0, &esi0.escaped_identifier).cmp(&(depth1, &esi1.escaped_identifier))
+        })
+        .map(|(_, esi)| esi)
         .collect();
 
     //3. return tuple of (RIL string,ExpressionSpecInvocations)
+    ctx.expression_cache.insert(
+        cache_key,
+        CachedExpression {
+            output_statement: output_string.clone(),
+            invocations: Vec::clone(&invocations),
+        },
+    );
     (output_string, invocations)
 }
 
+/// A symbol within an expression that does not resolve to a property in scope, a nested field of one,
+/// or a built-in, or a call to a function that is not registered.  Displayed as a rustc-style diagnostic, e.g.:
+///
+/// ```text
+/// error[E0425]: cannot find value `num_clicked` in this scope
+///   --> in the template of `Example`, on `<Text>`
+///    |
+///    | self.num_clicked + 1
+///    |      ^^^^^^^^^^^ not found in this scope
+///    |
+///    = help: a symbol with a similar name exists: `num_clicks`
+/// ```
+#[derive(Debug)]
+pub struct UnresolvedSymbolError {
+    /// e.g. `error[E0425]: cannot find value `foo` in this scope`
+    pub message: String,
+    /// Shown next to the carets, e.g. `not found in this scope`
+    pub label: &'static str,
+    pub component: String,
+    pub node: String,
+    pub paxel: String,
+    /// Byte range of the unresolved symbol within `paxel`
+    pub span: Range<usize>,
+    /// Names that are similar to the unresolved symbol, closest first, suggested in its place
+    pub similar_names: Vec<String>,
+    /// What the unresolved symbol is, as named in suggestions: `symbol`, `field` for a nested field like
+    /// `bar` in `self.foo.bar`, or `function`
+    pub kind: &'static str,
+}
+
+impl std::fmt::Display for UnresolvedSymbolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line_start = self.paxel[..self.span.start]
+            .rfind('\n')
+            .map_or(0, |i| i + 1);
+        let line_end = self.paxel[self.span.start..]
+            .find('\n')
+            .map_or(self.paxel.len(), |i| self.span.start + i);
+        let line = self.paxel[line_start..line_end].trim_end();
+        let indent = line.len() - line.trim_start().len();
+        let column = self.paxel[line_start + indent..self.span.start]
+            .chars()
+            .count();
+        let width = self.paxel[self.span.start..self.span.end.min(line_end)]
+            .chars()
+            .count()
+            .max(1);
+
+        writeln!(f, "{}", self.message)?;
+        writeln!(
+            f,
+            "  --> in the template of `{}`, on `<{}>`",
+            self.component, self.node
+        )?;
+        writeln!(f, "   |")?;
+        writeln!(f, "   | {}", line.trim_start())?;
+        write!(
+            f,
+            "   | {}{} {}",
+            " ".repeat(column),
+            "^".repeat(width),
+            self.label
+        )?;
+
+        let kind = self.kind;
+        let similar_names = self
+            .similar_names
+            .iter()
+            .map(|name| format!("`{}`", name))
+            .join(", ");
+        match self.similar_names.len() {
+            0 => Ok(()),
+            1 => write!(
+                f,
+                "\n   |\n   = help: a {} with a similar name exists: {}",
+                kind, similar_names
+            ),
+            _ => write!(
+                f,
+                "\n   |\n   = help: {}s with similar names exist: {}",
+                kind, similar_names
+            ),
+        }
+    }
+}
+
+/// Returns an `UnresolvedSymbolError` if `sym`, a symbol within the expression `paxel`, does not
+/// resolve.  Index accesses into non-`Vec`s are left to `resolve_symbol_as_prop_def`.
+fn find_unresolved_symbol(
+    sym: &str,
+    paxel: &str,
+    ctx: &ExpressionCompilationContext,
+) -> Option<UnresolvedSymbolError> {
+    if resolve_builtin(sym, ctx).is_some() {
+        return None;
+    }
+
+    let sym_start = paxel.find(sym).unwrap_or(0);
+    //`self` is skipped when searching for the span of each atomic symbol within `sym`
+    let mut search_start = if sym.starts_with("self") { 4 } else { 0 };
+    let mut locate = |name: &str| {
+        let start = sym[search_start..]
+            .find(name)
+            .map_or(search_start, |i| search_start + i);
+        search_start = start + name.len();
+        sym_start + start..sym_start + start + name.len()
+    };
+    let unresolved_symbol_error = |message: String,
+                                   label: &'static str,
+                                   span: Range<usize>,
+                                   name: &str,
+                                   names_in_scope: Vec<&str>,
+                                   kind: &'static str| {
+        Some(UnresolvedSymbolError {
+            message,
+            label,
+            component: ctx.component_def.pascal_identifier.clone(),
+            node: ctx.active_node_def.pascal_identifier.clone(),
+            paxel: paxel.to_string(),
+            span,
+            similar_names: find_similar_names(name, names_in_scope),
+            kind,
+        })
+    };
+
+    let split_symbols = clean_and_split_symbols(sym);
+    let (root_symbol, root_indices) = split_index_accesses(&split_symbols[0]);
+    let root_span = locate(root_symbol);
+    let mut type_id = match ctx
+        .scope_stack
+        .iter()
+        .find_map(|frame| frame.get(root_symbol))
+    {
+        Some(root_symbol_pd) => root_symbol_pd.type_id.clone(),
+        None => {
+            let names_in_scope = ctx
+                .scope_stack
+                .iter()
+                .flat_map(|frame| frame.keys().map(String::as_str))
+                .chain(BUILTIN_MAP.keys().copied())
+                .collect();
+            return unresolved_symbol_error(
+                format!(
+                    "error[E0425]: cannot find value `{}` in this scope",
+                    root_symbol
+                ),
+                "not found in this scope",
+                root_span,
+                root_symbol,
+                names_in_scope,
+                "symbol",
+            );
+        }
+    };
+
+    let mut indices = root_indices;
+    for atomic_symbol in split_symbols[1..].iter() {
+        for _ in indices.iter() {
+            type_id = ctx
+                .type_table
+                .get(&type_id)?
+                .inner_iterable_type_id
+                .clone()?;
+        }
+        let (name, nested_indices) = split_index_accesses(atomic_symbol);
+        let span = locate(name);
+        let td = ctx.type_table.get(&type_id)?;
+        match td.property_definitions.iter().find(|pd| pd.name == name) {
+            Some(pd) => type_id = pd.type_id.clone(),
+            None => {
+                return unresolved_symbol_error(
+                    format!("error[E0609]: no field `{}` on type `{}`", name, td.type_id),
+                    "unknown field",
+                    span,
+                    name,
+                    td.property_definitions
+                        .iter()
+                        .map(|pd| pd.name.as_str())
+                        .collect(),
+                    "field",
+                );
+            }
+        }
+        indices = nested_indices;
+    }
+    None
+}
+
+/// Returns an `UnresolvedSymbolError` if `name`, an unqualified function called with `arg_count` arguments
+/// within the expression `paxel`, is not registered in `ctx.expression_functions` with that many arguments
+fn find_unresolved_function(
+    name: &str,
+    arg_count: usize,
+    paxel: &str,
+    ctx: &ExpressionCompilationContext,
+) -> Option<UnresolvedSymbolError> {
+    let start = paxel
+        .match_indices(name)
+        .map(|(i, _)| i)
+        .find(|i| paxel[i + name.len()..].trim_start().starts_with('('))
+        .unwrap_or(0);
+    let (message, label, similar_names) = match ctx.expression_functions.get(name) {
+        Some(function) if function.arg_type_ids.len() == arg_count => return None,
+        Some(function) => (
+            format!(
+                "error[E0061]: this function takes {} argument(s) but {} were supplied",
+                function.arg_type_ids.len(),
+                arg_count
+            ),
+            "called with the wrong number of arguments",
+            vec![],
+        ),
+        None => (
+            format!(
+                "error[E0425]: cannot find function `{}` in this scope",
+                name
+            ),
+            "not found in this scope",
+            find_similar_names(name, ctx.expression_functions.names().collect()),
+        ),
+    };
+    Some(UnresolvedSymbolError {
+        message,
+        label,
+        component: ctx.component_def.pascal_identifier.clone(),
+        node: ctx.active_node_def.pascal_identifier.clone(),
+        paxel: paxel.to_string(),
+        span: start..start + name.len(),
+        similar_names,
+        kind: "function",
+    })
+}
+
+/// Returns the names within `candidates` similar enough to `name` to be suggested in its place, closest first.
+/// Like rustc, a name is similar if it is within an edit distance of a third of the length of `name`.
+fn find_similar_names(name: &str, candidates: Vec<&str>) -> Vec<String> {
+    let max_distance = name.len().max(3) / 3;
+    candidates
+        .into_iter()
+        .unique()
+        .map(|candidate| {
+            (
+                get_edit_distance(name, candidate.trim_start_matches('$')),
+                candidate,
+            )
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .sorted()
+        .map(|(_, candidate)| candidate.to_string())
+        .collect()
+}
+
+/// Levenshtein distance between `a` and `b`, in chars
+fn get_edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut distances: Vec<usize> = (0..=b.len()).collect();
+    for (i, char_a) in a.chars().enumerate() {
+        let mut previous_diagonal = distances[0];
+        distances[0] = i + 1;
+        for (j, char_b) in b.iter().enumerate() {
+            let substitution = previous_diagonal + usize::from(char_a != *char_b);
+            previous_diagonal = distances[j + 1];
+            distances[j + 1] = substitution
+                .min(distances[j] + 1)
+                .min(previous_diagonal + 1);
+        }
+    }
+    distances[b.len()]
+}
+
 pub struct ExpressionCompilationContext<'a> {
     /// Current component definition, i.e. the `Component` that houses
     /// any compiled expressions and related property definitions
@@ -707,6 +1413,10 @@ pub struct ExpressionCompilationContext<'a> {
     /// to be appended to as expressions are compiled during traversal
     pub expression_specs: &'a mut HashMap<usize, ExpressionSpec>,
 
+    /// Ids of the specs in `expression_specs`, by their contents, for sharing vtable entries between
+    /// equivalent expressions
+    pub expression_spec_ids: &'a mut HashMap<ExpressionSpecKey, usize>,
+
     /// The current template node whose expressions are being compiled.  For example `<SomeNode some_property={/* some expression */} />`
     pub active_node_def: TemplateNodeDefinition,
 
@@ -715,29 +1425,114 @@ pub struct ExpressionCompilationContext<'a> {
 
     /// Type table, used for looking up property types by string type_ids
     pub type_table: &'a TypeTable,
+
+    /// Expressions compiled by previous builds, reused when their inputs are unchanged
+    pub expression_cache: &'a mut ExpressionCache,
+
+    /// Host functions callable from expressions, by name
+    pub expression_functions: &'a ExpressionFunctionRegistry,
+
+    /// Memoized hash of `scope_stack` and the types reachable from it, for keying `expression_cache`.
+    /// Reset to `None` whenever `scope_stack` changes
+    pub scope_fingerprint: Option<u64>,
+
+    /// Unresolved symbols and functions found so far, all reported once compilation is complete
+    pub unresolved_symbol_errors: Vec<UnresolvedSymbolError>,
 }
 
 lazy_static! {
-    static ref BUILTIN_MAP : HashMap<&'static str, ()> = HashMap::from([
-        //TODO! hook into real runtime logic here instead of PropertyDefinition::default.
-        //      this probably requires referring to event handlers instead of directly to PropertyDefinition via HashMap<String, PropertyDefinition>
-        ("$container",())
+    /// Engine-provided symbols, usable in any expression without declaring a corresponding property.
+    /// Maps each symbol to its (type_id, RIL accessor), where the accessor reads the value off of the
+    /// `ExpressionContext` (`ec`) under evaluation:
+    ///
+    /// - `$frames_elapsed` (`usize`): the number of engine ticks since the program started
+    /// - `$time` (`f64`): the wall-clock seconds since the program started, as reported by the chassis
+    /// - `$viewport_width` (`f64`): the width of the viewport in px
+    /// - `$viewport_height` (`f64`): the height of the viewport in px
+    /// - `$device_pixel_ratio` (`f64`): physical px per logical px on the rendering device, `1.0` unless reported otherwise by the chassis
+    ///
+    /// The `$` may be omitted (e.g. `frames_elapsed * 0.01`) as long as no property in scope shares the name.
+    static ref BUILTIN_MAP : HashMap<&'static str, (&'static str, &'static str)> = HashMap::from([
+        ("$frames_elapsed", ("usize", "ec.read_frames_elapsed()")),
+        ("$time", ("f64", "ec.read_seconds_elapsed()")),
+        ("$viewport_width", ("f64", "ec.engine.get_viewport_bounds().0")),
+        ("$viewport_height", ("f64", "ec.engine.get_viewport_bounds().1")),
+        ("$device_pixel_ratio", ("f64", "ec.engine.device_pixel_ratio")),
     ]);
 }
 
+/// Returns the (type_id, RIL accessor) for `sym` if it refers to a built-in, either explicitly, like `$time`,
+/// or implicitly, like `time`, when no property in scope shadows it
+fn resolve_builtin(
+    sym: &str,
+    ctx: &ExpressionCompilationContext,
+) -> Option<(&'static str, &'static str)> {
+    if let Some(builtin) = BUILTIN_MAP.get(sym) {
+        Some(*builtin)
+    } else if !sym.contains(".") && ctx.resolve_symbol_as_prop_def(sym).is_none() {
+        BUILTIN_MAP.get(format!("${}", sym).as_str()).copied()
+    } else {
+        None
+    }
+}
+
 pub fn clean_and_split_symbols(possibly_nested_symbols: &str) -> Vec<String> {
+    let mut possibly_nested_symbols = possibly_nested_symbols.to_string();
+    possibly_nested_symbols.retain(|c| !c.is_whitespace());
     let entire_symbol = if possibly_nested_symbols.starts_with("self.") {
         possibly_nested_symbols.replacen("self.", "", 1)
     } else if possibly_nested_symbols.starts_with("this.") {
         possibly_nested_symbols.replacen("this.", "", 1)
     } else {
-        possibly_nested_symbols.to_string()
+        possibly_nested_symbols
     };
 
-    entire_symbol
-        .split(".")
-        .map(|atomic_symbol| atomic_symbol.to_string())
-        .collect::<Vec<_>>()
+    //split on `.`, except within index expressions, like the `.` in `items[self.selected].name`
+    let mut split_symbols = vec!["".to_string()];
+    let mut bracket_depth = 0;
+    for c in entire_symbol.chars() {
+        match c {
+            '.' if bracket_depth == 0 => split_symbols.push("".to_string()),
+            _ => {
+                if c == '[' {
+                    bracket_depth += 1;
+                } else if c == ']' {
+                    bracket_depth -= 1;
+                }
+                split_symbols.last_mut().unwrap().push(c);
+            }
+        }
+    }
+    split_symbols
+}
+
+/// Splits an atomic symbol like `items[i][0]` into its name, `items`, and the PAXEL of
+/// each of its index expressions, `["i", "0"]`
+pub fn split_index_accesses(atomic_symbol: &str) -> (&str, Vec<&str>) {
+    let (name, mut remaining) = match atomic_symbol.find('[') {
+        Some(bracket) => atomic_symbol.split_at(bracket),
+        None => return (atomic_symbol, vec![]),
+    };
+
+    let mut indices = vec![];
+    while !remaining.is_empty() {
+        let mut bracket_depth = 0;
+        let close = remaining
+            .char_indices()
+            .find(|(_, c)| {
+                if *c == '[' {
+                    bracket_depth += 1;
+                } else if *c == ']' {
+                    bracket_depth -= 1;
+                }
+                bracket_depth == 0
+            })
+            .unwrap()
+            .0;
+        indices.push(&remaining[1..close]);
+        remaining = &remaining[close + 1..];
+    }
+    (name, indices)
 }
 
 impl<'a> ExpressionCompilationContext<'a> {
@@ -749,11 +1544,11 @@ impl<'a> ExpressionCompilationContext<'a> {
         let split_symbols = clean_and_split_symbols(symbol);
         let mut split_symbols = split_symbols.iter();
 
-        let root_symbol = split_symbols.next().unwrap();
+        let (root_symbol, root_indices) = split_index_accesses(split_symbols.next().unwrap());
 
-        let root_symbol_pd = if BUILTIN_MAP.contains_key(root_symbol.as_str()) {
-            // resolve root symbol through builtin map
-            None //FUTURE: support built-ins
+        let root_symbol_pd = if BUILTIN_MAP.contains_key(root_symbol) {
+            // built-ins are not backed by a PropertyDefinition; see `resolve_builtin`
+            None
         } else {
             // resolve through scope stack
             let mut found = false;
@@ -775,27 +1570,419 @@ impl<'a> ExpressionCompilationContext<'a> {
             ret
         };
 
-        // handle nested symbols like `foo.bar`.
+        // handle nested symbols like `foo.bar`, and index accesses like `foo[0]`
         match root_symbol_pd {
             Some(root_symbol_pd) => {
                 let mut ret = vec![root_symbol_pd];
+                self.push_index_accesses(&mut ret, &root_indices, symbol);
                 //return terminal nested symbol's PropertyDefinition, or root's if there are no nested symbols
-                while let Some(atomic_symbol) = split_symbols.next() {
+                for atomic_symbol in split_symbols {
+                    let (name, indices) = split_index_accesses(atomic_symbol);
                     let td = ret.last().unwrap().get_type_definition(self.type_table);
                     ret.push(
                         td.property_definitions
                             .iter()
-                            .find(|pd| pd.name == *atomic_symbol)
-                            .expect(&format!(
-                                "Unable to resolve nested symbol `{}` while evaluating `{}`.",
-                                atomic_symbol, symbol
-                            ))
+                            .find(|pd| pd.name == name)
+                            .unwrap_or_else(|| {
+                                panic!(
+                                    "Unable to resolve nested symbol `{}` while evaluating `{}`.",
+                                    name, symbol
+                                )
+                            })
                             .clone(),
                     );
+                    self.push_index_accesses(&mut ret, &indices, symbol);
                 }
                 Some(ret)
             }
             None => None,
         }
     }
+
+    /// For each index access into the last element of `prop_def_chain`, like the `[i]` in `items[i]`,
+    /// appends a PropertyDefinition describing the accessed element, i.e. of the `Vec`'s inner iterable type
+    fn push_index_accesses(
+        &self,
+        prop_def_chain: &mut Vec<PropertyDefinition>,
+        indices: &[&str],
+        symbol: &str,
+    ) {
+        for index in indices {
+            let element_type_id = prop_def_chain
+                .last()
+                .unwrap()
+                .get_inner_iterable_type_definition(self.type_table)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Unable to index into `{}` while evaluating `{}`; only `Vec`s may be indexed.",
+                        prop_def_chain.last().unwrap().name,
+                        symbol
+                    )
+                })
+                .type_id
+                .clone();
+            prop_def_chain.push(PropertyDefinition::primitive_with_name(
+                &element_type_id,
+                &format!("[{}]", index),
+            ));
+        }
+    }
+
+    /// Key for `paxel` in `expression_cache`: a hash of the expression with everything its compilation reads,
+    /// that is the containing component, the symbols in scope and the types reachable from them.  Changes to
+    /// types that the expression cannot reach, e.g. in another component, leave the key unchanged.
+    fn get_expression_cache_key(&mut self, paxel: &str) -> String {
+        if self.scope_fingerprint.is_none() {
+            self.scope_fingerprint = Some(self.get_scope_fingerprint());
+        }
+        let mut hasher = DefaultHasher::new();
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        paxel.hash(&mut hasher);
+        self.component_def.type_id_escaped.hash(&mut hasher);
+        self.scope_fingerprint.hash(&mut hasher);
+        self.expression_functions.hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    fn get_scope_fingerprint(&self) -> u64 {
+        // Sorted, so that the fingerprint doesn't depend on HashMap iteration order
+        let scope_stack: Vec<BTreeMap<&String, &PropertyDefinition>> = self
+            .scope_stack
+            .iter()
+            .map(|frame| frame.iter().collect())
+            .collect();
+
+        let mut reachable_types: BTreeMap<&String, &TypeDefinition> = BTreeMap::new();
+        let mut pending_type_ids: Vec<&String> = scope_stack
+            .iter()
+            .flat_map(|frame| frame.values().map(|pd| &pd.type_id))
+            .collect();
+        while let Some(type_id) = pending_type_ids.pop() {
+            if reachable_types.contains_key(type_id) {
+                continue;
+            }
+            if let Some(type_def) = self.type_table.get(type_id) {
+                reachable_types.insert(type_id, type_def);
+                pending_type_ids.extend(type_def.inner_iterable_type_id.iter());
+                pending_type_ids.extend(type_def.property_definitions.iter().map(|pd| &pd.type_id));
+            }
+        }
+
+        let mut hasher = DefaultHasher::new();
+        serde_json::to_string(&(scope_stack, reachable_types))
+            .unwrap()
+            .hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Location of the `ExpressionCache` within the `.pax` directory
+const EXPRESSION_CACHE_PATH: &str = "expr-cache/expressions.json";
+
+/// Compiled expressions persisted between builds under `.pax/expr-cache`, keyed by a hash of each
+/// expression's input (see `ExpressionCompilationContext::get_expression_cache_key`), so that
+/// incremental builds only recompile the expressions whose input or reachable types have changed
+#[derive(Serialize, Deserialize, Default)]
+pub struct ExpressionCache {
+    entries: HashMap<String, CachedExpression>,
+
+    /// Keys read or written since loading.  Only these entries are saved, which prunes any stale entries
+    #[serde(skip)]
+    used_keys: HashSet<String>,
+
+    /// Number of expressions reused from the cache since loading
+    #[serde(skip)]
+    pub hits: usize,
+}
+
+/// The compiled portion of an `ExpressionSpec`, i.e. everything but its vtable id and return type
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CachedExpression {
+    pub output_statement: String,
+    pub invocations: Vec<ExpressionSpecInvocation>,
+}
+
+impl ExpressionCache {
+    /// Loads the cache from `pax_dir`, or returns an empty cache if none exists or it is unreadable,
+    /// e.g. after a change to its format
+    pub fn load(pax_dir: &Path) -> Self {
+        fs::read_to_string(Self::get_path(pax_dir))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes each entry used since loading to `pax_dir`
+    pub fn save(&self, pax_dir: &Path) -> std::io::Result<()> {
+        let used_entries = ExpressionCache {
+            entries: self
+                .entries
+                .iter()
+                .filter(|(key, _)| self.used_keys.contains(*key))
+                .map(|(key, entry)| (key.clone(), entry.clone()))
+                .collect(),
+            ..Default::default()
+        };
+        let path = Self::get_path(pax_dir);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, serde_json::to_string(&used_entries)?)
+    }
+
+    fn get_path(pax_dir: &Path) -> PathBuf {
+        pax_dir.join(EXPRESSION_CACHE_PATH)
+    }
+
+    fn get(&mut self, key: &str) -> Option<CachedExpression> {
+        let entry = self.entries.get(key)?.clone();
+        self.used_keys.insert(key.to_string());
+        self.hits += 1;
+        Some(entry)
+    }
+
+    fn insert(&mut self, key: String, entry: CachedExpression) {
+        self.used_keys.insert(key.clone());
+        self.entries.insert(key, entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        clean_and_split_symbols, compile_all_expressions, compile_all_expressions_with_cache,
+        split_index_accesses, ExpressionCache,
+    };
+    use crate::manifest::{PaxManifest, PropertyDefinition, ValueDefinition};
+    use crate::parsing::{run_pratt_parser_with_functions, ParsingContext};
+    use crate::tests::build_test_manifest;
+    use crate::{find_expression_compilation_errors, get_expression_functions, PaxBuildError};
+    use itertools::Itertools;
+
+    #[test]
+    fn test_split_index_accesses() {
+        assert_eq!(
+            clean_and_split_symbols("self.items[self.selected].name"),
+            vec!["items[self.selected]", "name"]
+        );
+        assert_eq!(
+            split_index_accesses("grid[i][self.cols[j]]"),
+            ("grid", vec!["i", "self.cols[j]"])
+        );
+        assert_eq!(split_index_accesses("items"), ("items", vec![]));
+    }
+
+    #[test]
+    fn test_expression_cache() {
+        let build_manifest = |total_type_id: &str| {
+            let pax = r#"
+                <Rectangle count={self.total * 2} />
+                <Rectangle count={self.total + 1} />
+            "#;
+            build_test_manifest(
+                ParsingContext::default(),
+                pax,
+                "Example",
+                true,
+                vec![PropertyDefinition::primitive_with_name(
+                    total_type_id,
+                    "total",
+                )],
+                vec![(
+                    "pax_std::primitives::Rectangle",
+                    vec![PropertyDefinition::primitive_with_name("usize", "count")],
+                )],
+            )
+        };
+        let get_output_statements = |manifest: &PaxManifest| {
+            manifest
+                .expression_specs
+                .as_ref()
+                .unwrap()
+                .values()
+                .map(|spec| spec.output_statement.clone())
+                .sorted()
+                .collect::<Vec<_>>()
+        };
+
+        let mut manifest = build_manifest("usize");
+        let mut expression_cache = ExpressionCache::default();
+        compile_all_expressions_with_cache(&mut manifest, &mut expression_cache).unwrap();
+        assert_eq!(expression_cache.hits, 0);
+        let output_statements = get_output_statements(&manifest);
+
+        let pax_dir = std::env::temp_dir().join(format!("pax-expr-cache-{}", uuid::Uuid::new_v4()));
+        expression_cache.save(&pax_dir).unwrap();
+        let mut expression_cache = ExpressionCache::load(&pax_dir);
+        let mut manifest = build_manifest("usize");
+        compile_all_expressions_with_cache(&mut manifest, &mut expression_cache).unwrap();
+        assert_eq!(expression_cache.hits, 2);
+        assert_eq!(get_output_statements(&manifest), output_statements);
+
+        // Changing the type of a symbol in scope invalidates the expressions that could read it
+        let mut expression_cache = ExpressionCache::load(&pax_dir);
+        let mut manifest = build_manifest("f64");
+        compile_all_expressions_with_cache(&mut manifest, &mut expression_cache).unwrap();
+        assert_eq!(expression_cache.hits, 0);
+        std::fs::remove_dir_all(&pax_dir).unwrap();
+    }
+
+    #[test]
+    fn test_equivalent_expressions_share_vtable_ids() {
+        let pax = r#"
+            <Rectangle count={self.total * 2} />
+            <Rectangle count={self.total + 1} />
+            <Rectangle count={self.total * 2} />
+            <Rectangle count={self.total} />
+        "#;
+        let mut manifest = build_test_manifest(
+            ParsingContext::default(),
+            pax,
+            "Example",
+            true,
+            vec![PropertyDefinition::primitive_with_name("usize", "total")],
+            vec![(
+                "pax_std::primitives::Rectangle",
+                vec![PropertyDefinition::primitive_with_name("usize", "count")],
+            )],
+        );
+        compile_all_expressions(&mut manifest).unwrap();
+
+        // Duplicates don't take ids of their own, leaving no gaps between the ids of the vtable entries
+        let ids = manifest
+            .expression_specs
+            .as_ref()
+            .unwrap()
+            .keys()
+            .copied()
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![0, 1, 2]);
+        let bound_ids = manifest.components["crate::Example"]
+            .template
+            .as_ref()
+            .unwrap()[1..]
+            .iter()
+            .map(|tnd| match &tnd.settings.as_ref().unwrap()[0].1 {
+                ValueDefinition::Expression(_, Some(id)) => *id,
+                _ => panic!("Expected a compiled expression"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(bound_ids, vec![0, 1, 0, 2]);
+    }
+
+    #[test]
+    fn test_repeat_scope_ends_with_repeat() {
+        let pax = r#"
+            for i in 0..3 {
+                <Rectangle count={i} />
+            }
+            <Rectangle count={i} />
+        "#;
+        let mut manifest = build_test_manifest(
+            ParsingContext::default(),
+            pax,
+            "Example",
+            true,
+            vec![],
+            vec![(
+                "pax_std::primitives::Rectangle",
+                vec![PropertyDefinition::primitive_with_name("usize", "count")],
+            )],
+        );
+
+        let errors = compile_all_expressions(&mut manifest).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].paxel, "i");
+    }
+
+    #[test]
+    fn test_expression_functions() {
+        let cargo_toml = r#"
+            [package]
+            name = "pax-example"
+
+            [package.metadata.pax.expression-functions]
+            lerp = { path = "crate::math::lerp", args = ["f64", "f64", "f64"], returns = "f64" }
+            format_currency = { path = "format_currency", args = ["usize", "String"], returns = "String" }
+        "#
+        .parse::<toml_edit::Document>()
+        .unwrap();
+        let expression_functions = get_expression_functions(&cargo_toml, "pax_example");
+        assert_eq!(
+            expression_functions.names().collect::<Vec<_>>(),
+            vec!["format_currency", "lerp"]
+        );
+        assert_eq!(
+            expression_functions.get("lerp").unwrap().path,
+            "pax_example::math::lerp"
+        );
+
+        //numeric arguments are converted from `Numeric`s and numeric results wrapped back into them
+        let (output, symbolic_ids, function_calls) = run_pratt_parser_with_functions(
+            "lerp(0, 100, self.progress) * 2",
+            &expression_functions,
+        );
+        assert_eq!(
+            output,
+            "(Numeric::from(pax_example::math::lerp(Numeric::from(Numeric::from(0)).get_as_float(),Numeric::from(Numeric::from(100)).get_as_float(),Numeric::from(progress).get_as_float()))*(Numeric::from(2)).into())"
+        );
+        assert_eq!(symbolic_ids, vec!["self.progress"]);
+        assert_eq!(function_calls, vec![("lerp".to_string(), 3)]);
+        let (output, _, _) = run_pratt_parser_with_functions(
+            r#"format_currency(self.cents, "USD")"#,
+            &expression_functions,
+        );
+        assert_eq!(
+            output,
+            "pax_example::format_currency((Numeric::from(cents).get_as_int() as usize),(\"USD\".to_string()))"
+        );
+        //qualified calls are lowered as they are, and unqualified ones are left as they are when unregistered
+        let (output, _, function_calls) =
+            run_pratt_parser_with_functions("Color::rgb(1, 0, lerpp(0))", &expression_functions);
+        assert_eq!(
+            output,
+            "Color::rgb((Numeric::from(1)),(Numeric::from(0)),(lerpp((Numeric::from(0)),)),)"
+        );
+        assert_eq!(function_calls, vec![("lerpp".to_string(), 1)]);
+
+        let get_diagnostics = |template: &str| {
+            let manifest = build_test_manifest(
+                ParsingContext::default(),
+                template,
+                "Example",
+                true,
+                vec![PropertyDefinition::primitive_with_name("f64", "progress")],
+                vec![],
+            );
+            find_expression_compilation_errors(&manifest, &expression_functions)
+                .into_iter()
+                .map(|diagnostic| match diagnostic {
+                    PaxBuildError::ExpressionCompilationFailed { message, .. } => message,
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>()
+        };
+        assert!(get_diagnostics("<Example x={lerp(0, 100, self.progress)} />").is_empty());
+        assert_eq!(
+            get_diagnostics("<Example x={lerpp(0, 100, self.progress)} />"),
+            vec![
+                "error[E0425]: cannot find function `lerpp` in this scope
+  --> in the template of `Example`, on `<Example>`
+   |
+   | lerpp(0, 100, self.progress)
+   | ^^^^^ not found in this scope
+   |
+   = help: a function with a similar name exists: `lerp`"
+            ]
+        );
+        assert_eq!(
+            get_diagnostics("<Example x={1 + lerp(0, self.progress)} />"),
+            vec![
+                "error[E0061]: this function takes 3 argument(s) but 2 were supplied
+  --> in the template of `Example`, on `<Example>`
+   |
+   | 1 + lerp(0, self.progress)
+   |     ^^^^ called with the wrong number of arguments"
+            ]
+        );
+    }
 }
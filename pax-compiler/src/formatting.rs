@@ -0,0 +1,755 @@
+use std::fs;
+use std::path::PathBuf;
+
+use pest::iterators::{Pair, Pairs};
+use pest::Parser;
+
+use crate::parsing::{PaxParser, Rule};
+use crate::FormatContext;
+
+const INDENT: &str = "    ";
+
+/// Width beyond which a tag's attributes are broken out one per line
+const MAX_LINE_WIDTH: usize = 100;
+
+/// Formats each `.pax` file and `#[inlined(...)]` template at `ctx.path`, a file or a directory to
+/// search (skipping hidden directories like `.pax` and `target`), rewriting any that change.
+/// With `ctx.check`, only reports the files that would change.  Returns `Err` with each file that is
+/// unformatted in check mode, or fails to parse, if any.
+pub fn perform_format(ctx: &FormatContext) -> Result<(), Vec<PathBuf>> {
+    let mut unformatted_paths = vec![];
+    let entries = walkdir::WalkDir::new(&ctx.path)
+        .into_iter()
+        .filter_entry(|entry| {
+            let file_name = entry.file_name().to_string_lossy();
+            entry.depth() == 0 || !(file_name.starts_with('.') || file_name == "target")
+        })
+        .filter_map(Result::ok);
+    for entry in entries {
+        let format: fn(&str) -> Result<String, String> =
+            match entry.path().extension().and_then(|ext| ext.to_str()) {
+                Some("pax") => format_pax,
+                Some("rs") => format_inlined_templates,
+                _ => continue,
+            };
+        let source = fs::read_to_string(entry.path())
+            .unwrap_or_else(|err| panic!("Failed to read {}: {}", entry.path().display(), err));
+
+        match format(&source) {
+            Ok(formatted) if formatted == source => {}
+            Ok(formatted) => {
+                if ctx.check {
+                    println!("Unformatted: {}", entry.path().display());
+                    unformatted_paths.push(entry.path().to_path_buf());
+                } else {
+                    fs::write(entry.path(), formatted).unwrap_or_else(|err| {
+                        panic!("Failed to write {}: {}", entry.path().display(), err)
+                    });
+                    println!("Formatted: {}", entry.path().display());
+                }
+            }
+            Err(err) => {
+                eprintln!("Failed to format {}: {}", entry.path().display(), err);
+                unformatted_paths.push(entry.path().to_path_buf());
+            }
+        }
+    }
+
+    if unformatted_paths.is_empty() {
+        Ok(())
+    } else {
+        Err(unformatted_paths)
+    }
+}
+
+/// Parses `source` as a Pax component definition and re-serializes it in canonical style:
+/// four-space indentation, one element per line, `id` and `class` ahead of other attributes with
+/// event bindings last, `key: value,` settings and handlers, and at most one blank line between items.
+/// Expressions and literal values are preserved verbatim, as are comments between elements, settings
+/// and handlers.  Comments anywhere else, e.g. between a tag's attributes, are reported as errors
+/// rather than dropped.
+pub fn format_pax(source: &str) -> Result<String, String> {
+    let pax_component_definition = PaxParser::parse(Rule::pax_component_definition, source)
+        .map_err(|err| format!("{}", err))?
+        .next()
+        .unwrap();
+
+    let mut formatter = Formatter {
+        source,
+        output: String::new(),
+        cursor: 0,
+        indent: 0,
+        is_at_block_start: true,
+        is_blank_line_pending: false,
+    };
+    for pair in pax_component_definition.into_inner() {
+        match pair.as_rule() {
+            Rule::root_tag_pair => {
+                formatter.trivia(pair.as_span().start())?;
+                formatter.any_tag_pair(pair.into_inner().next().unwrap())?;
+            }
+            Rule::settings_block_declaration => {
+                formatter.blank_line();
+                formatter.trivia(pair.as_span().start())?;
+                formatter.settings_block(pair)?;
+            }
+            Rule::handlers_block_declaration => {
+                formatter.blank_line();
+                formatter.trivia(pair.as_span().start())?;
+                formatter.handlers_block(pair)?;
+            }
+            Rule::EOI => formatter.trivia(pair.as_span().start())?,
+            _ => unreachable!(),
+        }
+    }
+    Ok(formatter.output)
+}
+
+/// Formats the contents of each `#[inlined(...)]` template in the Rust `source` with `format_pax`,
+/// indented one level past the attribute.  The rest of `source` is left untouched.
+pub fn format_inlined_templates(source: &str) -> Result<String, String> {
+    const INLINED_ATTRIBUTE: &str = "#[inlined(";
+
+    let mut output = String::with_capacity(source.len());
+    let mut remaining = source;
+    while let Some(attribute_start) = remaining.find(INLINED_ATTRIBUTE) {
+        let contents_start = attribute_start + INLINED_ATTRIBUTE.len();
+        let contents_end = contents_start
+            + find_closing_paren(&remaining[contents_start..])
+                .ok_or_else(|| "unterminated `#[inlined(...)]` attribute".to_string())?;
+
+        let line_start = remaining[..attribute_start]
+            .rfind('\n')
+            .map_or(0, |newline| newline + 1);
+        let attribute_indent: String = remaining[line_start..attribute_start]
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect();
+
+        output += &remaining[..contents_start];
+        output.push('\n');
+        for line in format_pax(&remaining[contents_start..contents_end])?.lines() {
+            if !line.is_empty() {
+                output += &attribute_indent;
+                output += INDENT;
+                output += line;
+            }
+            output.push('\n');
+        }
+        output += &attribute_indent;
+        remaining = &remaining[contents_end..];
+    }
+    output += remaining;
+    Ok(output)
+}
+
+/// Returns the offset of the `)` that closes an already-opened `(`, skipping over string literals
+fn find_closing_paren(source: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut is_in_string = false;
+    let mut chars = source.char_indices();
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '\\' if is_in_string => {
+                chars.next();
+            }
+            '"' => is_in_string = !is_in_string,
+            '(' if !is_in_string => depth += 1,
+            ')' if !is_in_string => {
+                if depth == 0 {
+                    return Some(offset);
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+struct Formatter<'a> {
+    source: &'a str,
+    output: String,
+    /// Offset into `source` up to which input has been formatted
+    cursor: usize,
+    indent: usize,
+    /// Whether nothing has been written since the last block was opened, in which case blank lines are omitted
+    is_at_block_start: bool,
+    /// Whether the next line should be preceded by a blank line
+    is_blank_line_pending: bool,
+}
+
+impl<'a> Formatter<'a> {
+    fn line(&mut self, text: &str) {
+        if self.is_blank_line_pending && !self.is_at_block_start {
+            self.output.push('\n');
+        }
+        self.is_blank_line_pending = false;
+        for line in text.lines() {
+            if !line.is_empty() {
+                for _ in 0..self.indent {
+                    self.output += INDENT;
+                }
+            }
+            self.output += line;
+            self.output.push('\n');
+        }
+        self.is_at_block_start = false;
+    }
+
+    /// Separates the next line, if any, from the previous one with a blank line
+    fn blank_line(&mut self) {
+        self.is_blank_line_pending = true;
+    }
+
+    fn open_block(&mut self) {
+        self.indent += 1;
+        self.is_at_block_start = true;
+        self.is_blank_line_pending = false;
+    }
+
+    fn close_block(&mut self) {
+        self.indent -= 1;
+        self.is_at_block_start = false;
+        self.is_blank_line_pending = false;
+    }
+
+    /// Writes any comments between the cursor and `until` on their own lines, separated by a blank line
+    /// wherever the source has one or more, then advances the cursor to `until`.
+    /// Any other tokens in between, like the braces around blocks, are skipped.
+    fn trivia(&mut self, until: usize) -> Result<(), String> {
+        let mut remaining = &self.source[self.cursor..until];
+        let mut newlines = 0;
+        while let Some(c) = remaining.chars().next() {
+            let comment_len = if remaining.starts_with("//") {
+                remaining.find('\n').unwrap_or(remaining.len())
+            } else if remaining.starts_with("/*") {
+                remaining.find("*/").unwrap() + 2
+            } else {
+                if c == '\n' {
+                    newlines += 1;
+                } else if !c.is_whitespace() {
+                    newlines = 0;
+                }
+                remaining = &remaining[c.len_utf8()..];
+                continue;
+            };
+
+            if newlines > 1 {
+                self.blank_line();
+            }
+            self.line(remaining[..comment_len].trim_end());
+            newlines = 0;
+            remaining = &remaining[comment_len..];
+        }
+        if newlines > 1 {
+            self.blank_line();
+        }
+        self.cursor = until;
+        Ok(())
+    }
+
+    /// Advances the cursor to `until`, which must not skip over any comments
+    fn inline(&mut self, until: usize) -> Result<(), String> {
+        let skipped = &self.source[self.cursor..until];
+        if let Some(offset) = skipped.find("//").or_else(|| skipped.find("/*")) {
+            let line = self.source[..self.cursor + offset].matches('\n').count() + 1;
+            return Err(format!(
+                "line {}: comments are only supported between elements, settings and handlers",
+                line
+            ));
+        }
+        self.cursor = until;
+        Ok(())
+    }
+
+    /// Consumes `pair`, returning its source trimmed of surrounding whitespace; see `dedented`
+    fn verbatim(&mut self, pair: Pair<Rule>) -> Result<String, String> {
+        self.inline(pair.as_span().start())?;
+        self.cursor = pair.as_span().end();
+        Ok(Self::dedented(&pair))
+    }
+
+    /// Returns the source of `pair` trimmed of surrounding whitespace, with any continuation lines
+    /// indented one level, keeping their relative indentation, except for a final line that
+    /// closes a delimiter opened on the first, e.g. `}` in `{\n    a * b\n}`
+    fn dedented(pair: &Pair<Rule>) -> String {
+        let mut lines = pair.as_str().trim().lines();
+        let mut dedented = lines.next().unwrap_or_default().to_string();
+        let mut continuation_lines: Vec<&str> = lines.map(str::trim_end).collect();
+        let closing_line = continuation_lines
+            .last()
+            .map(|line| line.trim_start())
+            .filter(|line| line.starts_with(['}', ')', ']']));
+        if closing_line.is_some() {
+            continuation_lines.pop();
+        }
+
+        let indent_of = |line: &str| line.len() - line.trim_start().len();
+        let common_indent = continuation_lines
+            .iter()
+            .filter(|line| !line.is_empty())
+            .map(|line| indent_of(line))
+            .min()
+            .unwrap_or(0);
+        for line in continuation_lines {
+            dedented.push('\n');
+            if !line.is_empty() {
+                dedented += INDENT;
+                dedented += &line[common_indent..];
+            }
+        }
+        if let Some(closing_line) = closing_line {
+            dedented.push('\n');
+            dedented += closing_line;
+        }
+        dedented
+    }
+
+    /// Writes `head` followed by the nodes within `inner_nodes`; see `nodes`
+    fn block(&mut self, head: &str, inner_nodes: Pair<Rule>, until: usize) -> Result<(), String> {
+        self.line(head);
+        self.nodes(inner_nodes, until)
+    }
+
+    /// Writes the nodes within `inner_nodes` as an indented block, including any trailing comments up to `until`
+    fn nodes(&mut self, inner_nodes: Pair<Rule>, until: usize) -> Result<(), String> {
+        self.open_block();
+        for node in inner_nodes.into_inner() {
+            self.trivia(node.as_span().start())?;
+            self.any_tag_pair(node)?;
+        }
+        self.trivia(until)?;
+        self.close_block();
+        Ok(())
+    }
+
+    fn any_tag_pair(&mut self, pair: Pair<Rule>) -> Result<(), String> {
+        let end = pair.as_span().end();
+        match pair.as_rule() {
+            Rule::self_closing_tag => {
+                let (pascal_identifier, attributes) = self.tag_head(pair.into_inner())?;
+                self.inline(end)?;
+                self.tag(&pascal_identifier, attributes, "/>");
+            }
+            Rule::matched_tag => {
+                let mut inner = pair.into_inner();
+                let open_tag = inner.next().unwrap();
+                let inner_nodes = inner.next().unwrap();
+                let closing_tag = inner.next().unwrap();
+
+                let open_tag_end = open_tag.as_span().end();
+                let (pascal_identifier, attributes) = self.tag_head(open_tag.into_inner())?;
+                self.inline(open_tag_end)?;
+
+                let closing_literal = format!("</{}>", pascal_identifier);
+                let content = inner_nodes.clone().into_inner().next();
+                match content {
+                    Some(content) if content.as_rule() == Rule::node_inner_content => {
+                        //e.g. `<Text>"Hello"</Text>`, kept on one line
+                        let content = self.verbatim(content)?;
+                        self.inline(end)?;
+                        self.tag(
+                            &pascal_identifier,
+                            attributes,
+                            &format!(">{}{}", content, closing_literal),
+                        );
+                    }
+                    _ => {
+                        self.tag(&pascal_identifier, attributes, ">");
+                        self.nodes(inner_nodes, closing_tag.as_span().start())?;
+                        self.cursor = end;
+                        self.line(&closing_literal);
+                    }
+                }
+            }
+            Rule::statement_control_flow => {
+                self.statement(pair.into_inner().next().unwrap())?;
+            }
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
+    /// Consumes the pascal identifier and attributes of a tag, returning the identifier along
+    /// with its attributes, in canonical order: `id`, `class`, other properties, then event bindings
+    fn tag_head(&mut self, mut pairs: Pairs<Rule>) -> Result<(String, Vec<String>), String> {
+        let pascal_identifier = self.verbatim(pairs.next().unwrap())?;
+
+        let mut attributes = vec![];
+        for attribute_key_value_pair in pairs {
+            self.inline(attribute_key_value_pair.as_span().start())?;
+            self.cursor = attribute_key_value_pair.as_span().end();
+
+            let mut inner = attribute_key_value_pair.into_inner();
+            let key_or_event_binding = inner.next().unwrap();
+            attributes.push(match key_or_event_binding.as_rule() {
+                Rule::attribute_event_binding => {
+                    let mut inner = key_or_event_binding.into_inner();
+                    let event_id = inner.next().unwrap().into_inner().next().unwrap();
+                    let handler = Self::literal_function(inner.next().unwrap());
+                    (3, format!("@{}={}", event_id.as_str(), handler))
+                }
+                _ => {
+                    let key = key_or_event_binding.as_str();
+                    let rank = match key {
+                        "id" => 0,
+                        "class" => 1,
+                        _ => 2,
+                    };
+                    let value = Self::dedented(&inner.next().unwrap());
+                    (rank, format!("{}={}", key, value))
+                }
+            });
+        }
+        attributes.sort_by_key(|(rank, _)| *rank);
+
+        Ok((
+            pascal_identifier,
+            attributes
+                .into_iter()
+                .map(|(_, attribute)| attribute)
+                .collect(),
+        ))
+    }
+
+    fn tag_literal(pascal_identifier: &str, attributes: &[String], close: &str) -> String {
+        let mut literal = format!("<{}", pascal_identifier);
+        for attribute in attributes {
+            literal += " ";
+            literal += attribute;
+        }
+        if close.starts_with('/') {
+            literal += " ";
+        }
+        literal + close
+    }
+
+    fn fits(&self, literal: &str) -> bool {
+        !literal.contains('\n') && self.indent * INDENT.len() + literal.len() <= MAX_LINE_WIDTH
+    }
+
+    /// Writes a tag on one line if it fits, or else with one attribute per line
+    fn tag(&mut self, pascal_identifier: &str, attributes: Vec<String>, close: &str) {
+        let literal = Self::tag_literal(pascal_identifier, &attributes, close);
+        if self.fits(&literal) {
+            self.line(&literal);
+        } else {
+            self.broken_tag(pascal_identifier, attributes, close);
+        }
+    }
+
+    fn broken_tag(&mut self, pascal_identifier: &str, attributes: Vec<String>, close: &str) {
+        self.line(&format!("<{}", pascal_identifier));
+        self.open_block();
+        for attribute in attributes {
+            self.line(&attribute);
+        }
+        self.close_block();
+        self.line(close);
+    }
+
+    fn statement(&mut self, pair: Pair<Rule>) -> Result<(), String> {
+        let end = pair.as_span().end();
+        match pair.as_rule() {
+            Rule::statement_if => {
+                let mut inner = pair.into_inner();
+                let mut head = format!("if {} {{", self.verbatim(inner.next().unwrap())?);
+                let mut inner_nodes = inner.next().unwrap();
+                for branch in inner {
+                    self.block(&head, inner_nodes, branch.as_span().start())?;
+                    let is_else_if = branch.as_rule() == Rule::statement_else_if;
+                    let mut branch_inner = branch.into_inner();
+                    head = if is_else_if {
+                        format!(
+                            "}} else if {} {{",
+                            self.verbatim(branch_inner.next().unwrap())?
+                        )
+                    } else {
+                        "} else {".to_string()
+                    };
+                    inner_nodes = branch_inner.next().unwrap();
+                }
+                self.block(&head, inner_nodes, end)?;
+                self.line("}");
+            }
+            Rule::statement_for => {
+                let mut inner = pair.into_inner();
+                let predicate = Self::predicate(inner.next().unwrap());
+                let source = self.verbatim(inner.next().unwrap())?;
+                let mut head = format!("for {} in {}", predicate, source);
+                let mut next = inner.next().unwrap();
+                if next.as_rule() == Rule::statement_for_key {
+                    let key = self.verbatim(next.into_inner().next().unwrap())?;
+                    head += &format!(" key={{{}}}", key);
+                    next = inner.next().unwrap();
+                }
+                self.block(&(head + " {"), next, end)?;
+                self.line("}");
+            }
+            Rule::statement_slot => {
+                let expression = self.verbatim(pair.into_inner().next().unwrap())?;
+                if expression.starts_with('(') {
+                    self.line(&format!("slot{}", expression));
+                } else {
+                    self.line(&format!("slot {}", expression));
+                }
+            }
+            _ => unreachable!(),
+        }
+        self.cursor = end;
+        Ok(())
+    }
+
+    /// e.g. `elem`, `(elem, i)` or `((name, count), i)`
+    fn predicate(pair: Pair<Rule>) -> String {
+        let members: Vec<String> = pair
+            .into_inner()
+            .map(|member| match member.as_rule() {
+                Rule::statement_for_predicate_destructuring => format!(
+                    "({})",
+                    member
+                        .into_inner()
+                        .map(|identifier| identifier.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                _ => member.as_str().to_string(),
+            })
+            .collect();
+        if members.len() == 1 {
+            members[0].clone()
+        } else {
+            format!("({})", members.join(", "))
+        }
+    }
+
+    /// e.g. `handle_click` or `self.handle_click`
+    fn literal_function(pair: Pair<Rule>) -> String {
+        let has_self = pair.as_str().starts_with("self.");
+        let identifier = pair.into_inner().next().unwrap().as_str();
+        if has_self {
+            format!("self.{}", identifier)
+        } else {
+            identifier.to_string()
+        }
+    }
+
+    fn settings_block(&mut self, pair: Pair<Rule>) -> Result<(), String> {
+        let end = pair.as_span().end();
+        self.line("@settings {");
+        self.open_block();
+        for selector_block in pair.into_inner() {
+            self.trivia(selector_block.as_span().start())?;
+            let mut inner = selector_block.into_inner();
+            let mut selector = self.verbatim(inner.next().unwrap())?;
+            selector.retain(|c| !c.is_whitespace());
+            self.literal_object(&selector, inner.next().unwrap(), "")?;
+        }
+        self.trivia(end)?;
+        self.close_block();
+        self.line("}");
+        Ok(())
+    }
+
+    /// Writes `prefix`, e.g. a selector or settings key, followed by the object's optional type
+    /// name and its key-value pairs as an indented block
+    fn literal_object(
+        &mut self,
+        prefix: &str,
+        pair: Pair<Rule>,
+        suffix: &str,
+    ) -> Result<(), String> {
+        let end = pair.as_span().end();
+        let mut head = prefix.to_string();
+        let mut inner = pair.into_inner().peekable();
+        if let Some(pascal_identifier) =
+            inner.next_if(|pair| pair.as_rule() == Rule::pascal_identifier)
+        {
+            head += " ";
+            head += &self.verbatim(pascal_identifier)?;
+        }
+        self.line(&(head + " {"));
+        self.open_block();
+        for settings_key_value_pair in inner {
+            self.trivia(settings_key_value_pair.as_span().start())?;
+            let mut inner = settings_key_value_pair.into_inner();
+            let key = self.verbatim(inner.next().unwrap().into_inner().next().unwrap())?;
+            let settings_value = inner.next().unwrap();
+            self.inline(settings_value.as_span().start())?;
+            let value = settings_value.clone().into_inner().next().unwrap();
+            if value.as_rule() == Rule::literal_object {
+                self.literal_object(&format!("{}:", key), value, ",")?;
+            } else {
+                let value = self.verbatim(settings_value)?;
+                self.line(&format!("{}: {},", key, value));
+            }
+        }
+        self.trivia(end)?;
+        self.close_block();
+        self.line(&format!("}}{}", suffix));
+        Ok(())
+    }
+
+    fn handlers_block(&mut self, pair: Pair<Rule>) -> Result<(), String> {
+        let end = pair.as_span().end();
+        self.line("@handlers {");
+        self.open_block();
+        for handlers_key_value_pair in pair.into_inner() {
+            self.trivia(handlers_key_value_pair.as_span().start())?;
+            let mut inner = handlers_key_value_pair.into_inner();
+            let key = self.verbatim(inner.next().unwrap().into_inner().next().unwrap())?;
+            let handlers_value = inner.next().unwrap();
+            self.inline(handlers_value.as_span().start())?;
+            self.cursor = handlers_value.as_span().end();
+            let value = handlers_value.into_inner().next().unwrap();
+            let value = if value.as_rule() == Rule::function_list {
+                format!(
+                    "[{}]",
+                    value
+                        .into_inner()
+                        .map(Self::literal_function)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            } else {
+                Self::literal_function(value)
+            };
+            self.line(&format!("{}: {},", key, value));
+        }
+        self.trivia(end)?;
+        self.close_block();
+        self.line("}");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_inlined_templates, format_pax};
+
+    #[test]
+    fn test_format_pax() {
+        let source = r#"
+// Root
+<Group  @click=handle_click   class=a id=root   >
+  <Rectangle fill={Color::rgb(1,0,0)}
+  width=10px/>
+
+
+    if self.visible
+    {
+        // Label
+        <Text text="hello" />
+    } else if self.count > 3 { <Text text="many"/> } else {
+    }
+    for (item,i) in self.items key={item.id} { <Text text={item.label} /> }
+    slot(0)
+</Group>
+@handlers { did_mount:handle_did_mount , click : [ self.a,b ] }
+@settings {
+  #root { fill: {Color::rgb(0,0,1)}, style: TextStyle { font_size: 12px } }
+  // Class
+  .a { width : 100% }
+}
+"#;
+        let expected = r#"// Root
+<Group id=root class=a @click=handle_click>
+    <Rectangle fill={Color::rgb(1,0,0)} width=10px />
+
+    if self.visible {
+        // Label
+        <Text text="hello" />
+    } else if self.count > 3 {
+        <Text text="many" />
+    } else {
+    }
+    for (item, i) in self.items key={item.id} {
+        <Text text={item.label} />
+    }
+    slot(0)
+</Group>
+
+@handlers {
+    did_mount: handle_did_mount,
+    click: [self.a, b],
+}
+
+@settings {
+    #root {
+        fill: {Color::rgb(0,0,1)},
+        style: TextStyle {
+            font_size: 12px,
+        },
+    }
+    // Class
+    .a {
+        width: 100%,
+    }
+}
+"#;
+        let formatted = format_pax(source).unwrap();
+        assert_eq!(formatted, expected);
+        assert_eq!(format_pax(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn test_format_pax_breaks_long_tags_and_reindents_expressions() {
+        let source = r#"<Rectangle fill={Fill::Solid(Color::rgb(0.5, 0.5, 1.0))} width=100% height=100% transform={
+      Transform2D::anchor(50%, 50%)
+      * Transform2D::rotate(rotation)
+  } />"#;
+        let expected = r#"<Rectangle
+    fill={Fill::Solid(Color::rgb(0.5, 0.5, 1.0))}
+    width=100%
+    height=100%
+    transform={
+        Transform2D::anchor(50%, 50%)
+        * Transform2D::rotate(rotation)
+    }
+/>
+"#;
+        let formatted = format_pax(source).unwrap();
+        assert_eq!(formatted, expected);
+        assert_eq!(format_pax(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn test_format_pax_rejects_comments_within_tags() {
+        let source = "<Rectangle // width\n width=10px />";
+        assert!(format_pax(source)
+            .unwrap_err()
+            .contains("comments are only supported between elements"));
+    }
+
+    #[test]
+    fn test_format_inlined_templates() {
+        let source = r#"#[derive(Pax)]
+#[inlined( <Group><Text text="(hi)" /></Group> )]
+pub struct Example {}
+
+mod nested {
+    #[inlined(
+<Rectangle width=10px/>
+    )]
+    pub struct Nested {}
+}
+"#;
+        let expected = r#"#[derive(Pax)]
+#[inlined(
+    <Group>
+        <Text text="(hi)" />
+    </Group>
+)]
+pub struct Example {}
+
+mod nested {
+    #[inlined(
+        <Rectangle width=10px />
+    )]
+    pub struct Nested {}
+}
+"#;
+        assert_eq!(format_inlined_templates(source).unwrap(), expected);
+    }
+}
@@ -0,0 +1,39 @@
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use log::{error, info};
+
+use crate::{get_or_create_pax_directory, init_logging, parse_manifest, PaxBuildError};
+
+//relative to pax_dir
+pub const GRAPH_DOT_PATH: &str = "graph.dot";
+
+/// Parses the Pax project at `path` and writes the GraphViz DOT graph of its component templates
+/// (see `PaxManifest::to_dot`) to `.pax/graph.dot`.
+/// Like `dump_manifest`, dependencies must already be resolvable by `cargo`.
+/// Returns `Err` with the error, if any, after printing it.
+pub fn perform_graph(path: &str) -> Result<(), PaxBuildError> {
+    init_logging();
+    let (manifest, _) = parse_manifest(
+        &Path::new(path).join("Cargo.toml"),
+        false,
+        false,
+        Arc::new(Mutex::new(vec![])),
+    )
+    .map_err(|err| {
+        error!("❌ {}", err);
+        err
+    })?;
+
+    let graph_path = get_or_create_pax_directory(path).join(GRAPH_DOT_PATH);
+    fs::write(&graph_path, manifest.to_dot()).map_err(|err| {
+        error!("❌ Failed to write {}: {}", graph_path.display(), err);
+        PaxBuildError::from(err)
+    })?;
+    info!(
+        "📈 Wrote component graph to {} — render it with e.g. `dot -Tsvg`",
+        graph_path.display()
+    );
+    Ok(())
+}
@@ -4,10 +4,17 @@ use lazy_static::lazy_static;
 
 use include_dir::{include_dir, Dir};
 
+pub mod bundle_size;
+pub mod check;
+pub mod doctor;
+pub mod dts;
 pub mod expressions;
+pub mod formatting;
+pub mod graph;
 pub mod manifest;
 pub mod parsing;
 pub mod templating;
+pub mod workspace;
 
 use pax_runtime_api::CommonProperties;
 
@@ -22,27 +29,36 @@ use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::Write;
 use std::str::FromStr;
+use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use actix_web::middleware::Logger;
 use actix_web::{App, HttpServer};
 use env_logger;
 use flate2::read::GzDecoder;
-use std::net::TcpListener;
+use log::{error, info, log, log_enabled, warn, Level};
+use std::net::{SocketAddr, TcpListener};
 use tar::Archive;
 
 #[cfg(unix)]
 use std::os::unix::process::CommandExt; // For the .pre_exec() method
 
+use crate::bundle_size::{
+    report_web_bundle_sizes, BundleFileSize, DEFAULT_WASM_SIZE_WARNING_BYTES,
+};
+use crate::dts::generate_web_interface_dts;
+use crate::expressions::{ExpressionCache, ExpressionFunction, ExpressionFunctionRegistry};
 use crate::manifest::{
     ComponentDefinition, EventDefinition, ExpressionSpec, LiteralBlockDefinition,
-    TemplateNodeDefinition, TypeDefinition, TypeTable, ValueDefinition,
+    PropertyDefinition, TemplateNodeDefinition, TypeDefinition, TypeTable, ValueDefinition,
 };
 use crate::templating::{
     press_template_codegen_cartridge_component_factory,
     press_template_codegen_cartridge_render_node_literal,
     TemplateArgsCodegenCartridgeComponentFactory, TemplateArgsCodegenCartridgeRenderNodeLiteral,
 };
+use crate::workspace::{add_workspace_member, find_enclosing_workspace_manifest};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
 use toml_edit::Item;
@@ -90,6 +106,39 @@ fn bundle_reexports_into_namespace_string(sorted_reexports: &Vec<String>) -> Str
     root.serialize_to_reexports()
 }
 
+/// Normalizes a module path or type id for addressing under `pax_reexports`, by stripping the
+/// leading `crate` segment of any path it contains.  For example: `crate::widgets::buttons` becomes
+/// `widgets::buttons`, bare `crate` becomes an empty string, and external-crate paths like
+/// `pax_std::primitives` are returned unchanged.  Paths nested in generics (e.g. `Vec<crate::Foo>`)
+/// are normalized as well.
+fn normalize_module_path(path: &str) -> String {
+    const CRATE_ROOT: &str = "crate";
+    let is_path_char = |c: char| c.is_alphanumeric() || c == '_' || c == ':';
+
+    let mut ret = String::with_capacity(path.len());
+    let mut remaining = path;
+    let mut at_path_start = true;
+    while let Some(c) = remaining.chars().next() {
+        if at_path_start && remaining.starts_with(CRATE_ROOT) {
+            let after_root = &remaining[CRATE_ROOT.len()..];
+            if let Some(stripped) = after_root.strip_prefix("::") {
+                remaining = stripped;
+                at_path_start = false;
+                continue;
+            }
+            if !after_root.starts_with(is_path_char) {
+                remaining = after_root;
+                at_path_start = false;
+                continue;
+            }
+        }
+        ret.push(c);
+        at_path_start = !is_path_char(c);
+        remaining = &remaining[c.len_utf8()..];
+    }
+    ret
+}
+
 fn update_property_prefixes_in_place(manifest: &mut PaxManifest, host_crate_info: &HostCrateInfo) {
     let mut updated_type_table = HashMap::new();
     manifest.type_table.iter_mut().for_each(|t| {
@@ -97,6 +146,10 @@ fn update_property_prefixes_in_place(manifest: &mut PaxManifest, host_crate_info
         t.1.type_id =
             t.1.type_id
                 .replace("{PREFIX}", &host_crate_info.import_prefix);
+        t.1.inner_iterable_type_id =
+            t.1.inner_iterable_type_id
+                .as_ref()
+                .map(|iiti| iiti.replace("{PREFIX}", &host_crate_info.import_prefix));
         t.1.property_definitions.iter_mut().for_each(|pd| {
             pd.type_id = pd
                 .type_id
@@ -117,10 +170,20 @@ fn clone_all_dependencies_to_tmp(
     pax_dir: &PathBuf,
     pax_version: &Option<String>,
     ctx: &RunContext,
-) {
+) -> Result<(), PaxBuildError> {
     let dest_pkg_root = pax_dir.join(PAX_DIR_PKG_PATH);
+    let mut missing_offline_pkgs = vec![];
     for pkg in ALL_PKGS {
-        if ctx.is_libdev_mode {
+        if let Some(vendor_dir) = &ctx.vendor_dir {
+            //Copy all packages from the vendored checkout on every build, as with libdev below,
+            //but from `<vendor_dir>/<pkg>` rather than from a monorepo root
+            let src = vendor_dir.join(pkg);
+            let dest = dest_pkg_root.join(pkg);
+
+            copy_dir_to(&src, &dest).unwrap_or_else(|err| {
+                panic!("Failed to copy vendored {:?} to {:?}: {}", src, dest, err)
+            });
+        } else if ctx.is_libdev_mode {
             //Copy all packages from monorepo root on every build.  this allows us to propagate changes
             //to a libdev build without "sticky caches."
             //
@@ -137,7 +200,10 @@ fn clone_all_dependencies_to_tmp(
                 .expect(&format!("Failed to copy from {:?} to {:?}", src, dest));
         } else {
             let dest = dest_pkg_root.join(pkg);
-            if !dest.exists() {
+            if !dest.exists() && ctx.offline {
+                //Never touch the network offline; collect everything missing and report it below
+                missing_offline_pkgs.push(pkg.to_string());
+            } else if !dest.exists() {
                 let pax_version = pax_version
                     .as_ref()
                     .expect("Pax version required but not found");
@@ -150,7 +216,14 @@ fn clone_all_dependencies_to_tmp(
                     pkg, pax_version
                 ));
 
+                let status = resp.status().as_u16();
                 let tarball_bytes = resp.bytes().expect("Failed to read tarball bytes");
+                check_tarball_response(pkg, pax_version, status, &tarball_bytes)?;
+                let malformed = |err: std::io::Error| PaxBuildError::MalformedPackageDownload {
+                    pkg: pkg.to_string(),
+                    version: pax_version.to_string(),
+                    reason: err.to_string(),
+                };
 
                 // Wrap the byte slice in a Cursor, so it can be used as a Read trait object.
                 let cursor = std::io::Cursor::new(&tarball_bytes[..]);
@@ -162,11 +235,11 @@ fn clone_all_dependencies_to_tmp(
                 let mut archive = Archive::new(gz);
 
                 // Iterate over the entries in the archive and modify the paths before extracting.
-                for entry_result in archive.entries().expect("Failed to read entries") {
-                    let mut entry = entry_result.expect("Failed to read entry");
+                for entry_result in archive.entries().map_err(malformed)? {
+                    let mut entry = entry_result.map_err(malformed)?;
                     let path = match entry
                         .path()
-                        .expect("Failed to get path")
+                        .map_err(malformed)?
                         .components()
                         .skip(1)
                         .collect::<PathBuf>()
@@ -188,6 +261,47 @@ fn clone_all_dependencies_to_tmp(
             }
         }
     }
+
+    if !missing_offline_pkgs.is_empty() {
+        return Err(PaxBuildError::MissingOfflineDependencies(
+            missing_offline_pkgs,
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that a crates.io download of `pkg` at `version`, which responded with HTTP `status` and `bytes`,
+/// is a gzipped tarball before it is unpacked, so that e.g. a 404 page surfaces as an actionable error
+/// rather than as an opaque failure to read tar entries
+fn check_tarball_response(
+    pkg: &str,
+    version: &str,
+    status: u16,
+    bytes: &[u8],
+) -> Result<(), PaxBuildError> {
+    const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
+
+    let malformed = |reason: String| PaxBuildError::MalformedPackageDownload {
+        pkg: pkg.to_string(),
+        version: version.to_string(),
+        reason,
+    };
+    if status == 404 {
+        return Err(PaxBuildError::PackageNotFound {
+            pkg: pkg.to_string(),
+            version: version.to_string(),
+        });
+    }
+    if !(200..300).contains(&status) {
+        return Err(malformed(format!("unexpected HTTP status {}", status)));
+    }
+    if bytes.is_empty() {
+        return Err(malformed("the response was empty".to_string()));
+    }
+    if !bytes.starts_with(&GZIP_MAGIC_BYTES) {
+        return Err(malformed("the response is not gzip data".to_string()));
+    }
+    Ok(())
 }
 
 fn generate_and_overwrite_properties_coproduct(
@@ -219,83 +333,10 @@ fn generate_and_overwrite_properties_coproduct(
     )
     .unwrap();
 
-    //build tuples for PropertiesCoproduct
-    let mut properties_coproduct_tuples: Vec<(String, String)> = manifest
-        .components
-        .iter()
-        .map(|comp_def| {
-            let mod_path = if &comp_def.1.module_path == "crate" {
-                "".to_string()
-            } else {
-                comp_def.1.module_path.replace("crate::", "") + "::"
-            };
-            (
-                comp_def.1.type_id_escaped.clone(),
-                format!(
-                    "{}{}{}",
-                    &host_crate_info.import_prefix, &mod_path, &comp_def.1.pascal_identifier
-                ),
-            )
-        })
-        .collect();
-    let set: HashSet<(String, String)> = properties_coproduct_tuples.drain(..).collect();
-    properties_coproduct_tuples.extend(set.into_iter());
-    properties_coproduct_tuples.sort();
-
-    //build tuples for TypesCoproduct
-    // - include all Property types, representing all possible return types for Expressions
-    // - include all T such that T is the iterator type for some Property<Vec<T>>
-    let mut types_coproduct_tuples: Vec<(String, String)> = manifest
-        .components
-        .iter()
-        .map(|cd| {
-            cd.1.get_property_definitions(&manifest.type_table)
-                .iter()
-                .map(|pm| {
-                    let td = pm.get_type_definition(&manifest.type_table);
-
-                    (
-                        td.type_id_escaped.clone(),
-                        host_crate_info.import_prefix.to_string()
-                            + &td.type_id.clone().replace("crate::", ""),
-                    )
-                })
-                .collect::<Vec<_>>()
-        })
-        .flatten()
-        .collect::<Vec<_>>();
-
-    let mut set: HashSet<_> = types_coproduct_tuples.drain(..).collect();
-
-    #[allow(non_snake_case)]
-    let TYPES_COPRODUCT_BUILT_INS = vec![
-        ("f64", "f64"),
-        ("bool", "bool"),
-        ("isize", "isize"),
-        ("usize", "usize"),
-        ("String", "String"),
-        (
-            "stdCOCOvecCOCOVecLABRstdCOCOrcCOCORcLABRPropertiesCoproductRABRRABR",
-            "std::vec::Vec<std::rc::Rc<PropertiesCoproduct>>",
-        ),
-        ("Transform2D", "pax_runtime_api::Transform2D"),
-        ("stdCOCOopsCOCORangeLABRisizeRABR", "std::ops::Range<isize>"),
-        ("Size", "pax_runtime_api::Size"),
-        ("Rotation", "pax_runtime_api::Rotation"),
-        ("SizePixels", "pax_runtime_api::SizePixels"),
-        ("Numeric", "pax_runtime_api::Numeric"),
-    ];
-
-    TYPES_COPRODUCT_BUILT_INS.iter().for_each(|builtin| {
-        set.insert((builtin.0.to_string(), builtin.1.to_string()));
-    });
-    types_coproduct_tuples.extend(set.into_iter());
-    types_coproduct_tuples.sort();
-
-    types_coproduct_tuples = types_coproduct_tuples
-        .into_iter()
-        .unique_by(|elem| elem.0.to_string())
-        .collect::<Vec<(String, String)>>();
+    let properties_coproduct_tuples =
+        manifest.get_properties_coproduct_tuples(&host_crate_info.import_prefix);
+    let types_coproduct_tuples =
+        manifest.get_types_coproduct_tuples(&host_crate_info.import_prefix);
 
     //press template into String
     let generated_lib_rs = templating::press_template_codegen_properties_coproduct_lib(
@@ -309,11 +350,13 @@ fn generate_and_overwrite_properties_coproduct(
     fs::write(target_dir.join("src/lib.rs"), generated_lib_rs).unwrap();
 }
 
+/// Generates and writes `pax-cartridge/src/lib.rs`.  Returns the time spent generating
+/// each component factory, keyed by component `type_id`.
 fn generate_and_overwrite_cartridge(
     pax_dir: &PathBuf,
     manifest: &PaxManifest,
     host_crate_info: &HostCrateInfo,
-) {
+) -> HashMap<String, Duration> {
     let target_dir = pax_dir.join(PAX_DIR_PKG_PATH).join("pax-cartridge");
 
     let target_cargo_full_path = fs::canonicalize(target_dir.join("Cargo.toml")).unwrap();
@@ -377,7 +420,7 @@ fn generate_and_overwrite_cartridge(
         .iter()
         .map(|path| {
             if !imports_builtins_set.contains(&**path) {
-                IMPORT_PREFIX.clone() + &path.replace("crate::", "")
+                IMPORT_PREFIX.clone() + &normalize_module_path(path)
             } else {
                 "".to_string()
             }
@@ -413,17 +456,8 @@ fn generate_and_overwrite_cartridge(
     //   If `const` is bit off for this work, must first populate symbols via pax_const => PaxManifest
     //     -- must also choose scoping rules; probably just component-level scoping for now
     //
-    // Throw errors when symbols in expressions cannot be resolved; ensure path forward to developer-friendly error messages
-    //     For reference, Rust's message is:
-    //  error[E0425]: cannot find value `not_defined` in this scope
-    //         --> pax-compiler/src/main.rs:404:13
-    //          |
-    //      404 |     let y = not_defined + 6;
-    //          |             ^^^^^^^^^^^ not found in this scope
-    //     Python uses:
-    // NameError: name 'z' is not defined
-    //     JavaScript uses:
-    // Uncaught ReferenceError: not_defined is not defined
+    // Symbols in expressions that cannot be resolved are reported while compiling expressions, before codegen,
+    // with rustc-style error messages; see `expressions::UnresolvedSymbolError`
 
     let mut expression_specs: Vec<ExpressionSpec> = manifest
         .expression_specs
@@ -434,12 +468,19 @@ fn generate_and_overwrite_cartridge(
         .collect();
     expression_specs = expression_specs.iter().sorted().cloned().collect();
 
+    let mut component_durations: HashMap<String, Duration> = HashMap::new();
     let component_factories_literal = manifest
         .components
         .values()
         .into_iter()
         .filter(|cd| !cd.is_primitive && !cd.is_struct_only_component)
-        .map(|cd| generate_cartridge_component_factory_literal(manifest, cd, host_crate_info))
+        .map(|cd| {
+            let component_start = Instant::now();
+            let literal =
+                generate_cartridge_component_factory_literal(manifest, cd, host_crate_info);
+            component_durations.insert(cd.type_id.clone(), component_start.elapsed());
+            literal
+        })
         .collect();
 
     //press template into String
@@ -455,6 +496,8 @@ fn generate_and_overwrite_cartridge(
     // Re: formatting the generated output, see prior art at `_format_generated_lib_rs`
     //write String to file
     fs::write(target_dir.join("src/lib.rs"), generated_lib_rs).unwrap();
+
+    component_durations
 }
 
 /// Note: this function was abandoned because RustFmt takes unacceptably long to format complex
@@ -496,6 +539,34 @@ fn generate_cartridge_render_nodes_literal(
     children_literal.join(",")
 }
 
+/// DOM-style event names accepted as aliases for their `HandlerRegistry` counterparts,
+/// e.g. `@keydown=self.handle_key` for `@key_down=self.handle_key`, as well as names for the
+/// lifecycle events, e.g. `@handlers { Mount: self.on_mount }` for `@handlers { mount: self.on_mount }`
+const EVENT_KEY_ALIASES: [(&str, &str); 12] = [
+    ("keydown", "key_down"),
+    ("keyup", "key_up"),
+    ("keypress", "key_press"),
+    ("mousedown", "mouse_down"),
+    ("mouseup", "mouse_up"),
+    ("mousemove", "mouse_move"),
+    ("mouseover", "mouse_over"),
+    ("mouseout", "mouse_out"),
+    ("Mount", "mount"),
+    ("Unmount", "unmount"),
+    ("PreRender", "will_render"),
+    ("PostRender", "did_render"),
+];
+
+/// Maps an event key, as written in `@handlers` or an `@event` binding, to the name of
+/// its `HandlerRegistry` field (sans `_handlers`)
+fn normalize_event_key(key: &str) -> String {
+    EVENT_KEY_ALIASES
+        .iter()
+        .find(|alias| alias.0 == key)
+        .map_or(key, |alias| alias.1)
+        .to_string()
+}
+
 fn generate_bound_events(
     inline_settings: Option<Vec<(String, ValueDefinition)>>,
 ) -> HashMap<String, String> {
@@ -503,42 +574,73 @@ fn generate_bound_events(
     if let Some(ref inline) = inline_settings {
         for (key, value) in inline.iter() {
             if let ValueDefinition::EventBindingTarget(s) = value {
-                ret.insert(key.clone().to_string(), s.clone().to_string());
+                ret.insert(normalize_event_key(key), s.clone().to_string());
             };
         }
     };
     ret
 }
 
+/// The literal for a property that is not set in a template: the expression given by
+/// `#[pax(default = "...")]` on the property, if any, or else the default for its type
+fn generate_default_property_literal(pd: &PropertyDefinition) -> String {
+    match &pd.default_value {
+        Some(default_value) => format!("PropertyLiteral::new({})", default_value),
+        None => "PropertyLiteral::new(Default::default())".to_string(),
+    }
+}
+
+/// Number literals are lowered to `Numeric::from(...)` whether they are written as `5` or `5.0`.
+/// For properties of a numeric primitive type, converts such a literal into that type explicitly,
+/// so that integer and float literals can be bound to any numeric property interchangeably.
+/// Any other literal, or a number literal bound to a `Numeric` property, is returned unchanged.
+fn coerce_numeric_literal(literal: &str, type_id: &str) -> String {
+    let is_number_literal =
+        literal.starts_with("Numeric::from") || literal.starts_with("(-Numeric::from");
+    let type_name = type_id.rsplit("::").next().unwrap();
+    if !is_number_literal || !manifest::SUPPORTED_NUMERIC_PRIMITIVES.contains(&type_name) {
+        literal.to_string()
+    } else if type_name == "f64" {
+        format!("({}).get_as_float()", literal)
+    } else {
+        format!("({}).get_as_int() as {}", literal, type_name)
+    }
+}
+
 fn recurse_literal_block(
     block: LiteralBlockDefinition,
     type_definition: &TypeDefinition,
+    type_table: &TypeTable,
     host_crate_info: &HostCrateInfo,
 ) -> String {
     let qualified_path = host_crate_info.import_prefix.to_string()
-        + &type_definition.import_path.clone().replace("crate::", "");
+        + &normalize_module_path(&type_definition.import_path);
 
     // Buffer to store the string representation of the struct
     let mut struct_representation = format!("\n{{ let mut ret = {}::default();", qualified_path);
 
     // Iterating through each (key, value) pair in the settings_key_value_pairs
     for (key, value_definition) in block.settings_key_value_pairs.iter() {
-        let fully_qualified_type = host_crate_info.import_prefix.to_string()
-            + &type_definition
-                .property_definitions
-                .iter()
-                .find(|pd| &pd.name == key)
-                .expect(&format!(
+        let property_type_id = &type_definition
+            .property_definitions
+            .iter()
+            .find(|pd| &pd.name == key)
+            .unwrap_or_else(|| {
+                panic!(
                     "Property {} not found on type {}",
                     key, type_definition.type_id
-                ))
-                .type_id;
+                )
+            })
+            .type_id;
+        let fully_qualified_type = host_crate_info.import_prefix.to_string() + property_type_id;
 
         let value_string = match value_definition {
             ValueDefinition::LiteralValue(value) => {
                 format!(
                     "ret.{} = Box::new(PropertyLiteral::new(Into::<{}>::into({})));",
-                    key, fully_qualified_type, value
+                    key,
+                    fully_qualified_type,
+                    coerce_numeric_literal(value, property_type_id)
                 )
             }
             ValueDefinition::Expression(_, id) | ValueDefinition::Identifier(_, id) => {
@@ -552,7 +654,37 @@ fn recurse_literal_block(
                 "ret.{} = Box::new(PropertyLiteral::new(Into::<{}>::into({})));",
                 key,
                 fully_qualified_type,
-                recurse_literal_block(inner_block.clone(), type_definition, host_crate_info),
+                recurse_literal_block(
+                    inner_block.clone(),
+                    type_definition,
+                    type_table,
+                    host_crate_info
+                ),
+            ),
+            ValueDefinition::LiteralList(elements) => format!(
+                "ret.{} = Box::new(PropertyLiteral::new({}));",
+                key,
+                recurse_literal_list(
+                    elements,
+                    type_table.get(property_type_id).unwrap_or_else(|| panic!(
+                        "Property {} has type_id {}, which is missing from the type table",
+                        key, property_type_id
+                    )),
+                    type_table,
+                    host_crate_info
+                ),
+            ),
+            ValueDefinition::LiteralEnumVariant(variant) => format!(
+                "ret.{} = Box::new(PropertyLiteral::new({}));",
+                key,
+                generate_enum_variant_literal(
+                    variant,
+                    type_table.get(property_type_id).unwrap_or_else(|| panic!(
+                        "Property {} has type_id {}, which is missing from the type table",
+                        key, property_type_id
+                    )),
+                    host_crate_info
+                ),
             ),
             _ => {
                 panic!("Incorrect value bound to inline setting")
@@ -567,6 +699,89 @@ fn recurse_literal_block(
     struct_representation
 }
 
+/// Generates a `vec![...]` of `elements`, for a property of the iterable type `type_definition`
+fn recurse_literal_list(
+    elements: &[ValueDefinition],
+    type_definition: &TypeDefinition,
+    type_table: &TypeTable,
+    host_crate_info: &HostCrateInfo,
+) -> String {
+    let inner_iterable_type_id = type_definition
+        .inner_iterable_type_id
+        .as_ref()
+        .unwrap_or_else(|| {
+            panic!(
+                "A list literal cannot be assigned to type {}, which is not iterable",
+                type_definition.type_id
+            )
+        });
+    let get_inner_iterable_type_definition = || {
+        type_table.get(inner_iterable_type_id).unwrap_or_else(|| {
+            panic!(
+                "Elements of {} have type_id {}, which is missing from the type table",
+                type_definition.type_id, inner_iterable_type_id
+            )
+        })
+    };
+
+    let elements_literal = elements
+        .iter()
+        .map(|element| match element {
+            ValueDefinition::LiteralValue(value) => {
+                coerce_numeric_literal(value, inner_iterable_type_id)
+            }
+            ValueDefinition::LiteralEnumVariant(variant) => generate_enum_variant_literal(
+                variant,
+                get_inner_iterable_type_definition(),
+                host_crate_info,
+            ),
+            ValueDefinition::Block(block) => recurse_literal_block(
+                block.clone(),
+                get_inner_iterable_type_definition(),
+                type_table,
+                host_crate_info,
+            ),
+            ValueDefinition::LiteralList(inner_elements) => recurse_literal_list(
+                inner_elements,
+                get_inner_iterable_type_definition(),
+                type_table,
+                host_crate_info,
+            ),
+            _ => {
+                panic!("Incorrect value bound to list element")
+            }
+        })
+        .join(", ");
+    format!("vec![{}]", elements_literal)
+}
+
+/// Qualifies the bare variant of an enum literal like `fit=Cover` with the path of its property's enum
+/// type `type_definition`, e.g. `some_crate::pax_reexports::pax_std::types::ImageFit::Cover`
+fn generate_enum_variant_literal(
+    variant: &str,
+    type_definition: &TypeDefinition,
+    host_crate_info: &HostCrateInfo,
+) -> String {
+    if type_definition.is_unknown_enum_variant(variant) {
+        panic!(
+            "`{}` is not a variant of `{}` — expected one of: {}",
+            variant,
+            type_definition.type_id,
+            type_definition
+                .enum_variant_names
+                .as_ref()
+                .unwrap()
+                .join(", ")
+        );
+    }
+    format!(
+        "{}{}::{}",
+        host_crate_info.import_prefix,
+        normalize_module_path(&type_definition.import_path),
+        variant
+    )
+}
+
 fn recurse_generate_render_nodes_literal(
     rngc: &RenderNodesGenerationContext,
     tnd: &TemplateNodeDefinition,
@@ -583,8 +798,6 @@ fn recurse_generate_render_nodes_literal(
         })
         .collect();
 
-    const DEFAULT_PROPERTY_LITERAL: &str = "PropertyLiteral::new(Default::default())";
-
     //pull inline event binding and store into map
     let events = generate_bound_events(tnd.settings.clone());
     let args = if tnd.type_id == parsing::TYPE_ID_REPEAT {
@@ -622,7 +835,8 @@ fn recurse_generate_render_nodes_literal(
             common_properties_literal,
             children_literal,
             slot_index_literal: "None".to_string(),
-            conditional_boolean_expression_literal: "None".to_string(),
+            conditional_arms_literal: vec![],
+            conditional_else_children_literal: vec![],
             pascal_identifier: rngc
                 .active_component_definition
                 .pascal_identifier
@@ -633,15 +847,44 @@ fn recurse_generate_render_nodes_literal(
             events,
             repeat_source_expression_literal_vec: rse_vec,
             repeat_source_expression_literal_range: rse_range,
+            repeat_key_expression_literal: rsd
+                .key_vtable_id
+                .map(|key_id| format!("Some(Box::new(PropertyExpression::new({})))", key_id))
+                .unwrap_or_else(|| "None".into()),
         }
     } else if tnd.type_id == parsing::TYPE_ID_IF {
         // If
-        let id = tnd
-            .control_flow_settings
+        let cfs = tnd.control_flow_settings.as_ref().unwrap();
+        let generate_branch_children_literal = |child_ids: &Vec<usize>| -> Vec<String> {
+            child_ids
+                .iter()
+                .map(|child_id| {
+                    let active_tnd =
+                        &rngc.active_component_definition.template.as_ref().unwrap()[*child_id];
+                    recurse_generate_render_nodes_literal(rngc, active_tnd, host_crate_info)
+                })
+                .collect()
+        };
+        let conditional_arms_literal = cfs
+            .conditional_arms
             .as_ref()
             .unwrap()
-            .condition_expression_vtable_id
-            .unwrap();
+            .iter()
+            .map(|arm| {
+                (
+                    format!(
+                        "Box::new(PropertyExpression::new({}))",
+                        arm.condition_expression_vtable_id.unwrap()
+                    ),
+                    generate_branch_children_literal(&arm.child_ids),
+                )
+            })
+            .collect();
+        let conditional_else_children_literal = cfs
+            .else_child_ids
+            .as_ref()
+            .map(generate_branch_children_literal)
+            .unwrap_or_default();
 
         let common_properties_literal = CommonProperties::get_default_properties_literal();
 
@@ -653,14 +896,14 @@ fn recurse_generate_render_nodes_literal(
             component_properties_struct: "None".to_string(),
             properties: vec![],
             common_properties_literal,
-            children_literal,
+            //a Conditional's children are passed per-branch, via `conditional_arms_literal` and `conditional_else_children_literal`
+            children_literal: vec![],
             slot_index_literal: "None".to_string(),
             repeat_source_expression_literal_vec: "None".to_string(),
             repeat_source_expression_literal_range: "None".to_string(),
-            conditional_boolean_expression_literal: format!(
-                "Some(Box::new(PropertyExpression::new({})))",
-                id
-            ),
+            repeat_key_expression_literal: "None".to_string(),
+            conditional_arms_literal,
+            conditional_else_children_literal,
             pascal_identifier: rngc
                 .active_component_definition
                 .pascal_identifier
@@ -672,6 +915,13 @@ fn recurse_generate_render_nodes_literal(
         }
     } else if tnd.type_id == parsing::TYPE_ID_SLOT {
         // Slot
+        if let Some(err) = get_out_of_range_slot_index_error(
+            rngc.components,
+            rngc.active_component_definition,
+            tnd,
+        ) {
+            panic!("{}", err);
+        }
         let id = tnd
             .control_flow_settings
             .as_ref()
@@ -693,7 +943,9 @@ fn recurse_generate_render_nodes_literal(
             slot_index_literal: format!("Some(Box::new(PropertyExpression::new({})))", id),
             repeat_source_expression_literal_vec: "None".to_string(),
             repeat_source_expression_literal_range: "None".to_string(),
-            conditional_boolean_expression_literal: "None".to_string(),
+            repeat_key_expression_literal: "None".to_string(),
+            conditional_arms_literal: vec![],
+            conditional_else_children_literal: vec![],
             pascal_identifier: rngc
                 .active_component_definition
                 .pascal_identifier
@@ -719,7 +971,15 @@ fn recurse_generate_render_nodes_literal(
 
         // Tuple of property_id, RIL literal string (e.g. `PropertyLiteral::new(...`_
         let property_ril_tuples: Vec<(String, String)> = component_for_current_node
-            .get_property_definitions(rngc.type_table)
+            .try_get_property_definitions(rngc.type_table)
+            .unwrap_or_else(|err| {
+                panic!(
+                    "Failed to generate `{}` node in the template of `{}`: {}",
+                    component_for_current_node.pascal_identifier,
+                    rngc.active_component_definition.pascal_identifier,
+                    err
+                )
+            })
             .iter()
             .map(|pd| {
                 let ril_literal_string = {
@@ -727,9 +987,25 @@ fn recurse_generate_render_nodes_literal(
                         if let Some(matched_setting) =
                             merged_settings.iter().find(|avd| avd.0 == pd.name)
                         {
+                            if pd.flags.is_readonly {
+                                panic!(
+                                    "{}",
+                                    PaxBuildError::ReadonlyPropertyBinding {
+                                        property: pd.name.clone(),
+                                        node: component_for_current_node.pascal_identifier.clone(),
+                                        component: rngc
+                                            .active_component_definition
+                                            .pascal_identifier
+                                            .clone(),
+                                    }
+                                );
+                            }
                             match &matched_setting.1 {
                                 ValueDefinition::LiteralValue(lv) => {
-                                    format!("PropertyLiteral::new({})", lv)
+                                    format!(
+                                        "PropertyLiteral::new({})",
+                                        coerce_numeric_literal(lv, &pd.type_id)
+                                    )
                                 }
                                 ValueDefinition::Expression(_, id)
                                 | ValueDefinition::Identifier(_, id) => {
@@ -743,7 +1019,53 @@ fn recurse_generate_render_nodes_literal(
                                         "PropertyLiteral::new({})",
                                         recurse_literal_block(
                                             block.clone(),
-                                            pd.get_type_definition(&rngc.type_table),
+                                            pd.try_get_type_definition(rngc.type_table)
+                                                .unwrap_or_else(|err| {
+                                                    panic!(
+                                                        "Failed to generate `{}` node in the template of `{}`: {}",
+                                                        component_for_current_node.pascal_identifier,
+                                                        rngc.active_component_definition.pascal_identifier,
+                                                        err
+                                                    )
+                                                }),
+                                            rngc.type_table,
+                                            host_crate_info
+                                        )
+                                    )
+                                }
+                                ValueDefinition::LiteralEnumVariant(variant) => {
+                                    format!(
+                                        "PropertyLiteral::new({})",
+                                        generate_enum_variant_literal(
+                                            variant,
+                                            pd.try_get_type_definition(rngc.type_table)
+                                                .unwrap_or_else(|err| {
+                                                    panic!(
+                                                        "Failed to generate `{}` node in the template of `{}`: {}",
+                                                        component_for_current_node.pascal_identifier,
+                                                        rngc.active_component_definition.pascal_identifier,
+                                                        err
+                                                    )
+                                                }),
+                                            host_crate_info
+                                        )
+                                    )
+                                }
+                                ValueDefinition::LiteralList(elements) => {
+                                    format!(
+                                        "PropertyLiteral::new({})",
+                                        recurse_literal_list(
+                                            elements,
+                                            pd.try_get_type_definition(rngc.type_table)
+                                                .unwrap_or_else(|err| {
+                                                    panic!(
+                                                        "Failed to generate `{}` node in the template of `{}`: {}",
+                                                        component_for_current_node.pascal_identifier,
+                                                        rngc.active_component_definition.pascal_identifier,
+                                                        err
+                                                    )
+                                                }),
+                                            rngc.type_table,
                                             host_crate_info
                                         )
                                     )
@@ -753,11 +1075,11 @@ fn recurse_generate_render_nodes_literal(
                                 }
                             }
                         } else {
-                            DEFAULT_PROPERTY_LITERAL.to_string()
+                            generate_default_property_literal(pd)
                         }
                     } else {
                         //no inline attributes at all; everything will be default
-                        DEFAULT_PROPERTY_LITERAL.to_string()
+                        generate_default_property_literal(pd)
                     }
                 };
 
@@ -849,7 +1171,9 @@ fn recurse_generate_render_nodes_literal(
             slot_index_literal: "None".to_string(),
             repeat_source_expression_literal_vec: "None".to_string(),
             repeat_source_expression_literal_range: "None".to_string(),
-            conditional_boolean_expression_literal: "None".to_string(),
+            repeat_key_expression_literal: "None".to_string(),
+            conditional_arms_literal: vec![],
+            conditional_else_children_literal: vec![],
             pascal_identifier: rngc
                 .active_component_definition
                 .pascal_identifier
@@ -875,7 +1199,7 @@ fn generate_events_map(events: Option<Vec<EventDefinition>>) -> HashMap<String,
     let _ = match events {
         Some(event_list) => {
             for e in event_list.iter() {
-                ret.insert(e.key.clone(), e.value.clone());
+                ret.insert(normalize_event_key(&e.key), e.value.clone());
             }
         }
         _ => {}
@@ -883,92 +1207,601 @@ fn generate_events_map(events: Option<Vec<EventDefinition>>) -> HashMap<String,
     ret
 }
 
-fn generate_cartridge_component_factory_literal(
-    manifest: &PaxManifest,
-    cd: &ComponentDefinition,
-    host_crate_info: &HostCrateInfo,
-) -> String {
-    let rngc = RenderNodesGenerationContext {
-        components: &manifest.components,
-        active_component_definition: cd,
-        type_table: &manifest.type_table,
-    };
-
-    let args = TemplateArgsCodegenCartridgeComponentFactory {
-        is_main_component: cd.is_main_component,
-        snake_case_type_id: cd.get_snake_case_id(),
-        component_properties_struct: cd.pascal_identifier.to_string(),
-        properties: cd
-            .get_property_definitions(&manifest.type_table)
-            .iter()
-            .map(|pd| {
-                (
-                    pd.clone(),
-                    pd.get_type_definition(&manifest.type_table)
-                        .type_id_escaped
-                        .clone(),
-                )
-            })
-            .collect(),
-        events: generate_events_map(cd.events.clone()),
-        render_nodes_literal: generate_cartridge_render_nodes_literal(&rngc, host_crate_info),
-        properties_coproduct_variant: cd.type_id_escaped.to_string(),
-    };
-
-    press_template_codegen_cartridge_component_factory(args)
-}
+/// Collects the names of the methods defined in `impl` blocks (including trait impls) for the
+/// type named `pascal_identifier` within the parsed Rust `file`, descending into inline modules.
+/// Methods generated by macros or provided by traits' default implementations are not seen.
+fn collect_impl_method_names(file: &syn::File, pascal_identifier: &str) -> HashSet<String> {
+    fn collect(items: &[syn::Item], pascal_identifier: &str, ret: &mut HashSet<String>) {
+        for item in items {
+            match item {
+                syn::Item::Impl(item_impl) => {
+                    let is_implementing_type = match &*item_impl.self_ty {
+                        syn::Type::Path(type_path) => type_path
+                            .path
+                            .segments
+                            .last()
+                            .is_some_and(|segment| segment.ident == pascal_identifier),
+                        _ => false,
+                    };
+                    if is_implementing_type {
+                        ret.extend(item_impl.items.iter().filter_map(
+                            |impl_item| match impl_item {
+                                syn::ImplItem::Method(method) => Some(method.sig.ident.to_string()),
+                                _ => None,
+                            },
+                        ));
+                    }
+                }
+                syn::Item::Mod(item_mod) => {
+                    if let Some((_, items)) = &item_mod.content {
+                        collect(items, pascal_identifier, ret);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
 
-fn get_or_create_pax_directory(working_dir: &str) -> PathBuf {
-    let working_path = std::path::Path::new(working_dir).join(".pax");
-    std::fs::create_dir_all(&working_path).unwrap();
-    fs::canonicalize(working_path).unwrap()
+    let mut ret = HashSet::new();
+    collect(&file.items, pascal_identifier, &mut ret);
+    ret
 }
 
-/// Pulled from host Cargo.toml
-struct HostCrateInfo {
-    /// for example: `pax-example`
-    name: String,
-    /// for example: `pax_example`
-    identifier: String,
-    /// for example: `some_crate::pax_reexports`,
-    import_prefix: String,
+/// Warns of each event handler referenced by a component defined in the host crate — whether via
+/// `@handlers` or an inline `@event=handler` binding — that names no method found on that component
+/// in the `.rs` files under `source_dir`.  Only a warning, because handlers may be defined where this
+/// check can't see them, e.g. by macros, through traits or outside `source_dir`; a handler that is
+/// truly missing still fails to compile.
+fn warn_missing_event_handlers(manifest: &PaxManifest, source_dir: &Path) {
+    match find_missing_event_handlers(manifest, source_dir) {
+        Ok(missing_event_handlers) => {
+            for missing in missing_event_handlers {
+                warn!("⚠️  {}", missing);
+            }
+        }
+        Err(err) => warn!("⚠️  Failed to check event handlers: {}", err),
+    }
 }
 
-fn get_host_crate_info(cargo_toml_path: &Path) -> HostCrateInfo {
-    let existing_cargo_toml = toml_edit::Document::from_str(
-        &fs::read_to_string(fs::canonicalize(cargo_toml_path).unwrap()).unwrap(),
-    )
-    .expect("Error loading host Cargo.toml");
+/// Returns a `PaxBuildError::MissingEventHandler` for each event handler that fails the check
+/// described in `warn_missing_event_handlers`.  Files that fail to parse as Rust are skipped.
+fn find_missing_event_handlers(
+    manifest: &PaxManifest,
+    source_dir: &Path,
+) -> Result<Vec<PaxBuildError>, PaxBuildError> {
+    let sources = walkdir::WalkDir::new(source_dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "rs"))
+        .map(|entry| fs::read_to_string(entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let files = sources
+        .iter()
+        .filter_map(|source| syn::parse_file(source).ok())
+        .collect::<Vec<_>>();
 
-    let name = existing_cargo_toml["package"]["name"]
-        .as_str()
-        .unwrap()
-        .to_string();
-    let identifier = name.replace("-", "_"); //NOTE: perhaps this could be less naive?
-    let import_prefix = format!("{}::pax_reexports::", &identifier);
+    let mut missing_event_handlers = vec![];
+    for cd in manifest
+        .components
+        .values()
+        .filter(|cd| cd.module_path.starts_with("crate"))
+        .sorted_by(|cd0, cd1| cd0.type_id.cmp(&cd1.type_id))
+    {
+        let mut handlers = generate_events_map(cd.events.clone())
+            .into_values()
+            .flatten()
+            .collect::<Vec<_>>();
+        if let Some(template) = &cd.template {
+            for tnd in template.iter() {
+                handlers.extend(generate_bound_events(tnd.settings.clone()).into_values());
+            }
+        }
+        if handlers.is_empty() {
+            continue;
+        }
 
-    HostCrateInfo {
-        name,
-        identifier,
-        import_prefix,
+        let methods: HashSet<String> = files
+            .iter()
+            .flat_map(|file| collect_impl_method_names(file, &cd.pascal_identifier))
+            .collect();
+        missing_event_handlers.extend(
+            handlers
+                .into_iter()
+                .sorted()
+                .dedup()
+                .filter(|h| !methods.contains(h))
+                .map(|missing| PaxBuildError::MissingEventHandler {
+                    handler: missing,
+                    component: cd.pascal_identifier.clone(),
+                }),
+        );
     }
+    Ok(missing_event_handlers)
 }
 
-#[allow(unused)]
-static TEMPLATE_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/templates");
+/// Returns a `PaxBuildError::LiteralTypeMismatch` for each literal setting, inline or from `@settings`,
+/// whose value can never be converted into the type of its property, e.g. `visible="yes"` for a `bool`.
+/// Only mismatches between numbers, booleans, strings, `Size`s and `Rotation`s are detected.  Enum literals
+/// naming a variant that their property's enum lacks are reported as `PaxBuildError::UnknownEnumVariant`.
+fn find_literal_type_mismatches(manifest: &PaxManifest) -> Vec<PaxBuildError> {
+    let mut literal_type_mismatches = vec![];
+    for cd in manifest
+        .components
+        .values()
+        .sorted_by(|cd0, cd1| cd0.type_id.cmp(&cd1.type_id))
+    {
+        for tnd in cd.template.iter().flatten() {
+            let node_component = match manifest.components.get(&tnd.type_id) {
+                Some(node_component) => node_component,
+                None => continue,
+            };
+            // Dangling type_ids are reported with context during codegen
+            let property_definitions =
+                match node_component.try_get_property_definitions(&manifest.type_table) {
+                    Ok(property_definitions) => property_definitions,
+                    Err(_) => continue,
+                };
+            let merged_settings = expressions::merge_inline_settings_with_settings_block(
+                tnd,
+                &cd.settings,
+                Some(&node_component.pascal_identifier),
+            );
+            recurse_find_literal_type_mismatches(
+                merged_settings
+                    .iter()
+                    .flatten()
+                    .sorted_by(|(key0, _), (key1, _)| key0.cmp(key1)),
+                property_definitions,
+                &manifest.type_table,
+                &cd.pascal_identifier,
+                &mut literal_type_mismatches,
+            );
+        }
+    }
+    literal_type_mismatches
+}
 
-/// Executes a shell command to run the feature-flagged parser at the specified path
-/// Returns an output object containing bytestreams of stdout/stderr as well as an exit code
-pub fn run_parser_binary(path: &str, process_child_ids: Arc<Mutex<Vec<u64>>>) -> Output {
-    let mut cmd = Command::new("cargo");
-    cmd.current_dir(path)
-        .arg("run")
-        .arg("--features")
-        .arg("parser")
-        .arg("--color")
-        .arg("always")
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped());
+/// Returns a `PaxBuildError::ReadonlyPropertyBinding` for each setting, inline or from `@settings`,
+/// that binds a readonly property of its node's component
+fn find_readonly_property_bindings(manifest: &PaxManifest) -> Vec<PaxBuildError> {
+    let mut readonly_property_bindings = vec![];
+    for cd in manifest
+        .components
+        .values()
+        .sorted_by(|cd0, cd1| cd0.type_id.cmp(&cd1.type_id))
+    {
+        for tnd in cd.template.iter().flatten() {
+            let node_component = match manifest.components.get(&tnd.type_id) {
+                Some(node_component) => node_component,
+                None => continue,
+            };
+            // Dangling type_ids are reported with context during codegen
+            let property_definitions =
+                match node_component.try_get_property_definitions(&manifest.type_table) {
+                    Ok(property_definitions) => property_definitions,
+                    Err(_) => continue,
+                };
+            let merged_settings = expressions::merge_inline_settings_with_settings_block(
+                tnd,
+                &cd.settings,
+                Some(&node_component.pascal_identifier),
+            );
+            for (key, _) in merged_settings
+                .iter()
+                .flatten()
+                .sorted_by(|(key0, _), (key1, _)| key0.cmp(key1))
+            {
+                if property_definitions
+                    .iter()
+                    .any(|pd| &pd.name == key && pd.flags.is_readonly)
+                {
+                    readonly_property_bindings.push(PaxBuildError::ReadonlyPropertyBinding {
+                        property: key.clone(),
+                        node: node_component.pascal_identifier.clone(),
+                        component: cd.pascal_identifier.clone(),
+                    });
+                }
+            }
+        }
+    }
+    readonly_property_bindings
+}
+
+fn recurse_find_literal_type_mismatches<'a>(
+    settings: impl Iterator<Item = &'a (String, ValueDefinition)>,
+    property_definitions: &[PropertyDefinition],
+    type_table: &TypeTable,
+    component: &str,
+    literal_type_mismatches: &mut Vec<PaxBuildError>,
+) {
+    for (key, value) in settings {
+        let property_type_id = match expressions::get_builtin_property_type(key).or_else(|| {
+            property_definitions
+                .iter()
+                .find(|pd| &pd.name == key)
+                .map(|pd| pd.type_id.as_str())
+        }) {
+            Some(property_type_id) => property_type_id,
+            None => continue,
+        };
+        find_value_literal_type_mismatches(
+            key,
+            value,
+            property_type_id,
+            type_table,
+            component,
+            literal_type_mismatches,
+        );
+    }
+}
+
+fn find_value_literal_type_mismatches(
+    key: &str,
+    value: &ValueDefinition,
+    type_id: &str,
+    type_table: &TypeTable,
+    component: &str,
+    literal_type_mismatches: &mut Vec<PaxBuildError>,
+) {
+    let mut push_mismatch = |literal_kind: &str| {
+        literal_type_mismatches.push(PaxBuildError::LiteralTypeMismatch {
+            literal_kind: literal_kind.to_string(),
+            property: key.to_string(),
+            expected_type: type_id.to_string(),
+            component: component.to_string(),
+        })
+    };
+    match value {
+        ValueDefinition::LiteralValue(literal) => {
+            if let Some(literal_kind) = get_unassignable_literal_kind(literal, type_id) {
+                push_mismatch(literal_kind);
+            }
+        }
+        ValueDefinition::LiteralEnumVariant(variant) => match type_table.get(type_id) {
+            Some(type_definition) if type_definition.is_unknown_enum_variant(variant) => {
+                literal_type_mismatches.push(PaxBuildError::UnknownEnumVariant {
+                    variant: variant.clone(),
+                    property: key.to_string(),
+                    enum_type: type_id.to_string(),
+                    expected_variants: type_definition
+                        .enum_variant_names
+                        .clone()
+                        .unwrap_or_default(),
+                    component: component.to_string(),
+                })
+            }
+            _ => {}
+        },
+        ValueDefinition::Block(block) => {
+            if let Some(type_definition) = type_table.get(type_id) {
+                recurse_find_literal_type_mismatches(
+                    block.settings_key_value_pairs.iter(),
+                    &type_definition.property_definitions,
+                    type_table,
+                    component,
+                    literal_type_mismatches,
+                );
+            }
+        }
+        //Each element is checked against the list's `inner_iterable_type_id`
+        ValueDefinition::LiteralList(elements) => match type_table.get(type_id) {
+            Some(TypeDefinition {
+                inner_iterable_type_id: Some(inner_iterable_type_id),
+                ..
+            }) => {
+                for element in elements.iter() {
+                    find_value_literal_type_mismatches(
+                        key,
+                        element,
+                        inner_iterable_type_id,
+                        type_table,
+                        component,
+                        literal_type_mismatches,
+                    );
+                }
+            }
+            Some(_) => push_mismatch("list"),
+            None => {}
+        },
+        _ => {}
+    }
+}
+
+/// If `literal`, as lowered to RIL by `run_pratt_parser`, can never be assigned to a property of type `type_id`,
+/// returns the kind of literal it is, e.g. `number`.  Literals of other kinds than those listed in
+/// `find_literal_type_mismatches`, like enum variants and tuples, as well as properties of other types,
+/// are assumed to be assignable.
+fn get_unassignable_literal_kind(literal: &str, type_id: &str) -> Option<&'static str> {
+    let literal_kind =
+        if literal.starts_with("Numeric::from") || literal.starts_with("(-Numeric::from") {
+            "number"
+        } else if literal.starts_with("Size::") {
+            "size"
+        } else if literal.starts_with("Rotation::") {
+            "rotation"
+        } else if literal == "true" || literal == "false" {
+            "boolean"
+        } else if literal.ends_with(".to_string()") {
+            "string"
+        } else {
+            return None;
+        };
+
+    let type_name = type_id.rsplit("::").next().unwrap();
+    let assignable_literal_kinds: &[&str] = match type_name {
+        "Numeric" => &["number"],
+        "bool" => &["boolean"],
+        "String" => &["string"],
+        "Size" | "SizePixels" => &["size"],
+        "Rotation" => &["rotation", "number", "size"],
+        _ if manifest::SUPPORTED_NUMERIC_PRIMITIVES.contains(&type_name) => &["number"],
+        _ => return None,
+    };
+    if assignable_literal_kinds.contains(&literal_kind) {
+        None
+    } else {
+        Some(literal_kind)
+    }
+}
+
+/// Compiles the expressions of every component, returning a `PaxBuildError::ExpressionCompilationFailed`
+/// for each unresolved symbol or function they reference
+fn find_expression_compilation_errors(
+    manifest: &PaxManifest,
+    expression_functions: &ExpressionFunctionRegistry,
+) -> Vec<PaxBuildError> {
+    match expressions::compile_all_expressions_with_functions(
+        &mut manifest.clone(),
+        &mut ExpressionCache::default(),
+        expression_functions,
+    ) {
+        Ok(_) => vec![],
+        Err(unresolved_symbol_errors) => unresolved_symbol_errors
+            .into_iter()
+            .map(PaxBuildError::from)
+            .collect(),
+    }
+}
+
+/// Returns a `PaxBuildError::SlotIndexOutOfRange` for each `slot` whose index can never be filled
+/// by an adoptee; see `get_out_of_range_slot_index_error`
+fn find_out_of_range_slot_indices(manifest: &PaxManifest) -> Vec<PaxBuildError> {
+    manifest
+        .components
+        .values()
+        .sorted_by(|cd0, cd1| cd0.type_id.cmp(&cd1.type_id))
+        .flat_map(|cd| {
+            cd.template
+                .iter()
+                .flatten()
+                .filter(|tnd| tnd.type_id == parsing::TYPE_ID_SLOT)
+                .filter_map(|tnd| get_out_of_range_slot_index_error(&manifest.components, cd, tnd))
+        })
+        .collect()
+}
+
+/// Checks a `slot` node (`slot_tnd`) within the template of `component` against the adoptees passed to
+/// `component` throughout the project.  Only statically known cases are checked: the slot index must be
+/// a literal, e.g. `slot(2)`, `component` must be instantiated at least once, and no instance may be passed
+/// `if` or `for` adoptees, which expand to a runtime-variable number of nodes.  Returns a
+/// `PaxBuildError::SlotIndexOutOfRange` if no instance passes enough adoptees to fill the slot.
+fn get_out_of_range_slot_index_error(
+    components: &HashMap<String, ComponentDefinition>,
+    component: &ComponentDefinition,
+    slot_tnd: &TemplateNodeDefinition,
+) -> Option<PaxBuildError> {
+    let index = get_static_slot_index(
+        slot_tnd
+            .control_flow_settings
+            .as_ref()?
+            .slot_index_expression_paxel
+            .as_ref()?,
+    )?;
+
+    let mut max_adoptee_count = None;
+    for cd in components.values() {
+        let template = match &cd.template {
+            Some(template) => template,
+            None => continue,
+        };
+        for instance_tnd in template
+            .iter()
+            .filter(|tnd| tnd.type_id == component.type_id)
+        {
+            let adoptees = instance_tnd.child_ids.iter().map(|id| &template[*id]);
+            if adoptees.clone().any(|adoptee| {
+                adoptee.type_id == parsing::TYPE_ID_IF || adoptee.type_id == parsing::TYPE_ID_REPEAT
+            }) {
+                return None;
+            }
+            max_adoptee_count = max_adoptee_count.max(Some(adoptees.count()));
+        }
+    }
+
+    match max_adoptee_count {
+        Some(adoptee_count) if index >= adoptee_count => Some(PaxBuildError::SlotIndexOutOfRange {
+            index,
+            adoptee_count,
+            component: component.pascal_identifier.clone(),
+        }),
+        _ => None,
+    }
+}
+
+/// Returns the index of a `slot` if its paxel is an integer literal, e.g. the `1` in `slot(1)`
+fn get_static_slot_index(paxel: &str) -> Option<usize> {
+    let mut paxel = paxel.trim();
+    while let Some(inner) = paxel
+        .strip_prefix('(')
+        .and_then(|paxel| paxel.strip_suffix(')'))
+    {
+        paxel = inner.trim();
+    }
+    paxel.parse().ok()
+}
+
+fn generate_cartridge_component_factory_literal(
+    manifest: &PaxManifest,
+    cd: &ComponentDefinition,
+    host_crate_info: &HostCrateInfo,
+) -> String {
+    let rngc = RenderNodesGenerationContext {
+        components: &manifest.components,
+        active_component_definition: cd,
+        type_table: &manifest.type_table,
+    };
+
+    let args = TemplateArgsCodegenCartridgeComponentFactory {
+        is_main_component: cd.is_main_component,
+        snake_case_type_id: cd.get_snake_case_id(),
+        component_properties_struct: cd.pascal_identifier.to_string(),
+        properties: cd
+            .try_get_property_definitions(&manifest.type_table)
+            .unwrap_or_else(|err| {
+                panic!(
+                    "Failed to generate component factory for `{}`: {}",
+                    cd.pascal_identifier, err
+                )
+            })
+            .iter()
+            .map(|pd| {
+                (
+                    pd.clone(),
+                    pd.try_get_type_definition(&manifest.type_table)
+                        .unwrap_or_else(|err| {
+                            panic!(
+                                "Failed to generate component factory for `{}`: {}",
+                                cd.pascal_identifier, err
+                            )
+                        })
+                        .type_id_escaped
+                        .clone(),
+                )
+            })
+            .collect(),
+        events: generate_events_map(cd.events.clone()),
+        render_nodes_literal: generate_cartridge_render_nodes_literal(&rngc, host_crate_info),
+        properties_coproduct_variant: cd.type_id_escaped.to_string(),
+    };
+
+    press_template_codegen_cartridge_component_factory(args)
+}
+
+fn get_or_create_pax_directory(working_dir: &str) -> PathBuf {
+    let working_path = std::path::Path::new(working_dir).join(".pax");
+    std::fs::create_dir_all(&working_path).unwrap();
+    fs::canonicalize(working_path).unwrap()
+}
+
+/// Pulled from host Cargo.toml
+struct HostCrateInfo {
+    /// for example: `pax-example`
+    name: String,
+    /// for example: `pax_example`
+    identifier: String,
+    /// for example: `some_crate::pax_reexports`,
+    import_prefix: String,
+    /// Host functions callable from expressions, declared under `[package.metadata.pax.expression-functions]`
+    expression_functions: ExpressionFunctionRegistry,
+}
+
+fn get_host_crate_info(cargo_toml_path: &Path) -> HostCrateInfo {
+    let existing_cargo_toml = toml_edit::Document::from_str(
+        &fs::read_to_string(fs::canonicalize(cargo_toml_path).unwrap()).unwrap(),
+    )
+    .expect("Error loading host Cargo.toml");
+
+    let name = existing_cargo_toml["package"]["name"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let identifier = name.replace("-", "_"); //NOTE: perhaps this could be less naive?
+    let import_prefix = format!("{}::pax_reexports::", &identifier);
+    let expression_functions = get_expression_functions(&existing_cargo_toml, &identifier);
+
+    HostCrateInfo {
+        name,
+        identifier,
+        import_prefix,
+        expression_functions,
+    }
+}
+
+/// Reads the host functions that expressions may call from the host Cargo.toml, declared by the name that
+/// expressions call them by along with their path within the host crate and their signature, e.g.:
+///
+/// ```toml
+/// [package.metadata.pax.expression-functions]
+/// lerp = { path = "math::lerp", args = ["f64", "f64", "f64"], returns = "f64" }
+/// ```
+///
+/// Each function must be `pub` and reachable from the root of the host crate at `path`, which may also be
+/// written starting with `crate::`.
+fn get_expression_functions(
+    cargo_toml: &toml_edit::Document,
+    host_identifier: &str,
+) -> ExpressionFunctionRegistry {
+    let mut expression_functions = ExpressionFunctionRegistry::default();
+    let declarations = cargo_toml
+        .get("package")
+        .and_then(|package| package.get("metadata"))
+        .and_then(|metadata| metadata.get("pax"))
+        .and_then(|pax| pax.get("expression-functions"))
+        .and_then(|declarations| declarations.as_table_like());
+    for (name, declaration) in declarations.into_iter().flat_map(|d| d.iter()) {
+        let malformed = format!(
+            "Malformed expression function `{}` in host Cargo.toml; expected e.g. `{} = {{ path = \"math::{}\", args = [\"f64\"], returns = \"f64\" }}`",
+            name, name, name
+        );
+        let path = declaration
+            .get("path")
+            .and_then(Item::as_str)
+            .unwrap_or_else(|| panic!("{}", malformed));
+        let arg_type_ids = declaration
+            .get("args")
+            .and_then(Item::as_array)
+            .unwrap_or_else(|| panic!("{}", malformed))
+            .iter()
+            .map(|arg| {
+                arg.as_str()
+                    .unwrap_or_else(|| panic!("{}", malformed))
+                    .to_string()
+            })
+            .collect();
+        let return_type_id = declaration
+            .get("returns")
+            .and_then(Item::as_str)
+            .unwrap_or_else(|| panic!("{}", malformed));
+        expression_functions.register(
+            name,
+            ExpressionFunction {
+                path: format!(
+                    "{}::{}",
+                    host_identifier,
+                    path.trim_start_matches("crate::")
+                ),
+                arg_type_ids,
+                return_type_id: return_type_id.to_string(),
+            },
+        );
+    }
+    expression_functions
+}
+
+#[allow(unused)]
+static TEMPLATE_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/templates");
+
+/// Executes a shell command to run the feature-flagged parser at the specified path
+/// Returns an output object containing bytestreams of stdout/stderr as well as an exit code
+/// When `is_offline`, cargo is passed `--offline` so that it never touches the network.
+pub fn run_parser_binary(
+    path: &str,
+    is_offline: bool,
+    no_default_features: bool,
+    process_child_ids: Arc<Mutex<Vec<u64>>>,
+) -> Output {
+    require_tool_on_path("cargo", CARGO_INSTALL_HINT).unwrap_or_else(|err| panic!("{}", err));
+    let mut cmd = get_parser_binary_command(path, is_offline, no_default_features);
 
     #[cfg(unix)]
     unsafe {
@@ -978,15 +1811,85 @@ pub fn run_parser_binary(path: &str, process_child_ids: Arc<Mutex<Vec<u64>>>) ->
     let child = cmd.spawn().expect("failed to spawn child");
 
     // child.stdin.take().map(drop);
-    let output = wait_with_output(&process_child_ids, child);
-    output
+    wait_with_output(&process_child_ids, child)
+}
+
+const CARGO_INSTALL_HINT: &str = "install Rust, including cargo, via https://rustup.rs";
+const WASM_PACK_INSTALL_HINT: &str = "install via `cargo install wasm-pack`";
+
+/// Checks that `tool` resolves to an executable file on `PATH`, so that a missing tool is reported
+/// along with `install_hint` rather than as an opaque failure to spawn it
+fn require_tool_on_path(tool: &str, install_hint: &str) -> Result<(), PaxBuildError> {
+    let file_names = [
+        tool.to_string(),
+        format!("{}{}", tool, std::env::consts::EXE_SUFFIX),
+    ];
+    let is_on_path = std::env::var_os("PATH").is_some_and(|path| {
+        std::env::split_paths(&path).any(|dir| {
+            file_names
+                .iter()
+                .any(|file_name| is_executable_file(&dir.join(file_name)))
+        })
+    });
+    if is_on_path {
+        Ok(())
+    } else {
+        Err(PaxBuildError::MissingTool {
+            tool: tool.to_string(),
+            install_hint: install_hint.to_string(),
+        })
+    }
+}
+
+fn is_executable_file(path: &Path) -> bool {
+    match fs::metadata(path) {
+        #[cfg(unix)]
+        Ok(metadata) if metadata.is_file() => {
+            use std::os::unix::fs::PermissionsExt;
+            metadata.permissions().mode() & 0o111 != 0
+        }
+        #[cfg(not(unix))]
+        Ok(metadata) => metadata.is_file(),
+        _ => false,
+    }
+}
+
+/// Checks up front for the tools that a build for `ctx` shells out to: `cargo`, for the parser binary
+/// and the macOS chassis, and `wasm-pack` for the web chassis
+fn require_build_tools(ctx: &RunContext) -> Result<(), PaxBuildError> {
+    require_tool_on_path("cargo", CARGO_INSTALL_HINT)?;
+    if let (RunTarget::Web, false) = (&ctx.target, ctx.dry_run) {
+        require_tool_on_path("wasm-pack", WASM_PACK_INSTALL_HINT)?;
+    }
+    Ok(())
+}
+
+/// The `cargo run --features parser` invocation that builds and runs the userland crate's parser binary
+fn get_parser_binary_command(path: &str, is_offline: bool, no_default_features: bool) -> Command {
+    let mut cmd = Command::new("cargo");
+    cmd.current_dir(path)
+        .arg("run")
+        .arg("--features")
+        .arg("parser")
+        .arg("--color")
+        .arg("always")
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+
+    if no_default_features {
+        cmd.arg("--no-default-features");
+    }
+    if is_offline {
+        cmd.arg("--offline");
+    }
+    cmd
 }
 
 use colored::{ColoredString, Colorize};
 
 use crate::parsing::escape_identifier;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize)]
 struct Metadata {
@@ -999,22 +1902,29 @@ struct Package {
     version: String,
 }
 
-fn get_version_of_whitelisted_packages(path: &str) -> Result<String, &'static str> {
+/// The version shared by every `pax-*` package in the dependency graph of the crate at `path`.  Fails if
+/// `cargo metadata` fails, if there is no `pax-*` dependency, or if `pax-*` packages' versions differ.
+fn get_version_of_whitelisted_packages(path: &str, is_offline: bool) -> Result<String, String> {
     let mut cmd = Command::new("cargo");
-    let output = cmd
-        .arg("metadata")
+    cmd.arg("metadata")
         .arg("--format-version=1")
-        .current_dir(path)
+        .current_dir(path);
+    if is_offline {
+        cmd.arg("--offline");
+    }
+    let output = cmd
         .output()
-        .expect("Failed to execute `cargo metadata`");
+        .map_err(|err| format!("Failed to execute `cargo metadata`: {}", err))?;
 
     if !output.status.success() {
-        eprintln!("{}", String::from_utf8_lossy(&output.stderr));
-        panic!("Failed to get metadata from Cargo");
+        return Err(format!(
+            "Failed to get metadata from Cargo: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
     }
 
-    let metadata: Metadata =
-        serde_json::from_slice(&output.stdout).expect("Failed to parse JSON from `cargo metadata`");
+    let metadata: Metadata = serde_json::from_slice(&output.stdout)
+        .map_err(|err| format!("Failed to parse JSON from `cargo metadata`: {}", err))?;
 
     let mut tracked_version: Option<String> = None;
 
@@ -1022,10 +1932,10 @@ fn get_version_of_whitelisted_packages(path: &str) -> Result<String, &'static st
         if ALL_PKGS.contains(&package.name.as_str()) {
             if let Some(ref version) = tracked_version {
                 if package.version != *version {
-                    panic!(
+                    return Err(format!(
                         "Version mismatch for {}: expected {}, found {}",
                         package.name, version, package.version
-                    );
+                    ));
                 }
             } else {
                 tracked_version = Some(package.version.clone());
@@ -1033,7 +1943,7 @@ fn get_version_of_whitelisted_packages(path: &str) -> Result<String, &'static st
         }
     }
 
-    tracked_version.ok_or("Cannot build a Pax project without a `pax-*` dependency somewhere in your project's dependency graph.  Add e.g. `pax-lang` to your Cargo.toml to resolve this error.")
+    tracked_version.ok_or_else(|| "Cannot build a Pax project without a `pax-*` dependency somewhere in your project's dependency graph.  Add e.g. `pax-lang` to your Cargo.toml to resolve this error.".to_string())
 }
 
 lazy_static! {
@@ -1041,85 +1951,558 @@ lazy_static! {
     static ref PAX_BADGE: ColoredString = "[Pax]".bold().on_black().white();
 }
 
+/// Installs the default logger, which writes each log record to stdout badged with `PAX_BADGE`, rendering
+/// requests served by the dev server as `🍱 Served` lines.  Shows `info` and above from Pax and the dev server,
+/// unless `RUST_LOG` is set, e.g. `RUST_LOG=pax_compiler=debug` to surface more detail.
+/// Does nothing if a logger is already installed, e.g. by a tool capturing Pax's log records itself.
+pub fn init_logging() {
+    let _ = env_logger::Builder::from_env(
+        env_logger::Env::default().default_filter_or("pax_compiler=info,actix_web=info"),
+    )
+    .format(|buf, record| {
+        if record.target().starts_with("actix_web") {
+            writeln!(buf, "{} 🍱 Served {}", *PAX_BADGE, record.args())
+        } else {
+            writeln!(buf, "{} {}", *PAX_BADGE, record.args())
+        }
+    })
+    .target(env_logger::Target::Stdout)
+    .try_init();
+}
+
 /// For the specified file path or current working directory, first compile Pax project,
 /// then run it with a patched build of the `chassis` appropriate for the specified platform
 /// See: pax-compiler-sequence-diagram.png
 pub fn perform_build(ctx: &RunContext) -> Result<(), ()> {
+    init_logging();
+    require_build_tools(ctx).map_err(|err| error!("❌ {}", err))?;
+    //`--verbose` details are logged at `info`, and otherwise at `debug` for `RUST_LOG` to surface
+    let detail_level = if ctx.verbose {
+        Level::Info
+    } else {
+        Level::Debug
+    };
+
     //First we clone dependencies into the .pax/pkg directory.  We must do this before running
     //the parser binary specifical for libdev in pax-example — see pax-example/Cargo.toml where
     //dependency paths are `.pax/pkg/*`.
-    let pax_dir = get_or_create_pax_directory(&ctx.path);
+    let project_dir = ctx.get_project_dir();
+    let project_dir_str = project_dir.to_str().unwrap();
+    let pax_dir = get_or_create_pax_directory(project_dir_str);
 
     //Inspect Cargo.lock to find declared pax lib versions.  Note that this is moot for
-    //libdev, where we don't care about a crates.io version (and where `cargo metadata` won't work
+    //libdev and vendored builds, where we don't care about a crates.io version (and where `cargo metadata` won't work
     //on a cold-start monorepo clone.)
-    let pax_version = if ctx.is_libdev_mode {
+    let pax_version = if ctx.is_libdev_mode || ctx.vendor_dir.is_some() {
         None
     } else {
-        Some(get_version_of_whitelisted_packages(&ctx.path).unwrap())
+        Some(
+            get_version_of_whitelisted_packages(project_dir_str, ctx.offline)
+                .unwrap_or_else(|err| panic!("{}", err)),
+        )
     };
-    clone_all_dependencies_to_tmp(&pax_dir, &pax_version, &ctx);
-
-    println!("{} 🛠️  Building parser binary with `cargo`...", *PAX_BADGE);
-    // Run parser bin from host project with `--features parser`
-    let output = run_parser_binary(&ctx.path, Arc::clone(&ctx.process_child_ids));
-
-    // Forward stderr only
-    std::io::stderr()
-        .write_all(output.stderr.as_slice())
-        .unwrap();
-    assert_eq!(
-        output.status.code().unwrap(),
-        0,
-        "Parsing failed — there is likely a syntax error in the provided pax"
-    );
-
-    let out = String::from_utf8(output.stdout).unwrap();
-    let mut manifest: PaxManifest =
-        serde_json::from_str(&out).expect(&format!("Malformed JSON from parser: {}", &out));
-    let host_cargo_toml_path = Path::new(&ctx.path).join("Cargo.toml");
-    let host_crate_info = get_host_crate_info(&host_cargo_toml_path);
-    update_property_prefixes_in_place(&mut manifest, &host_crate_info);
+    clone_all_dependencies_to_tmp(&pax_dir, &pax_version, ctx)
+        .unwrap_or_else(|err| panic!("{}", err));
 
-    println!("{} 🧮 Compiling expressions", *PAX_BADGE);
-    expressions::compile_all_expressions(&mut manifest);
+    let mut build_report = BuildReport {
+        target: <&RunTarget as Into<&str>>::into(&ctx.target).to_string(),
+        ..Default::default()
+    };
 
-    println!("{} 🦀 Generating Rust", *PAX_BADGE);
+    info!("🛠️  Building parser binary with `cargo`...");
+    let phase_start = Instant::now();
+    let (mut manifest, host_crate_info) = parse_manifest(
+        &ctx.get_manifest_path(),
+        ctx.offline,
+        ctx.parser_no_default_features,
+        Arc::clone(&ctx.process_child_ids),
+    )
+    .unwrap_or_else(|err| panic!("{}", err));
+    build_report.timings_ms.parse = phase_start.elapsed().as_millis();
+    if ctx.dump_types {
+        print!("{}", manifest.dump_types());
+    }
+    warn_missing_event_handlers(&manifest, &project_dir.join("src"));
+
+    info!("🧮 Compiling expressions");
+    let phase_start = Instant::now();
+    let mut expression_cache = ExpressionCache::load(&pax_dir);
+    let expression_compilation_durations = expressions::compile_all_expressions_with_functions(
+        &mut manifest,
+        &mut expression_cache,
+        &host_crate_info.expression_functions,
+    )
+    .unwrap_or_else(|unresolved_symbol_errors| {
+        panic!("{}", unresolved_symbol_errors.iter().join("\n\n"))
+    });
+    if let Err(err) = expression_cache.save(&pax_dir) {
+        warn!("⚠️  Failed to write expression cache: {}", err);
+    }
+    build_report.timings_ms.expression_compilation = phase_start.elapsed().as_millis();
+    build_report.cached_expression_count = expression_cache.hits;
+    if log_enabled!(detail_level) {
+        log!(detail_level, "📊 {}", manifest.stats());
+    }
+    build_report.component_count = manifest.components.len();
+    build_report.expression_count = manifest
+        .expression_specs
+        .as_ref()
+        .map_or(0, |expression_specs| expression_specs.len());
+
+    info!("🦀 Generating Rust");
+    let phase_start = Instant::now();
+    let unused_import_paths = manifest.remove_unused_import_paths();
+    if !unused_import_paths.is_empty() {
+        log!(
+            detail_level,
+            "🧹 Dropped {} unused import(s): {}",
+            unused_import_paths.len(),
+            unused_import_paths.join(", ")
+        );
+    }
     generate_reexports_partial_rs(&pax_dir, &manifest);
     generate_and_overwrite_properties_coproduct(&pax_dir, &manifest, &host_crate_info);
-    generate_and_overwrite_cartridge(&pax_dir, &manifest, &host_crate_info);
+    let codegen_durations = generate_and_overwrite_cartridge(&pax_dir, &manifest, &host_crate_info);
+    if let RunTarget::Web = ctx.target {
+        generate_web_interface_dts(&pax_dir, &manifest);
+    }
+    build_report.timings_ms.codegen = phase_start.elapsed().as_millis();
+    build_report.component_timings_us =
+        collect_component_timings(&expression_compilation_durations, &codegen_durations);
+
+    if ctx.dry_run {
+        //Everything has been generated; skip the (slow) chassis and interface builds
+        info!("✅ Dry run complete — Pax compiled to RIL without errors");
+        if ctx.should_write_build_report {
+            write_build_report(&pax_dir, &build_report);
+        }
+        return Ok(());
+    }
 
     //7. Build the appropriate `chassis` from source, with the patched `Cargo.toml`, Properties Coproduct, and Cartridge from above
-    println!("{} 🧱 Building cartridge with `cargo`", *PAX_BADGE);
-    build_chassis_with_cartridge(&pax_dir, &ctx, Arc::clone(&ctx.process_child_ids));
+    info!("🧱 Building cartridge with `cargo`");
+    let phase_start = Instant::now();
+    let chassis_output =
+        build_chassis_with_cartridge(&pax_dir, ctx, Arc::clone(&ctx.process_child_ids));
+    build_report.timings_ms.chassis_build = phase_start.elapsed().as_millis();
+    if let RunTarget::Web = ctx.target {
+        if chassis_output.status.success() {
+            build_report.web_bundle = report_web_bundle_sizes(
+                &pax_dir,
+                ctx.wasm_size_warning_bytes
+                    .unwrap_or(DEFAULT_WASM_SIZE_WARNING_BYTES),
+            );
+        }
+    }
+    build_report.artifact_path = get_artifact_path(&pax_dir, ctx)
+        .to_str()
+        .unwrap()
+        .to_string();
 
     if ctx.should_also_run {
         //8a::run: compile and run `interface`, with freshly built chassis plugged in
-        println!(
-            "{} 🐇 Running Pax {}...",
-            *PAX_BADGE,
+        info!(
+            "🐇 Running Pax {}...",
             <&RunTarget as Into<&str>>::into(&ctx.target)
         );
+
+        //Running blocks until the user exits, so the report must be written beforehand
+        if ctx.should_write_build_report {
+            write_build_report(&pax_dir, &build_report);
+        }
+        build_interface_with_chassis(&pax_dir, ctx, Arc::clone(&ctx.process_child_ids));
     } else {
         //8b::compile: compile and write executable binary / package to disk at specified or implicit path
-        println!(
-            "{} 🛠 Compiling executable package for {}...",
-            *PAX_BADGE,
+        info!(
+            "🛠 Compiling executable package for {}...",
             <&RunTarget as Into<&str>>::into(&ctx.target)
         );
+        let phase_start = Instant::now();
+        build_interface_with_chassis(&pax_dir, ctx, Arc::clone(&ctx.process_child_ids));
+        build_report.timings_ms.interface_build = Some(phase_start.elapsed().as_millis());
+
+        if ctx.should_write_build_report {
+            write_build_report(&pax_dir, &build_report);
+        }
+
+        if let Some(post_build_command) = &ctx.post_build_command {
+            if !chassis_output.status.success() {
+                error!("❌ Cartridge build failed; skipping post-build command");
+                return Err(());
+            }
+            run_post_build_command(&pax_dir, ctx, post_build_command)?;
+        }
     }
-    build_interface_with_chassis(&pax_dir, &ctx, Arc::clone(&ctx.process_child_ids));
 
     Ok(())
 }
 
-fn copy_dir_to(src_dir: &Path, dst_dir: &Path) -> std::io::Result<()> {
-    if !dst_dir.exists() {
-        fs::create_dir_all(dst_dir)?;
+/// Runs `command` through the shell from the project directory, with `PAX_DIR` and `PAX_OUTPUT_DIR`
+/// (the directory holding built artifacts) set in its environment.  Its output is forwarded as it arrives.
+fn run_post_build_command(pax_dir: &PathBuf, ctx: &RunContext, command: &str) -> Result<(), ()> {
+    info!("🚚 Running post-build command `{}`", command);
+    let output_dir = get_output_path(pax_dir, ctx);
+    let output_dir = fs::canonicalize(&output_dir).unwrap_or(output_dir);
+
+    #[cfg(windows)]
+    let mut cmd = Command::new("cmd");
+    #[cfg(windows)]
+    cmd.arg("/C");
+    #[cfg(not(windows))]
+    let mut cmd = Command::new("sh");
+    #[cfg(not(windows))]
+    cmd.arg("-c");
+
+    cmd.arg(command)
+        .current_dir(ctx.get_project_dir())
+        .env("PAX_DIR", pax_dir)
+        .env("PAX_OUTPUT_DIR", &output_dir)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+
+    #[cfg(unix)]
+    unsafe {
+        cmd.pre_exec(pre_exec_hook);
     }
 
-    for entry_result in fs::read_dir(src_dir)? {
-        let entry = entry_result?;
+    let child = cmd.spawn().expect("failed to spawn post-build command");
+    let output = wait_with_prefixed_output(&ctx.process_child_ids, child);
+    if !output.status.success() {
+        error!("❌ Post-build command failed ({})", output.status);
+        return Err(());
+    }
+    Ok(())
+}
+
+/// Runs the parser binary for the project whose `Cargo.toml` is at `manifest_path`, deserializes the
+/// resulting `PaxManifest`, and resolves its `{PREFIX}`ed type ids against the host crate.  Forwards the parser's stderr.
+fn parse_manifest(
+    manifest_path: &Path,
+    is_offline: bool,
+    no_default_features: bool,
+    process_child_ids: Arc<Mutex<Vec<u64>>>,
+) -> Result<(PaxManifest, HostCrateInfo), PaxBuildError> {
+    // Run parser bin from host project with `--features parser`
+    let project_dir = get_manifest_dir(manifest_path);
+    let output = run_parser_binary(
+        project_dir.to_str().unwrap(),
+        is_offline,
+        no_default_features,
+        process_child_ids,
+    );
+
+    // Forward stderr only
+    std::io::stderr().write_all(output.stderr.as_slice())?;
+    if output.status.code() != Some(0) {
+        return Err(PaxBuildError::ParseFailed);
+    }
+
+    let out = String::from_utf8(output.stdout).unwrap();
+    let mut manifest: PaxManifest = serde_json::from_str(&out)
+        .map_err(|err| PaxBuildError::MalformedManifest(format!("{}: {}", err, &out)))?;
+    let host_crate_info = get_host_crate_info(manifest_path);
+    update_property_prefixes_in_place(&mut manifest, &host_crate_info);
+
+    Ok((manifest, host_crate_info))
+}
+
+/// Runs the parse and expression-compilation stages of `perform_build` for the project at `path`,
+/// then writes the resulting `PaxManifest` to `out` as pretty-printed JSON.  Useful for debugging codegen.
+/// If any expression fails to compile, returns the first of its errors; `check::perform_check` reports them all.
+/// Note that dependencies must already be resolvable by `cargo`, e.g. by a previous build in libdev mode.
+pub fn dump_manifest(path: &str, out: &mut dyn Write) -> Result<(), PaxBuildError> {
+    let (mut manifest, host_crate_info) = parse_manifest(
+        &Path::new(path).join("Cargo.toml"),
+        false,
+        false,
+        Arc::new(Mutex::new(vec![])),
+    )?;
+    expressions::compile_all_expressions_with_functions(
+        &mut manifest,
+        &mut ExpressionCache::default(),
+        &host_crate_info.expression_functions,
+    )
+    .map_err(|unresolved_symbol_errors| {
+        PaxBuildError::from(unresolved_symbol_errors.into_iter().next().unwrap())
+    })?;
+
+    serde_json::to_writer_pretty(&mut *out, &manifest)
+        .map_err(|err| PaxBuildError::MalformedManifest(err.to_string()))?;
+    writeln!(out)?;
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum PaxBuildError {
+    /// The parser binary exited unsuccessfully, e.g. due to a syntax error in the provided pax
+    ParseFailed,
+    /// The manifest could not be (de)serialized
+    MalformedManifest(String),
+    /// A component binds an event to a method that is not defined on that component
+    MissingEventHandler {
+        handler: String,
+        component: String,
+    },
+    /// A literal setting's value can never be converted into the type of its property
+    LiteralTypeMismatch {
+        /// e.g. `number` or `string`
+        literal_kind: String,
+        property: String,
+        expected_type: String,
+        component: String,
+    },
+    /// An enum literal like `fit=Cover` names a variant that its property's enum type does not have
+    UnknownEnumVariant {
+        variant: String,
+        property: String,
+        enum_type: String,
+        /// Variants of `enum_type`
+        expected_variants: Vec<String>,
+        component: String,
+    },
+    /// Compiling a component's expressions failed, e.g. due to an unresolved symbol
+    ExpressionCompilationFailed {
+        component: String,
+        message: String,
+    },
+    /// A template binds a value to a readonly property of the node's component, e.g. `<Counter count=5 />`
+    /// where `count` is declared with `#[pax(readonly)]`
+    ReadonlyPropertyBinding {
+        property: String,
+        /// The component declaring `property`
+        node: String,
+        component: String,
+    },
+    /// A `slot` has a literal index that no instance of its component passes enough adoptees to fill
+    SlotIndexOutOfRange {
+        index: usize,
+        /// The most adoptees passed to any instance of `component`
+        adoptee_count: usize,
+        component: String,
+    },
+    /// Building offline, but these packages have not already been fetched into `.pax/pkg`
+    MissingOfflineDependencies(Vec<String>),
+    /// crates.io has no such version of a Pax package
+    PackageNotFound {
+        pkg: String,
+        version: String,
+    },
+    /// A Pax package downloaded from crates.io is not a readable tarball
+    MalformedPackageDownload {
+        pkg: String,
+        version: String,
+        reason: String,
+    },
+    /// A command-line tool that the build shells out to is not on `PATH`
+    MissingTool {
+        tool: String,
+        /// How to install `tool`, e.g. ``install via `cargo install wasm-pack` ``
+        install_hint: String,
+    },
+    /// The script that builds and launches the macOS dev harness is missing from the chassis, or not executable
+    MissingHarnessScript(PathBuf),
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for PaxBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaxBuildError::ParseFailed => write!(
+                f,
+                "Parsing failed — there is likely a syntax error in the provided pax"
+            ),
+            PaxBuildError::MalformedManifest(msg) => {
+                write!(f, "Malformed JSON from parser: {}", msg)
+            }
+            PaxBuildError::MissingEventHandler { handler, component } => write!(
+                f,
+                "No handler named `{}` on component `{}` — check that `{}::{}` is defined",
+                handler, component, component, handler
+            ),
+            PaxBuildError::LiteralTypeMismatch {
+                literal_kind,
+                property,
+                expected_type,
+                component,
+            } => write!(
+                f,
+                "A {} literal cannot be assigned to `{}`, of type `{}`, within the template of `{}`",
+                literal_kind, property, expected_type, component
+            ),
+            PaxBuildError::UnknownEnumVariant {
+                variant,
+                property,
+                enum_type,
+                expected_variants,
+                component,
+            } => write!(
+                f,
+                "`{}` is not a variant of `{}`, the type of `{}` within the template of `{}` — expected one of: {}",
+                variant,
+                enum_type,
+                property,
+                component,
+                expected_variants.join(", ")
+            ),
+            PaxBuildError::ExpressionCompilationFailed { component, message } => write!(
+                f,
+                "Failed to compile expressions within the template of `{}`: {}",
+                component, message
+            ),
+            PaxBuildError::ReadonlyPropertyBinding {
+                property,
+                node,
+                component,
+            } => write!(
+                f,
+                "`{}` of `{}` is readonly and cannot be bound within the template of `{}` — it is set only by `{}` itself",
+                property, node, component, node
+            ),
+            PaxBuildError::SlotIndexOutOfRange {
+                index,
+                adoptee_count,
+                component,
+            } => write!(
+                f,
+                "`slot({})` within the template of `{}` can never be filled — instances of `{}` are passed at most {} adoptee{}",
+                index,
+                component,
+                component,
+                adoptee_count,
+                if *adoptee_count == 1 { "" } else { "s" }
+            ),
+            PaxBuildError::MissingOfflineDependencies(pkgs) => write!(
+                f,
+                "Cannot build offline — missing from `.pax/pkg`: {}.  Build once with network access to fetch them",
+                pkgs.join(", ")
+            ),
+            PaxBuildError::PackageNotFound { pkg, version } => write!(
+                f,
+                "{} {} not found on crates.io — check the version of Pax declared in Cargo.toml",
+                pkg, version
+            ),
+            PaxBuildError::MalformedPackageDownload {
+                pkg,
+                version,
+                reason,
+            } => write!(
+                f,
+                "Failed to download {} {} from crates.io: {}",
+                pkg, version, reason
+            ),
+            PaxBuildError::MissingTool { tool, install_hint } => {
+                write!(f, "{} not found; {}", tool, install_hint)
+            }
+            PaxBuildError::MissingHarnessScript(path) => write!(
+                f,
+                "{} is missing or not executable — `chmod +x` it, or run `pax clean` to fetch the macOS chassis afresh",
+                path.display()
+            ),
+            PaxBuildError::Io(err) => write!(f, "I/O error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for PaxBuildError {}
+
+impl From<std::io::Error> for PaxBuildError {
+    fn from(err: std::io::Error) -> Self {
+        PaxBuildError::Io(err)
+    }
+}
+
+impl From<expressions::UnresolvedSymbolError> for PaxBuildError {
+    fn from(err: expressions::UnresolvedSymbolError) -> Self {
+        PaxBuildError::ExpressionCompilationFailed {
+            component: err.component.clone(),
+            message: err.to_string(),
+        }
+    }
+}
+
+//relative to pax_dir
+pub const BUILD_REPORT_JSON_PATH: &str = "build-report.json";
+
+/// Machine-readable summary of a `perform_build`, written to `.pax/build-report.json`
+/// when `RunContext::should_write_build_report` is set.  Intended for consumption by CI
+/// and editor integrations.
+#[derive(Serialize, Default)]
+pub struct BuildReport {
+    pub target: String,
+    pub timings_ms: BuildReportTimings,
+    /// Path to the built artifact: the `public` directory for web, or the output directory for macOS.
+    /// Empty for a dry run, which builds no artifact
+    pub artifact_path: String,
+    pub component_count: usize,
+    pub expression_count: usize,
+    /// Number of expressions reused from `.pax/expr-cache` rather than recompiled
+    pub cached_expression_count: usize,
+    /// Per-component breakdown of expression compilation and codegen, slowest first
+    pub component_timings_us: Vec<ComponentBuildTimings>,
+    /// Sizes of the generated `.wasm` and `.js` bundle files; empty for non-web targets
+    pub web_bundle: Vec<BundleFileSize>,
+}
+
+/// Wall-clock durations, in microseconds, spent on a single component during `perform_build`
+#[derive(Serialize, Default)]
+pub struct ComponentBuildTimings {
+    pub type_id: String,
+    pub expression_compilation: u128,
+    pub codegen: u128,
+}
+
+fn collect_component_timings(
+    expression_compilation_durations: &HashMap<String, Duration>,
+    codegen_durations: &HashMap<String, Duration>,
+) -> Vec<ComponentBuildTimings> {
+    let type_ids: HashSet<&String> = expression_compilation_durations
+        .keys()
+        .chain(codegen_durations.keys())
+        .collect();
+
+    let mut ret: Vec<ComponentBuildTimings> = type_ids
+        .into_iter()
+        .map(|type_id| ComponentBuildTimings {
+            type_id: type_id.clone(),
+            expression_compilation: expression_compilation_durations
+                .get(type_id)
+                .map_or(0, |d| d.as_micros()),
+            codegen: codegen_durations.get(type_id).map_or(0, |d| d.as_micros()),
+        })
+        .collect();
+    ret.sort_by(|a, b| {
+        (b.expression_compilation + b.codegen)
+            .cmp(&(a.expression_compilation + a.codegen))
+            .then_with(|| a.type_id.cmp(&b.type_id))
+    });
+    ret
+}
+
+/// Wall-clock durations, in milliseconds, of each phase of `perform_build`
+#[derive(Serialize, Default)]
+pub struct BuildReportTimings {
+    pub parse: u128,
+    pub expression_compilation: u128,
+    pub codegen: u128,
+    pub chassis_build: u128,
+    /// `None` for `run`, where the interface phase blocks until exit
+    pub interface_build: Option<u128>,
+}
+
+fn write_build_report(pax_dir: &Path, build_report: &BuildReport) {
+    let path = pax_dir.join(BUILD_REPORT_JSON_PATH);
+    fs::write(&path, serde_json::to_string_pretty(build_report).unwrap())
+        .expect("Failed to write build report");
+    info!("📋 Wrote build report to {}", path.to_str().unwrap());
+}
+
+fn copy_dir_to(src_dir: &Path, dst_dir: &Path) -> std::io::Result<()> {
+    if !dst_dir.exists() {
+        fs::create_dir_all(dst_dir)?;
+    }
+
+    for entry_result in fs::read_dir(src_dir)? {
+        let entry = entry_result?;
         let file_type = entry.file_type()?;
         let src_path = entry.path();
         let dst_path = dst_dir.join(entry.file_name());
@@ -1127,20 +2510,67 @@ fn copy_dir_to(src_dir: &Path, dst_dir: &Path) -> std::io::Result<()> {
         if file_type.is_dir() {
             copy_dir_to(&src_path, &dst_path)?;
         } else {
-            fs::copy(&src_path, &dst_path)?;
+            copy_file_or_symlink(&src_path, &dst_path)?;
         }
     }
 
     Ok(())
 }
 
-fn start_static_http_server(fs_path: PathBuf) -> std::io::Result<()> {
-    // Initialize logging
+/// Copies the file at `src` to `dst`, overwriting it.  Unlike a bare `fs::copy`, a symlink is recreated
+/// as a link rather than copied from its target, and on unix the permission bits (e.g. `+x` on bundled
+/// scripts like `run-debuggable-mac-app.sh`) are explicitly carried over.
+fn copy_file_or_symlink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    let metadata = fs::symlink_metadata(src)?;
+    //Never write through an existing link at `dst`
+    if let Ok(dst_metadata) = fs::symlink_metadata(dst) {
+        if dst_metadata.file_type().is_symlink() || metadata.file_type().is_symlink() {
+            fs::remove_file(dst)?;
+        }
+    }
 
-    std::env::set_var("RUST_LOG", "actix_web=info");
-    env_logger::Builder::from_env(env_logger::Env::default())
-        .format(|buf, record| writeln!(buf, "{} 🍱 Served {}", *PAX_BADGE, record.args()))
-        .init();
+    if metadata.file_type().is_symlink() {
+        let link_target = fs::read_link(src)?;
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&link_target, dst)?;
+        #[cfg(windows)]
+        if src.is_dir() {
+            std::os::windows::fs::symlink_dir(&link_target, dst)?;
+        } else {
+            std::os::windows::fs::symlink_file(&link_target, dst)?;
+        }
+        return Ok(());
+    }
+
+    fs::copy(src, dst)?;
+    #[cfg(unix)]
+    fs::set_permissions(dst, metadata.permissions())?;
+    Ok(())
+}
+
+/// Builds the static file service used by the dev server.  ETag and Last-Modified headers
+/// are enabled so that browsers can revalidate (and `If-Range` can be honored), and `Range:`
+/// requests are answered with `206 Partial Content`, which is needed to seek within video
+/// and other large media served from `public/`.
+fn static_files_service(fs_path: PathBuf) -> actix_files::Files {
+    actix_files::Files::new("/*", fs_path)
+        .index_file("index.html")
+        .use_etag(true)
+        .use_last_modified(true)
+}
+
+/// Number of worker threads serving `pax run`'s static files, unless `RunContext::server_workers`
+/// specifies otherwise
+pub const DEFAULT_SERVER_WORKERS: usize = 2;
+
+/// Serves `fs_path` on the first free port from 8080, blocking until the server stops.
+/// Once the server is listening, sends its bound address through `ready_tx`, if any.
+fn start_static_http_server(
+    fs_path: PathBuf,
+    workers: usize,
+    ready_tx: Option<Sender<SocketAddr>>,
+) -> std::io::Result<()> {
+    init_logging();
 
     // Create a Runtime
     let runtime = actix_rt::System::new().block_on(async {
@@ -1149,27 +2579,35 @@ fn start_static_http_server(fs_path: PathBuf) -> std::io::Result<()> {
             // Check if the port is available
             if TcpListener::bind(("127.0.0.1", port)).is_ok() {
                 // Log the server details
-                println!(
-                    "{} 🗂️  Serving static files from {}",
-                    *PAX_BADGE,
+                info!(
+                    "🗂️  Serving static files from {}",
                     &fs_path.to_str().unwrap()
                 );
                 let address_msg = format!("http://127.0.0.1:{}", port).blue();
                 let server_running_at_msg = format!("Server running at {}", address_msg).bold();
-                println!("{} 📠 {}", *PAX_BADGE, server_running_at_msg);
+                info!("📠 {}", server_running_at_msg);
+                info!(
+                    "👷 Serving with {} worker{}",
+                    workers,
+                    if workers == 1 { "" } else { "s" }
+                );
                 break HttpServer::new(move || {
-                    App::new().wrap(Logger::new("| %s | %U")).service(
-                        actix_files::Files::new("/*", fs_path.clone()).index_file("index.html"),
-                    )
+                    App::new()
+                        .wrap(Logger::new("| %s | %U"))
+                        .service(static_files_service(fs_path.clone()))
                 })
                 .bind(("127.0.0.1", port))
                 .expect("Error binding to address")
-                .workers(2);
+                .workers(workers);
             } else {
                 port += 1; // Try the next port
             }
         };
 
+        if let (Some(ready_tx), Some(addr)) = (ready_tx, server.addrs().first()) {
+            // The receiver may have hung up, in which case nobody is waiting to hear about it
+            let _ = ready_tx.send(*addr);
+        }
         server.run().await
     });
 
@@ -1198,12 +2636,15 @@ fn build_interface_with_chassis(
         false
     };
 
-    let target_folder: &str = ctx.target.borrow().into();
-
-    let output_path = pax_dir.join("build").join(target_folder);
+    let output_path = get_output_path(pax_dir, ctx);
     let output_path_str = output_path.to_str().unwrap();
 
-    std::fs::create_dir_all(&output_path).ok();
+    std::fs::create_dir_all(&output_path).unwrap_or_else(|err| {
+        panic!(
+            "Failed to create output directory {}: {}",
+            output_path_str, err
+        )
+    });
 
     let verbose_val = format!("{}", ctx.verbose);
     let exclude_arch_val = if std::env::consts::ARCH == "aarch64" {
@@ -1224,12 +2665,30 @@ fn build_interface_with_chassis(
             eprintln!("Error copying assets: {}", e);
         }
 
+        // For a `build` rather than a `run`, stage the assembled `public/` directory at the output path
+        // (`.pax/build/Web` or the requested output directory), yielding a self-contained deployable folder.
+        // A `run` keeps serving `public/` in place.
+        if !ctx.should_also_run {
+            if let Err(e) = copy_dir_recursively(&interface_path.join("public"), &output_path) {
+                eprintln!("Error copying build output to {}: {}", output_path_str, e);
+            }
+        }
+
         // Start local server if this is a `run` rather than a `build`
         if ctx.should_also_run {
-            let _ = start_static_http_server(interface_path.join("public"));
+            let workers = ctx.server_workers.unwrap_or(DEFAULT_SERVER_WORKERS).max(1);
+            let _ = start_static_http_server(
+                interface_path.join("public"),
+                workers,
+                ctx.ready_tx.clone(),
+            );
         }
     } else {
         let script = "./run-debuggable-mac-app.sh";
+        let script_path = interface_path.join(script);
+        if !is_executable_file(&script_path) {
+            panic!("{}", PaxBuildError::MissingHarnessScript(script_path));
+        }
         let should_also_run = &format!("{}", ctx.should_also_run);
         let mut cmd = Command::new(script);
         cmd.current_dir(&interface_path)
@@ -1255,8 +2714,32 @@ fn build_interface_with_chassis(
     }
 }
 
+/// Returns the directory into which built artifacts are written: `ctx.output_dir` if specified,
+/// otherwise `.pax/build/<target>`
+fn get_output_path(pax_dir: &Path, ctx: &RunContext) -> PathBuf {
+    if let Some(output_dir) = &ctx.output_dir {
+        return output_dir.clone();
+    }
+    let target_folder: &str = ctx.target.borrow().into();
+    pax_dir.join("build").join(target_folder)
+}
+
+/// Returns the path of the final built artifact for the target of `ctx`
+fn get_artifact_path(pax_dir: &Path, ctx: &RunContext) -> PathBuf {
+    match ctx.target {
+        RunTarget::Web if !ctx.should_also_run => get_output_path(pax_dir, ctx),
+        RunTarget::Web => pax_dir
+            .join(PAX_DIR_PKG_PATH)
+            .join("pax-chassis-web")
+            .join("interface")
+            .join("public"),
+        RunTarget::MacOS => get_output_path(pax_dir, ctx),
+    }
+}
+
 fn copy_dir_recursively(src: &Path, dest: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    if src.is_dir() {
+    //Inspect `src` itself rather than what it points to, so that a symlinked directory is copied as a link
+    if fs::symlink_metadata(src)?.is_dir() {
         // If source is a directory, create the corresponding directory in the destination,
         // and copy its contents recursively
         fs::create_dir_all(dest)?;
@@ -1267,8 +2750,8 @@ fn copy_dir_recursively(src: &Path, dest: &Path) -> Result<(), Box<dyn std::erro
             copy_dir_recursively(&path, &dest_child)?;
         }
     } else {
-        // If source is a file, just copy it to the destination
-        fs::copy(src, dest)?;
+        // If source is a file or symlink, just copy it to the destination
+        copy_file_or_symlink(src, dest)?;
     }
     Ok(())
 }
@@ -1301,38 +2784,33 @@ pub fn build_chassis_with_cartridge(
     //swap them for our locally cloned filesystem versions during compilation.
     let existing_cargo_toml_path = chassis_path.join("Cargo.toml");
     let existing_cargo_toml_string = fs::read_to_string(&existing_cargo_toml_path).unwrap();
-    let mut existing_cargo_toml =
-        toml_edit::Document::from_str(&existing_cargo_toml_string).unwrap();
-
-    //In builds where we don't wipe out the `pkg` directory (e.g. those installed from crates.io),
-    //the Cargo.toml may already have been patched.  Injecting an additional patch would break cargo.
-    if !existing_cargo_toml_string.contains("patch.crates-io") {
-        let mut patch_table = toml_edit::table();
-        for pkg in ALL_PKGS {
-            patch_table[pkg]["path"] = toml_edit::value(format!("../{}", pkg));
-        }
-
-        existing_cargo_toml.insert("patch.crates-io", patch_table);
-        fs::write(
-            existing_cargo_toml_path,
-            existing_cargo_toml
-                .to_string()
-                .replace("\"patch.crates-io\"", "patch.crates-io"),
-        )
-        .unwrap();
+    let patched_cargo_toml_string = patch_cargo_toml_with_local_pkgs(&existing_cargo_toml_string);
+    //Only write on change, so that cargo doesn't see a touched manifest on every build
+    if patched_cargo_toml_string != existing_cargo_toml_string {
+        fs::write(existing_cargo_toml_path, patched_cargo_toml_string).unwrap();
     }
 
     //string together a shell call to build our chassis, with cartridge inserted via `patch`
     match target {
         RunTarget::MacOS => {
+            require_tool_on_path("cargo", CARGO_INSTALL_HINT)
+                .unwrap_or_else(|err| panic!("{}", err));
             let mut cmd = Command::new("cargo");
             cmd.current_dir(&chassis_path)
                 .arg("build")
                 .arg("--color")
                 .arg("always")
                 .env("PAX_DIR", &pax_dir)
-                .stdout(std::process::Stdio::inherit())
-                .stderr(std::process::Stdio::inherit());
+                .stdout(get_forwarded_stdio(ctx.verbose))
+                .stderr(get_forwarded_stdio(ctx.verbose));
+
+            if ctx.offline {
+                cmd.arg("--offline");
+            }
+
+            if let Some(features) = ctx.get_chassis_features_arg() {
+                cmd.arg("--features").arg(features);
+            }
 
             #[cfg(unix)]
             unsafe {
@@ -1341,11 +2819,17 @@ pub fn build_chassis_with_cartridge(
 
             let child = cmd.spawn().expect("failed to spawn child");
             // child.stdin.take().map(drop);
-            let output = wait_with_output(&process_child_ids, child);
+            let output = if ctx.verbose {
+                wait_with_prefixed_output(&process_child_ids, child)
+            } else {
+                wait_with_output(&process_child_ids, child)
+            };
 
             output
         }
         RunTarget::Web => {
+            require_tool_on_path("wasm-pack", WASM_PACK_INSTALL_HINT)
+                .unwrap_or_else(|err| panic!("{}", err));
             let mut cmd = Command::new("wasm-pack");
             cmd.current_dir(&chassis_path)
                 .arg("build")
@@ -1362,8 +2846,8 @@ pub fn build_chassis_with_cartridge(
                         .unwrap(),
                 )
                 .env("PAX_DIR", &pax_dir)
-                .stdout(std::process::Stdio::inherit())
-                .stderr(std::process::Stdio::inherit());
+                .stdout(get_forwarded_stdio(ctx.verbose))
+                .stderr(get_forwarded_stdio(ctx.verbose));
 
             //approximate `should_also_run` as "dev build," `!should_also_run` as prod
             if ctx.should_also_run {
@@ -1372,6 +2856,21 @@ pub fn build_chassis_with_cartridge(
                 cmd.arg("--release");
             }
 
+            //keep wasm-pack from installing tools (e.g. wasm-bindgen) on demand, and forward
+            //`--offline` and `--features` to its underlying `cargo build`.  Extra cargo args must come last, after `--`
+            let mut cargo_args = vec![];
+            if ctx.offline {
+                cmd.arg("--mode").arg("no-install");
+                cargo_args.push("--offline".to_string());
+            }
+            if let Some(features) = ctx.get_chassis_features_arg() {
+                cargo_args.push("--features".to_string());
+                cargo_args.push(features);
+            }
+            if !cargo_args.is_empty() {
+                cmd.arg("--").args(cargo_args);
+            }
+
             #[cfg(unix)]
             unsafe {
                 cmd.pre_exec(pre_exec_hook);
@@ -1379,15 +2878,58 @@ pub fn build_chassis_with_cartridge(
 
             let child = cmd.spawn().expect("failed to spawn child");
             // child.stdin.take().map(drop);
-            let output = wait_with_output(&process_child_ids, child);
+            let output = if ctx.verbose {
+                wait_with_prefixed_output(&process_child_ids, child)
+            } else {
+                wait_with_output(&process_child_ids, child)
+            };
 
             output
         }
     }
 }
 
+/// Points a `[patch.crates-io]` entry for each of `ALL_PKGS` at its sibling directory in `.pax/pkg`,
+/// creating the table if absent and updating existing entries in place.  In builds where we don't wipe
+/// out the `pkg` directory (e.g. those installed from crates.io), the Cargo.toml may already have been
+/// patched; because entries are overwritten rather than appended, re-running converges on the same output.
+fn patch_cargo_toml_with_local_pkgs(cargo_toml: &str) -> String {
+    let mut doc = toml_edit::Document::from_str(cargo_toml).unwrap();
+    let patch_table = doc
+        .as_table_mut()
+        .entry("patch")
+        .or_insert_with(toml_edit::table)
+        .as_table_mut()
+        .expect("`patch` in chassis Cargo.toml must be a table");
+    //render as `[patch.crates-io]` rather than an empty `[patch]` header
+    patch_table.set_implicit(true);
+    let crates_io_table = patch_table
+        .entry("crates-io")
+        .or_insert_with(toml_edit::table)
+        .as_table_mut()
+        .expect("`patch.crates-io` in chassis Cargo.toml must be a table");
+    for pkg in ALL_PKGS {
+        crates_io_table[pkg]["path"] = toml_edit::value(format!("../{}", pkg));
+    }
+    doc.to_string()
+}
+
+/// In verbose mode, child output is piped so that it can be prefixed by `wait_with_prefixed_output`;
+/// otherwise it is inherited directly
+fn get_forwarded_stdio(is_verbose: bool) -> std::process::Stdio {
+    if is_verbose {
+        std::process::Stdio::piped()
+    } else {
+        std::process::Stdio::inherit()
+    }
+}
+
 static PAX_CREATE_TEMPLATE: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/new-project-template");
 const PAX_CREATE_TEMPLATE_DIR_NAME: &str = "new-project-template";
+/// Environment variable which, when set, points `perform_create` to a new-project template on the
+/// filesystem, read in place of the template embedded in the compiler.  Lets template authors
+/// iterate without rebuilding the compiler, in or out of libdev mode.
+pub const PAX_TEMPLATE_DIR_ENV_VAR: &str = "PAX_TEMPLATE_DIR";
 
 pub fn perform_create(ctx: &CreateContext) {
     let full_path = Path::new(&ctx.path);
@@ -1399,17 +2941,30 @@ pub fn perform_create(ctx: &CreateContext) {
     let _ = fs::create_dir_all(&full_path);
 
     // clone template into full_path
-    if ctx.is_libdev_mode {
+    let template_src = if let Some(template_dir) = std::env::var_os(PAX_TEMPLATE_DIR_ENV_VAR) {
+        Some(PathBuf::from(template_dir))
+    } else if ctx.is_libdev_mode {
         //For is_libdev_mode, we copy our monorepo @/pax-compiler/new-project-template directory
         //to the target directly.  This enables iterating on new-project-template during libdev
         //without the sticky caches associated with `include_dir`
         let pax_compiler_cargo_root = Path::new(env!("CARGO_MANIFEST_DIR"));
-        let template_src = pax_compiler_cargo_root.join(PAX_CREATE_TEMPLATE_DIR_NAME);
+        Some(pax_compiler_cargo_root.join(PAX_CREATE_TEMPLATE_DIR_NAME))
+    } else {
+        None
+    };
 
+    if let Some(template_src) = template_src {
         let mut options = CopyOptions::new();
         options.overwrite = true;
 
-        for entry in std::fs::read_dir(&template_src).expect("Failed to read template directory") {
+        let entries = std::fs::read_dir(&template_src).unwrap_or_else(|err| {
+            panic!(
+                "Failed to read template directory {}: {}",
+                template_src.display(),
+                err
+            )
+        });
+        for entry in entries {
             let entry_path = entry.expect("Failed to read entry").path();
             if entry_path.is_dir() {
                 dir::copy(&entry_path, &full_path, &options).expect("Failed to copy directory");
@@ -1496,7 +3051,7 @@ pub fn perform_create(ctx: &CreateContext) {
         .as_table_mut()
     {
         if let Some(name_item) = package.get_mut("name") {
-            *name_item = toml_edit::Item::Value(crate_name.into());
+            *name_item = toml_edit::Item::Value(crate_name.clone().into());
         }
         if let Some(version_item) = package.get_mut("version") {
             *version_item = toml_edit::Item::Value(ctx.version.clone().into());
@@ -1507,6 +3062,24 @@ pub fn perform_create(ctx: &CreateContext) {
     fs::write(&full_path.join("Cargo.toml"), doc.to_string())
         .expect("Failed to write modified Cargo.toml");
 
+    // Cargo refuses to build a crate nested within a workspace that does not list it as a member
+    if let Some(workspace_manifest_path) = find_enclosing_workspace_manifest(full_path) {
+        if ctx.should_add_to_workspace {
+            add_workspace_member(&workspace_manifest_path, full_path);
+            println!(
+                "Added {} to the workspace members in {}.",
+                crate_name,
+                workspace_manifest_path.display()
+            );
+        } else {
+            println!(
+                "\nNote: {} is inside the Cargo workspace at {}, which does not list it as a member.\nRe-run with `--workspace` to add it, or add it to `[workspace] members` manually.",
+                crate_name,
+                workspace_manifest_path.display()
+            );
+        }
+    }
+
     println!(
         "\nCreated new Pax project at {}.\nTo run:\n  `cd {} && pax run --target=web`",
         full_path.to_str().unwrap(),
@@ -1514,10 +3087,18 @@ pub fn perform_create(ctx: &CreateContext) {
     );
 }
 
+pub struct FormatContext {
+    pub path: String,
+    /// Report unformatted files rather than rewriting them
+    pub check: bool,
+}
+
 pub struct CreateContext {
     pub path: String,
     pub is_libdev_mode: bool,
     pub version: String,
+    /// Whether to add the new project to the `[workspace] members` of an enclosing Cargo workspace, if any
+    pub should_add_to_workspace: bool,
 }
 
 pub struct RunContext {
@@ -1527,6 +3108,89 @@ pub struct RunContext {
     pub should_also_run: bool,
     pub is_libdev_mode: bool,
     pub process_child_ids: Arc<Mutex<Vec<u64>>>,
+    /// Also write a JSON report of the build to `.pax/build-report.json`
+    pub should_write_build_report: bool,
+    /// Destination for built artifacts; defaults to `.pax/build/<target>` when `None`
+    pub output_dir: Option<PathBuf>,
+    /// Never touch the network: fail if any dependency is missing from `.pax/pkg` rather than
+    /// downloading it, and pass `--offline` to `cargo` / `wasm-pack`
+    pub offline: bool,
+    /// Local directory containing a checkout of each `pax-*` package (as `<vendor_dir>/<pkg>`), copied
+    /// into `.pax/pkg` instead of downloading from crates.io.  Unlike libdev mode, makes no assumptions
+    /// about where the directory lives relative to the project.
+    pub vendor_dir: Option<PathBuf>,
+    /// Stop after parsing, expression compilation and codegen, before building the chassis.
+    /// A fast check that a project compiles to RIL, e.g. for editor save hooks
+    pub dry_run: bool,
+    /// Gzipped `.wasm` size above which a web build warns; defaults to `DEFAULT_WASM_SIZE_WARNING_BYTES` when `None`
+    pub wasm_size_warning_bytes: Option<u64>,
+    /// Shell command to run from the project directory after a successful build (e.g. to deploy),
+    /// with `PAX_DIR` and `PAX_OUTPUT_DIR` set.  Not run by `pax run` or dry runs
+    pub post_build_command: Option<String>,
+    /// Path to the project's `Cargo.toml`, for building a project other than the one at `path`;
+    /// defaults to `<path>/Cargo.toml` when `None`.  `.pax` and child processes live in its parent directory
+    pub manifest_path: Option<PathBuf>,
+    /// Number of worker threads for the dev server started by `pax run`, clamped to at least 1;
+    /// defaults to `DEFAULT_SERVER_WORKERS` when `None`
+    pub server_workers: Option<usize>,
+    /// Cargo features enabled when building the generated chassis (which includes the cartridge), passed
+    /// as `--features`.  Features are resolved against the chassis crate, so a feature of a dependency
+    /// like the cartridge or the userland crate must be addressed as `<crate>/<feature>`, e.g.
+    /// `pax-cartridge/debug-telemetry`.  They are enabled in addition to the chassis' default features
+    /// (e.g. `console_error_panic_hook` for web), which remain on.  The userland crate's `parser` feature
+    /// is reserved for the parser binary and must not be enabled here.
+    pub chassis_features: Vec<String>,
+    /// Sent the dev server's address once it is listening, e.g. so that a supervising process can open
+    /// a browser or run tests against it.  Only a web `run` starts a server; otherwise nothing is sent
+    pub ready_tx: Option<Sender<SocketAddr>>,
+    /// Pass `--no-default-features` when building the parser binary, alongside `--features parser`,
+    /// for userland crates whose default features conflict with the `parser` feature
+    pub parser_no_default_features: bool,
+    /// Print the type table (see `PaxManifest::dump_types`) to stdout once the manifest is parsed and
+    /// its type ids are prefixed, for debugging type resolution
+    pub dump_types: bool,
+}
+
+impl RunContext {
+    /// `chassis_features` as the comma-separated value of cargo's `--features`, or `None` if there are none
+    pub fn get_chassis_features_arg(&self) -> Option<String> {
+        let features: Vec<&str> = self
+            .chassis_features
+            .iter()
+            .map(|feature| feature.trim())
+            .filter(|feature| !feature.is_empty())
+            .collect();
+        if features.is_empty() {
+            None
+        } else {
+            Some(features.join(","))
+        }
+    }
+
+    /// The project's `Cargo.toml`: `manifest_path` if specified, otherwise `<path>/Cargo.toml`
+    pub fn get_manifest_path(&self) -> PathBuf {
+        match &self.manifest_path {
+            Some(manifest_path) => manifest_path.clone(),
+            None => Path::new(&self.path).join("Cargo.toml"),
+        }
+    }
+
+    /// The directory containing the project's `Cargo.toml`, where `.pax` lives and from which
+    /// `cargo` and other child processes are run
+    pub fn get_project_dir(&self) -> PathBuf {
+        match &self.manifest_path {
+            Some(manifest_path) => get_manifest_dir(manifest_path),
+            None => PathBuf::from(&self.path),
+        }
+    }
+}
+
+/// The directory containing `manifest_path`, i.e. `.` for a bare `Cargo.toml`
+fn get_manifest_dir(manifest_path: &Path) -> PathBuf {
+    match manifest_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
 }
 
 pub enum RunTarget {
@@ -1603,6 +3267,55 @@ pub fn wait_with_output(
     output
 }
 
+/// Like `wait_with_output`, but for a child spawned with piped stdout/stderr: re-emits each line of
+/// the child's output as it arrives, prefixed with `PAX_BADGE` so that it is attributable among
+/// Pax's own log lines.  The returned `Output` still contains the child's full (unprefixed) output.
+pub fn wait_with_prefixed_output(
+    process_child_ids: &Arc<Mutex<Vec<u64>>>,
+    mut child: std::process::Child,
+) -> std::process::Output {
+    fn forward_lines<R: std::io::Read + Send + 'static>(
+        pipe: Option<R>,
+        is_stderr: bool,
+    ) -> std::thread::JoinHandle<Vec<u8>> {
+        std::thread::spawn(move || {
+            let mut captured = vec![];
+            if let Some(pipe) = pipe {
+                let mut reader = std::io::BufReader::new(pipe);
+                let mut line = vec![];
+                while std::io::BufRead::read_until(&mut reader, b'\n', &mut line).unwrap_or(0) > 0 {
+                    let text = String::from_utf8_lossy(&line);
+                    let text = text.trim_end_matches(&['\r', '\n'][..]);
+                    if is_stderr {
+                        warn!("{}", text);
+                    } else {
+                        info!("{}", text);
+                    }
+                    captured.append(&mut line);
+                }
+            }
+            captured
+        })
+    }
+
+    let child_id: u64 = child.id().into();
+    process_child_ids.lock().expect(ERR_ASYNC).push(child_id);
+    let stdout_forwarder = forward_lines(child.stdout.take(), false);
+    let stderr_forwarder = forward_lines(child.stderr.take(), true);
+    let status = child.wait().expect("Failed to wait for child process");
+    let output = std::process::Output {
+        status,
+        stdout: stdout_forwarder.join().unwrap(),
+        stderr: stderr_forwarder.join().unwrap(),
+    };
+    assert!(
+        process_child_ids.lock().expect(ERR_ASYNC).pop().unwrap() == child_id,
+        "{}",
+        ERR_ASYNC
+    );
+    output
+}
+
 #[cfg(unix)]
 fn pre_exec_hook() -> Result<(), std::io::Error> {
     // Set a new process group for this command
@@ -1710,8 +3423,103 @@ impl NamespaceTrieNode {
 
 #[cfg(test)]
 mod tests {
-    use super::NamespaceTrieNode;
+    use super::{
+        check_tarball_response, coerce_numeric_literal, collect_impl_method_names,
+        copy_dir_recursively, copy_dir_to, escape_identifier, find_expression_compilation_errors,
+        find_literal_type_mismatches, find_missing_event_handlers, find_out_of_range_slot_indices,
+        find_readonly_property_bindings, generate_bound_events, generate_default_property_literal,
+        generate_enum_variant_literal, generate_events_map, get_parser_binary_command,
+        is_executable_file, normalize_module_path, patch_cargo_toml_with_local_pkgs,
+        perform_create, recurse_literal_list, require_tool_on_path, run_post_build_command,
+        start_static_http_server, static_files_service, CreateContext, HostCrateInfo,
+        NamespaceTrieNode, PaxBuildError, RunContext, RunTarget, ALL_PKGS,
+        PAX_TEMPLATE_DIR_ENV_VAR,
+    };
+    use crate::expressions::{
+        merge_inline_settings_with_settings_block, ExpressionFunctionRegistry,
+    };
+    use crate::manifest::{
+        sort_and_dedup_coproduct_tuples, EventDefinition, ExpressionSpec, ExpressionSpecInvocation,
+        LiteralBlockDefinition, PaxManifest, PropertyDefinition, PropertyDefinitionFlags,
+        SettingsSelectorBlockDefinition, TemplateNodeDefinition, ValueDefinition,
+    };
+    use crate::parsing::{
+        assemble_component_definition, assemble_primitive_definition, assemble_type_definition,
+        parse_selector_attributes_from_final_pairs_of_tag, ParsingContext, PaxParser, Reflectable,
+        Rule,
+    };
+    use crate::templating::{
+        press_template_codegen_cartridge_lib, TemplateArgsCodegenCartridgeLib,
+    };
+    use actix_web::http::{header, StatusCode};
+    use actix_web::App;
+    use itertools::Itertools;
+    use pest::Parser;
     use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+    use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
+
+    /// Builds the manifest of a project holding the component `crate::<pascal_identifier>`, with the
+    /// properties `properties` and the template `pax`, and the primitives `primitives`, each a type_id
+    /// like `pax_std::primitives::Rectangle` and its properties.  The template may instantiate both the
+    /// primitives and the component itself; the types of any other properties may be registered in `ctx`.
+    pub(crate) fn build_test_manifest(
+        ctx: ParsingContext,
+        pax: &str,
+        pascal_identifier: &str,
+        is_main_component: bool,
+        properties: Vec<PropertyDefinition>,
+        primitives: Vec<(&str, Vec<PropertyDefinition>)>,
+    ) -> PaxManifest {
+        let type_id = format!("crate::{}", pascal_identifier);
+        let mut template_map = HashMap::from([(pascal_identifier.to_string(), type_id.clone())]);
+        let mut components = HashMap::new();
+        let mut ctx = ctx;
+        for (primitive_type_id, primitive_properties) in primitives {
+            let (module_path, primitive_pascal_identifier) =
+                primitive_type_id.rsplit_once("::").unwrap();
+            ctx = assemble_type_definition(
+                ctx,
+                primitive_properties,
+                None,
+                primitive_type_id,
+                primitive_type_id.to_string(),
+            )
+            .0;
+            components.insert(
+                primitive_type_id.to_string(),
+                assemble_primitive_definition(
+                    primitive_pascal_identifier,
+                    module_path,
+                    format!("{}Instance", primitive_type_id),
+                    primitive_type_id,
+                ),
+            );
+            template_map.insert(
+                primitive_pascal_identifier.to_string(),
+                primitive_type_id.to_string(),
+            );
+        }
+        let (ctx, _) = assemble_type_definition(ctx, properties, None, &type_id, type_id.clone());
+        let (ctx, component) = assemble_component_definition(
+            ctx,
+            pax,
+            pascal_identifier,
+            is_main_component,
+            template_map,
+            "parser",
+            &type_id,
+        );
+        components.insert(type_id, component);
+        PaxManifest {
+            components,
+            main_component_type_id: ctx.main_component_type_id,
+            expression_specs: None,
+            type_table: ctx.type_table,
+            import_paths: ctx.import_paths,
+        }
+    }
 
     #[test]
     fn test_serialize_to_reexports() {
@@ -1777,4 +3585,1181 @@ mod tests {
 
         assert_eq!(output, expected_output);
     }
+
+    #[test]
+    fn test_normalize_module_path() {
+        assert_eq!(normalize_module_path("crate"), "");
+        assert_eq!(normalize_module_path("crate::Example"), "Example");
+        assert_eq!(
+            normalize_module_path("crate::widgets::buttons"),
+            "widgets::buttons"
+        );
+        assert_eq!(
+            normalize_module_path("crate::widgets::buttons::Button"),
+            "widgets::buttons::Button"
+        );
+        assert_eq!(
+            normalize_module_path("pax_std::primitives::Rectangle"),
+            "pax_std::primitives::Rectangle"
+        );
+        assert_eq!(
+            normalize_module_path("my_crate::widgets::Button"),
+            "my_crate::widgets::Button"
+        );
+        assert_eq!(
+            normalize_module_path("std::vec::Vec<crate::widgets::buttons::Button>"),
+            "std::vec::Vec<widgets::buttons::Button>"
+        );
+    }
+
+    #[test]
+    fn test_serialize_nested_module_reexports() {
+        let mut root_node = NamespaceTrieNode {
+            node_string: None,
+            children: HashMap::new(),
+        };
+        root_node.insert("crate::widgets::buttons::Button");
+
+        let expected_output = r#"pub mod pax_reexports {
+    pub mod widgets {
+        pub mod buttons{
+            pub use crate::widgets::buttons::Button;
+        }
+    }
+
+}"#;
+
+        assert_eq!(root_node.serialize_to_reexports(), expected_output);
+    }
+
+    #[test]
+    fn test_expression_spec_is_equivalent() {
+        let spec =
+            |id: usize, input_statement: &str, pascalized_return_type: &str| ExpressionSpec {
+                id,
+                pascalized_return_type: pascalized_return_type.to_string(),
+                invocations: vec![],
+                output_statement: "(Numeric::from(1)+Numeric::from(2))".to_string(),
+                input_statement: input_statement.to_string(),
+                is_repeat_source_iterable_expression: false,
+                repeat_source_iterable_type_id_escaped: "".to_string(),
+            };
+
+        let a = spec(0, "1+2", "Numeric");
+        let b = spec(1, "1 + 2", "Numeric");
+        let c = spec(2, "1+2", "Size");
+
+        assert!(a.is_equivalent(&b));
+        assert!(!a.is_equivalent(&c));
+        //`PartialEq` remains id-based
+        assert!(a != b);
+    }
+
+    #[test]
+    fn test_press_cartridge_lib_reads_builtins_from_engine() {
+        let invocation = ExpressionSpecInvocation {
+            root_identifier: "$frames_elapsed".to_string(),
+            escaped_identifier: escape_identifier("$frames_elapsed".to_string()),
+            stack_offset: 0,
+            properties_coproduct_type: "".to_string(),
+            iterable_type_id_escaped: "".to_string(),
+            is_numeric: true,
+            is_primitive_nonnumeric: false,
+            property_flags: PropertyDefinitionFlags::default(),
+            nested_symbol_tail_literal: "".to_string(),
+            is_nested_numeric: false,
+            builtin_accessor_literal: Some("ec.read_frames_elapsed()".to_string()),
+        };
+        let output = press_template_codegen_cartridge_lib(TemplateArgsCodegenCartridgeLib {
+            imports: vec![],
+            consts: vec![],
+            expression_specs: vec![ExpressionSpec {
+                id: 0,
+                pascalized_return_type: "Numeric".to_string(),
+                invocations: vec![invocation],
+                output_statement: "(_DOLLframes__elapsed*Numeric::from(0.01))".to_string(),
+                input_statement: "$frames_elapsed * 0.01".to_string(),
+                is_repeat_source_iterable_expression: false,
+                repeat_source_iterable_type_id_escaped: "".to_string(),
+            }],
+            component_factories_literal: vec![],
+        });
+
+        assert!(
+            output.contains("let _DOLLframes__elapsed = Numeric::from(ec.read_frames_elapsed());")
+        );
+        assert!(!output.contains("peek_nth"));
+    }
+
+    #[test]
+    fn test_press_cartridge_lib_reads_repeat_len_from_stack_frame() {
+        let invocation = ExpressionSpecInvocation {
+            root_identifier: "len".to_string(),
+            escaped_identifier: escape_identifier("len".to_string()),
+            stack_offset: 0,
+            properties_coproduct_type: "".to_string(),
+            iterable_type_id_escaped: "usize".to_string(),
+            is_numeric: true,
+            is_primitive_nonnumeric: false,
+            property_flags: PropertyDefinitionFlags {
+                is_binding_repeat_len: true,
+                ..Default::default()
+            },
+            nested_symbol_tail_literal: "".to_string(),
+            is_nested_numeric: false,
+            builtin_accessor_literal: None,
+        };
+        let output = press_template_codegen_cartridge_lib(TemplateArgsCodegenCartridgeLib {
+            imports: vec![],
+            consts: vec![],
+            expression_specs: vec![ExpressionSpec {
+                id: 0,
+                pascalized_return_type: "Numeric".to_string(),
+                invocations: vec![invocation],
+                output_statement: "(i/len)".to_string(),
+                input_statement: "i/len".to_string(),
+                is_repeat_source_iterable_expression: false,
+                repeat_source_iterable_type_id_escaped: "".to_string(),
+            }],
+            component_factories_literal: vec![],
+        });
+
+        assert!(output.contains("if let PropertiesCoproduct::RepeatItem(_, _, len) = properties"));
+        assert!(output.contains("Numeric::from(*len)"));
+    }
+
+    #[test]
+    fn test_collect_impl_method_names() {
+        let source = r##"
+            impl Words {
+                pub fn handle_click(&mut self, ctx: RuntimeContext, args: ArgsClick) {
+                    let brace = '{';
+                    log("} fn not_a_method() {");
+                    log(r#"" } fn not_a_method_either() {"#);
+                    fn nested_helper() {}
+                }
+                // fn commented_out() {}
+                fn handle_iter<'a>(&'a self) -> impl Iterator<Item = &'a str> + 'a {
+                    std::iter::empty()
+                }
+            }
+            impl Default for Words {
+                fn default() -> Self { unimplemented!() }
+            }
+            impl<T> WordsWrapper<T> where T: Clone {
+                fn handle_scroll(&self) {}
+            }
+            mod handlers {
+                impl super::Words {
+                    fn handle_key_down(&self) {}
+                }
+            }
+        "##;
+        let file = syn::parse_file(source).unwrap();
+
+        let methods = collect_impl_method_names(&file, "Words");
+
+        assert_eq!(
+            methods.into_iter().sorted().collect::<Vec<_>>(),
+            vec!["default", "handle_click", "handle_iter", "handle_key_down"]
+        );
+        assert_eq!(
+            collect_impl_method_names(&file, "WordsWrapper")
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec!["handle_scroll"]
+        );
+    }
+
+    #[test]
+    fn test_generate_bound_events_normalizes_dom_aliases() {
+        let inline_settings = vec![
+            (
+                "keydown".to_string(),
+                ValueDefinition::EventBindingTarget("self.handle_key".to_string()),
+            ),
+            (
+                "key_up".to_string(),
+                ValueDefinition::EventBindingTarget("self.handle_key".to_string()),
+            ),
+            (
+                "mousemove".to_string(),
+                ValueDefinition::EventBindingTarget("self.handle_drag".to_string()),
+            ),
+            (
+                "click".to_string(),
+                ValueDefinition::EventBindingTarget("self.handle_click".to_string()),
+            ),
+        ];
+
+        let events = generate_bound_events(Some(inline_settings));
+
+        assert_eq!(events.get("key_down").unwrap(), "self.handle_key");
+        assert_eq!(events.get("key_up").unwrap(), "self.handle_key");
+        assert_eq!(events.get("mouse_move").unwrap(), "self.handle_drag");
+        assert_eq!(events.get("click").unwrap(), "self.handle_click");
+        assert!(!events.contains_key("keydown"));
+    }
+
+    #[test]
+    fn test_generate_events_map_normalizes_lifecycle_aliases() {
+        let event = |key: &str, handlers: &[&str]| EventDefinition {
+            key: key.to_string(),
+            value: handlers.iter().map(|handler| handler.to_string()).collect(),
+        };
+        let events = generate_events_map(Some(vec![
+            event("Mount", &["on_mount"]),
+            event("Unmount", &["on_unmount"]),
+            event("PreRender", &["on_pre_render", "log_frame"]),
+            event("PostRender", &["on_post_render"]),
+            event("did_mount", &["handle_did_mount"]),
+        ]));
+
+        assert_eq!(events.get("mount").unwrap(), &vec!["on_mount".to_string()]);
+        assert_eq!(
+            events.get("unmount").unwrap(),
+            &vec!["on_unmount".to_string()]
+        );
+        assert_eq!(
+            events.get("will_render").unwrap(),
+            &vec!["on_pre_render".to_string(), "log_frame".to_string()]
+        );
+        assert_eq!(
+            events.get("did_render").unwrap(),
+            &vec!["on_post_render".to_string()]
+        );
+        assert_eq!(
+            events.get("did_mount").unwrap(),
+            &vec!["handle_did_mount".to_string()]
+        );
+        assert!(!events.contains_key("Mount"));
+    }
+
+    #[test]
+    fn test_merge_settings_block_specificity() {
+        let block = |selector: &str, key: &str, value: &str| SettingsSelectorBlockDefinition {
+            selector: selector.to_string(),
+            value_block: LiteralBlockDefinition {
+                explicit_type_pascal_identifier: None,
+                settings_key_value_pairs: vec![(
+                    key.to_string(),
+                    ValueDefinition::LiteralValue(value.to_string()),
+                )],
+            },
+        };
+        let settings_block = Some(vec![
+            block("#hero", "fill", "id"),
+            block(".card", "fill", "class"),
+            block(".card", "stroke", "class"),
+            block("Rectangle", "fill", "type"),
+            block("Rectangle", "stroke", "type"),
+            block("Rectangle", "corner_radii", "type"),
+            block("Ellipse", "width", "other_type"),
+            block(".other", "height", "other_class"),
+        ]);
+        let node = TemplateNodeDefinition {
+            settings: Some(vec![(
+                "corner_radii".to_string(),
+                ValueDefinition::LiteralValue("inline".to_string()),
+            )]),
+            element_id: Some("hero".to_string()),
+            classes: vec!["card".to_string()],
+            ..Default::default()
+        };
+
+        let merged =
+            merge_inline_settings_with_settings_block(&node, &settings_block, Some("Rectangle"))
+                .unwrap();
+        let merged: HashMap<String, String> = merged
+            .into_iter()
+            .filter_map(|(key, value)| match value {
+                ValueDefinition::LiteralValue(lv) => Some((key, lv)),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(merged.get("fill").unwrap(), "id");
+        assert_eq!(merged.get("stroke").unwrap(), "class");
+        assert_eq!(merged.get("corner_radii").unwrap(), "inline");
+        assert!(!merged.contains_key("width"));
+        assert!(!merged.contains_key("height"));
+
+        assert!(PaxParser::parse(Rule::selector_block, "Rectangle { width: 50% }").is_ok());
+    }
+
+    #[test]
+    fn test_parse_selector_attributes() {
+        let parse = |tag: &str| {
+            let mut tag_pairs = PaxParser::parse(Rule::self_closing_tag, tag)
+                .unwrap()
+                .next()
+                .unwrap()
+                .into_inner();
+            tag_pairs.next(); //pascal identifier
+            parse_selector_attributes_from_final_pairs_of_tag(tag_pairs)
+        };
+
+        let (element_id, classes) =
+            parse(r#"<Rectangle id="hero" class="card  elevated primary" fill={self.color} />"#);
+        assert_eq!(element_id, Some("hero".to_string()));
+        assert_eq!(classes, vec!["card", "elevated", "primary"]);
+
+        let (element_id, classes) = parse(r#"<Rectangle id=hero class=single />"#);
+        assert_eq!(element_id, Some("hero".to_string()));
+        assert_eq!(classes, vec!["single"]);
+
+        let (element_id, classes) = parse(r#"<Rectangle width=50% />"#);
+        assert_eq!(element_id, None);
+        assert!(classes.is_empty());
+    }
+
+    #[test]
+    fn test_patch_cargo_toml_with_local_pkgs_converges() {
+        let original = r#"[package]
+name = "pax-chassis-web"
+
+# patch.crates-io is injected at build time
+[dependencies]
+pax-core = "0.8.0"
+
+[patch.crates-io]
+pax-core = { path = "/stale/pax-core" }
+"#;
+        let patched = patch_cargo_toml_with_local_pkgs(original);
+        let doc = toml_edit::Document::from_str(&patched).unwrap();
+        for pkg in ALL_PKGS {
+            assert_eq!(
+                doc["patch"]["crates-io"][pkg]["path"].as_str(),
+                Some(format!("../{}", pkg).as_str())
+            );
+        }
+        assert_eq!(patched.matches("[patch.crates-io]").count(), 1);
+        assert!(!patched.contains("/stale/"));
+        assert_eq!(patch_cargo_toml_with_local_pkgs(&patched), patched);
+
+        //a mention of `patch.crates-io` in a comment must not prevent injection
+        let commented = "# no patch.crates-io here\n[package]\nname = \"pax-chassis-web\"\n";
+        let patched = patch_cargo_toml_with_local_pkgs(commented);
+        let doc = toml_edit::Document::from_str(&patched).unwrap();
+        assert_eq!(
+            doc["patch"]["crates-io"]["pax-core"]["path"].as_str(),
+            Some("../pax-core")
+        );
+    }
+
+    #[test]
+    fn test_reflect_tuple_type() {
+        assert_eq!(
+            <Vec<(String, usize)>>::get_type_id(),
+            "std::vec::Vec<({PREFIX}std::string::String,{PREFIX}usize)>"
+        );
+
+        let (ctx, _) = <Vec<(String, usize)>>::parse_to_manifest(ParsingContext::default());
+        let tuple_type_id = <Vec<(String, usize)>>::get_iterable_type_id().unwrap();
+        let tuple_type_definition = ctx.type_table.get(&tuple_type_id).unwrap();
+        assert_eq!(
+            tuple_type_definition
+                .property_definitions
+                .iter()
+                .map(|pd| (pd.name.as_str(), pd.type_id.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("0", "std::string::String"), ("1", "usize")]
+        );
+        assert!(ctx.type_table.contains_key("usize"));
+    }
+
+    #[actix_web::test]
+    async fn test_static_files_range_request() {
+        let fs_path = std::env::temp_dir().join(format!("pax-range-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&fs_path).unwrap();
+        std::fs::write(fs_path.join("media.bin"), b"0123456789").unwrap();
+
+        let app = actix_web::test::init_service(
+            App::new().service(static_files_service(fs_path.clone())),
+        )
+        .await;
+        let req = actix_web::test::TestRequest::get()
+            .uri("/media.bin")
+            .insert_header((header::RANGE, "bytes=2-5"))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(
+            resp.headers().get(header::CONTENT_RANGE).unwrap(),
+            "bytes 2-5/10"
+        );
+        assert!(resp.headers().contains_key(header::ETAG));
+        assert_eq!(actix_web::test::read_body(resp).await, "2345");
+
+        std::fs::remove_dir_all(&fs_path).unwrap();
+    }
+
+    #[test]
+    fn test_static_http_server_signals_readiness() {
+        let fs_path = std::env::temp_dir().join(format!("pax-ready-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&fs_path).unwrap();
+
+        let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+        let server_fs_path = fs_path.clone();
+        // The server blocks until the process exits, so leave its thread running
+        std::thread::spawn(move || start_static_http_server(server_fs_path, 1, Some(ready_tx)));
+
+        let addr = ready_rx
+            .recv_timeout(std::time::Duration::from_secs(30))
+            .expect("Server never signaled readiness");
+        assert!(std::net::TcpStream::connect(addr).is_ok());
+
+        std::fs::remove_dir_all(&fs_path).unwrap();
+    }
+
+    #[test]
+    fn test_check_reports_every_diagnostic() {
+        let pax = r#"
+            <Rectangle id=r visible="yes" width=10 @click=handle_click />
+            <Rectangle visible=true width=10px label={self.cont + 1} count=3 @scroll=handle_scroll />
+            <Rectangle visible=true label={self.lable} />
+
+            @settings {
+                #r { label: 5 }
+            }
+            @handlers {
+                did_mount: handle_did_mount,
+            }
+        "#;
+
+        let manifest = build_test_manifest(
+            ParsingContext::default(),
+            pax,
+            "Example",
+            true,
+            vec![PropertyDefinition::primitive_with_name("usize", "count")],
+            vec![(
+                "pax_std::primitives::Rectangle",
+                vec![
+                    PropertyDefinition::primitive_with_name("bool", "visible"),
+                    PropertyDefinition::primitive_with_name("String", "label"),
+                    PropertyDefinition::primitive_with_name("usize", "count"),
+                ],
+            )],
+        );
+
+        let source_dir = std::env::temp_dir().join(format!("pax-check-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&source_dir).unwrap();
+        std::fs::write(
+            source_dir.join("lib.rs"),
+            "impl Example { pub fn handle_did_mount(&mut self, ctx: RuntimeContext) {} }",
+        )
+        .unwrap();
+
+        let diagnostics = find_missing_event_handlers(&manifest, &source_dir)
+            .unwrap()
+            .into_iter()
+            .chain(find_literal_type_mismatches(&manifest))
+            .chain(find_expression_compilation_errors(
+                &manifest,
+                &ExpressionFunctionRegistry::default(),
+            ))
+            .map(|diagnostic| match diagnostic {
+                PaxBuildError::MissingEventHandler { handler, .. } => handler,
+                PaxBuildError::LiteralTypeMismatch {
+                    literal_kind,
+                    property,
+                    ..
+                } => format!("{} {}", literal_kind, property),
+                PaxBuildError::ExpressionCompilationFailed { message, .. } => message,
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        std::fs::remove_dir_all(&source_dir).unwrap();
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                "handle_click",
+                "handle_scroll",
+                "number label",
+                "string visible",
+                "number width",
+                "error[E0425]: cannot find value `cont` in this scope
+  --> in the template of `Example`, on `<Rectangle>`
+   |
+   | self.cont + 1
+   |      ^^^^ not found in this scope
+   |
+   = help: a symbol with a similar name exists: `count`",
+                "error[E0425]: cannot find value `lable` in this scope
+  --> in the template of `Example`, on `<Rectangle>`
+   |
+   | self.lable
+   |      ^^^^^ not found in this scope",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_out_of_range_slot_indices() {
+        let ctx = ParsingContext::default();
+        let (ctx, panel) = assemble_component_definition(
+            ctx,
+            "<Group>slot(0) slot((1)) slot(2) slot(self.index)</Group>",
+            "Panel",
+            false,
+            HashMap::from([(
+                "Group".to_string(),
+                "pax_std::primitives::Group".to_string(),
+            )]),
+            "parser",
+            "crate::Panel",
+        );
+        let (_, example) = assemble_component_definition(
+            ctx,
+            "<Panel><Group /><Group /></Panel> <Panel><Group /></Panel>",
+            "Example",
+            true,
+            HashMap::from([
+                ("Panel".to_string(), "crate::Panel".to_string()),
+                (
+                    "Group".to_string(),
+                    "pax_std::primitives::Group".to_string(),
+                ),
+            ]),
+            "parser",
+            "crate::Example",
+        );
+        let mut components = HashMap::from([
+            (panel.type_id.clone(), panel),
+            (example.type_id.clone(), example),
+        ]);
+        let manifest = PaxManifest {
+            components: components.clone(),
+            main_component_type_id: "crate::Example".to_string(),
+            expression_specs: None,
+            type_table: Default::default(),
+            import_paths: Default::default(),
+        };
+
+        let diagnostics = find_out_of_range_slot_indices(&manifest)
+            .into_iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            vec!["`slot(2)` within the template of `Panel` can never be filled — instances of `Panel` are passed at most 2 adoptees"]
+        );
+
+        //A `for` adoptee makes the adoptee count runtime-variable, so nothing is reported
+        let (_, example) = assemble_component_definition(
+            ParsingContext::default(),
+            "<Panel><Group /> for i in 0..5 { <Group /> }</Panel>",
+            "Example",
+            true,
+            HashMap::from([
+                ("Panel".to_string(), "crate::Panel".to_string()),
+                (
+                    "Group".to_string(),
+                    "pax_std::primitives::Group".to_string(),
+                ),
+            ]),
+            "parser",
+            "crate::Example",
+        );
+        components.insert(example.type_id.clone(), example);
+        let manifest = PaxManifest {
+            components,
+            main_component_type_id: "crate::Example".to_string(),
+            expression_specs: None,
+            type_table: Default::default(),
+            import_paths: Default::default(),
+        };
+        assert!(find_out_of_range_slot_indices(&manifest).is_empty());
+    }
+
+    #[test]
+    fn test_check_tarball_response() {
+        let gzipped = {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            std::io::Write::write_all(&mut encoder, b"tar").unwrap();
+            encoder.finish().unwrap()
+        };
+        assert!(check_tarball_response("pax-core", "0.4.0", 200, &gzipped).is_ok());
+
+        let errors = [
+            (404, b"<html>Not Found</html>".as_slice()),
+            (500, gzipped.as_slice()),
+            (200, b"".as_slice()),
+            (200, b"<html>Not Found</html>".as_slice()),
+        ]
+        .into_iter()
+        .map(|(status, bytes)| {
+            check_tarball_response("pax-core", "0.4.0", status, bytes)
+                .unwrap_err()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                "pax-core 0.4.0 not found on crates.io — check the version of Pax declared in Cargo.toml",
+                "Failed to download pax-core 0.4.0 from crates.io: unexpected HTTP status 500",
+                "Failed to download pax-core 0.4.0 from crates.io: the response was empty",
+                "Failed to download pax-core 0.4.0 from crates.io: the response is not gzip data",
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_helpers_preserve_symlinks_and_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp_dir = std::env::temp_dir().join(format!("pax-copy-{}", uuid::Uuid::new_v4()));
+        let src_dir = tmp_dir.join("src");
+        std::fs::create_dir_all(src_dir.join("nested")).unwrap();
+        let script_path = src_dir.join("run-debuggable-mac-app.sh");
+        std::fs::write(&script_path, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        std::os::unix::fs::symlink("run-debuggable-mac-app.sh", src_dir.join("run.sh")).unwrap();
+        std::os::unix::fs::symlink("nested", src_dir.join("linked")).unwrap();
+
+        for (dst_dir, copy) in [
+            (
+                tmp_dir.join("dst-copy-dir-to"),
+                (|src, dst| copy_dir_to(src, dst).map_err(|err| err.to_string()))
+                    as fn(&Path, &Path) -> Result<(), String>,
+            ),
+            (tmp_dir.join("dst-copy-dir-recursively"), |src, dst| {
+                copy_dir_recursively(src, dst).map_err(|err| err.to_string())
+            }),
+        ] {
+            // Copy twice, to exercise overwriting existing files and links
+            copy(&src_dir, &dst_dir).unwrap();
+            copy(&src_dir, &dst_dir).unwrap();
+
+            let mode = std::fs::metadata(dst_dir.join("run-debuggable-mac-app.sh"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o755);
+            for (link, link_target) in [
+                ("run.sh", "run-debuggable-mac-app.sh"),
+                ("linked", "nested"),
+            ] {
+                assert!(std::fs::symlink_metadata(dst_dir.join(link))
+                    .unwrap()
+                    .file_type()
+                    .is_symlink());
+                assert_eq!(
+                    std::fs::read_link(dst_dir.join(link)).unwrap(),
+                    PathBuf::from(link_target)
+                );
+            }
+        }
+        std::fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_post_build_command() {
+        let project_dir =
+            std::env::temp_dir().join(format!("pax-post-build-{}", uuid::Uuid::new_v4()));
+        let output_dir = project_dir.join("dist");
+        std::fs::create_dir_all(&output_dir).unwrap();
+        let output_dir = std::fs::canonicalize(output_dir).unwrap();
+        let ctx = RunContext {
+            target: RunTarget::Web,
+            path: project_dir.to_str().unwrap().to_string(),
+            verbose: false,
+            should_also_run: false,
+            is_libdev_mode: false,
+            process_child_ids: Arc::new(Mutex::new(vec![])),
+            should_write_build_report: false,
+            output_dir: Some(output_dir.clone()),
+            offline: false,
+            vendor_dir: None,
+            dry_run: false,
+            wasm_size_warning_bytes: None,
+            post_build_command: None,
+            manifest_path: None,
+            server_workers: None,
+            chassis_features: vec![],
+            ready_tx: None,
+            parser_no_default_features: false,
+            dump_types: false,
+        };
+        let pax_dir = project_dir.join(".pax");
+
+        assert!(run_post_build_command(
+            &pax_dir,
+            &ctx,
+            "echo \"$PAX_DIR $PAX_OUTPUT_DIR\" > deployed.txt"
+        )
+        .is_ok());
+        assert_eq!(
+            std::fs::read_to_string(project_dir.join("deployed.txt")).unwrap(),
+            format!("{} {}\n", pax_dir.display(), output_dir.display())
+        );
+        assert!(ctx.process_child_ids.lock().unwrap().is_empty());
+
+        assert!(run_post_build_command(&pax_dir, &ctx, "exit 3").is_err());
+        std::fs::remove_dir_all(&project_dir).unwrap();
+    }
+
+    #[test]
+    fn test_perform_create_from_template_dir() {
+        let tmp_dir = std::env::temp_dir().join(format!("pax-create-{}", uuid::Uuid::new_v4()));
+        let template_dir = tmp_dir.join("template");
+        std::fs::create_dir_all(template_dir.join("src")).unwrap();
+        std::fs::write(
+            template_dir.join("Cargo.toml.template"),
+            "[package]\nname = \"template\"\nversion = \"0.0.0\"\n\n[dependencies]\npax-lang = { version = \"0.0.0\" }\n",
+        )
+        .unwrap();
+        std::fs::write(template_dir.join("src/lib.rs"), "// from template dir").unwrap();
+
+        std::env::set_var(PAX_TEMPLATE_DIR_ENV_VAR, &template_dir);
+        let project_dir = tmp_dir.join("my-project");
+        perform_create(&CreateContext {
+            path: project_dir.to_str().unwrap().to_string(),
+            is_libdev_mode: false,
+            version: "1.2.3".to_string(),
+            should_add_to_workspace: false,
+        });
+        std::env::remove_var(PAX_TEMPLATE_DIR_ENV_VAR);
+
+        assert_eq!(
+            std::fs::read_to_string(project_dir.join("src/lib.rs")).unwrap(),
+            "// from template dir"
+        );
+        assert!(!project_dir.join("Cargo.toml.template").exists());
+        let cargo_toml = std::fs::read_to_string(project_dir.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"my-project\""));
+        assert!(cargo_toml.contains("pax-lang = { version = \"1.2.3\" }"));
+        std::fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_parser_binary_command_args() {
+        let get_args = |is_offline: bool, no_default_features: bool| {
+            get_parser_binary_command(".", is_offline, no_default_features)
+                .get_args()
+                .map(|arg| arg.to_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            get_args(false, false),
+            vec!["run", "--features", "parser", "--color", "always"]
+        );
+        assert_eq!(
+            get_args(true, true),
+            vec![
+                "run",
+                "--features",
+                "parser",
+                "--color",
+                "always",
+                "--no-default-features",
+                "--offline"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_require_tool_on_path() {
+        let err = require_tool_on_path("pax-nonexistent-tool", "install via `cargo install it`")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "pax-nonexistent-tool not found; install via `cargo install it`"
+        );
+
+        let tmp_dir = std::env::temp_dir().join(format!("pax-tool-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&tmp_dir).unwrap();
+        let script_path = tmp_dir.join("run-debuggable-mac-app.sh");
+        assert!(!is_executable_file(&script_path));
+        std::fs::write(&script_path, "#!/bin/sh\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert!(!is_executable_file(&script_path));
+            std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        assert!(is_executable_file(&script_path));
+        assert!(!is_executable_file(&tmp_dir));
+        std::fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_run_context_chassis_features_arg() {
+        let mut ctx = RunContext {
+            target: RunTarget::Web,
+            path: ".".to_string(),
+            verbose: false,
+            should_also_run: false,
+            is_libdev_mode: false,
+            process_child_ids: Arc::new(Mutex::new(vec![])),
+            should_write_build_report: false,
+            output_dir: None,
+            offline: false,
+            vendor_dir: None,
+            dry_run: false,
+            wasm_size_warning_bytes: None,
+            post_build_command: None,
+            manifest_path: None,
+            server_workers: None,
+            chassis_features: vec![],
+            ready_tx: None,
+            parser_no_default_features: false,
+            dump_types: false,
+        };
+        assert_eq!(ctx.get_chassis_features_arg(), None);
+
+        ctx.chassis_features = vec![" ".to_string()];
+        assert_eq!(ctx.get_chassis_features_arg(), None);
+
+        ctx.chassis_features = vec![
+            "pax-cartridge/debug-telemetry".to_string(),
+            "".to_string(),
+            " my-feature ".to_string(),
+        ];
+        assert_eq!(
+            ctx.get_chassis_features_arg(),
+            Some("pax-cartridge/debug-telemetry,my-feature".to_string())
+        );
+    }
+
+    #[test]
+    fn test_run_context_manifest_path() {
+        let mut ctx = RunContext {
+            target: RunTarget::Web,
+            path: "examples/hello".to_string(),
+            verbose: false,
+            should_also_run: false,
+            is_libdev_mode: false,
+            process_child_ids: Arc::new(Mutex::new(vec![])),
+            should_write_build_report: false,
+            output_dir: None,
+            offline: false,
+            vendor_dir: None,
+            dry_run: false,
+            wasm_size_warning_bytes: None,
+            post_build_command: None,
+            manifest_path: None,
+            server_workers: None,
+            chassis_features: vec![],
+            ready_tx: None,
+            parser_no_default_features: false,
+            dump_types: false,
+        };
+        assert_eq!(
+            ctx.get_manifest_path(),
+            PathBuf::from("examples/hello/Cargo.toml")
+        );
+        assert_eq!(ctx.get_project_dir(), PathBuf::from("examples/hello"));
+
+        ctx.manifest_path = Some(PathBuf::from("/monorepo/apps/site/Cargo.toml"));
+        assert_eq!(
+            ctx.get_manifest_path(),
+            PathBuf::from("/monorepo/apps/site/Cargo.toml")
+        );
+        assert_eq!(ctx.get_project_dir(), PathBuf::from("/monorepo/apps/site"));
+
+        ctx.manifest_path = Some(PathBuf::from("Cargo.toml"));
+        assert_eq!(ctx.get_project_dir(), PathBuf::from("."));
+    }
+
+    #[test]
+    fn test_coerce_numeric_literal() {
+        assert_eq!(
+            coerce_numeric_literal("Numeric::from(5)", "f64"),
+            "(Numeric::from(5)).get_as_float()"
+        );
+        assert_eq!(
+            coerce_numeric_literal("(-Numeric::from(5.0))", "isize"),
+            "((-Numeric::from(5.0))).get_as_int() as isize"
+        );
+        assert_eq!(
+            coerce_numeric_literal("Numeric::from(5.0)", "pax_lang::api::Numeric"),
+            "Numeric::from(5.0)"
+        );
+        assert_eq!(
+            coerce_numeric_literal("Size::Pixels(5.into())", "u32"),
+            "Size::Pixels(5.into())"
+        );
+    }
+
+    #[test]
+    fn test_literal_list() {
+        let pax = r#"
+            <Rectangle id=r offsets=[1, -2.5, "three"] visible=[true] />
+
+            @settings {
+                #r { grid: [[true, false], []] }
+            }
+        "#;
+        let ctx = ParsingContext::default();
+        let (ctx, _) = Vec::<f64>::parse_to_manifest(ctx);
+        let (ctx, _) = Vec::<Vec<bool>>::parse_to_manifest(ctx);
+        let manifest = build_test_manifest(
+            ctx,
+            pax,
+            "Example",
+            true,
+            vec![],
+            vec![(
+                "pax_std::primitives::Rectangle",
+                vec![
+                    PropertyDefinition::primitive_with_name(&Vec::<f64>::get_type_id(), "offsets"),
+                    PropertyDefinition::primitive_with_name(
+                        &Vec::<Vec<bool>>::get_type_id(),
+                        "grid",
+                    ),
+                    PropertyDefinition::primitive_with_name("bool", "visible"),
+                ],
+            )],
+        );
+
+        let diagnostics = find_literal_type_mismatches(&manifest)
+            .into_iter()
+            .map(|diagnostic| match diagnostic {
+                PaxBuildError::LiteralTypeMismatch {
+                    literal_kind,
+                    property,
+                    ..
+                } => format!("{} {}", literal_kind, property),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(diagnostics, vec!["string offsets", "list visible"]);
+
+        let example = manifest.components.get("crate::Example").unwrap();
+        let tnd = &example.template.as_ref().unwrap()[1];
+        let merged_settings =
+            merge_inline_settings_with_settings_block(tnd, &example.settings, Some("Rectangle"))
+                .unwrap();
+        let get_list = |key: &str| match &merged_settings.iter().find(|(k, _)| k == key).unwrap().1
+        {
+            ValueDefinition::LiteralList(elements) => elements.clone(),
+            _ => panic!("Expected `{}` to be a literal list", key),
+        };
+        let host_crate_info = HostCrateInfo {
+            name: "pax-example".to_string(),
+            identifier: "pax_example".to_string(),
+            import_prefix: "pax_example::pax_reexports::".to_string(),
+            expression_functions: ExpressionFunctionRegistry::default(),
+        };
+        let generate = |key: &str, type_id: &str| {
+            recurse_literal_list(
+                &get_list(key),
+                manifest.type_table.get(type_id).unwrap(),
+                &manifest.type_table,
+                &host_crate_info,
+            )
+        };
+        assert_eq!(
+            generate("offsets", &Vec::<f64>::get_type_id()),
+            "vec![(Numeric::from(1)).get_as_float(), (Numeric::from(-2.5)).get_as_float(), \"three\".to_string()]"
+        );
+        assert_eq!(
+            generate("grid", &Vec::<Vec<bool>>::get_type_id()),
+            "vec![vec![true, false], vec![]]"
+        );
+    }
+
+    #[test]
+    fn test_literal_enum_variant() {
+        let pax = r#"
+            <Rectangle fit=Cover />
+            <Rectangle fit=Fill fits=[Contain, Cover] />
+        "#;
+        let ctx = ParsingContext::default();
+        let (mut ctx, _) = assemble_type_definition(
+            ctx,
+            vec![],
+            None,
+            "pax_std::types::ImageFit",
+            "pax_std::types::ImageFit".to_string(),
+        );
+        ctx.type_table
+            .get_mut("pax_std::types::ImageFit")
+            .unwrap()
+            .enum_variant_names = Some(vec!["Cover".to_string(), "Contain".to_string()]);
+        let (ctx, _) = assemble_type_definition(
+            ctx,
+            vec![],
+            Some("pax_std::types::ImageFit".to_string()),
+            "std::vec::Vec<pax_std::types::ImageFit>",
+            "std::vec::Vec".to_string(),
+        );
+        let manifest = build_test_manifest(
+            ctx,
+            pax,
+            "Example",
+            true,
+            vec![],
+            vec![(
+                "pax_std::primitives::Rectangle",
+                vec![
+                    PropertyDefinition::primitive_with_name("pax_std::types::ImageFit", "fit"),
+                    PropertyDefinition::primitive_with_name(
+                        "std::vec::Vec<pax_std::types::ImageFit>",
+                        "fits",
+                    ),
+                ],
+            )],
+        );
+
+        let diagnostics = find_literal_type_mismatches(&manifest)
+            .into_iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            vec!["`Fill` is not a variant of `pax_std::types::ImageFit`, the type of `fit` within the template of `Example` — expected one of: Cover, Contain"]
+        );
+
+        let example = manifest.components.get("crate::Example").unwrap();
+        let settings = example.template.as_ref().unwrap()[2]
+            .settings
+            .as_ref()
+            .unwrap();
+        let fits = match &settings.iter().find(|(key, _)| key == "fits").unwrap().1 {
+            ValueDefinition::LiteralList(elements) => elements,
+            _ => panic!("Expected `fits` to be a literal list"),
+        };
+        assert!(
+            matches!(&fits[0], ValueDefinition::LiteralEnumVariant(variant) if variant == "Contain")
+        );
+
+        let host_crate_info = HostCrateInfo {
+            name: "pax-example".to_string(),
+            identifier: "pax_example".to_string(),
+            import_prefix: "pax_example::pax_reexports::".to_string(),
+            expression_functions: ExpressionFunctionRegistry::default(),
+        };
+        assert_eq!(
+            recurse_literal_list(
+                fits,
+                manifest
+                    .type_table
+                    .get("std::vec::Vec<pax_std::types::ImageFit>")
+                    .unwrap(),
+                &manifest.type_table,
+                &host_crate_info,
+            ),
+            "vec![pax_example::pax_reexports::pax_std::types::ImageFit::Contain, pax_example::pax_reexports::pax_std::types::ImageFit::Cover]"
+        );
+        let image_fit = manifest.type_table.get("pax_std::types::ImageFit").unwrap();
+        assert!(std::panic::catch_unwind(|| {
+            generate_enum_variant_literal("Fill", image_fit, &host_crate_info)
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_sort_and_dedup_coproduct_tuples() {
+        let tuples = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(id, path)| (id.to_string(), path.to_string()))
+                .collect::<Vec<_>>()
+        };
+        let prefix = "pax_app::pax_reexports::";
+
+        // A built-in and its re-export are the same type; the built-in sorts first and is kept
+        assert_eq!(
+            sort_and_dedup_coproduct_tuples(
+                tuples(&[
+                    ("f64", "pax_app::pax_reexports::f64"),
+                    ("Size", "pax_runtime_api::Size"),
+                    ("f64", "f64"),
+                    ("Size", "pax_runtime_api::Size"),
+                ]),
+                prefix
+            ),
+            tuples(&[("Size", "pax_runtime_api::Size"), ("f64", "f64")])
+        );
+
+        let collision = std::panic::catch_unwind(|| {
+            sort_and_dedup_coproduct_tuples(
+                tuples(&[
+                    ("aCOCOb", "pax_app::pax_reexports::a::b"),
+                    ("aCOCOb", "pax_app::pax_reexports::aCOCOb"),
+                ]),
+                prefix,
+            )
+        })
+        .unwrap_err();
+        let message = collision.downcast_ref::<String>().unwrap();
+        assert!(message.contains("`pax_app::pax_reexports::a::b`"));
+        assert!(message.contains("`pax_app::pax_reexports::aCOCOb`"));
+    }
+
+    #[test]
+    fn test_find_readonly_property_bindings() {
+        let pax = r#"
+            <Counter count=5 label="a" />
+            <Counter id=c label={self.total} />
+
+            @settings {
+                #c { count: {self.total + 1} }
+            }
+        "#;
+
+        let mut count = PropertyDefinition::primitive_with_name("usize", "count");
+        count.flags.is_readonly = true;
+        let manifest = build_test_manifest(
+            ParsingContext::default(),
+            pax,
+            "Example",
+            true,
+            vec![PropertyDefinition::primitive_with_name("usize", "total")],
+            vec![(
+                "crate::Counter",
+                vec![
+                    count,
+                    PropertyDefinition::primitive_with_name("String", "label"),
+                ],
+            )],
+        );
+
+        let diagnostics = find_readonly_property_bindings(&manifest)
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            vec![
+                "`count` of `Counter` is readonly and cannot be bound within the template of `Example` — it is set only by `Counter` itself";
+                2
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate_default_property_literal() {
+        let mut pd = PropertyDefinition {
+            name: "fill".to_string(),
+            type_id: "pax_std::types::Color".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_default_property_literal(&pd),
+            "PropertyLiteral::new(Default::default())"
+        );
+
+        pd.default_value = Some("Color::rgb(1.0,1.0,1.0)".to_string());
+        assert_eq!(
+            generate_default_property_literal(&pd),
+            "PropertyLiteral::new(Color::rgb(1.0,1.0,1.0))"
+        );
+
+        let round_tripped: PropertyDefinition =
+            serde_json::from_str(&serde_json::to_string(&pd).unwrap()).unwrap();
+        assert_eq!(
+            round_tripped.default_value.as_deref(),
+            Some("Color::rgb(1.0,1.0,1.0)")
+        );
+    }
 }
@@ -1,13 +1,14 @@
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::parsing::escape_identifier;
+use crate::parsing::{escape_identifier, TYPE_ID_IF, TYPE_ID_REPEAT, TYPE_ID_SLOT};
+use itertools::Itertools;
 use serde_derive::{Deserialize, Serialize};
 #[allow(unused_imports)]
 use serde_json;
 
 /// Definition container for an entire Pax cartridge
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct PaxManifest {
     pub components: HashMap<String, ComponentDefinition>,
     pub main_component_type_id: String,
@@ -24,6 +25,30 @@ impl PartialEq<Self> for ExpressionSpec {
     }
 }
 
+/// The contents of an `ExpressionSpec` that determine its vtable entry, i.e. all but its `id` and
+/// `input_statement`.  Specs with equal keys can share one vtable entry.
+pub type ExpressionSpecKey = (String, Vec<ExpressionSpecInvocation>, String, bool, String);
+
+impl ExpressionSpec {
+    /// Content-based equality, ignoring `id` and the (informational) `input_statement`.
+    /// Used to deduplicate vtable entries for expressions that compile to identical RIL;
+    /// note that `PartialEq` compares by `id` only.
+    pub fn is_equivalent(&self, other: &Self) -> bool {
+        self.get_key() == other.get_key()
+    }
+
+    /// Key under which equivalent specs (see `is_equivalent`) collide, for deduplicating them by hash
+    pub fn get_key(&self) -> ExpressionSpecKey {
+        (
+            self.pascalized_return_type.clone(),
+            self.invocations.clone(),
+            self.output_statement.clone(),
+            self.is_repeat_source_iterable_expression,
+            self.repeat_source_iterable_type_id_escaped.clone(),
+        )
+    }
+}
+
 impl PartialOrd<Self> for ExpressionSpec {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.id.partial_cmp(&other.id)
@@ -67,7 +92,7 @@ pub struct ExpressionSpec {
 /// For example, if an expression uses `i`, that `i` needs to be "invoked," bound dynamically
 /// to some data on the other side of `i` for the context of a particular expression.  `ExpressionSpecInvocation`
 /// holds the recipe for such an `invocation`, populated as a part of expression compilation.
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct ExpressionSpecInvocation {
     /// Identifier of the top-level symbol (stripped of `this` or `self`) for nested symbols (`foo` for `foo.bar`) or the
     /// identifier itself for non-nested symbols (`foo` for `foo`)
@@ -101,6 +126,10 @@ pub struct ExpressionSpecInvocation {
     /// Flag describing whether the nested symbolic invocation, e.g. `foo.bar`, ultimately
     /// resolves to a numeric type (as opposed to `is_numeric`, which represents the root of a nested type)
     pub is_nested_numeric: bool,
+
+    /// For engine-provided built-ins like `$frames_elapsed`, the RIL that reads the
+    /// built-in's value off of the evaluating `ExpressionContext`, rather than off of a stack frame
+    pub builtin_accessor_literal: Option<String>,
 }
 
 pub const SUPPORTED_NUMERIC_PRIMITIVES: [&str; 13] = [
@@ -157,7 +186,733 @@ impl ComponentDefinition {
     }
 
     pub fn get_property_definitions<'a>(&self, tt: &'a TypeTable) -> &'a Vec<PropertyDefinition> {
-        &tt.get(&self.type_id).unwrap().property_definitions
+        self.try_get_property_definitions(tt)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Like `get_property_definitions`, but returns an error naming the missing `type_id`
+    /// instead of panicking when this component's type is absent from the type table
+    pub fn try_get_property_definitions<'a>(
+        &self,
+        tt: &'a TypeTable,
+    ) -> Result<&'a Vec<PropertyDefinition>, String> {
+        tt.get(&self.type_id)
+            .map(|td| &td.property_definitions)
+            .ok_or_else(|| {
+                format!(
+                    "Component `{}` has type_id `{}`, which is missing from the type table",
+                    self.pascal_identifier, self.type_id
+                )
+            })
+    }
+
+    /// The events declared in this component's `@handlers` block, sorted by event name
+    pub fn get_event_infos(&self) -> Vec<EventInfo> {
+        let mut event_infos: Vec<EventInfo> = self
+            .events
+            .iter()
+            .flatten()
+            .map(|ed| EventInfo {
+                name: ed.key.clone(),
+                handlers: ed.value.clone(),
+            })
+            .collect();
+        event_infos.sort_by(|ei0, ei1| ei0.name.cmp(&ei1.name));
+        event_infos
+    }
+}
+
+impl PaxManifest {
+    /// Describes the properties and events of the component registered as `type_id`, in a
+    /// serializable shape suitable for tooling (e.g. a property inspector).
+    /// Returns `None` if no such component exists in this manifest.
+    pub fn get_component_info(&self, type_id: &str) -> Option<ComponentInfo> {
+        let cd = self.components.get(type_id)?;
+        Some(ComponentInfo {
+            type_id: cd.type_id.clone(),
+            pascal_identifier: cd.pascal_identifier.clone(),
+            properties: self.get_property_infos(type_id)?,
+            events: cd.get_event_infos(),
+        })
+    }
+
+    /// Describes the declared properties of the component registered as `type_id`, in declaration order.
+    /// A property is considered expression-bound if any node instantiating this component, in any template
+    /// of this manifest, binds it to an expression or identifier — whether inline or through `@settings`.
+    pub fn get_property_infos(&self, type_id: &str) -> Option<Vec<PropertyInfo>> {
+        let cd = self.components.get(type_id)?;
+
+        let mut expression_bound_properties = HashSet::new();
+        for containing_cd in self.components.values() {
+            for tnd in containing_cd.template.iter().flatten() {
+                if tnd.type_id != type_id {
+                    continue;
+                }
+                let merged_settings = crate::expressions::merge_inline_settings_with_settings_block(
+                    tnd,
+                    &containing_cd.settings,
+                    Some(&cd.pascal_identifier),
+                );
+                for (key, value) in merged_settings.iter().flatten() {
+                    if let ValueDefinition::Expression(..) | ValueDefinition::Identifier(..) = value
+                    {
+                        expression_bound_properties.insert(key.clone());
+                    }
+                }
+            }
+        }
+
+        let property_definitions = match self.type_table.get(type_id) {
+            Some(td) => td.property_definitions.as_slice(),
+            None => &[],
+        };
+        Some(
+            property_definitions
+                .iter()
+                .map(|pd| PropertyInfo {
+                    name: pd.name.clone(),
+                    type_id: pd.type_id.clone(),
+                    type_display_name: get_type_display_name(&pd.type_id),
+                    default_value: get_primitive_default_value(&pd.type_id),
+                    is_expression_bound: expression_bound_properties.contains(&pd.name),
+                    flags: pd.flags.clone(),
+                })
+                .collect(),
+        )
+    }
+
+    /// The (variant identifier, type) pair of each `PropertiesCoproduct` variant, sorted: one per component,
+    /// with its type imported through `import_prefix`, plus one per tuple `T` for some `Property<Vec<T>>`,
+    /// so that Repeat can wrap each datum (e.g. for `for ((name, count), i) in self.pairs`)
+    pub fn get_properties_coproduct_tuples(&self, import_prefix: &str) -> Vec<(String, String)> {
+        let mut properties_coproduct_tuples: Vec<(String, String)> = self
+            .components
+            .values()
+            .map(|cd| {
+                let mod_path = crate::normalize_module_path(&cd.module_path);
+                let mod_path = if mod_path.is_empty() {
+                    mod_path
+                } else {
+                    mod_path + "::"
+                };
+                (
+                    cd.type_id_escaped.clone(),
+                    format!("{}{}{}", import_prefix, &mod_path, &cd.pascal_identifier),
+                )
+            })
+            .collect();
+
+        self.type_table
+            .values()
+            .filter_map(|td| td.inner_iterable_type_id.as_ref())
+            .filter(|iiti| iiti.starts_with('('))
+            .for_each(|iiti| {
+                properties_coproduct_tuples
+                    .push((escape_identifier(iiti.to_string()), iiti.to_string()))
+            });
+
+        sort_and_dedup_coproduct_tuples(properties_coproduct_tuples, import_prefix)
+    }
+
+    /// The (variant identifier, type) pair of each `TypesCoproduct` variant, sorted and unique by identifier:
+    /// the type of every component property, imported through `import_prefix`, representing all possible
+    /// return types for expressions, plus the types used internally by the runtime (`TYPES_COPRODUCT_BUILT_INS`)
+    pub fn get_types_coproduct_tuples(&self, import_prefix: &str) -> Vec<(String, String)> {
+        let mut tuples: Vec<(String, String)> =
+            self.components
+                .values()
+                .flat_map(|cd| {
+                    cd.try_get_property_definitions(&self.type_table)
+                        .unwrap_or_else(|err| panic!("{}", err))
+                        .iter()
+                        .map(|pd| {
+                            let td = pd.try_get_type_definition(&self.type_table).unwrap_or_else(
+                                |err| panic!("In component `{}`: {}", cd.pascal_identifier, err),
+                            );
+                            (
+                                td.type_id_escaped.clone(),
+                                import_prefix.to_string()
+                                    + &crate::normalize_module_path(&td.type_id),
+                            )
+                        })
+                })
+                .collect();
+
+        TYPES_COPRODUCT_BUILT_INS.iter().for_each(|builtin| {
+            tuples.push((builtin.0.to_string(), builtin.1.to_string()));
+        });
+        sort_and_dedup_coproduct_tuples(tuples, import_prefix)
+    }
+
+    /// Counts describing the size of this manifest and of the code generated from it, e.g. for
+    /// understanding build times or spotting an explosion of types from generic monomorphization
+    pub fn stats(&self) -> ManifestStats {
+        ManifestStats {
+            component_count: self.components.len(),
+            property_count: self
+                .components
+                .values()
+                .filter_map(|cd| self.type_table.get(&cd.type_id))
+                .map(|td| td.property_definitions.len())
+                .sum(),
+            type_count: self.type_table.len(),
+            expression_count: self
+                .expression_specs
+                .as_ref()
+                .map_or(0, |expression_specs| expression_specs.len()),
+            properties_coproduct_variant_count: self.get_properties_coproduct_tuples("").len(),
+            types_coproduct_variant_count: self.get_types_coproduct_tuples("").len(),
+        }
+    }
+
+    /// Renders the template of each component as a GraphViz DOT graph, for visualizing (e.g. with
+    /// `dot -Tsvg`) the structure of a project.  Each component with a template becomes a cluster
+    /// whose nodes are its `TemplateNodeDefinition`s, connected to their `child_ids`; control-flow
+    /// nodes (`for`, `if`, `slot`) are drawn as diamonds, and a dashed edge links each instance of a
+    /// component to the root of that component's template.
+    pub fn to_dot(&self) -> String {
+        let get_node_id =
+            |type_id: &str, tnd_id: usize| format!("\"{}#{}\"", escape_dot_string(type_id), tnd_id);
+
+        let mut dot = String::from("digraph pax_manifest {\n    node [shape=box];\n");
+        for cd in self
+            .components
+            .values()
+            .sorted_by(|cd0, cd1| cd0.type_id.cmp(&cd1.type_id))
+        {
+            let template = match &cd.template {
+                Some(template) => template,
+                None => continue,
+            };
+            dot += &format!(
+                "    subgraph \"cluster_{}\" {{\n        label=\"{}\";\n",
+                escape_dot_string(&cd.type_id),
+                escape_dot_string(&cd.pascal_identifier)
+            );
+            for tnd in template.iter() {
+                let (label, attributes) = if tnd.id == 0 {
+                    (cd.pascal_identifier.clone(), ", style=bold")
+                } else if tnd.type_id == TYPE_ID_REPEAT {
+                    let source = tnd
+                        .control_flow_settings
+                        .as_ref()
+                        .and_then(|cfa| cfa.repeat_source_definition.as_ref())
+                        .and_then(|rsd| {
+                            rsd.range_expression_paxel
+                                .clone()
+                                .or_else(|| rsd.symbolic_binding.clone())
+                        })
+                        .unwrap_or_default();
+                    (format!("for {}", source), ", shape=diamond")
+                } else if tnd.type_id == TYPE_ID_IF {
+                    let condition = tnd
+                        .control_flow_settings
+                        .as_ref()
+                        .and_then(|cfa| cfa.conditional_arms.as_ref())
+                        .and_then(|arms| arms.first())
+                        .map(|arm| arm.condition_expression_paxel.clone())
+                        .unwrap_or_default();
+                    (format!("if {}", condition), ", shape=diamond")
+                } else if tnd.type_id == TYPE_ID_SLOT {
+                    let index = tnd
+                        .control_flow_settings
+                        .as_ref()
+                        .and_then(|cfa| cfa.slot_index_expression_paxel.clone())
+                        .unwrap_or_default();
+                    (format!("slot {}", index), ", shape=diamond")
+                } else {
+                    (tnd.pascal_identifier.clone(), "")
+                };
+                dot += &format!(
+                    "        {} [label=\"{}\"{}];\n",
+                    get_node_id(&cd.type_id, tnd.id),
+                    escape_dot_string(label.trim()),
+                    attributes
+                );
+            }
+            dot += "    }\n";
+
+            for tnd in template.iter() {
+                for child_id in tnd.child_ids.iter() {
+                    dot += &format!(
+                        "    {} -> {};\n",
+                        get_node_id(&cd.type_id, tnd.id),
+                        get_node_id(&cd.type_id, *child_id)
+                    );
+                }
+                if self
+                    .components
+                    .get(&tnd.type_id)
+                    .is_some_and(|instance_cd| instance_cd.template.is_some())
+                {
+                    dot += &format!(
+                        "    {} -> {} [style=dashed];\n",
+                        get_node_id(&cd.type_id, tnd.id),
+                        get_node_id(&tnd.type_id, 0)
+                    );
+                }
+            }
+        }
+        dot += "}\n";
+        dot
+    }
+
+    /// Renders each `TypeDefinition` in `type_table`, sorted by `type_id`, with the identifiers used to
+    /// refer to it in generated code and its property list — for diagnosing a type that was mis-escaped
+    /// or mis-prefixed (see `update_property_prefixes_in_place`), which otherwise tends to surface only
+    /// as a cartridge compile error.
+    pub fn dump_types(&self) -> String {
+        let mut dump = String::new();
+        for td in self
+            .type_table
+            .values()
+            .sorted_by(|td0, td1| td0.type_id.cmp(&td1.type_id))
+        {
+            dump += &format!(
+                "{}\n    type_id_escaped: {}\n    import_path: {}\n    inner_iterable_type_id: {}\n",
+                td.type_id,
+                td.type_id_escaped,
+                td.import_path,
+                td.inner_iterable_type_id.as_deref().unwrap_or("-")
+            );
+            if let Some(enum_variant_names) = &td.enum_variant_names {
+                dump += &format!(
+                    "    enum_variant_names: {}\n",
+                    enum_variant_names.join(", ")
+                );
+            }
+            if td.property_definitions.is_empty() {
+                dump += "    properties: -\n";
+            } else {
+                dump += "    properties:\n";
+                for pd in td.property_definitions.iter() {
+                    dump += &format!("        {}: {}\n", pd.name, pd.type_id);
+                }
+            }
+        }
+        dump
+    }
+
+    /// Returns, sorted, the entries of `import_paths` that no longer refer to a type in use: those that
+    /// are neither the import path of a type in `type_table` nor of a component, and whose type is not
+    /// named by any compiled expression or literal setting.  Entries in `BUILTIN_IMPORT_PATHS` are
+    /// always considered in use.
+    pub fn get_unused_import_paths(&self) -> Vec<String> {
+        //import paths of generic types may or may not include their generics, e.g. `std::vec::Vec`
+        let strip_generics = |path: &str| path.split('<').next().unwrap_or(path).to_string();
+        let mut used_import_paths: HashSet<String> = BUILTIN_IMPORT_PATHS
+            .iter()
+            .map(|path| path.to_string())
+            .collect();
+        for td in self.type_table.values() {
+            used_import_paths.insert(strip_generics(&td.import_path));
+            used_import_paths.insert(strip_generics(&td.type_id));
+        }
+        for cd in self.components.values() {
+            used_import_paths.insert(strip_generics(&cd.type_id));
+        }
+
+        //identifiers written into generated code, e.g. the `Color` in `Color::rgb(...)`
+        let mut referenced_identifiers: HashSet<String> = HashSet::new();
+        let mut collect_identifiers = |code: &str| {
+            code.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .filter(|identifier| !identifier.is_empty())
+                .for_each(|identifier| {
+                    referenced_identifiers.insert(identifier.to_string());
+                });
+        };
+        for expression_spec in self
+            .expression_specs
+            .iter()
+            .flat_map(|specs| specs.values())
+        {
+            collect_identifiers(&expression_spec.output_statement);
+        }
+        for cd in self.components.values() {
+            let template_settings = cd
+                .template
+                .iter()
+                .flatten()
+                .filter_map(|tnd| tnd.settings.as_ref())
+                .flatten();
+            for (_, value_definition) in template_settings {
+                collect_value_definition_identifiers(value_definition, &mut collect_identifiers);
+            }
+            for selector_block in cd.settings.iter().flatten() {
+                collect_literal_block_identifiers(
+                    &selector_block.value_block,
+                    &mut collect_identifiers,
+                );
+            }
+        }
+
+        self.import_paths
+            .iter()
+            .filter(|path| {
+                let is_referenced = path
+                    .rsplit("::")
+                    .next()
+                    .is_some_and(|identifier| referenced_identifiers.contains(identifier));
+                !used_import_paths.contains(&strip_generics(path)) && !is_referenced
+            })
+            .cloned()
+            .sorted()
+            .collect()
+    }
+
+    /// Drops the entries of `get_unused_import_paths` from `import_paths`, so that they aren't reexported
+    /// or imported by the cartridge, returning the dropped paths
+    pub fn remove_unused_import_paths(&mut self) -> Vec<String> {
+        let unused_import_paths = self.get_unused_import_paths();
+        for path in unused_import_paths.iter() {
+            self.import_paths.remove(path);
+        }
+        unused_import_paths
+    }
+
+    /// Merges `other`, e.g. a manifest parsed from a separate unit of the same project, into this one:
+    /// `components`, `type_table` and `import_paths` are unioned, and `other`'s expression specs are
+    /// renumbered past this manifest's, along with the vtable ids bound in `other`'s templates, so that
+    /// the two never collide.  A component or type present in both manifests must be defined identically
+    /// (up to vtable ids), in which case this manifest's definition is kept.  `main_component_type_id` is
+    /// taken from `other` only if this manifest has none.  On error, this manifest is left unchanged.
+    pub fn merge(&mut self, mut other: PaxManifest) -> Result<(), MergeError> {
+        if !self.main_component_type_id.is_empty()
+            && !other.main_component_type_id.is_empty()
+            && self.main_component_type_id != other.main_component_type_id
+        {
+            return Err(MergeError::ConflictingMainComponent(
+                self.main_component_type_id.clone(),
+                other.main_component_type_id,
+            ));
+        }
+        for (type_id, other_cd) in other.components.iter() {
+            if let Some(cd) = self.components.get(type_id) {
+                if !is_same_component_definition(cd, other_cd) {
+                    return Err(MergeError::ConflictingComponent(type_id.clone()));
+                }
+            }
+        }
+        for (type_id, other_td) in other.type_table.iter() {
+            if let Some(td) = self.type_table.get(type_id) {
+                if serde_json::to_value(td).ok() != serde_json::to_value(other_td).ok() {
+                    return Err(MergeError::ConflictingType(type_id.clone()));
+                }
+            }
+        }
+
+        if let Some(other_expression_specs) = other.expression_specs.take() {
+            let offset = self
+                .expression_specs
+                .iter()
+                .flat_map(|specs| specs.keys())
+                .max()
+                .map_or(0, |id| id + 1);
+            for cd in other.components.values_mut() {
+                for_each_vtable_id_mut(cd, &mut |vtable_id| {
+                    if let Some(id) = vtable_id {
+                        *id += offset;
+                    }
+                });
+            }
+            self.expression_specs
+                .get_or_insert_with(HashMap::new)
+                .extend(other_expression_specs.into_values().map(|mut spec| {
+                    spec.id += offset;
+                    (spec.id, spec)
+                }));
+        }
+
+        for (type_id, cd) in other.components {
+            self.components.entry(type_id).or_insert(cd);
+        }
+        for (type_id, td) in other.type_table {
+            self.type_table.entry(type_id).or_insert(td);
+        }
+        self.import_paths.extend(other.import_paths);
+        if self.main_component_type_id.is_empty() {
+            self.main_component_type_id = other.main_component_type_id;
+        }
+        Ok(())
+    }
+}
+
+/// Reason that two manifests could not be merged, see `PaxManifest::merge`
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeError {
+    /// Both manifests define a component with this type_id, but differently
+    ConflictingComponent(String),
+    /// Both manifests define a type with this type_id, but differently
+    ConflictingType(String),
+    /// The manifests have different main components, (this manifest's, the other's)
+    ConflictingMainComponent(String, String),
+}
+
+impl std::fmt::Display for MergeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeError::ConflictingComponent(type_id) => write!(
+                f,
+                "Cannot merge manifests — component `{}` is defined differently in each",
+                type_id
+            ),
+            MergeError::ConflictingType(type_id) => write!(
+                f,
+                "Cannot merge manifests — type `{}` is defined differently in each",
+                type_id
+            ),
+            MergeError::ConflictingMainComponent(main, other_main) => write!(
+                f,
+                "Cannot merge manifests — their main components differ: `{}` and `{}`",
+                main, other_main
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+/// Compares two component definitions by content, ignoring the vtable ids bound in their templates,
+/// which depend on the order in which each manifest's expressions were compiled
+fn is_same_component_definition(a: &ComponentDefinition, b: &ComponentDefinition) -> bool {
+    let without_vtable_ids = |cd: &ComponentDefinition| {
+        let mut cd = cd.clone();
+        for_each_vtable_id_mut(&mut cd, &mut |vtable_id| *vtable_id = None);
+        serde_json::to_value(cd).ok()
+    };
+    without_vtable_ids(a) == without_vtable_ids(b)
+}
+
+/// Visits every vtable id binding within `cd`'s template and settings
+fn for_each_vtable_id_mut(cd: &mut ComponentDefinition, f: &mut impl FnMut(&mut Option<usize>)) {
+    for tnd in cd.template.iter_mut().flatten() {
+        for (_, value_definition) in tnd.settings.iter_mut().flatten() {
+            for_each_value_definition_vtable_id_mut(value_definition, f);
+        }
+        if let Some(cfs) = tnd.control_flow_settings.as_mut() {
+            f(&mut cfs.slot_index_expression_vtable_id);
+            for arm in cfs.conditional_arms.iter_mut().flatten() {
+                f(&mut arm.condition_expression_vtable_id);
+            }
+            if let Some(rsd) = cfs.repeat_source_definition.as_mut() {
+                f(&mut rsd.vtable_id);
+                f(&mut rsd.key_vtable_id);
+            }
+        }
+    }
+    for selector_block in cd.settings.iter_mut().flatten() {
+        for (_, value_definition) in selector_block
+            .value_block
+            .settings_key_value_pairs
+            .iter_mut()
+        {
+            for_each_value_definition_vtable_id_mut(value_definition, f);
+        }
+    }
+}
+
+fn for_each_value_definition_vtable_id_mut(
+    value_definition: &mut ValueDefinition,
+    f: &mut impl FnMut(&mut Option<usize>),
+) {
+    match value_definition {
+        ValueDefinition::Expression(_, vtable_id) | ValueDefinition::Identifier(_, vtable_id) => {
+            f(vtable_id)
+        }
+        ValueDefinition::Block(block) => {
+            for (_, value_definition) in block.settings_key_value_pairs.iter_mut() {
+                for_each_value_definition_vtable_id_mut(value_definition, f);
+            }
+        }
+        ValueDefinition::LiteralList(elements) => {
+            for value_definition in elements.iter_mut() {
+                for_each_value_definition_vtable_id_mut(value_definition, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Import paths that generated code may depend upon without any property, component or expression
+/// referring to them, e.g. `Size` for the common properties `width` and `height`
+const BUILTIN_IMPORT_PATHS: [&str; 3] = [
+    "pax_lang::api::Numeric",
+    "pax_lang::api::Size",
+    "pax_lang::api::SizePixels",
+];
+
+fn collect_value_definition_identifiers(
+    value_definition: &ValueDefinition,
+    collect_identifiers: &mut impl FnMut(&str),
+) {
+    match value_definition {
+        ValueDefinition::LiteralValue(literal) => collect_identifiers(literal),
+        ValueDefinition::Block(block) => {
+            collect_literal_block_identifiers(block, collect_identifiers)
+        }
+        ValueDefinition::LiteralList(elements) => {
+            for value_definition in elements.iter() {
+                collect_value_definition_identifiers(value_definition, collect_identifiers);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_literal_block_identifiers(
+    block: &LiteralBlockDefinition,
+    collect_identifiers: &mut impl FnMut(&str),
+) {
+    if let Some(explicit_type) = &block.explicit_type_pascal_identifier {
+        collect_identifiers(explicit_type);
+    }
+    for (_, value_definition) in block.settings_key_value_pairs.iter() {
+        collect_value_definition_identifiers(value_definition, collect_identifiers);
+    }
+}
+
+/// Escapes `s` for use within a double-quoted DOT string
+fn escape_dot_string(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Sorts coproduct (variant identifier, type) tuples by identifier, then by type, keeping the first tuple
+/// for each identifier, so that generated coproducts are identical across builds.
+/// Types that differ only by `import_prefix`, e.g. a built-in like `f64` and its re-export, are the same type;
+/// panics, naming both types, if two different types would otherwise share a variant identifier.
+pub(crate) fn sort_and_dedup_coproduct_tuples(
+    tuples: Vec<(String, String)>,
+    import_prefix: &str,
+) -> Vec<(String, String)> {
+    let unprefixed = |path: &str| path.strip_prefix(import_prefix).unwrap_or(path).to_string();
+    let mut ret: Vec<(String, String)> = vec![];
+    for (id, path) in tuples.into_iter().sorted().dedup() {
+        match ret.last() {
+            Some((last_id, last_path)) if *last_id == id => {
+                if unprefixed(last_path) != unprefixed(&path) {
+                    panic!(
+                        "Types `{}` and `{}` both generate the coproduct variant `{}`; rename one of them",
+                        last_path, path, id
+                    );
+                }
+            }
+            _ => ret.push((id, path)),
+        }
+    }
+    ret
+}
+
+/// Types that every `TypesCoproduct` includes, as (variant identifier, type), regardless of the properties declared in the manifest
+const TYPES_COPRODUCT_BUILT_INS: [(&str, &str); 12] = [
+    ("f64", "f64"),
+    ("bool", "bool"),
+    ("isize", "isize"),
+    ("usize", "usize"),
+    ("String", "String"),
+    (
+        "std_COCOvec_COCOVec_LABRstd_COCOrc_COCORc_LABRPropertiesCoproduct_RABR_RABR",
+        "std::vec::Vec<std::rc::Rc<PropertiesCoproduct>>",
+    ),
+    ("Transform2D", "pax_runtime_api::Transform2D"),
+    (
+        "std_COCOops_COCORange_LABRisize_RABR",
+        "std::ops::Range<isize>",
+    ),
+    ("Size", "pax_runtime_api::Size"),
+    ("Rotation", "pax_runtime_api::Rotation"),
+    ("SizePixels", "pax_runtime_api::SizePixels"),
+    ("Numeric", "pax_runtime_api::Numeric"),
+];
+
+/// Size of a manifest, see `PaxManifest::stats`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ManifestStats {
+    pub component_count: usize,
+    /// Properties declared across all components
+    pub property_count: usize,
+    /// Distinct types in the `TypeTable`
+    pub type_count: usize,
+    /// Entries in the expression vtable; zero until expressions are compiled
+    pub expression_count: usize,
+    pub properties_coproduct_variant_count: usize,
+    pub types_coproduct_variant_count: usize,
+}
+
+impl std::fmt::Display for ManifestStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} components, {} properties, {} types, {} expressions, {} PropertiesCoproduct variants, {} TypesCoproduct variants",
+            self.component_count,
+            self.property_count,
+            self.type_count,
+            self.expression_count,
+            self.properties_coproduct_variant_count,
+            self.types_coproduct_variant_count
+        )
+    }
+}
+
+/// Tooling-facing description of a component: its declared properties and the events it handles
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ComponentInfo {
+    pub type_id: String,
+    pub pascal_identifier: String,
+    pub properties: Vec<PropertyInfo>,
+    pub events: Vec<EventInfo>,
+}
+
+/// Tooling-facing description of a single declared property, see `PaxManifest::get_property_infos`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PropertyInfo {
+    pub name: String,
+    pub type_id: String,
+    /// `type_id` stripped of module paths, e.g. `Vec<Color>` for `std::vec::Vec<pax_std::types::Color>`
+    pub type_display_name: String,
+    /// The value, as written in Pax, that this property takes when left unset.  Only known
+    /// statically for the supported primitives; `None` for any other type.
+    pub default_value: Option<String>,
+    /// Whether some template in the manifest binds this property to an expression or identifier
+    pub is_expression_bound: bool,
+    pub flags: PropertyDefinitionFlags,
+}
+
+/// Tooling-facing description of a handled event, e.g. `did_mount` and the handlers bound to it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EventInfo {
+    pub name: String,
+    pub handlers: Vec<String>,
+}
+
+/// Strips the module paths from every segment of `type_id`, e.g. `std::vec::Vec<pax_std::types::Color>` => `Vec<Color>`
+fn get_type_display_name(type_id: &str) -> String {
+    let mut display_name = String::new();
+    let mut segment = String::new();
+    let mut chars = type_id.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            segment.clear();
+        } else if c.is_alphanumeric() || c == '_' {
+            segment.push(c);
+        } else {
+            display_name.push_str(&segment);
+            display_name.push(c);
+            segment.clear();
+        }
+    }
+    display_name.push_str(&segment);
+    display_name
+}
+
+/// `Default::default()` for the supported primitives, as written in Pax
+fn get_primitive_default_value(type_id: &str) -> Option<String> {
+    match type_id {
+        "f64" => Some("0.0".to_string()),
+        "bool" => Some("false".to_string()),
+        "String" => Some("\"\"".to_string()),
+        _ if ExpressionSpecInvocation::is_numeric(type_id) => Some("0".to_string()),
+        _ => None,
     }
 }
 
@@ -177,6 +932,12 @@ pub struct TemplateNodeDefinition {
     pub control_flow_settings: Option<ControlFlowSettingsDefinition>,
     /// IFF this TND is NOT a control-flow node: parsed key-value store of attribute definitions (like `some_key="some_value"`)
     pub settings: Option<Vec<(String, ValueDefinition)>>,
+    /// The `x` in an inline `id="x"` (or `id=x`) attribute, used to match `#x` selectors in `@settings`.
+    /// Stored separately from `settings`, as it is not a property on the node's component.
+    pub element_id: Option<String>,
+    /// The space-separated classes in an inline `class="foo bar"` attribute, used to match `.foo` / `.bar`
+    /// selectors in `@settings`.  Stored separately from `settings`, as they are not properties on the node's component.
+    pub classes: Vec<String>,
     /// e.g. the `SomeName` in `<SomeName some_key="some_value" />`
     pub pascal_identifier: String,
 }
@@ -209,19 +970,44 @@ pub struct PropertyDefinition {
 
     /// Statically known type_id for this Property's associated TypeDefinition
     pub type_id: String,
+
+    /// Rust expression for this property's value wherever it is not set, given by `#[pax(default = "...")]`.
+    /// `None` for properties that default to `Default::default()`
+    #[serde(default)]
+    pub default_value: Option<String>,
 }
 
 impl PropertyDefinition {
     pub fn get_type_definition<'a>(&'a self, tt: &'a TypeTable) -> &TypeDefinition {
-        tt.get(&self.type_id).unwrap()
+        self.try_get_type_definition(tt)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Like `get_type_definition`, but returns an error naming the missing `type_id`
+    /// instead of panicking when this property's type is absent from the type table
+    pub fn try_get_type_definition<'a>(
+        &'a self,
+        tt: &'a TypeTable,
+    ) -> Result<&'a TypeDefinition, String> {
+        tt.get(&self.type_id).ok_or_else(|| {
+            format!(
+                "Property `{}` has type_id `{}`, which is missing from the type table",
+                self.name, self.type_id
+            )
+        })
     }
 
     pub fn get_inner_iterable_type_definition<'a>(
         &'a self,
         tt: &'a TypeTable,
     ) -> Option<&TypeDefinition> {
-        if let Some(ref iiti) = tt.get(&self.type_id).unwrap().inner_iterable_type_id {
-            Some(tt.get(iiti).unwrap())
+        if let Some(ref iiti) = self.get_type_definition(tt).inner_iterable_type_id {
+            Some(tt.get(iiti).unwrap_or_else(|| {
+                panic!(
+                    "Property `{}` iterates over type_id `{}`, which is missing from the type table",
+                    self.name, iiti
+                )
+            }))
         } else {
             None
         }
@@ -231,7 +1017,7 @@ impl PropertyDefinition {
 /// These flags describe the aspects of properties that affect RIL codegen.
 /// Properties are divided into modal axes (exactly one value should be true per axis per struct instance)
 /// Codegen considers each element of the cartesian product of these axes
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct PropertyDefinitionFlags {
     // // //
     // Binding axis
@@ -240,6 +1026,12 @@ pub struct PropertyDefinitionFlags {
     pub is_binding_repeat_i: bool,
     /// Does this property represent `elem` in `for (elem, i)` OR `for elem in 0..5` ?
     pub is_binding_repeat_elem: bool,
+    /// Does this property represent the length of the iterable, bound to the reserved symbol `len`
+    /// inside every `for` body?
+    pub is_binding_repeat_len: bool,
+    /// For a member of a destructured `elem`, like `count` in `for ((name, count), i)`:
+    /// which member of which tuple type this property represents
+    pub destructured_repeat_elem: Option<DestructuredRepeatElemDefinition>,
 
     // // //
     // Source axis
@@ -253,6 +1045,18 @@ pub struct PropertyDefinitionFlags {
     /// This distinction affects our ability to dirty-watch a particular property, and
     /// has implications on codegen
     pub is_property_wrapped: bool,
+    /// Is this property readonly, declared with `#[pax(readonly)]`?  Readonly properties are set only by
+    /// their own component, e.g. from its event handlers, and cannot be bound by the templates that instantiate it
+    pub is_readonly: bool,
+}
+
+/// Locates a member of a destructured Repeat `elem` within its tuple
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DestructuredRepeatElemDefinition {
+    /// type_id of the tuple being destructured, i.e. the iterable type of the Repeat source
+    pub tuple_type_id: String,
+    /// Position of this member within the tuple, e.g. `1` for `count` in `(name, count)`
+    pub index: usize,
 }
 
 /// Describes static metadata surrounding a property, for example
@@ -265,6 +1069,7 @@ impl PropertyDefinition {
             name: symbol_name.to_string(),
             flags: PropertyDefinitionFlags::default(),
             type_id: type_name.to_string(),
+            default_value: None,
         }
     }
 }
@@ -287,14 +1092,27 @@ pub struct TypeDefinition {
 
     /// A vec of PropertyType, describing known addressable (sub-)properties of this PropertyType
     pub property_definitions: Vec<PropertyDefinition>,
+
+    /// Names of this type's variants if it is an enum, against which enum literals like `fit=Cover`
+    /// are validated.  `None` for other types, as well as for enums whose variants are unknown
+    #[serde(default)]
+    pub enum_variant_names: Option<Vec<String>>,
 }
 
 impl TypeDefinition {
+    /// Whether `variant` is known not to be one of `enum_variant_names`
+    pub fn is_unknown_enum_variant(&self, variant: &str) -> bool {
+        self.enum_variant_names
+            .as_ref()
+            .is_some_and(|enum_variant_names| !enum_variant_names.iter().any(|v| v == variant))
+    }
+
     pub fn primitive(type_name: &str) -> Self {
         Self {
             type_id_escaped: escape_identifier(type_name.to_string()),
             type_id: type_name.to_string(),
             property_definitions: vec![],
+            enum_variant_names: None,
             inner_iterable_type_id: None,
             import_path: type_name.to_string(),
         }
@@ -307,6 +1125,7 @@ impl TypeDefinition {
             type_id: type_id.to_string(),
             type_id_escaped: escape_identifier(type_id.to_string()),
             property_definitions: vec![],
+            enum_variant_names: None,
             inner_iterable_type_id: Some(inner_iterable_type_id),
             import_path: "std::vec::Vec".to_string(),
         }
@@ -318,6 +1137,7 @@ impl TypeDefinition {
             type_id: type_id.to_string(),
             type_id_escaped: escape_identifier(type_id.to_string()),
             property_definitions: vec![],
+            enum_variant_names: None,
             inner_iterable_type_id: Some("isize".to_string()),
             import_path: "std::ops::Range".to_string(),
         }
@@ -329,6 +1149,7 @@ impl TypeDefinition {
             type_id: type_id.to_string(),
             type_id_escaped: escape_identifier(type_id.to_string()),
             property_definitions: vec![],
+            enum_variant_names: None,
             inner_iterable_type_id: None,
             import_path: "std::rc::Rc".to_string(),
         }
@@ -342,6 +1163,10 @@ pub enum ValueDefinition {
     Undefined, //Used for `Default`
     LiteralValue(String),
     Block(LiteralBlockDefinition),
+    /// Elements of a literal list like `[0%, 50%, 100%]`, for a property of an iterable type like `Vec<T>`
+    LiteralList(Vec<ValueDefinition>),
+    /// Bare variant name of a literal like `fit=Cover`, qualified at codegen with the property's enum type
+    LiteralEnumVariant(String),
     /// (Expression contents, vtable id binding)
     Expression(String, Option<usize>),
     /// (Expression contents, vtable id binding)
@@ -350,12 +1175,14 @@ pub enum ValueDefinition {
 }
 
 /// Container for holding parsed data describing a Repeat (`for`)
-/// predicate, for example the `(elem, i)` in `for (elem, i) in foo` or
-/// the `elem` in `for elem in foo`
+/// predicate, for example the `(elem, i)` in `for (elem, i) in foo`,
+/// the `elem` in `for elem in foo`, or the `((name, count), i)` in `for ((name, count), i) in foo`
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ControlFlowRepeatPredicateDefinition {
     ElemId(String),
     ElemIdIndexId(String, String),
+    /// A tuple `elem` destructured into its members' ids, plus the index id
+    ElemTupleIndexId(Vec<String>, String),
 }
 
 /// Container for storing parsed control flow information, for
@@ -363,14 +1190,28 @@ pub enum ControlFlowRepeatPredicateDefinition {
 /// expressions and the related vtable ids (for "punching" during expression compilation)
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct ControlFlowSettingsDefinition {
-    pub condition_expression_paxel: Option<String>,
-    pub condition_expression_vtable_id: Option<usize>,
+    /// For a conditional: the ordered `if` / `else if` arms, of which the first truthy one is rendered.
+    /// A bare `if` is a single arm.
+    pub conditional_arms: Option<Vec<ControlFlowConditionalArmDefinition>>,
+    /// For a conditional with an `else` branch: the ids of the TNDs rendered when no arm's condition is true.
+    pub else_child_ids: Option<Vec<usize>>,
     pub slot_index_expression_paxel: Option<String>,
     pub slot_index_expression_vtable_id: Option<usize>,
     pub repeat_predicate_definition: Option<ControlFlowRepeatPredicateDefinition>,
     pub repeat_source_definition: Option<ControlFlowRepeatSourceDefinition>,
 }
 
+/// Container for a single `if` / `else if` arm of a Conditional: its condition
+/// expression and the ids of the TNDs rendered while that arm is active.
+/// Note that a Conditional's `child_ids` span every arm (and its `else` branch), so
+/// that generic tree traversals visit all of its descendants.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ControlFlowConditionalArmDefinition {
+    pub condition_expression_paxel: String,
+    pub condition_expression_vtable_id: Option<usize>,
+    pub child_ids: Vec<usize>,
+}
+
 /// Container describing the possible variants of a Repeat source
 /// — namely a range expression in PAXEL or a symbolic binding
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -378,9 +1219,15 @@ pub struct ControlFlowRepeatSourceDefinition {
     pub range_expression_paxel: Option<String>,
     pub vtable_id: Option<usize>,
     pub symbolic_binding: Option<String>,
+    /// Optional per-element key (e.g. the `item.id` in `for item in self.items key={item.id}`),
+    /// by which Repeat reuses existing instances rather than rebuilding them
+    pub key_expression_paxel: Option<String>,
+    pub key_vtable_id: Option<usize>,
 }
 
-/// Container for parsed Settings blocks (inside `@settings`)
+/// Container for parsed Settings blocks (inside `@settings`).
+/// `selector` is one of a pascal identifier (e.g. `Rectangle`), `.class` or `#id`; matched blocks are
+/// merged into each node's settings during expression compilation, with inline settings winning on conflict.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SettingsSelectorBlockDefinition {
     pub selector: String,
@@ -411,3 +1258,404 @@ pub struct EventDefinition {
     pub key: String,
     pub value: Vec<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        MergeError, PaxManifest, PropertyDefinition, TypeDefinition, TypeTable, ValueDefinition,
+    };
+    use crate::parsing::{assemble_component_definition, ParsingContext, Reflectable};
+    use crate::tests::build_test_manifest;
+    use itertools::Itertools;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_manifest_stats() {
+        let pax = r#"
+            <Rectangle count={self.total * 2} />
+            <Rectangle label="hi" />
+        "#;
+        let mut manifest = build_test_manifest(
+            ParsingContext::default(),
+            pax,
+            "Example",
+            true,
+            vec![PropertyDefinition::primitive_with_name("usize", "total")],
+            vec![(
+                "pax_std::primitives::Rectangle",
+                vec![
+                    PropertyDefinition::primitive_with_name("String", "label"),
+                    PropertyDefinition::primitive_with_name("usize", "count"),
+                    PropertyDefinition::primitive_with_name("f64", "corner_radius"),
+                ],
+            )],
+        );
+
+        let stats = manifest.stats();
+        assert_eq!(stats.component_count, 2);
+        assert_eq!(stats.property_count, 4);
+        assert_eq!(stats.type_count, manifest.type_table.len());
+        assert_eq!(stats.expression_count, 0);
+        assert_eq!(stats.properties_coproduct_variant_count, 2);
+        // `String`, `usize` and `f64` are already among the built-ins
+        assert_eq!(stats.types_coproduct_variant_count, 12);
+
+        crate::expressions::compile_all_expressions(&mut manifest).unwrap();
+        assert_eq!(manifest.stats().expression_count, 1);
+        assert_eq!(
+            manifest.stats().to_string(),
+            format!(
+                "2 components, 4 properties, {} types, 1 expressions, 2 PropertiesCoproduct variants, 12 TypesCoproduct variants",
+                manifest.type_table.len()
+            )
+        );
+    }
+
+    #[test]
+    fn test_manifest_merge() {
+        // Parses and compiles one unit of a project, holding a single component besides `Rectangle`
+        let build_unit = |pax: &str, pascal_identifier: &str, is_main_component: bool| {
+            let mut manifest = build_test_manifest(
+                ParsingContext::default(),
+                pax,
+                pascal_identifier,
+                is_main_component,
+                vec![PropertyDefinition::primitive_with_name("usize", "total")],
+                vec![(
+                    "pax_std::primitives::Rectangle",
+                    vec![
+                        PropertyDefinition::primitive_with_name("String", "label"),
+                        PropertyDefinition::primitive_with_name("usize", "count"),
+                    ],
+                )],
+            );
+            crate::expressions::compile_all_expressions(&mut manifest).unwrap();
+            manifest
+        };
+        let get_bound_output_statement = |manifest: &PaxManifest, type_id: &str| {
+            let tnd = &manifest.components[type_id].template.as_ref().unwrap()[1];
+            match &tnd.settings.as_ref().unwrap()[0].1 {
+                ValueDefinition::Expression(_, Some(id)) => {
+                    manifest.expression_specs.as_ref().unwrap()[id]
+                        .output_statement
+                        .clone()
+                }
+                _ => panic!("Expected a compiled expression"),
+            }
+        };
+
+        let mut manifest = build_unit("<Rectangle count={self.total * 2} />", "Example", true);
+        let card = build_unit("<Rectangle count={self.total + 1} />", "Card", false);
+        let example_output_statement = get_bound_output_statement(&manifest, "crate::Example");
+        let card_output_statement = get_bound_output_statement(&card, "crate::Card");
+
+        manifest.merge(card).unwrap();
+        assert_eq!(manifest.main_component_type_id, "crate::Example");
+        assert_eq!(manifest.components.len(), 3);
+        assert_eq!(manifest.expression_specs.as_ref().unwrap().len(), 2);
+        // Both units numbered their expressions from zero; each still binds its own
+        assert_eq!(
+            get_bound_output_statement(&manifest, "crate::Example"),
+            example_output_statement
+        );
+        assert_eq!(
+            get_bound_output_statement(&manifest, "crate::Card"),
+            card_output_statement
+        );
+
+        // A type defined differently in each unit can't be merged, and leaves the manifest untouched
+        let mut conflicting = build_unit("<Rectangle />", "Badge", false);
+        conflicting
+            .type_table
+            .get_mut("pax_std::primitives::Rectangle")
+            .unwrap()
+            .property_definitions
+            .pop();
+        assert_eq!(
+            manifest.merge(conflicting).unwrap_err(),
+            MergeError::ConflictingType("pax_std::primitives::Rectangle".to_string())
+        );
+        assert!(!manifest.components.contains_key("crate::Badge"));
+
+        let other_main = build_unit("<Rectangle />", "App", true);
+        assert_eq!(
+            manifest.merge(other_main).unwrap_err().to_string(),
+            "Cannot merge manifests — their main components differ: `crate::Example` and `crate::App`"
+        );
+    }
+
+    #[test]
+    fn test_get_component_info() {
+        let pax = r#"
+            <Rectangle class=bound label="hi" />
+            <Rectangle count=3 />
+
+            @settings {
+                .bound { count: {self.total * 2} }
+            }
+            @handlers {
+                will_render: [handle_will_render, log_frame],
+                did_mount: handle_did_mount,
+            }
+        "#;
+
+        let manifest = build_test_manifest(
+            ParsingContext::default(),
+            pax,
+            "Example",
+            true,
+            vec![PropertyDefinition::primitive_with_name("usize", "total")],
+            vec![(
+                "pax_std::primitives::Rectangle",
+                vec![
+                    PropertyDefinition::primitive_with_name("String", "label"),
+                    PropertyDefinition::primitive_with_name("usize", "count"),
+                    PropertyDefinition::primitive_with_name(
+                        "std::vec::Vec<pax_std::types::Color>",
+                        "palette",
+                    ),
+                ],
+            )],
+        );
+
+        let rectangle_info = manifest
+            .get_component_info("pax_std::primitives::Rectangle")
+            .unwrap();
+        assert_eq!(rectangle_info.pascal_identifier, "Rectangle");
+        assert!(rectangle_info.events.is_empty());
+        assert_eq!(
+            rectangle_info
+                .properties
+                .iter()
+                .map(|pi| (
+                    pi.name.as_str(),
+                    pi.type_display_name.as_str(),
+                    pi.default_value.as_deref(),
+                    pi.is_expression_bound,
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("label", "String", Some("\"\""), false),
+                ("count", "usize", Some("0"), true),
+                ("palette", "Vec<Color>", None, false),
+            ]
+        );
+
+        let example_info = manifest.get_component_info("crate::Example").unwrap();
+        assert!(!example_info.properties[0].is_expression_bound);
+        assert_eq!(
+            example_info
+                .events
+                .iter()
+                .map(|ei| (ei.name.as_str(), ei.handlers.join(",")))
+                .collect::<Vec<_>>(),
+            vec![
+                ("did_mount", "handle_did_mount".to_string()),
+                ("will_render", "handle_will_render,log_frame".to_string()),
+            ]
+        );
+        assert!(serde_json::to_string(&example_info).is_ok());
+
+        assert!(manifest.get_component_info("crate::Missing").is_none());
+    }
+
+    #[test]
+    fn test_manifest_to_dot() {
+        let ctx = ParsingContext::default();
+        let (ctx, panel) = assemble_component_definition(
+            ctx,
+            "<Group>slot(0)</Group>",
+            "Panel",
+            false,
+            HashMap::from([(
+                "Group".to_string(),
+                "pax_std::primitives::Group".to_string(),
+            )]),
+            "parser",
+            "crate::Panel",
+        );
+        let (_, example) = assemble_component_definition(
+            ctx,
+            "<Panel><Group /></Panel> for i in 0..3 { if self.visible { <Group /> } }",
+            "Example",
+            true,
+            HashMap::from([
+                ("Panel".to_string(), "crate::Panel".to_string()),
+                (
+                    "Group".to_string(),
+                    "pax_std::primitives::Group".to_string(),
+                ),
+            ]),
+            "parser",
+            "crate::Example",
+        );
+        let manifest = PaxManifest {
+            components: HashMap::from([
+                (panel.type_id.clone(), panel),
+                (example.type_id.clone(), example),
+            ]),
+            main_component_type_id: "crate::Example".to_string(),
+            expression_specs: None,
+            type_table: Default::default(),
+            import_paths: Default::default(),
+        };
+
+        assert_eq!(
+            manifest.to_dot(),
+            r#"digraph pax_manifest {
+    node [shape=box];
+    subgraph "cluster_crate::Example" {
+        label="Example";
+        "crate::Example#0" [label="Example", style=bold];
+        "crate::Example#1" [label="Panel"];
+        "crate::Example#2" [label="Group"];
+        "crate::Example#3" [label="for 0..3", shape=diamond];
+        "crate::Example#4" [label="if self.visible", shape=diamond];
+        "crate::Example#5" [label="Group"];
+    }
+    "crate::Example#0" -> "crate::Example#1";
+    "crate::Example#0" -> "crate::Example#3";
+    "crate::Example#1" -> "crate::Example#2";
+    "crate::Example#1" -> "crate::Panel#0" [style=dashed];
+    "crate::Example#3" -> "crate::Example#4";
+    "crate::Example#4" -> "crate::Example#5";
+    subgraph "cluster_crate::Panel" {
+        label="Panel";
+        "crate::Panel#0" [label="Panel", style=bold];
+        "crate::Panel#1" [label="Group"];
+        "crate::Panel#2" [label="slot (0)", shape=diamond];
+    }
+    "crate::Panel#0" -> "crate::Panel#1";
+    "crate::Panel#1" -> "crate::Panel#2";
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_manifest_dump_types() {
+        let example = TypeDefinition {
+            type_id: "{PREFIX}Example".to_string(),
+            type_id_escaped: "{PREFIX}Example".to_string(),
+            import_path: "crate::Example".to_string(),
+            inner_iterable_type_id: None,
+            property_definitions: vec![
+                PropertyDefinition::primitive_with_name("usize", "total"),
+                PropertyDefinition::primitive_with_name("std::vec::Vec<usize>", "counts"),
+            ],
+            enum_variant_names: None,
+        };
+        let counts = TypeDefinition {
+            type_id: "std::vec::Vec<usize>".to_string(),
+            type_id_escaped: "std_COCOvec_COCOVec_LABRusize_RABR".to_string(),
+            import_path: "std::vec::Vec".to_string(),
+            inner_iterable_type_id: Some("usize".to_string()),
+            property_definitions: vec![],
+            enum_variant_names: None,
+        };
+        let manifest = PaxManifest {
+            components: HashMap::new(),
+            main_component_type_id: "{PREFIX}Example".to_string(),
+            expression_specs: None,
+            type_table: TypeTable::from([
+                (counts.type_id.clone(), counts),
+                (example.type_id.clone(), example),
+            ]),
+            import_paths: Default::default(),
+        };
+
+        assert_eq!(
+            manifest.dump_types(),
+            r#"std::vec::Vec<usize>
+    type_id_escaped: std_COCOvec_COCOVec_LABRusize_RABR
+    import_path: std::vec::Vec
+    inner_iterable_type_id: usize
+    properties: -
+{PREFIX}Example
+    type_id_escaped: {PREFIX}Example
+    import_path: crate::Example
+    inner_iterable_type_id: -
+    properties:
+        total: usize
+        counts: std::vec::Vec<usize>
+"#
+        );
+    }
+
+    #[test]
+    fn test_remove_unused_import_paths() {
+        let (ctx, example) = assemble_component_definition(
+            ParsingContext::default(),
+            "<Stacker direction=StackerDirection::Vertical />",
+            "Example",
+            true,
+            HashMap::from([(
+                "Stacker".to_string(),
+                "pax_std::stacker::Stacker".to_string(),
+            )]),
+            "parser",
+            "crate::Example",
+        );
+        let (ctx, _) = Vec::<String>::parse_to_manifest(ctx);
+        let mut manifest = PaxManifest {
+            components: HashMap::from([(example.type_id.clone(), example)]),
+            main_component_type_id: "crate::Example".to_string(),
+            expression_specs: None,
+            type_table: ctx.type_table,
+            import_paths: [
+                "crate::Example",
+                "crate::widgets::Stale",
+                "pax_lang::api::Size",
+                "pax_std::types::StackerDirection",
+                "std::vec::Vec",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        };
+
+        assert_eq!(
+            manifest.remove_unused_import_paths(),
+            vec!["crate::widgets::Stale".to_string()]
+        );
+        assert_eq!(
+            manifest.import_paths.iter().sorted().collect::<Vec<_>>(),
+            vec![
+                "crate::Example",
+                "pax_lang::api::Size",
+                "pax_std::types::StackerDirection",
+                "std::vec::Vec"
+            ]
+        );
+        assert!(manifest.get_unused_import_paths().is_empty());
+    }
+
+    #[test]
+    fn test_try_get_definitions_report_missing_type_id() {
+        let (_, example) = assemble_component_definition(
+            ParsingContext::default(),
+            "<Group />",
+            "Example",
+            true,
+            HashMap::from([(
+                "Group".to_string(),
+                "pax_std::primitives::Group".to_string(),
+            )]),
+            "parser",
+            "crate::Example",
+        );
+        let type_table = TypeTable::new();
+        let err = example
+            .try_get_property_definitions(&type_table)
+            .unwrap_err();
+        assert!(err.contains("`Example`") && err.contains("`crate::Example`"));
+
+        let pd = PropertyDefinition {
+            name: "label".to_string(),
+            type_id: "crate::Missing".to_string(),
+            ..Default::default()
+        };
+        let err = pd.try_get_type_definition(&type_table).unwrap_err();
+        assert!(err.contains("`label`") && err.contains("`crate::Missing`"));
+    }
+}
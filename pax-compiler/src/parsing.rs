@@ -5,9 +5,11 @@ use std::rc::Rc;
 use itertools::{Itertools, MultiPeek};
 use std::ops::RangeFrom;
 
+use crate::expressions::ExpressionFunctionRegistry;
 use crate::manifest::{
-    get_primitive_type_table, ComponentDefinition, ControlFlowRepeatPredicateDefinition,
-    ControlFlowRepeatSourceDefinition, ControlFlowSettingsDefinition, EventDefinition,
+    get_primitive_type_table, ComponentDefinition, ControlFlowConditionalArmDefinition,
+    ControlFlowRepeatPredicateDefinition, ControlFlowRepeatSourceDefinition,
+    ControlFlowSettingsDefinition, EventDefinition, ExpressionSpecInvocation,
     LiteralBlockDefinition, PropertyDefinition, SettingsSelectorBlockDefinition,
     TemplateNodeDefinition, TypeDefinition, TypeTable, ValueDefinition,
 };
@@ -25,52 +27,161 @@ pub struct PaxParser;
 
 /// Returns (RIL output string, `symbolic id`s found during parse)
 /// where a `symbolic id` may be something like `self.num_clicks` or `i`
+/// Folds a unary minus into a lowered number literal, with or without a unit, so that e.g. `-45` lowers
+/// to `Numeric::from(-45)` and `-10px` to `Size::Pixels((-10).into())` rather than negating at runtime.
+/// Returns `None` if `literal` is anything other than an unsigned number literal.
+fn negate_number_literal(literal: &str) -> Option<String> {
+    let is_unsigned_number =
+        |value: &str| !value.is_empty() && value.chars().all(|c| c.is_ascii_digit() || c == '.');
+    if let Some(value) = literal
+        .strip_prefix("Numeric::from(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        let value = value.trim();
+        return is_unsigned_number(value).then(|| format!("Numeric::from(-{})", value));
+    }
+    [
+        "Size::Pixels(",
+        "Size::Percent(",
+        "Rotation::Degrees(",
+        "Rotation::Radians(",
+    ]
+    .iter()
+    .find_map(|constructor| {
+        let value = literal
+            .strip_prefix(constructor)?
+            .strip_suffix(".into())")?
+            .trim();
+        is_unsigned_number(value).then(|| format!("{}(-{}).into())", constructor, value))
+    })
+}
+
 pub fn run_pratt_parser(input_paxel: &str) -> (String, Vec<String>) {
-    // Operator precedence is declared via the ordering here:
+    let (output, symbolic_ids, _) =
+        run_pratt_parser_with_functions(input_paxel, &ExpressionFunctionRegistry::default());
+    (output, symbolic_ids)
+}
+
+/// Same as `run_pratt_parser`, but lowers calls to the unqualified functions registered in `functions`, like
+/// `lerp(...)`, to calls to the host functions they name.  Also returns the name and argument count of each
+/// unqualified function call found during parse; calls to unregistered functions are left as they are.
+pub fn run_pratt_parser_with_functions(
+    input_paxel: &str,
+    functions: &ExpressionFunctionRegistry,
+) -> (String, Vec<String>, Vec<(String, usize)>) {
+    // Operator precedence is declared via the ordering here, from lowest to highest, matching Rust's
     let pratt = PrattParser::new()
         .op(Op::infix(Rule::xo_tern_then, Assoc::Left)
             | Op::infix(Rule::xo_tern_else, Assoc::Right))
-        .op(Op::infix(Rule::xo_bool_and, Assoc::Left) | Op::infix(Rule::xo_bool_or, Assoc::Left))
-        .op(Op::infix(Rule::xo_add, Assoc::Left) | Op::infix(Rule::xo_sub, Assoc::Left))
-        .op(Op::infix(Rule::xo_mul, Assoc::Left) | Op::infix(Rule::xo_div, Assoc::Left))
-        .op(Op::infix(Rule::xo_mod, Assoc::Left))
-        .op(Op::infix(Rule::xo_exp, Assoc::Right))
-        .op(Op::prefix(Rule::xo_neg))
+        .op(Op::infix(Rule::xo_bool_or, Assoc::Left))
+        .op(Op::infix(Rule::xo_bool_and, Assoc::Left))
         .op(Op::infix(Rule::xo_rel_eq, Assoc::Left)
             | Op::infix(Rule::xo_rel_neq, Assoc::Left)
             | Op::infix(Rule::xo_rel_lt, Assoc::Left)
             | Op::infix(Rule::xo_rel_lte, Assoc::Left)
             | Op::infix(Rule::xo_rel_gt, Assoc::Left)
             | Op::infix(Rule::xo_rel_gte, Assoc::Left))
-        .op(Op::prefix(Rule::xo_bool_not));
+        .op(Op::infix(Rule::xo_add, Assoc::Left) | Op::infix(Rule::xo_sub, Assoc::Left))
+        .op(Op::infix(Rule::xo_mul, Assoc::Left)
+            | Op::infix(Rule::xo_div, Assoc::Left)
+            | Op::infix(Rule::xo_mod, Assoc::Left))
+        //unlike Rust's binary operators, `^` (exponentiation) binds tighter than unary `-`, so `-x^2` is `-(x^2)`
+        .op(Op::prefix(Rule::xo_neg) | Op::prefix(Rule::xo_bool_not))
+        .op(Op::infix(Rule::xo_exp, Assoc::Right));
 
     let pairs = PaxParser::parse(Rule::expression_body, input_paxel)
         .expect(&format!("unsuccessful pratt parse {}", &input_paxel));
 
     let symbolic_ids = Rc::new(RefCell::new(vec![]));
-    let output = recurse_pratt_parse_to_string(pairs, &pratt, Rc::clone(&symbolic_ids));
-    (output, symbolic_ids.take())
+    let function_calls = Rc::new(RefCell::new(vec![]));
+    let output = recurse_pratt_parse_to_string(
+        pairs,
+        &pratt,
+        Rc::clone(&symbolic_ids),
+        functions,
+        Rc::clone(&function_calls),
+    );
+    (output, symbolic_ids.take(), function_calls.take())
+}
+
+/// Methods callable on symbols and function calls within expressions, like `self.items.len()`, as
+/// (method name, argument count, return type_id, RIL with `{receiver}` and `{args}` standing in for the
+/// RIL of the receiver and of the comma-separated arguments).
+/// Calls to any other method are rejected at compile time.  Numeric return types are wrapped in
+/// `Numeric`, so results compose with the rest of the expression, e.g. `self.items.len() * 10`.
+/// Note that numeric properties are already `Numeric`s in RIL, so numeric conversions are lowered
+/// to `Numeric`'s own accessors.
+pub static PAXEL_METHODS: &[(&str, usize, &str, &str)] = &[
+    ("len", 0, "usize", "{receiver}.len()"),
+    ("to_string", 0, "String", "{receiver}.to_string()"),
+    ("to_int", 0, "isize", "{receiver}.get_as_int()"),
+    ("to_float", 0, "f64", "{receiver}.get_as_float()"),
+    ("with_alpha", 1, "Color", "{receiver}.with_alpha({args})"),
+    ("lighten", 1, "Color", "{receiver}.lighten({args})"),
+    ("darken", 1, "Color", "{receiver}.darken({args})"),
+];
+
+/// Lowers a call to `method` with the RIL `args` on the RIL `receiver`, panicking if `method`
+/// is not in `PAXEL_METHODS` or is called with the wrong number of arguments
+fn lower_method_call_to_ril(receiver: &str, method: &str, args: &[String]) -> String {
+    let (_, arg_count, return_type_id, ril) = PAXEL_METHODS
+        .iter()
+        .find(|(name, _, _, _)| *name == method)
+        .unwrap_or_else(|| {
+            panic!(
+                "Method `{}` is not supported in expressions.  Supported methods: {}",
+                method,
+                PAXEL_METHODS.iter().map(|(name, _, _, _)| *name).join(", ")
+            )
+        });
+    if args.len() != *arg_count {
+        panic!(
+            "Method `{}` takes {} argument(s) but {} were supplied",
+            method,
+            arg_count,
+            args.len()
+        );
+    }
+    let args = args.iter().map(|arg| format!("({})", arg)).join(",");
+    let ril = ril.replace("{receiver}", receiver).replace("{args}", &args);
+    if ExpressionSpecInvocation::is_numeric(return_type_id) {
+        format!("Numeric::from({})", ril)
+    } else {
+        ril
+    }
 }
 
 /// Removes leading `self.` or `this.`, escapes remaining symbol to be a suitable atomic identifier
 fn convert_symbolic_binding_from_paxel_to_ril(xo_symbol: Pair<Rule>) -> String {
-    let mut pairs = xo_symbol.clone().into_inner();
-    let maybe_this_or_self = pairs.next().unwrap().as_str();
-
-    let self_or_this_removed = if maybe_this_or_self == "this" || maybe_this_or_self == "self" {
-        let mut output = "".to_string();
-
-        //accumulate remaining identifiers, having skipped `this` or `self` with the original `.next()`
-        pairs.for_each(|pair| output += &*(".".to_owned() + pair.as_str()));
-
-        //remove initial fencepost "."
-        output.replacen(".", "", 1)
-    } else {
-        //remove original binding; no self or this
-        xo_symbol.as_str().to_string()
-    };
+    escape_identifier(crate::expressions::clean_and_split_symbols(xo_symbol.as_str()).join("."))
+}
 
-    escape_identifier(self_or_this_removed)
+/// Records `xo_symbol` as a symbolic id, along with any symbols within its index expressions,
+/// like the `i` in `self.items[i]`; returns the RIL identifier for `xo_symbol`
+fn visit_xo_symbol(
+    xo_symbol: Pair<Rule>,
+    pratt_parser: &PrattParser<Rule>,
+    symbolic_ids: Rc<RefCell<Vec<String>>>,
+    functions: &ExpressionFunctionRegistry,
+    function_calls: Rc<RefCell<Vec<(String, usize)>>>,
+) -> String {
+    xo_symbol
+        .clone()
+        .into_inner()
+        .filter(|pair| pair.as_rule() == Rule::expression_body)
+        .for_each(|expression_body| {
+            recurse_pratt_parse_to_string(
+                expression_body.into_inner(),
+                pratt_parser,
+                Rc::clone(&symbolic_ids),
+                functions,
+                Rc::clone(&function_calls),
+            );
+        });
+    symbolic_ids
+        .borrow_mut()
+        .push(xo_symbol.as_str().to_string());
+    convert_symbolic_binding_from_paxel_to_ril(xo_symbol)
 }
 
 /// Workhorse method for compiling Expressions into Rust Intermediate Language (RIL, a string of Rust)
@@ -78,6 +189,8 @@ fn recurse_pratt_parse_to_string<'a>(
     expression: Pairs<Rule>,
     pratt_parser: &PrattParser<Rule>,
     symbolic_ids: Rc<RefCell<Vec<String>>>,
+    functions: &ExpressionFunctionRegistry,
+    function_calls: Rc<RefCell<Vec<(String, usize)>>>,
 ) -> String {
     pratt_parser
         .map_primary(move |primary| match primary.as_rule() {
@@ -86,7 +199,7 @@ fn recurse_pratt_parse_to_string<'a>(
                 /* expression_grouped = { "(" ~ expression_body ~ ")" ~ literal_number_unit? } */
                 let mut inner = primary.into_inner();
 
-                let exp_bod = recurse_pratt_parse_to_string(inner.next().unwrap().into_inner(), pratt_parser, Rc::clone(&symbolic_ids));
+                let exp_bod = recurse_pratt_parse_to_string(inner.next().unwrap().into_inner(), pratt_parser, Rc::clone(&symbolic_ids), functions, Rc::clone(&function_calls));
                 if let Some(literal_number_unit) = inner.next() {
                     let unit = literal_number_unit.as_str();
 
@@ -113,6 +226,7 @@ fn recurse_pratt_parse_to_string<'a>(
                 let mut pairs = primary.into_inner();
 
                 let mut output = "".to_string();
+                let mut is_qualified = false;
                 let mut next_pair = pairs.next().unwrap();
                 while let Rule::identifier = next_pair.as_rule() {
                     output = output + next_pair.as_str();
@@ -120,14 +234,25 @@ fn recurse_pratt_parse_to_string<'a>(
                     if let Rule::identifier = next_pair.as_rule() {
                         //look-ahead
                         output = output + "::";
+                        is_qualified = true;
                     }
                 };
 
-                let mut expression_body_pairs = next_pair.into_inner();
+                let args = next_pair.into_inner().map(|arg| {
+                    recurse_pratt_parse_to_string(arg.into_inner(), pratt_parser, Rc::clone(&symbolic_ids), functions, Rc::clone(&function_calls))
+                }).collect::<Vec<_>>();
+
+                //unqualified calls, like `lerp(...)`, may only call registered host functions
+                if !is_qualified {
+                    function_calls.borrow_mut().push((output.clone(), args.len()));
+                    if let Some(function) = functions.get(&output).filter(|function| function.arg_type_ids.len() == args.len()) {
+                        return function.lower_call_to_ril(&args);
+                    }
+                }
 
                 output = output + "(";
-                while let Some(next_pair) = expression_body_pairs.next() {
-                    output = output + "(" + &recurse_pratt_parse_to_string(next_pair.into_inner(), pratt_parser, Rc::clone(&symbolic_ids)) + "),"
+                for arg in args.iter() {
+                    output = output + "(" + arg + "),"
                 }
                 output = output + ")";
 
@@ -178,6 +303,12 @@ fn recurse_pratt_parse_to_string<'a>(
 
                         let value = inner.next().unwrap().as_str();
                         let unit = inner.next().unwrap().as_str();
+                        //parenthesize signed values, as `-10.into()` would parse as `-(10.into())`
+                        let value = if value.starts_with('-') {
+                            format!("({})", value)
+                        } else {
+                            value.to_string()
+                        };
 
                         if unit == "px" {
                             format!("Size::Pixels({}.into())", value)
@@ -192,9 +323,10 @@ fn recurse_pratt_parse_to_string<'a>(
                         }
                     },
                     Rule::literal_number => {
+                        let sign = if literal_kind.as_str().starts_with('-') { "-" } else { "" };
                         let mut inner = literal_kind.into_inner();
                         let value = inner.next().unwrap().as_str();
-                        format!("Numeric::from({})", value)
+                        format!("Numeric::from({}{})", sign, value)
                     },
                     Rule::string => {
                         //TODO: figure out string concatenation.  Might need to introduce another operator?  Or perhaps a higher-level string type, which supports addition-as-concatenation — like we do with Numeric
@@ -215,12 +347,12 @@ fn recurse_pratt_parse_to_string<'a>(
 
                 //for parsing xo_object_settings_key_value_pair
                 //iterate over key-value pairs; recurse into expressions
-                fn handle_xoskvp<'a>(xoskvp: Pair<Rule>, pratt_parser: &PrattParser<Rule>, symbolic_ids: Rc<RefCell<Vec<String>>>) -> String {
+                fn handle_xoskvp(xoskvp: Pair<Rule>, pratt_parser: &PrattParser<Rule>, symbolic_ids: Rc<RefCell<Vec<String>>>, functions: &ExpressionFunctionRegistry, function_calls: Rc<RefCell<Vec<(String, usize)>>>) -> String {
                     let mut inner_kvp = xoskvp.into_inner();
                     let settings_key = inner_kvp.next().unwrap().as_str().to_string();
                     let expression_body = inner_kvp.next().unwrap().into_inner();
 
-                    let ril = recurse_pratt_parse_to_string(expression_body, pratt_parser, Rc::clone(&symbolic_ids));
+                    let ril = recurse_pratt_parse_to_string(expression_body, pratt_parser, Rc::clone(&symbolic_ids), functions, Rc::clone(&function_calls));
                     format!("{}: {},\n",settings_key, ril)
                 }
 
@@ -230,29 +362,46 @@ fn recurse_pratt_parse_to_string<'a>(
                 } else {
                     //no explicit type declaration, like `{...}`
                     // -- this token is the first k/v pair of object declaration; handle as such
-                    let ril = handle_xoskvp(maybe_identifier, pratt_parser.clone(), Rc::clone(&symbolic_ids));
+                    let ril = handle_xoskvp(maybe_identifier, pratt_parser, Rc::clone(&symbolic_ids), functions, Rc::clone(&function_calls));
                     output += &ril;
                 }
 
                 let mut remaining_kvps = inner.into_iter();
 
                 while let Some(xoskkvp) = remaining_kvps.next() {
-                    let ril =  handle_xoskvp(xoskkvp, pratt_parser.clone(), Rc::clone(&symbolic_ids));
+                    let ril =  handle_xoskvp(xoskkvp, pratt_parser, Rc::clone(&symbolic_ids), functions, Rc::clone(&function_calls));
                     output += &ril;
                 }
 
                 output
             },
             Rule::xo_symbol => {
-                symbolic_ids.borrow_mut().push(primary.as_str().to_string());
-                format!("{}",convert_symbolic_binding_from_paxel_to_ril(primary))
+                visit_xo_symbol(primary, pratt_parser, Rc::clone(&symbolic_ids), functions, Rc::clone(&function_calls))
+            },
+            Rule::xo_method_call => {
+                /* xo_method_call = { xo_method_call_receiver ~ xo_method_call_suffix+ }
+                   xo_method_call_receiver = { xo_function_call | xo_symbol }
+                   xo_method_call_suffix = { "." ~ identifier ~ "(" ~ xo_function_args_list ~ ")" } */
+                let mut pairs = primary.into_inner();
+
+                //the receiver is lowered like any other symbol or function call
+                let receiver = recurse_pratt_parse_to_string(pairs.next().unwrap().into_inner(), pratt_parser, Rc::clone(&symbolic_ids), functions, Rc::clone(&function_calls));
+
+                pairs.fold(receiver, |receiver, suffix| {
+                    let mut suffix = suffix.into_inner();
+                    let method = suffix.next().unwrap().as_str();
+                    let args = suffix.next().unwrap().into_inner().map(|arg| {
+                        recurse_pratt_parse_to_string(arg.into_inner(), pratt_parser, Rc::clone(&symbolic_ids), functions, Rc::clone(&function_calls))
+                    }).collect::<Vec<_>>();
+                    lower_method_call_to_ril(&format!("({})", receiver), method, &args)
+                })
             },
             Rule::xo_tuple => {
                 let mut tuple = primary.into_inner();
                 let exp0 = tuple.next().unwrap();
                 let exp1 = tuple.next().unwrap();
-                let exp0 = recurse_pratt_parse_to_string( exp0.into_inner(), pratt_parser, Rc::clone(&symbolic_ids));
-                let exp1 = recurse_pratt_parse_to_string( exp1.into_inner(), pratt_parser, Rc::clone(&symbolic_ids));
+                let exp0 = recurse_pratt_parse_to_string( exp0.into_inner(), pratt_parser, Rc::clone(&symbolic_ids), functions, Rc::clone(&function_calls));
+                let exp1 = recurse_pratt_parse_to_string( exp1.into_inner(), pratt_parser, Rc::clone(&symbolic_ids), functions, Rc::clone(&function_calls));
                 format!("({},{})", exp0, exp1)
             },
             Rule::xo_list => {
@@ -260,18 +409,18 @@ fn recurse_pratt_parse_to_string<'a>(
                 let mut vec = Vec::new();
 
                 while let Some(item) = list.next() {
-                    let item_str = recurse_pratt_parse_to_string(item.into_inner(), pratt_parser, Rc::clone(&symbolic_ids));
+                    let item_str = recurse_pratt_parse_to_string(item.into_inner(), pratt_parser, Rc::clone(&symbolic_ids), functions, Rc::clone(&function_calls));
                     vec.push(item_str);
                 }
                 format!("vec![{}]", vec.join(","))
             },
             Rule::expression_body => {
-                recurse_pratt_parse_to_string(primary.into_inner(), pratt_parser.clone(), Rc::clone(&symbolic_ids))
+                recurse_pratt_parse_to_string(primary.into_inner(), pratt_parser, Rc::clone(&symbolic_ids), functions, Rc::clone(&function_calls))
             },
             _ => unreachable!("{}",primary.as_str()),
         })
         .map_prefix(|op, rhs| match op.as_rule() {
-            Rule::xo_neg => format!("(-{})", rhs),
+            Rule::xo_neg => negate_number_literal(&rhs).unwrap_or_else(|| format!("(-{})", rhs)),
             Rule::xo_bool_not => format!("(!{})", rhs),
             _ => unreachable!(),
         })
@@ -337,6 +486,8 @@ fn parse_template_from_component_definition_string(ctx: &mut TemplateNodeParseCo
             type_id: "IMPLICIT_ROOT".to_string(),
             control_flow_settings: None,
             settings: None,
+            element_id: None,
+            classes: vec![],
             pascal_identifier: "<UNREACHABLE>".to_string(),
         },
     );
@@ -382,6 +533,8 @@ fn recurse_visit_tag_pairs_for_template(
                 .unwrap()
                 .into_inner();
             let pascal_identifier = open_tag.next().unwrap().as_str();
+            let (element_id, classes) =
+                parse_selector_attributes_from_final_pairs_of_tag(open_tag.clone());
 
             //push the empty frame for this node's children
             ctx.child_id_tracking_stack.push(vec![]);
@@ -408,7 +561,9 @@ fn recurse_visit_tag_pairs_for_template(
                     .get(pascal_identifier.clone())
                     .expect(&format!("Template key not found {}", &pascal_identifier))
                     .to_string(),
-                settings: parse_inline_attribute_from_final_pairs_of_tag(open_tag),
+                settings: parse_inline_attribute_from_final_pairs_of_tag(open_tag.clone()),
+                element_id,
+                classes,
                 child_ids: ctx.child_id_tracking_stack.pop().unwrap(),
                 pascal_identifier: pascal_identifier.to_string(),
             };
@@ -420,6 +575,8 @@ fn recurse_visit_tag_pairs_for_template(
         Rule::self_closing_tag => {
             let mut tag_pairs = any_tag_pair.into_inner();
             let pascal_identifier = tag_pairs.next().unwrap().as_str();
+            let (element_id, classes) =
+                parse_selector_attributes_from_final_pairs_of_tag(tag_pairs.clone());
 
             let mut template_node = TemplateNodeDefinition {
                 id: new_id,
@@ -430,6 +587,8 @@ fn recurse_visit_tag_pairs_for_template(
                     .expect(&format!("Template key not found {}", &pascal_identifier))
                     .to_string(),
                 settings: parse_inline_attribute_from_final_pairs_of_tag(tag_pairs),
+                element_id,
+                classes,
                 child_ids: vec![],
                 pascal_identifier: pascal_identifier.to_string(),
             };
@@ -457,12 +616,58 @@ fn recurse_visit_tag_pairs_for_template(
                         })
                     }
 
+                    //the leading `if` is the first arm; any `else if`s follow in order
+                    let mut conditional_arms = vec![ControlFlowConditionalArmDefinition {
+                        condition_expression_paxel: expression_body,
+                        condition_expression_vtable_id: None, //This will be written back to this data structure later, during expression compilation
+                        child_ids: ctx.child_id_tracking_stack.pop().unwrap(),
+                    }];
+                    let mut else_child_ids = None;
+
+                    //each subsequent branch tracks its children in a separate frame
+                    for branch in statement_if {
+                        let branch_rule = branch.as_rule();
+                        let mut branch = branch.into_inner();
+                        let condition_expression_paxel = if branch_rule == Rule::statement_else_if {
+                            Some(branch.next().unwrap().as_str().to_string())
+                        } else {
+                            None
+                        };
+
+                        ctx.child_id_tracking_stack.push(vec![]);
+                        if let Some(inner_nodes) = branch.next() {
+                            inner_nodes.into_inner().for_each(|sub_tag_pair| {
+                                recurse_visit_tag_pairs_for_template(ctx, sub_tag_pair);
+                            })
+                        }
+                        let child_ids = ctx.child_id_tracking_stack.pop().unwrap();
+
+                        match condition_expression_paxel {
+                            Some(condition_expression_paxel) => {
+                                conditional_arms.push(ControlFlowConditionalArmDefinition {
+                                    condition_expression_paxel,
+                                    condition_expression_vtable_id: None,
+                                    child_ids,
+                                })
+                            }
+                            None => else_child_ids = Some(child_ids),
+                        }
+                    }
+
+                    //the Conditional's own `child_ids` span every branch
+                    let child_ids = conditional_arms
+                        .iter()
+                        .flat_map(|arm| arm.child_ids.iter())
+                        .chain(else_child_ids.iter().flatten())
+                        .cloned()
+                        .collect();
+
                     //`if` TemplateNodeDefinition
                     TemplateNodeDefinition {
                         id: new_id.clone(),
                         control_flow_settings: Some(ControlFlowSettingsDefinition {
-                            condition_expression_paxel: Some(expression_body),
-                            condition_expression_vtable_id: None, //This will be written back to this data structure later, during expression compilation
+                            conditional_arms: Some(conditional_arms),
+                            else_child_ids,
                             slot_index_expression_paxel: None,
                             slot_index_expression_vtable_id: None,
                             repeat_predicate_definition: None,
@@ -470,7 +675,9 @@ fn recurse_visit_tag_pairs_for_template(
                         }),
                         type_id: TYPE_ID_IF.to_string(),
                         settings: None,
-                        child_ids: ctx.child_id_tracking_stack.pop().unwrap(),
+                        element_id: None,
+                        classes: vec![],
+                        child_ids,
                         pascal_identifier: "Conditional".to_string(),
                     }
                 }
@@ -480,9 +687,38 @@ fn recurse_visit_tag_pairs_for_template(
                     let mut predicate_declaration = for_statement.next().unwrap().into_inner();
                     let source = for_statement.next().unwrap();
 
-                    let prospective_inner_nodes = for_statement.next();
+                    let mut prospective_inner_nodes = for_statement.next();
+                    let key_expression_paxel = match &prospective_inner_nodes {
+                        Some(key) if key.as_rule() == Rule::statement_for_key => {
+                            let key_expression_paxel = key
+                                .clone()
+                                .into_inner()
+                                .next()
+                                .unwrap()
+                                .as_str()
+                                .to_string();
+                            prospective_inner_nodes = for_statement.next();
+                            Some(key_expression_paxel)
+                        }
+                        _ => None,
+                    };
 
-                    if predicate_declaration.clone().count() > 1 {
+                    if predicate_declaration.peek().unwrap().as_rule()
+                        == Rule::statement_for_predicate_destructuring
+                    {
+                        //destructured tuple, like the `(name, count), i` in `for ((name, count), i) in self.some_list`
+                        let elem_ids = predicate_declaration
+                            .next()
+                            .unwrap()
+                            .into_inner()
+                            .map(|elem_id| elem_id.as_str().to_string())
+                            .collect();
+                        cfavd.repeat_predicate_definition =
+                            Some(ControlFlowRepeatPredicateDefinition::ElemTupleIndexId(
+                                elem_ids,
+                                predicate_declaration.next().unwrap().as_str().to_string(),
+                            ));
+                    } else if predicate_declaration.clone().count() > 1 {
                         //tuple, like the `elem, i` in `for (elem, i) in self.some_list`
                         cfavd.repeat_predicate_definition =
                             Some(ControlFlowRepeatPredicateDefinition::ElemIdIndexId(
@@ -505,6 +741,8 @@ fn recurse_visit_tag_pairs_for_template(
                                 range_expression_paxel: Some(inner_source.as_str().to_string()),
                                 vtable_id: None, //This will be written back to this data structure later, during expression compilation
                                 symbolic_binding: None,
+                                key_expression_paxel,
+                                key_vtable_id: None,
                             }
                         }
                         Rule::xo_symbol => ControlFlowRepeatSourceDefinition {
@@ -513,6 +751,8 @@ fn recurse_visit_tag_pairs_for_template(
                             symbolic_binding: Some(convert_symbolic_binding_from_paxel_to_ril(
                                 inner_source,
                             )),
+                            key_expression_paxel,
+                            key_vtable_id: None,
                         },
                         _ => {
                             unreachable!()
@@ -533,6 +773,8 @@ fn recurse_visit_tag_pairs_for_template(
                         type_id: TYPE_ID_REPEAT.to_string(),
                         control_flow_settings: Some(cfavd),
                         settings: None,
+                        element_id: None,
+                        classes: vec![],
                         child_ids: ctx.child_id_tracking_stack.pop().unwrap(),
                         pascal_identifier: "Repeat".to_string(),
                     }
@@ -551,8 +793,8 @@ fn recurse_visit_tag_pairs_for_template(
                     TemplateNodeDefinition {
                         id: *&new_id.clone(),
                         control_flow_settings: Some(ControlFlowSettingsDefinition {
-                            condition_expression_paxel: None,
-                            condition_expression_vtable_id: None,
+                            conditional_arms: None,
+                            else_child_ids: None,
                             slot_index_expression_paxel: Some(expression_body),
                             slot_index_expression_vtable_id: None, //This will be written back to this data structure later, during expression compilation
                             repeat_predicate_definition: None,
@@ -560,6 +802,8 @@ fn recurse_visit_tag_pairs_for_template(
                         }),
                         type_id: TYPE_ID_SLOT.to_string(),
                         settings: None,
+                        element_id: None,
+                        classes: vec![],
                         child_ids: ctx.child_id_tracking_stack.pop().unwrap(),
                         pascal_identifier: "Slot".to_string(),
                     }
@@ -584,10 +828,61 @@ fn recurse_visit_tag_pairs_for_template(
     }
 }
 
+/// Parses the `id` and `class` attributes of a tag, which target `@settings` selectors
+/// rather than properties.  Returns the element id (if any) and the space-separated classes, in order.
+pub(crate) fn parse_selector_attributes_from_final_pairs_of_tag(
+    final_pairs_of_tag: Pairs<Rule>,
+) -> (Option<String>, Vec<String>) {
+    let mut element_id = None;
+    let mut classes = vec![];
+    final_pairs_of_tag
+        .filter(|attribute_key_value_pair| {
+            attribute_key_value_pair
+                .clone()
+                .into_inner()
+                .next()
+                .unwrap()
+                .as_rule()
+                == Rule::identifier
+        })
+        .for_each(|attribute_key_value_pair| {
+            let mut kv = attribute_key_value_pair.into_inner();
+            let key = kv.next().unwrap().as_str();
+            let raw_value = kv
+                .next()
+                .unwrap()
+                .as_str()
+                .trim_matches(|c| c == '"' || c == '\'' || c == '`');
+            match key {
+                "id" => {
+                    if element_id.is_some() {
+                        panic!("Specified more than one id inline!");
+                    }
+                    element_id = Some(raw_value.to_string());
+                }
+                "class" => {
+                    classes.extend(raw_value.split_whitespace().map(|class| class.to_string()));
+                }
+                _ => {}
+            }
+        });
+    (element_id, classes)
+}
+
 fn parse_inline_attribute_from_final_pairs_of_tag(
     final_pairs_of_tag: Pairs<Rule>,
 ) -> Option<Vec<(String, ValueDefinition)>> {
     let vec: Vec<(String, ValueDefinition)> = final_pairs_of_tag
+        .filter(|attribute_key_value_pair| {
+            //`id` and `class` are parsed separately, by `parse_selector_attributes_from_final_pairs_of_tag`
+            let key = attribute_key_value_pair
+                .clone()
+                .into_inner()
+                .next()
+                .unwrap();
+            !(key.as_rule() == Rule::identifier
+                && (key.as_str() == "id" || key.as_str() == "class"))
+        })
         .map(|attribute_key_value_pair| {
             match attribute_key_value_pair
                 .clone()
@@ -637,6 +932,12 @@ fn parse_inline_attribute_from_final_pairs_of_tag(
                         Rule::literal_object => ValueDefinition::Block(
                             derive_value_definition_from_literal_object_pair(raw_value),
                         ),
+                        Rule::literal_list => ValueDefinition::LiteralList(
+                            derive_value_definitions_from_literal_list_pair(raw_value),
+                        ),
+                        Rule::literal_enum_variant => {
+                            ValueDefinition::LiteralEnumVariant(raw_value.as_str().to_string())
+                        }
                         Rule::expression_body => {
                             ValueDefinition::Expression(raw_value.as_str().to_string(), None)
                         }
@@ -697,6 +998,12 @@ fn derive_value_definition_from_literal_object_pair(
                             derive_value_definition_from_literal_object_pair(raw_value),
                         )
                     }
+                    Rule::literal_list => ValueDefinition::LiteralList(
+                        derive_value_definitions_from_literal_list_pair(raw_value),
+                    ),
+                    Rule::literal_enum_variant => {
+                        ValueDefinition::LiteralEnumVariant(raw_value.as_str().to_string())
+                    }
                     // Rule::literal_enum_value => {ValueDefinition::Enum(raw_value.as_str().to_string())},
                     Rule::expression_body => {
                         ValueDefinition::Expression(raw_value.as_str().to_string(), None)
@@ -712,6 +1019,33 @@ fn derive_value_definition_from_literal_object_pair(
     }
 }
 
+fn derive_value_definitions_from_literal_list_pair(
+    literal_list: Pair<Rule>,
+) -> Vec<ValueDefinition> {
+    literal_list
+        .into_inner()
+        .map(|element| match element.as_rule() {
+            Rule::literal_value => {
+                //we want to pratt-parse literals, mostly to unpack `px` and `%` (recursively)
+                let (output_string, _) = crate::parsing::run_pratt_parser(element.as_str());
+                ValueDefinition::LiteralValue(output_string)
+            }
+            Rule::literal_object => {
+                ValueDefinition::Block(derive_value_definition_from_literal_object_pair(element))
+            }
+            Rule::literal_list => ValueDefinition::LiteralList(
+                derive_value_definitions_from_literal_list_pair(element),
+            ),
+            Rule::literal_enum_variant => {
+                ValueDefinition::LiteralEnumVariant(element.as_str().to_string())
+            }
+            _ => {
+                unreachable!("Parsing error 830164532: {:?}", element.as_rule());
+            }
+        })
+        .collect()
+}
+
 fn parse_settings_from_component_definition_string(
     pax: &str,
 ) -> Option<Vec<SettingsSelectorBlockDefinition>> {
@@ -973,6 +1307,7 @@ pub fn assemble_type_definition(
         type_id_escaped,
         inner_iterable_type_id,
         property_definitions,
+        enum_variant_names: None,
         import_path,
     };
 
@@ -982,21 +1317,98 @@ pub fn assemble_type_definition(
     (ctx, new_def)
 }
 
+/// Escapes a type path or symbol into a valid Rust identifier, e.g. for `PropertiesCoproduct` / `TypesCoproduct` variants.
+/// Every character outside of `[A-Za-z0-9]` is mapped to `_` followed by a token, e.g. `::` to `_COCO`;
+/// any character without a dedicated token (including non-ASCII unicode) is escaped by its code point as `_UNICxxxxxx`.
+/// Since `_` only ever starts a token, and a literal `_` is escaped as `__`, distinct inputs yield distinct outputs.
 pub fn escape_identifier(input: String) -> String {
-    input
-        .replace("(", "LPAR")
-        .replace("::", "COCO")
-        .replace(")", "RPAR")
-        .replace("<", "LABR")
-        .replace(">", "RABR")
-        .replace(",", "COMM")
-        .replace(".", "PERI")
-        .replace("[", "LSQB")
-        .replace("]", "RSQB")
-        .replace("/", "FSLA")
-        .replace("\\", "BSLA")
-        .replace("#", "HASH")
-        .replace("-", "HYPH")
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            ':' if chars.peek() == Some(&':') => {
+                chars.next();
+                "COCO"
+            }
+            ':' => "COLO",
+            '(' => "LPAR",
+            ')' => "RPAR",
+            '<' => "LABR",
+            '>' => "RABR",
+            ',' => "COMM",
+            '.' => "PERI",
+            '[' => "LSQB",
+            ']' => "RSQB",
+            '/' => "FSLA",
+            '\\' => "BSLA",
+            '#' => "HASH",
+            '-' => "HYPH",
+            ' ' => "SPAC",
+            '&' => "AMPE",
+            '\'' => "APOS",
+            '*' => "ASTE",
+            ';' => "SEMI",
+            '!' => "EXCL",
+            '=' => "EQUA",
+            '+' => "PLUS",
+            '$' => "DOLL",
+            '_' => "_",
+            c if c.is_ascii_alphanumeric() => {
+                output.push(c);
+                continue;
+            }
+            c => {
+                output.push_str(&format!("_UNIC{:06X}", c as u32));
+                continue;
+            }
+        };
+        output.push('_');
+        output.push_str(token);
+    }
+    output
+}
+
+/// Prepends the `{PREFIX}` placeholder (resolved to the host crate's `pax_reexports` prefix after parsing)
+/// to a type id nested inside another type id, like the `T` in `Vec<T>`.
+/// Tuples aren't addressable through `pax_reexports`; their ids already prefix each member instead.
+fn prefix_nested_type_id(type_id: &str) -> String {
+    if type_id.starts_with('(') {
+        type_id.to_string()
+    } else {
+        format!("{}{}", "{PREFIX}", type_id)
+    }
+}
+
+/// Registers a tuple's TypeDefinition, whose members are addressable by position
+/// (as `PropertyDefinition`s named `0`, `1`, ...), e.g. when destructured by Repeat
+fn parse_tuple_to_manifest(
+    mut ctx: ParsingContext,
+    type_id: String,
+    member_type_ids: Vec<String>,
+) -> (ParsingContext, Vec<PropertyDefinition>) {
+    let property_definitions: Vec<PropertyDefinition> = member_type_ids
+        .into_iter()
+        .enumerate()
+        .map(|(i, member_type_id)| PropertyDefinition {
+            name: i.to_string(),
+            flags: Default::default(),
+            type_id: member_type_id,
+            default_value: None,
+        })
+        .collect();
+
+    ctx.type_table
+        .entry(type_id.clone())
+        .or_insert_with(|| TypeDefinition {
+            type_id_escaped: escape_identifier(type_id.clone()),
+            import_path: type_id.clone(),
+            type_id,
+            inner_iterable_type_id: None,
+            property_definitions: property_definitions.clone(),
+            enum_variant_names: None,
+        });
+
+    (ctx, property_definitions)
 }
 
 /// This trait is used only to extend primitives like u64
@@ -1012,6 +1424,7 @@ pub trait Reflectable {
             type_id_escaped: escape_identifier(type_id.to_string()),
             inner_iterable_type_id: None,
             property_definitions: vec![],
+            enum_variant_names: None,
             import_path: type_id.to_string(),
         };
 
@@ -1137,6 +1550,7 @@ impl<T: Reflectable> Reflectable for std::option::Option<T> {
             type_id_escaped: escape_identifier(type_id.to_string()),
             inner_iterable_type_id: None,
             property_definitions: vec![],
+            enum_variant_names: None,
             import_path: type_id.to_string(),
         };
 
@@ -1155,7 +1569,10 @@ impl<T: Reflectable> Reflectable for std::option::Option<T> {
     }
 
     fn get_type_id() -> String {
-        format!("std::option::Option<{}{}>", "{PREFIX}", &T::get_type_id())
+        format!(
+            "std::option::Option<{}>",
+            prefix_nested_type_id(&T::get_type_id())
+        )
     }
 }
 
@@ -1199,6 +1616,58 @@ impl Reflectable for kurbo::Point {
     }
 }
 
+impl<A: Reflectable, B: Reflectable> Reflectable for (A, B) {
+    fn parse_to_manifest(ctx: ParsingContext) -> (ParsingContext, Vec<PropertyDefinition>) {
+        let (ctx, _) = A::parse_to_manifest(ctx);
+        let (ctx, _) = B::parse_to_manifest(ctx);
+        parse_tuple_to_manifest(
+            ctx,
+            Self::get_type_id(),
+            vec![A::get_type_id(), B::get_type_id()],
+        )
+    }
+    fn get_import_path() -> String {
+        Self::get_type_id()
+    }
+    fn get_self_pascal_identifier() -> String {
+        "Tuple".to_string()
+    }
+    fn get_type_id() -> String {
+        format!(
+            "({},{})",
+            prefix_nested_type_id(&A::get_type_id()),
+            prefix_nested_type_id(&B::get_type_id())
+        )
+    }
+}
+
+impl<A: Reflectable, B: Reflectable, C: Reflectable> Reflectable for (A, B, C) {
+    fn parse_to_manifest(ctx: ParsingContext) -> (ParsingContext, Vec<PropertyDefinition>) {
+        let (ctx, _) = A::parse_to_manifest(ctx);
+        let (ctx, _) = B::parse_to_manifest(ctx);
+        let (ctx, _) = C::parse_to_manifest(ctx);
+        parse_tuple_to_manifest(
+            ctx,
+            Self::get_type_id(),
+            vec![A::get_type_id(), B::get_type_id(), C::get_type_id()],
+        )
+    }
+    fn get_import_path() -> String {
+        Self::get_type_id()
+    }
+    fn get_self_pascal_identifier() -> String {
+        "Tuple".to_string()
+    }
+    fn get_type_id() -> String {
+        format!(
+            "({},{},{})",
+            prefix_nested_type_id(&A::get_type_id()),
+            prefix_nested_type_id(&B::get_type_id()),
+            prefix_nested_type_id(&C::get_type_id())
+        )
+    }
+}
+
 impl<T: Reflectable> Reflectable for std::vec::Vec<T> {
     fn parse_to_manifest(mut ctx: ParsingContext) -> (ParsingContext, Vec<PropertyDefinition>) {
         let type_id = Self::get_type_id();
@@ -1208,6 +1677,7 @@ impl<T: Reflectable> Reflectable for std::vec::Vec<T> {
             import_path: Self::get_import_path(),
             inner_iterable_type_id: Self::get_iterable_type_id(),
             property_definitions: vec![],
+            enum_variant_names: None,
         };
 
         if !ctx.type_table.contains_key(&type_id) {
@@ -1226,12 +1696,348 @@ impl<T: Reflectable> Reflectable for std::vec::Vec<T> {
     fn get_type_id() -> String {
         //Need to encode generics contents as part of unique id for iterables
         format!(
-            "std::vec::Vec<{}{}>",
-            "{PREFIX}",
-            &Self::get_iterable_type_id().unwrap()
+            "std::vec::Vec<{}>",
+            prefix_nested_type_id(&Self::get_iterable_type_id().unwrap())
         )
     }
     fn get_iterable_type_id() -> Option<String> {
         Some(T::get_type_id())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        assemble_component_definition, escape_identifier, run_pratt_parser, ParsingContext,
+        PaxParser, Rule,
+    };
+    use crate::manifest::ValueDefinition;
+    use pest::Parser;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_escape_identifier_is_collision_resistant() {
+        let inputs = vec![
+            "Vec<(A, B)>",
+            "Vec<A, B>",
+            "Vec<A,B>",
+            "Vec<(A,B)>",
+            "&'static str",
+            "&static str",
+            "std::vec::Vec<A>",
+            "std:vec:Vec<A>",
+            "Größe",
+            "Grsse",
+            "*const u8",
+            "[u8; 4]",
+            "a::B",
+            "aCOCOB",
+            "a_COCOB",
+            "Aä",
+            "AUNIC0000E4",
+            "A_UNIC0000E4",
+            "CO:",
+            "::LO",
+            "_",
+            "__",
+        ];
+
+        let escaped: Vec<String> = inputs
+            .iter()
+            .map(|input| escape_identifier(input.to_string()))
+            .collect();
+
+        for (i, output) in escaped.iter().enumerate() {
+            assert!(
+                output
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_'),
+                "`{}` escaped to invalid identifier `{}`",
+                inputs[i],
+                output
+            );
+            for (j, other) in escaped.iter().enumerate() {
+                if i != j {
+                    assert_ne!(
+                        output, other,
+                        "`{}` collides with `{}`",
+                        inputs[i], inputs[j]
+                    );
+                }
+            }
+        }
+
+        //must match the escapes hardcoded in `TYPES_COPRODUCT_BUILT_INS` and pax-core
+        assert_eq!(
+            escape_identifier("std::ops::Range<isize>".to_string()),
+            "std_COCOops_COCORange_LABRisize_RABR"
+        );
+    }
+
+    #[test]
+    fn test_pratt_parse_boolean_operators() {
+        let (output, symbolic_ids) = run_pratt_parser("self.count > 3 && self.enabled");
+        assert_eq!(output, "((count>Numeric::from(3 ))&&enabled)");
+        assert_eq!(
+            symbolic_ids
+                .iter()
+                .map(|symbolic_id| symbolic_id.trim())
+                .collect::<Vec<_>>(),
+            vec!["self.count", "self.enabled"]
+        );
+
+        //precedence matches Rust's: `||` < `&&` < comparisons < arithmetic < unary
+        for (paxel, ril) in [
+            ("a >= b", "(a>=b)"),
+            ("a <= b", "(a<=b)"),
+            ("a + 1 > b", "((a+Numeric::from(1 ))>b)"),
+            ("a || b && c", "(a||(b&&c))"),
+            ("!a && b == c", "((!a)&&(b==c))"),
+            ("a != b || !(c < d)", "((a!=b)||(!(c<d)))"),
+            ("-a ^ 2", "(-((a).pow(Numeric::from(2))))"),
+        ] {
+            assert_eq!(run_pratt_parser(paxel).0, ril, "{}", paxel);
+        }
+    }
+
+    #[test]
+    fn test_pratt_parse_exponent_binds_tighter_than_unary_minus() {
+        //`-2^2` is `-(2^2)`, i.e. -4, while `(-2)^2` is 4
+        assert_eq!(
+            run_pratt_parser("-2^2").0,
+            "(-((Numeric::from(2)).pow(Numeric::from(2))))"
+        );
+        assert_eq!(
+            run_pratt_parser("(-2)^2").0,
+            "((Numeric::from(-2)).pow(Numeric::from(2)))"
+        );
+    }
+
+    #[test]
+    fn test_pratt_parse_signed_number_literals() {
+        for (paxel, ril) in [
+            ("-45", "Numeric::from(-45)"),
+            ("-4.5", "Numeric::from(-4.5)"),
+            ("-10px", "Size::Pixels((-10).into())"),
+            ("-2.5%", "Size::Percent((-2.5).into())"),
+            ("-45deg", "Rotation::Degrees((-45).into())"),
+            ("-(1 + 2)", "(-(Numeric::from(1 )+Numeric::from(2)))"),
+            ("-2 ^ 2", "(-((Numeric::from(2 )).pow(Numeric::from(2))))"),
+        ] {
+            assert_eq!(run_pratt_parser(paxel).0, ril, "{}", paxel);
+        }
+
+        let (_, example) = assemble_component_definition(
+            ParsingContext::default(),
+            "<Group x=-10px y=-25% rotate=-45 />",
+            "Example",
+            true,
+            HashMap::from([(
+                "Group".to_string(),
+                "pax_std::primitives::Group".to_string(),
+            )]),
+            "parser",
+            "crate::Example",
+        );
+        let settings = example.template.unwrap()[1].settings.clone().unwrap();
+        let literals: Vec<_> = settings
+            .iter()
+            .map(|(key, vd)| match vd {
+                ValueDefinition::LiteralValue(lv) => (key.as_str(), lv.as_str()),
+                _ => panic!("Expected a literal for `{}`", key),
+            })
+            .collect();
+        assert_eq!(
+            literals,
+            vec![
+                ("x", "Size::Pixels((-10).into())"),
+                ("y", "Size::Percent((-25).into())"),
+                ("rotate", "Numeric::from(-45)"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pratt_parse_method_calls() {
+        let (output, symbolic_ids) = run_pratt_parser("self.items.len() * 10");
+        assert_eq!(
+            output,
+            "(Numeric::from((items).len())*(Numeric::from(10)).into())"
+        );
+        assert_eq!(symbolic_ids, vec!["self.items"]);
+
+        let (output, symbolic_ids) = run_pratt_parser("self.name.to_string().len()");
+        assert_eq!(output, "Numeric::from(((name).to_string()).len())");
+        assert_eq!(symbolic_ids, vec!["self.name"]);
+
+        //symbols without calls are unaffected
+        let (output, _) = run_pratt_parser("self.items.first");
+        assert_eq!(output, "items_PERIfirst");
+
+        //methods may take arguments, and may be called on function calls
+        let (output, symbolic_ids) = run_pratt_parser("self.fill.with_alpha(self.opacity/2)");
+        assert_eq!(output, "(fill).with_alpha(((opacity/Numeric::from(2))))");
+        assert_eq!(symbolic_ids, vec!["self.fill", "self.opacity"]);
+
+        let (output, _) = run_pratt_parser("Color::rgb(1, 0, 0).darken(0.2).with_alpha(0.5)");
+        assert_eq!(
+            output,
+            "((Color::rgb((Numeric::from(1)),(Numeric::from(0)),(Numeric::from(0)),)).darken((Numeric::from(0.2)))).with_alpha((Numeric::from(0.5)))"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Method `with_alpha` takes 1 argument(s) but 0 were supplied")]
+    fn test_pratt_parse_rejects_method_calls_with_wrong_arg_count() {
+        run_pratt_parser("self.fill.with_alpha()");
+    }
+
+    #[test]
+    #[should_panic(expected = "Method `clear` is not supported in expressions")]
+    fn test_pratt_parse_rejects_unsupported_method_calls() {
+        run_pratt_parser("self.items.clear()");
+    }
+
+    #[test]
+    fn test_pratt_parse_index_access() {
+        let (output, symbolic_ids) = run_pratt_parser("self.items[i] + 1");
+        assert_eq!(output, "(items_LSQBi_RSQB+Numeric::from(1))");
+        //the index is a dependency of its own
+        assert_eq!(symbolic_ids, vec!["i", "self.items[i]"]);
+
+        let (output, symbolic_ids) = run_pratt_parser("self.items[ 0 ].len()");
+        assert_eq!(output, "Numeric::from((items_LSQB0_RSQB).len())");
+        assert_eq!(symbolic_ids, vec!["self.items[ 0 ]"]);
+    }
+
+    #[test]
+    fn test_parse_if_else() {
+        let statement_if = PaxParser::parse(
+            Rule::statement_if,
+            "if self.is_on { <Rectangle /> <Ellipse /> } else { <Text /> }",
+        )
+        .unwrap()
+        .next()
+        .unwrap();
+        let rules = statement_if
+            .into_inner()
+            .map(|pair| pair.as_rule())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rules,
+            vec![
+                Rule::expression_body,
+                Rule::inner_nodes,
+                Rule::statement_else
+            ]
+        );
+
+        let statement_if = PaxParser::parse(Rule::statement_if, "if self.is_on { <Rectangle /> }")
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(statement_if.into_inner().count(), 2);
+
+        let statement_if = PaxParser::parse(
+            Rule::statement_if,
+            "if self.a { <Rectangle /> } else if self.b { <Ellipse /> } elseif self.c { } else { <Text /> }",
+        )
+        .unwrap()
+        .next()
+        .unwrap();
+        let branches = statement_if
+            .into_inner()
+            .skip(2)
+            .map(|pair| {
+                let rule = pair.as_rule();
+                (
+                    rule,
+                    pair.into_inner()
+                        .next()
+                        .unwrap()
+                        .as_str()
+                        .trim()
+                        .to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            branches,
+            vec![
+                (Rule::statement_else_if, "self.b".to_string()),
+                (Rule::statement_else_if, "self.c".to_string()),
+                (Rule::statement_else, "<Text />".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_keyed_for() {
+        let statement_for = PaxParser::parse(
+            Rule::statement_for,
+            "for item in self.items key={item.id} { <Rectangle /> }",
+        )
+        .unwrap()
+        .next()
+        .unwrap();
+        let pairs = statement_for.into_inner().collect::<Vec<_>>();
+        assert_eq!(
+            pairs.iter().map(|pair| pair.as_rule()).collect::<Vec<_>>(),
+            vec![
+                Rule::statement_for_predicate_declaration,
+                Rule::statement_for_source,
+                Rule::statement_for_key,
+                Rule::inner_nodes
+            ]
+        );
+        assert_eq!(
+            pairs[2].clone().into_inner().next().unwrap().as_str(),
+            "item.id"
+        );
+
+        let statement_for =
+            PaxParser::parse(Rule::statement_for, "for i in 0..5 { <Rectangle /> }")
+                .unwrap()
+                .next()
+                .unwrap();
+        assert!(statement_for
+            .into_inner()
+            .all(|pair| pair.as_rule() != Rule::statement_for_key));
+    }
+
+    #[test]
+    fn test_parse_destructured_for_predicate() {
+        let predicate = PaxParser::parse(
+            Rule::statement_for_predicate_declaration,
+            "((name, count), i)",
+        )
+        .unwrap()
+        .next()
+        .unwrap();
+        let mut pairs = predicate.into_inner();
+        let destructuring = pairs.next().unwrap();
+        assert_eq!(
+            destructuring.as_rule(),
+            Rule::statement_for_predicate_destructuring
+        );
+        assert_eq!(
+            destructuring
+                .into_inner()
+                .map(|pair| pair.as_str())
+                .collect::<Vec<_>>(),
+            vec!["name", "count"]
+        );
+        assert_eq!(pairs.next().unwrap().as_str(), "i");
+
+        //single-ident and `(elem, i)` predicates are unaffected
+        for source in ["elem", "(elem, i)"] {
+            let predicate = PaxParser::parse(Rule::statement_for_predicate_declaration, source)
+                .unwrap()
+                .next()
+                .unwrap();
+            assert!(predicate
+                .into_inner()
+                .all(|pair| pair.as_rule() == Rule::identifier));
+        }
+    }
+}
@@ -63,12 +63,25 @@ pub struct TemplateArgsCodegenCartridgeRenderNodeLiteral {
     pub slot_index_literal: String,
     pub repeat_source_expression_literal_vec: String,
     pub repeat_source_expression_literal_range: String,
-    pub conditional_boolean_expression_literal: String,
+    pub repeat_key_expression_literal: String,
+    //0: condition literal RIL (e.g. "Box::new(PropertyExpression::new(3))"), 1: literals of the arm's children
+    pub conditional_arms_literal: Vec<(String, Vec<String>)>,
+    pub conditional_else_children_literal: Vec<String>,
     pub pascal_identifier: String,
     pub type_id_escaped: String,
     pub events: HashMap<String, String>,
 }
 
+#[derive(Serialize)]
+pub struct TemplateArgsCodegenWebInterfaceDts {
+    pub pascal_identifier: String,
+    pub type_id: String,
+    //0: property name, 1: TypeScript type (e.g. `number`), 2: Rust type display name (e.g. `f64`)
+    pub properties: Vec<(String, String, String)>,
+    /// Names of the events handled by the root component, e.g. `did_mount`
+    pub events: Vec<String>,
+}
+
 #[allow(unused)]
 static TEMPLATE_CODEGEN_PROPERTIES_COPRODUCT_LIB: &str =
     include_str!("../templates/properties-coproduct-lib.tera");
@@ -141,3 +154,22 @@ pub fn press_template_codegen_cartridge_render_node_literal(
     )
     .unwrap()
 }
+
+#[allow(unused)]
+static TEMPLATE_CODEGEN_WEB_INTERFACE_DTS: &str =
+    include_str!("../templates/web-interface-dts.tera");
+pub fn press_template_codegen_web_interface_dts(
+    args: TemplateArgsCodegenWebInterfaceDts,
+) -> String {
+    let template = TEMPLATE_DIR
+        .get_file("web-interface-dts.tera")
+        .unwrap()
+        .contents_utf8()
+        .unwrap();
+    Tera::one_off(
+        template,
+        &tera::Context::from_serialize(args).unwrap(),
+        false,
+    )
+    .unwrap()
+}
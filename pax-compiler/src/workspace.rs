@@ -0,0 +1,85 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use itertools::Itertools;
+
+/// Walks up from the parent of `crate_dir` looking for a `Cargo.toml` with a `[workspace]` table,
+/// returning the path to the first one found
+pub(crate) fn find_enclosing_workspace_manifest(crate_dir: &Path) -> Option<PathBuf> {
+    let crate_dir = fs::canonicalize(crate_dir).ok()?;
+    crate_dir.ancestors().skip(1).find_map(|dir| {
+        let cargo_toml_path = dir.join("Cargo.toml");
+        let doc = fs::read_to_string(&cargo_toml_path)
+            .ok()?
+            .parse::<toml_edit::Document>()
+            .ok()?;
+        doc.as_table()
+            .contains_key("workspace")
+            .then_some(cargo_toml_path)
+    })
+}
+
+/// Appends `crate_dir`, relative to the workspace root, to the `[workspace] members` of the
+/// `Cargo.toml` at `workspace_manifest_path`, unless it is already listed
+pub(crate) fn add_workspace_member(workspace_manifest_path: &Path, crate_dir: &Path) {
+    let workspace_dir = workspace_manifest_path.parent().unwrap();
+    let crate_dir = fs::canonicalize(crate_dir).unwrap();
+    let member = crate_dir
+        .strip_prefix(workspace_dir)
+        .expect("crate is not within the workspace")
+        .components()
+        .map(|component| component.as_os_str().to_str().unwrap())
+        .join("/");
+
+    let mut doc = fs::read_to_string(workspace_manifest_path)
+        .expect("Failed to read workspace Cargo.toml")
+        .parse::<toml_edit::Document>()
+        .expect("Failed to parse workspace Cargo.toml");
+    let members = doc["workspace"]
+        .as_table_mut()
+        .expect("`workspace` is not a table")
+        .entry("members")
+        .or_insert_with(toml_edit::array)
+        .as_array_mut()
+        .expect("`workspace.members` is not an array");
+    if !members.iter().any(|m| m.as_str() == Some(member.as_str())) {
+        members.push(member);
+    }
+    fs::write(workspace_manifest_path, doc.to_string())
+        .expect("Failed to write workspace Cargo.toml");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{add_workspace_member, find_enclosing_workspace_manifest};
+
+    #[test]
+    fn test_add_workspace_member() {
+        let tmp_dir = std::env::temp_dir().join(format!("pax-workspace-{}", uuid::Uuid::new_v4()));
+        let project_dir = tmp_dir.join("apps").join("my-project");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        assert!(find_enclosing_workspace_manifest(&project_dir).is_none());
+
+        let workspace_manifest_path = tmp_dir.join("Cargo.toml");
+        std::fs::write(
+            &workspace_manifest_path,
+            "[workspace]\nmembers = [\"existing\"]\n",
+        )
+        .unwrap();
+        //A crate's own manifest does not make it its own workspace
+        std::fs::write(project_dir.join("Cargo.toml"), "[package]\n").unwrap();
+        let found = find_enclosing_workspace_manifest(&project_dir).unwrap();
+        assert_eq!(
+            found,
+            std::fs::canonicalize(&workspace_manifest_path).unwrap()
+        );
+
+        add_workspace_member(&found, &project_dir);
+        add_workspace_member(&found, &project_dir);
+        assert_eq!(
+            std::fs::read_to_string(&workspace_manifest_path).unwrap(),
+            "[workspace]\nmembers = [\"existing\", \"apps/my-project\"]\n"
+        );
+        std::fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+}
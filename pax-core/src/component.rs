@@ -54,6 +54,28 @@ impl<R: 'static + RenderContext> RenderNode<R> for ComponentInstance<R> {
         (*rtc.runtime).borrow_mut().pop_stack_frame();
     }
 
+    fn handle_mount(&mut self, rtc: &mut RenderTreeContext<R>) {
+        if let Some(registry) = &self.handler_registry {
+            for handler in (*registry).borrow().mount_handlers.iter() {
+                handler(
+                    Rc::clone(&self.properties),
+                    rtc.distill_userland_node_context(),
+                );
+            }
+        }
+    }
+
+    fn handle_unmount(&mut self, rtc: &mut RenderTreeContext<R>) {
+        if let Some(registry) = &self.handler_registry {
+            for handler in (*registry).borrow().unmount_handlers.iter() {
+                handler(
+                    Rc::clone(&self.properties),
+                    rtc.distill_userland_node_context(),
+                );
+            }
+        }
+    }
+
     fn instantiate(args: InstantiationArgs<R>) -> Rc<RefCell<Self>> {
         let mut instance_registry = (*args.instance_registry).borrow_mut();
         let instance_id = instance_registry.mint_id();
@@ -6,19 +6,43 @@ use pax_properties_coproduct::TypesCoproduct;
 use pax_runtime_api::{CommonProperties, Layer, PropertyInstance, Size};
 use piet_common::RenderContext;
 
+/// A single `if` / `else if` arm of a Conditional: a boolean condition and
+/// the subtree rendered while that arm is active.
+pub type ConditionalArm<R> = (Box<dyn PropertyInstance<bool>>, RenderNodePtrList<R>);
+
 /// A special "control-flow" primitive, Conditional (`if`) allows for a
-/// subtree of a component template to be rendered conditionally,
-/// based on the value of the property `boolean_expression`.
+/// subtree of a component template to be rendered conditionally.
+/// A Conditional holds an ordered list of arms (`if`, then any `else if`s), of which
+/// the first with a `true` condition is rendered, plus an optional default subtree (`else`)
+/// rendered when no arm's condition is `true`.
 /// The Pax compiler handles ConditionalInstance specially
-/// with the `if` syntax in templates.
+/// with the `if` / `else if` / `else` syntax in templates.
 pub struct ConditionalInstance<R: 'static + RenderContext> {
     pub instance_id: u32,
 
-    pub boolean_expression: Box<dyn PropertyInstance<bool>>,
-    pub true_branch_children: RenderNodePtrList<R>,
-    pub false_branch_children: RenderNodePtrList<R>,
+    pub arms: Vec<ConditionalArm<R>>,
+    pub else_children: RenderNodePtrList<R>,
     pub cleanup_children: RenderNodePtrList<R>,
     pub common_properties: CommonProperties,
+    /// The branch rendered as of the last `compute_properties`: `Some(Some(i))` for `arms[i]`,
+    /// `Some(None)` for `else_children`, and `None` before any branch has been rendered
+    /// (so that nothing is unmounted on the first frame)
+    active_branch: Option<Option<usize>>,
+}
+
+impl<R: 'static + RenderContext> ConditionalInstance<R> {
+    /// Index of the first arm whose (most recently computed) condition is `true`,
+    /// or `None` if the `else` branch should be rendered
+    fn get_truthy_arm_index(&self) -> Option<usize> {
+        self.arms.iter().position(|(condition, _)| *condition.get())
+    }
+
+    fn get_branch_children(&self, branch: Option<usize>) -> RenderNodePtrList<R> {
+        match branch {
+            Some(i) => Rc::clone(&self.arms[i].1),
+            None => Rc::clone(&self.else_children),
+        }
+    }
 }
 
 impl<R: 'static + RenderContext> RenderNode<R> for ConditionalInstance<R> {
@@ -38,16 +62,16 @@ impl<R: 'static + RenderContext> RenderNode<R> for ConditionalInstance<R> {
         let instance_id = instance_registry.mint_id();
         let ret = Rc::new(RefCell::new(Self {
             instance_id,
-            true_branch_children: match args.children {
+            arms: args
+                .conditional_arms
+                .expect("Conditional requires conditional_arms"),
+            else_children: match args.conditional_else_children {
                 None => Rc::new(RefCell::new(vec![])),
                 Some(children) => children,
             },
             common_properties: args.common_properties,
-            boolean_expression: args
-                .conditional_boolean_expression
-                .expect("Conditional requires boolean_expression"),
-            false_branch_children: Rc::new(RefCell::new(vec![])),
             cleanup_children: Rc::new(RefCell::new(vec![])),
+            active_branch: None,
         }));
 
         instance_registry.register(instance_id, Rc::clone(&ret) as RenderNodePtr<R>);
@@ -55,41 +79,48 @@ impl<R: 'static + RenderContext> RenderNode<R> for ConditionalInstance<R> {
     }
 
     fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
-        if let Some(boolean_expression) =
-            rtc.compute_vtable_value(self.boolean_expression._get_vtable_id())
-        {
-            let old_value = *self.boolean_expression.get();
-            let new_value = if let TypesCoproduct::bool(v) = boolean_expression {
-                v
-            } else {
-                unreachable!()
-            };
+        for (condition, _) in self.arms.iter_mut() {
+            if let Some(new_value) = rtc.compute_vtable_value(condition._get_vtable_id()) {
+                let new_value = if let TypesCoproduct::bool(v) = new_value {
+                    v
+                } else {
+                    unreachable!()
+                };
+                condition.set(new_value);
+            }
+        }
 
-            let mut instance_registry = (*rtc.engine.instance_registry).borrow_mut();
-            if old_value && !new_value {
-                (*self.true_branch_children)
-                    .borrow_mut()
-                    .iter()
-                    .for_each(|child| {
-                        let instance_id = (*(*child)).borrow_mut().get_instance_id();
-                        instance_registry.deregister(instance_id);
-                        instance_registry.mark_for_unmount(instance_id);
-                    });
-                self.cleanup_children = self.true_branch_children.clone();
+        let new_branch = self.get_truthy_arm_index();
+        if let Some(old_branch) = self.active_branch {
+            if old_branch != new_branch {
+                //unmount whichever branch is being switched away from
+                let mut instance_registry = (*rtc.engine.instance_registry).borrow_mut();
+                let outgoing_children = self.get_branch_children(old_branch);
+                (*outgoing_children).borrow_mut().iter().for_each(|child| {
+                    let instance_id = (*(*child)).borrow_mut().get_instance_id();
+                    instance_registry.deregister(instance_id);
+                    instance_registry.mark_for_unmount(instance_id);
+                });
+                self.cleanup_children = outgoing_children;
+
+                //re-register the branch being switched to, if it was previously switched away from
+                let incoming_children = self.get_branch_children(new_branch);
+                (*incoming_children).borrow().iter().for_each(|child| {
+                    let instance_id = (*(*child)).borrow().get_instance_id();
+                    if !instance_registry.is_registered(instance_id) {
+                        instance_registry.register(instance_id, Rc::clone(child));
+                    }
+                });
             }
-            self.boolean_expression.set(new_value);
         }
+        self.active_branch = Some(new_branch);
     }
 
     fn should_flatten(&self) -> bool {
         true
     }
     fn get_rendering_children(&self) -> RenderNodePtrList<R> {
-        if *self.boolean_expression.get() {
-            Rc::clone(&self.true_branch_children)
-        } else {
-            Rc::clone(&self.false_branch_children)
-        }
+        self.get_branch_children(self.get_truthy_arm_index())
     }
     fn pop_cleanup_children(&mut self) -> RenderNodePtrList<R> {
         let ret = self.cleanup_children.clone();
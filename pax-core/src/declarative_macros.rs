@@ -35,3 +35,50 @@ macro_rules! unsafe_unwrap {
         unwrap_impl::<$enum_type, $target_type>($source_enum)
     }};
 }
+
+/// Borrows the target value from an enum using raw memory access, without moving
+/// or replacing the enum's contents.  The by-reference companion to `unsafe_unwrap!`.
+///
+/// Parameters:
+/// - `$source_enum`: The enum instance to borrow the target value from.
+/// - `$enum_type`: The type of the enum.
+/// - `$target_type`: The type of the target value to borrow.
+#[macro_export]
+macro_rules! unsafe_unwrap_ref {
+    ($source_enum:expr, $enum_type:ty, $target_type:ty) => {{
+        fn unwrap_ref_impl<T, U>(source_enum: &T) -> &U {
+            let size_of_enum = std::mem::size_of::<T>();
+            let size_of_target = std::mem::size_of::<U>();
+            let align_of_enum = std::mem::align_of::<T>();
+
+            assert!(size_of_target < size_of_enum, "The size_of target_type must be less than the size_of enum_type.");
+
+            unsafe {
+                let enum_ptr = source_enum as *const T;
+                &*((enum_ptr as *const u8).add(align_of_enum) as *const U)
+            }
+        }
+        unwrap_ref_impl::<$enum_type, $target_type>(&$source_enum)
+    }};
+}
+
+/// Extracts the value of the specified tuple variant from an enum, checking the discriminant
+/// first.  Returns `Some(value)` if `$source_enum` is `$enum_type::$variant(value)`, or `None`
+/// otherwise.  A checked alternative to `unsafe_unwrap!` for paths where a mismatched variant
+/// should be handled gracefully rather than crash.
+///
+/// Parameters:
+/// - `$source_enum`: The enum instance to extract the target value from.
+/// - `$enum_type`: The type of the enum, or a path to it.
+/// - `$variant`: The variant of the enum holding the target value.
+#[macro_export]
+macro_rules! try_unwrap {
+    ($source_enum:expr, $enum_type:path, $variant:ident) => {{
+        use $enum_type as EnumType;
+        match $source_enum {
+            EnumType::$variant(value) => Some(value),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }};
+}
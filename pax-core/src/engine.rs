@@ -3,10 +3,11 @@ use std::collections::{HashMap, HashSet};
 
 use std::rc::{Rc, Weak};
 
-use kurbo::Vec2;
+use kurbo::{Point, Vec2};
 
 use pax_message::NativeMessage;
 
+use piet::{FontFamily, Text, TextLayoutBuilder};
 use piet_common::RenderContext;
 
 use crate::runtime::Runtime;
@@ -18,19 +19,37 @@ use pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
 
 use pax_runtime_api::{
     ArgsClick, ArgsContextMenu, ArgsDoubleClick, ArgsJab, ArgsKeyDown, ArgsKeyPress, ArgsKeyUp,
-    ArgsMouseDown, ArgsMouseMove, ArgsMouseOut, ArgsMouseOver, ArgsMouseUp, ArgsScroll,
+    ArgsMouseDown, ArgsMouseMove, ArgsMouseOut, ArgsMouseOver, ArgsMouseUp, ArgsResize, ArgsScroll,
     ArgsTouchEnd, ArgsTouchMove, ArgsTouchStart, ArgsWheel, CommonProperties, Interpolatable,
-    Layer, Rotation, RuntimeContext, Size, Transform2D, TransitionManager, ZIndex,
+    KeyboardEventArgs, Layer, ModifierKey, MouseEventArgs, Rotation, RuntimeContext, Size,
+    Transform2D, TransitionManager, ZIndex,
 };
 
+/// Stroke color of the bounding boxes, and color of the labels, drawn by the debug overlay
+const DEBUG_OVERLAY_COLOR: piet::Color = piet::Color::rgba8(255, 0, 255, 200);
+/// Font size, in logical px, of the labels drawn by the debug overlay
+const DEBUG_OVERLAY_FONT_SIZE: f64 = 10.0;
+/// Key which, along with Control and Shift, toggles the debug overlay in debug builds
+const DEBUG_OVERLAY_SHORTCUT_KEY: &str = "d";
+
 pub struct PaxEngine<R: 'static + RenderContext> {
     pub frames_elapsed: usize,
+    /// Wall-clock seconds since the engine started, as reported by the chassis
+    pub seconds_elapsed: f64,
+    /// Ratio of physical to logical pixels on the rendering device, as reported by the chassis.
+    /// All bounds, transforms and event coordinates in the engine are in logical px; the chassis
+    /// is responsible for scaling its drawing surfaces by this ratio.  Defaults to `1.0`.
+    pub device_pixel_ratio: f64,
     pub instance_registry: Rc<RefCell<InstanceRegistry<R>>>,
     pub expression_table: HashMap<usize, Box<dyn Fn(ExpressionContext<R>) -> TypesCoproduct>>,
     pub main_component: Rc<RefCell<ComponentInstance<R>>>,
     pub runtime: Rc<RefCell<Runtime<R>>>,
     pub image_map: HashMap<Vec<u32>, (Box<Vec<u8>>, usize, usize)>,
     viewport_tab: TransformAndBounds,
+    /// Whether the viewport bounds changed since the last tick, i.e. whether `resize` handlers should fire this tick
+    is_viewport_resized: bool,
+    /// Whether to outline and label every node after rendering it; see `set_debug_overlay_enabled`
+    is_debug_overlay_enabled: bool,
 }
 
 pub struct RenderTreeContext<'a, R: 'static + RenderContext> {
@@ -38,6 +57,10 @@ pub struct RenderTreeContext<'a, R: 'static + RenderContext> {
     pub transform_global: Affine,
     pub transform_scroller_reset: Affine,
     pub bounds: (f64, f64),
+    pub bounds_viewport: (f64, f64),
+    /// Opacity accumulated from this node and its ancestors, to be multiplied into
+    /// rendered alpha by primitives.  `1.0` is fully opaque.
+    pub opacity: f64,
     pub runtime: Rc<RefCell<Runtime<R>>>,
     pub node: RenderNodePtr<R>,
     pub parent_repeat_expanded_node: Option<Weak<RepeatExpandedNode<R>>>,
@@ -48,7 +71,11 @@ pub struct RenderTreeContext<'a, R: 'static + RenderContext> {
 macro_rules! handle_vtable_update {
     ($rtc:expr, $var:ident . $field:ident, $types_coproduct_type:ident) => {{
         let current_prop = &mut *$var.$field.as_ref().borrow_mut();
-        if let Some(new_value) = $rtc.compute_vtable_value(current_prop._get_vtable_id()) {
+        if let Some(new_value) = $rtc.compute_eased_value(current_prop._get_transition_manager()) {
+            current_prop.set(new_value);
+        } else if let Some(new_value) =
+            $rtc.compute_vtable_value(current_prop._get_dirty_vtable_id($rtc.engine.frames_elapsed))
+        {
             let new_value = if let TypesCoproduct::$types_coproduct_type(val) = new_value {
                 val
             } else {
@@ -63,7 +90,13 @@ macro_rules! handle_vtable_update_optional {
     ($rtc:expr, $var:ident . $field:ident, $types_coproduct_type:ident) => {{
         if let Some(_) = $var.$field {
             let current_prop = &mut *$var.$field.as_ref().unwrap().borrow_mut();
-            if let Some(new_value) = $rtc.compute_vtable_value(current_prop._get_vtable_id()) {
+            if let Some(new_value) =
+                $rtc.compute_eased_value(current_prop._get_transition_manager())
+            {
+                current_prop.set(new_value);
+            } else if let Some(new_value) = $rtc
+                .compute_vtable_value(current_prop._get_dirty_vtable_id($rtc.engine.frames_elapsed))
+            {
                 let new_value = if let TypesCoproduct::$types_coproduct_type(val) = new_value {
                     val
                 } else {
@@ -96,6 +129,12 @@ impl<R: 'static + RenderContext> PropertiesComputable<R> for CommonProperties {
         handle_vtable_update_optional!(rtc, self.anchor_y, Size);
         handle_vtable_update_optional!(rtc, self.x, Size);
         handle_vtable_update_optional!(rtc, self.y, Size);
+        handle_vtable_update_optional!(rtc, self.min_width, Size);
+        handle_vtable_update_optional!(rtc, self.max_width, Size);
+        handle_vtable_update_optional!(rtc, self.min_height, Size);
+        handle_vtable_update_optional!(rtc, self.max_height, Size);
+        handle_vtable_update_optional!(rtc, self.opacity, f64);
+        //`z_index` is computed by the parent, ahead of sorting its children; see `recurse_traverse_render_tree`
     }
 }
 
@@ -103,7 +142,9 @@ impl<'a, R: 'static + RenderContext> RenderTreeContext<'a, R> {
     pub fn distill_userland_node_context(&self) -> RuntimeContext {
         RuntimeContext {
             bounds_parent: self.bounds,
+            bounds_viewport: self.bounds_viewport,
             frames_elapsed: self.engine.frames_elapsed,
+            device_pixel_ratio: self.engine.device_pixel_ratio,
         }
     }
 }
@@ -115,6 +156,8 @@ impl<'a, R: 'static + RenderContext> Clone for RenderTreeContext<'a, R> {
             transform_global: self.transform_global.clone(),
             transform_scroller_reset: self.transform_scroller_reset.clone(),
             bounds: self.bounds.clone(),
+            bounds_viewport: self.bounds_viewport,
+            opacity: self.opacity,
             runtime: Rc::clone(&self.runtime),
             node: Rc::clone(&self.node),
             parent_repeat_expanded_node: self.parent_repeat_expanded_node.clone(),
@@ -156,10 +199,14 @@ impl<'a, R: RenderContext> RenderTreeContext<'a, R> {
                         progress,
                     );
                     tm.value = Some(new_value.clone());
+                    pax_runtime_api::mark_properties_dirty();
                     tm.value.clone()
                 };
             } else {
-                return tm.value.clone();
+                //the queue is exhausted: yield the final value once, then relinquish control back to
+                //the property's own literal value (which the caller sets to this final value) or expression
+                pax_runtime_api::mark_properties_dirty();
+                return tm.value.take();
             }
         }
         None
@@ -220,8 +267,13 @@ pub struct HandlerRegistry<R: 'static + RenderContext> {
     pub double_click_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsDoubleClick)>,
     pub context_menu_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsContextMenu)>,
     pub wheel_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsWheel)>,
+    pub resize_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsResize)>,
+    //lifecycle handlers, which are passed properties directly rather than a stack frame
     pub will_render_handlers: Vec<fn(Rc<RefCell<PropertiesCoproduct>>, RuntimeContext)>,
+    pub did_render_handlers: Vec<fn(Rc<RefCell<PropertiesCoproduct>>, RuntimeContext)>,
     pub did_mount_handlers: Vec<fn(Rc<RefCell<PropertiesCoproduct>>, RuntimeContext)>,
+    pub mount_handlers: Vec<fn(Rc<RefCell<PropertiesCoproduct>>, RuntimeContext)>,
+    pub unmount_handlers: Vec<fn(Rc<RefCell<PropertiesCoproduct>>, RuntimeContext)>,
 }
 
 impl<R: 'static + RenderContext> Default for HandlerRegistry<R> {
@@ -244,8 +296,12 @@ impl<R: 'static + RenderContext> Default for HandlerRegistry<R> {
             double_click_handlers: Vec::new(),
             context_menu_handlers: Vec::new(),
             wheel_handlers: Vec::new(),
+            resize_handlers: Vec::new(),
             will_render_handlers: Vec::new(),
+            did_render_handlers: Vec::new(),
             did_mount_handlers: Vec::new(),
+            mount_handlers: Vec::new(),
+            unmount_handlers: Vec::new(),
         }
     }
 }
@@ -264,6 +320,17 @@ pub struct RepeatExpandedNode<R: 'static + RenderContext> {
 }
 
 impl<R: 'static + RenderContext> RepeatExpandedNode<R> {
+    /// Returns a copy of `mouse` with `local_x`/`local_y` expressed in this node's
+    /// pre-transform space, where `(0,0)` is the node's top-left corner.
+    fn localize_mouse_event_args(&self, mouse: &MouseEventArgs) -> MouseEventArgs {
+        let local_point = self.tab.inverse_transform_point(&(mouse.x, mouse.y));
+        MouseEventArgs {
+            local_x: local_point.x,
+            local_y: local_point.y,
+            ..mouse.clone()
+        }
+    }
+
     pub fn dispatch_scroll(&self, args_scroll: ArgsScroll) {
         if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
             let handlers = &(*registry).borrow().scroll_handlers;
@@ -409,6 +476,9 @@ impl<R: 'static + RenderContext> RepeatExpandedNode<R> {
     }
 
     pub fn dispatch_click(&self, args_click: ArgsClick) {
+        let args_click = ArgsClick {
+            mouse: self.localize_mouse_event_args(&args_click.mouse),
+        };
         if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
             let handlers = &(*registry).borrow().click_handlers;
             handlers.iter().for_each(|handler| {
@@ -426,6 +496,9 @@ impl<R: 'static + RenderContext> RepeatExpandedNode<R> {
     }
 
     pub fn dispatch_mouse_down(&self, args_mouse_down: ArgsMouseDown) {
+        let args_mouse_down = ArgsMouseDown {
+            mouse: self.localize_mouse_event_args(&args_mouse_down.mouse),
+        };
         if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
             let handlers = &(*registry).borrow().mouse_down_handlers;
             handlers.iter().for_each(|handler| {
@@ -446,6 +519,9 @@ impl<R: 'static + RenderContext> RepeatExpandedNode<R> {
     }
 
     pub fn dispatch_mouse_up(&self, args_mouse_up: ArgsMouseUp) {
+        let args_mouse_up = ArgsMouseUp {
+            mouse: self.localize_mouse_event_args(&args_mouse_up.mouse),
+        };
         if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
             let handlers = &(*registry).borrow().mouse_up_handlers;
             handlers.iter().for_each(|handler| {
@@ -463,6 +539,9 @@ impl<R: 'static + RenderContext> RepeatExpandedNode<R> {
     }
 
     pub fn dispatch_mouse_move(&self, args_mouse_move: ArgsMouseMove) {
+        let args_mouse_move = ArgsMouseMove {
+            mouse: self.localize_mouse_event_args(&args_mouse_move.mouse),
+        };
         if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
             let handlers = &(*registry).borrow().mouse_move_handlers;
             handlers.iter().for_each(|handler| {
@@ -483,6 +562,9 @@ impl<R: 'static + RenderContext> RepeatExpandedNode<R> {
     }
 
     pub fn dispatch_mouse_over(&self, args_mouse_over: ArgsMouseOver) {
+        let args_mouse_over = ArgsMouseOver {
+            mouse: self.localize_mouse_event_args(&args_mouse_over.mouse),
+        };
         if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
             let handlers = &(*registry).borrow().mouse_over_handlers;
             handlers.iter().for_each(|handler| {
@@ -503,6 +585,9 @@ impl<R: 'static + RenderContext> RepeatExpandedNode<R> {
     }
 
     pub fn dispatch_mouse_out(&self, args_mouse_out: ArgsMouseOut) {
+        let args_mouse_out = ArgsMouseOut {
+            mouse: self.localize_mouse_event_args(&args_mouse_out.mouse),
+        };
         if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
             let handlers = &(*registry).borrow().mouse_out_handlers;
             handlers.iter().for_each(|handler| {
@@ -520,6 +605,9 @@ impl<R: 'static + RenderContext> RepeatExpandedNode<R> {
     }
 
     pub fn dispatch_double_click(&self, args_double_click: ArgsDoubleClick) {
+        let args_double_click = ArgsDoubleClick {
+            mouse: self.localize_mouse_event_args(&args_double_click.mouse),
+        };
         if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
             let handlers = &(*registry).borrow().double_click_handlers;
             handlers.iter().for_each(|handler| {
@@ -540,6 +628,9 @@ impl<R: 'static + RenderContext> RepeatExpandedNode<R> {
     }
 
     pub fn dispatch_context_menu(&self, args_context_menu: ArgsContextMenu) {
+        let args_context_menu = ArgsContextMenu {
+            mouse: self.localize_mouse_event_args(&args_context_menu.mouse),
+        };
         if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
             let handlers = &(*registry).borrow().context_menu_handlers;
             handlers.iter().for_each(|handler| {
@@ -592,15 +683,26 @@ pub struct InstanceRegistry<R: 'static + RenderContext> {
     ///tracks whichs instance nodes are marked for unmounting, to be done at the correct point in the render tree lifecycle
     marked_for_unmount_set: HashSet<u32>,
 
+    ///instances registered or deregistered since the engine last fired their `handle_mount` / `handle_unmount`, in order
+    pending_lifecycle_events: Vec<InstanceLifecycleEvent<R>>,
+
     ///register holding the next value to mint as an id
     next_id: u32,
 }
 
+/// A change to the set of instances tracked by `InstanceRegistry`, awaiting the corresponding
+/// `RenderNode::handle_mount` or `RenderNode::handle_unmount`
+pub(crate) enum InstanceLifecycleEvent<R: 'static + RenderContext> {
+    Mount(RenderNodePtr<R>),
+    Unmount(RenderNodePtr<R>),
+}
+
 impl<R: 'static + RenderContext> InstanceRegistry<R> {
     pub fn new() -> Self {
         Self {
             mounted_set: HashSet::new(),
             marked_for_unmount_set: HashSet::new(),
+            pending_lifecycle_events: vec![],
             instance_map: HashMap::new(),
             repeat_expanded_node_cache: vec![],
             next_id: 0,
@@ -613,12 +715,29 @@ impl<R: 'static + RenderContext> InstanceRegistry<R> {
         new_id
     }
 
+    /// Tracks `node` by `instance_id`.  The engine fires `node`'s `handle_mount` at its next opportunity,
+    /// before `node` is first rendered
     pub fn register(&mut self, instance_id: u32, node: RenderNodePtr<R>) {
+        self.pending_lifecycle_events
+            .push(InstanceLifecycleEvent::Mount(Rc::clone(&node)));
         self.instance_map.insert(instance_id, node);
     }
 
+    /// Stops tracking the node registered as `instance_id`.  The engine fires that node's `handle_unmount`
+    /// at its next opportunity
     pub fn deregister(&mut self, instance_id: u32) {
-        self.instance_map.remove(&instance_id);
+        if let Some(node) = self.instance_map.remove(&instance_id) {
+            self.pending_lifecycle_events
+                .push(InstanceLifecycleEvent::Unmount(node));
+        }
+    }
+
+    pub fn is_registered(&self, instance_id: u32) -> bool {
+        self.instance_map.contains_key(&instance_id)
+    }
+
+    pub(crate) fn take_pending_lifecycle_events(&mut self) -> Vec<InstanceLifecycleEvent<R>> {
+        std::mem::take(&mut self.pending_lifecycle_events)
     }
 
     pub fn mark_mounted(&mut self, id_chain: Vec<u32>) {
@@ -657,6 +776,8 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
         pax_runtime_api::register_logger(logger);
         PaxEngine {
             frames_elapsed: 0,
+            seconds_elapsed: 0.0,
+            device_pixel_ratio: 1.0,
             instance_registry,
             expression_table,
             runtime: Rc::new(RefCell::new(Runtime::new())),
@@ -666,6 +787,8 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
                 bounds: viewport_size,
                 clipping_bounds: Some(viewport_size),
             },
+            is_viewport_resized: false,
+            is_debug_overlay_enabled: false,
             image_map: HashMap::new(),
         }
     }
@@ -686,6 +809,8 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
             transform_global: Affine::default(),
             transform_scroller_reset: Affine::default(),
             bounds: self.viewport_tab.bounds,
+            bounds_viewport: self.viewport_tab.bounds,
+            opacity: 1.0,
             runtime: self.runtime.clone(),
             node: Rc::clone(&cast_component_rc),
             parent_repeat_expanded_node: None,
@@ -704,8 +829,8 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
         //reset the marked_for_unmount set
         self.instance_registry.borrow_mut().marked_for_unmount_set = HashSet::new();
 
-        let native_render_queue = (*self.runtime).borrow_mut().take_native_message_queue();
-        native_render_queue.into()
+        //flush this tick's batch of native patches to the chassis, all at once
+        (*self.runtime).borrow_mut().take_native_message_queue()
     }
 
     fn recurse_traverse_render_tree(
@@ -715,7 +840,7 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
         node: RenderNodePtr<R>,
         z_index_info: &mut ZIndex,
         marked_for_unmount: bool,
-    ) {
+    ) -> Option<kurbo::Rect> {
         //Recurse:
         //  - compute properties for this node
         //  - fire lifecycle events for this node
@@ -723,12 +848,18 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
         //  - we now have the back-most leaf node.  Render it.  Return.
         //  - we're now at the second back-most leaf node.  Render it.  Return ...
         //  - manage unmounting, if marked
+        //  - return the global (scroller-normalized) rect enclosing this subtree's rendered content, which
+        //    stops at clipping containers; used to measure the content of scrolling containers
 
         //populate a pointer to this (current) `RenderNode` onto `rtc`
         rtc.node = Rc::clone(&node);
 
         //lifecycle: compute_properties happens before rendering
         node.borrow_mut().compute_properties(rtc);
+
+        //lifecycle: mount / unmount any instances (de)registered since the last node was visited, e.g. the
+        //children that a `Repeat` or `Conditional` has just created or discarded in `compute_properties`
+        self.fire_instance_lifecycle_events(rtc);
         let accumulated_transform = rtc.transform_global;
         let accumulated_scroller_normalized_transform = rtc.transform_scroller_reset;
         let accumulated_bounds = rtc.bounds;
@@ -902,7 +1033,14 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
                 * desugared_transform
                 * node_transform_property_computed;
 
+        //multiply this node's `opacity` into the accumulated opacity, so that it cascades to descendants
+        let node_opacity = match rtc.node.borrow().get_common_properties().opacity {
+            Some(ref val) => *val.borrow().get(),
+            None => 1.0,
+        };
+
         rtc.bounds = new_accumulated_bounds.clone();
+        rtc.opacity = rtc.opacity * node_opacity;
         rtc.transform_global = new_accumulated_transform.clone();
         rtc.transform_scroller_reset = new_scroller_normalized_accumulated_transform.clone();
 
@@ -927,6 +1065,25 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
                     panic!("can't bind events without a component")
                 }
             }
+
+            //fire `resize` handlers, if the viewport changed size since last tick
+            if rtc.engine.is_viewport_resized {
+                let handlers = &(*registry).borrow().resize_handlers;
+                if !handlers.is_empty() {
+                    let stack_frame = rtc.runtime.borrow_mut().peek_stack_frame().unwrap();
+                    let args_resize = ArgsResize {
+                        width: rtc.bounds_viewport.0,
+                        height: rtc.bounds_viewport.1,
+                    };
+                    handlers.iter().for_each(|handler| {
+                        handler(
+                            Rc::clone(&stack_frame),
+                            rtc.distill_userland_node_context(),
+                            args_resize.clone(),
+                        );
+                    });
+                }
+            }
         }
 
         //create the `repeat_expanded_node` for the current node
@@ -1003,23 +1160,77 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
             rtc.transform_scroller_reset = reset_transform.clone();
         }
 
-        children.borrow_mut().iter().rev().for_each(|child| {
-            //note that we're iterating starting from the last child, for z-index (.rev())
+        //non-native scrolling containers (e.g. a `Frame` with scroll overflow) translate their children by
+        //their scroll offset, in their own coordinate space.  Both the global and the scroller-normalized
+        //transforms are translated, so that children render and hit-test at their scrolled positions.
+        let child_scroll_translation = if z_index_info.get_current_layer() == Layer::Scroller {
+            None
+        } else {
+            match node.borrow_mut().get_scroll_offset() {
+                (0.0, 0.0) => None,
+                (scroll_offset_x, scroll_offset_y) => {
+                    Some(Affine::translate((scroll_offset_x, scroll_offset_y)))
+                }
+            }
+        };
+
+        //note that we're iterating starting from the last child, for z-index (.rev()),
+        //then stable-sorting by the `z_index` common property, so that equal `z_index`s retain document order.
+        //Because layers are assigned in traversal order, both canvas and native layers reflect this ordering.
+        //Each child's `z_index` is computed here, ahead of its other properties, so that the sort reflects
+        //this tick's value rather than the last.
+        let mut sorted_children: Vec<RenderNodePtr<R>> =
+            children.borrow().iter().rev().map(Rc::clone).collect();
+        sorted_children.iter().for_each(|child| {
+            let child_borrowed = child.borrow();
+            let common_properties = child_borrowed.get_common_properties();
+            handle_vtable_update_optional!(rtc, common_properties.z_index, isize);
+        });
+        sorted_children.sort_by_key(
+            |child| match child.borrow().get_common_properties().z_index {
+                Some(ref val) => *val.borrow().get(),
+                None => 0,
+            },
+        );
+
+        let mut content_bounds: Option<kurbo::Rect> = None;
+        sorted_children.iter().for_each(|child| {
             let mut new_rtc = rtc.clone();
             new_rtc.parent_repeat_expanded_node = Some(Rc::downgrade(&repeat_expanded_node));
+            if let Some(scroll_translation) = child_scroll_translation {
+                new_rtc.transform_global *= scroll_translation;
+                new_rtc.transform_scroller_reset *= scroll_translation;
+            }
             // if it's a scroller reset the z-index context for its children
-            self.recurse_traverse_render_tree(
+            let child_bounds = self.recurse_traverse_render_tree(
                 &mut new_rtc,
                 rcs,
                 Rc::clone(child),
                 &mut child_z_index_info.clone(),
                 marked_for_unmount,
             );
+            content_bounds = union_bounding_boxes(content_bounds, child_bounds);
             //FUTURE: for dependency management, return computed values from subtree above
 
             subtree_depth = subtree_depth.max(child_z_index_info.get_level());
         });
 
+        //report the extent of this node's content back to it, in its own unscrolled coordinate space.
+        //Scrollers' children are laid out in their own scroller-normalized space, so they are skipped.
+        if let Some(content_bounds) = content_bounds {
+            if z_index_info.get_current_layer() != Layer::Scroller {
+                let content_transform = new_scroller_normalized_accumulated_transform
+                    * child_scroll_translation.unwrap_or_default();
+                if content_transform.determinant() != 0.0 {
+                    node.borrow_mut().handle_content_bounds(
+                        content_transform
+                            .inverse()
+                            .transform_rect_bbox(content_bounds),
+                    );
+                }
+            }
+        }
+
         let is_viewport_culled = !repeat_expanded_node_tab.intersects(&self.viewport_tab);
 
         //lifecycle: compute_native_patches — for elements with native components (for example Text, Frame, and form control elements),
@@ -1061,6 +1272,39 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
             }
         }
 
+        //debug overlay: outline and label this node, atop its own rendering
+        if self.is_debug_overlay_enabled && !is_viewport_culled {
+            let bounding_box = node.borrow().get_bounding_box(&repeat_expanded_node_tab);
+            if let Some(bounding_box) = bounding_box {
+                let label = {
+                    let mut node_borrowed = node.borrow_mut();
+                    format!(
+                        "{} #{} ({:?})",
+                        node_borrowed.get_type_name(),
+                        instance_id,
+                        node_borrowed.get_layer_type()
+                    )
+                };
+                let canvas_id = if rcs.contains_key(&canvas_id) {
+                    canvas_id.as_str()
+                } else {
+                    "0"
+                };
+                if let Some(rc) = rcs.get_mut(canvas_id) {
+                    rc.stroke(bounding_box, &DEBUG_OVERLAY_COLOR, 1.0);
+                    if let Ok(layout) = rc
+                        .text()
+                        .new_text_layout(label)
+                        .font(FontFamily::MONOSPACE, DEBUG_OVERLAY_FONT_SIZE)
+                        .text_color(DEBUG_OVERLAY_COLOR)
+                        .build()
+                    {
+                        rc.draw_text(&layout, bounding_box.origin());
+                    }
+                }
+            }
+        }
+
         //Handle node unmounting
         if marked_for_unmount {
             //lifecycle: will_unmount
@@ -1073,8 +1317,50 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
                 .remove(&id_chain); //, "Tried to unmount a node, but it was not mounted");
         }
 
+        //fire `did_render` handlers, now that this node and its descendants have rendered.
+        //Note that this must happen before `handle_did_render`, which pops a component's stack frame
+        let registry = (*node).borrow().get_handler_registry();
+        if let Some(registry) = registry {
+            match rtc.runtime.borrow_mut().peek_stack_frame() {
+                Some(stack_frame) => {
+                    for handler in (*registry).borrow().did_render_handlers.iter() {
+                        handler(
+                            stack_frame.borrow_mut().get_properties(),
+                            rtc.distill_userland_node_context(),
+                        );
+                    }
+                }
+                None => {
+                    panic!("can't bind events without a component")
+                }
+            }
+        }
+
         //lifecycle: did_render
         node.borrow_mut().handle_did_render(rtc, rcs);
+
+        if marked_for_unmount {
+            return None;
+        }
+        let bounding_box = node.borrow().get_bounding_box(&repeat_expanded_node_tab);
+        if clipping_bounds.is_some() {
+            bounding_box
+        } else {
+            union_bounding_boxes(bounding_box, content_bounds)
+        }
+    }
+
+    fn fire_instance_lifecycle_events(&self, rtc: &mut RenderTreeContext<R>) {
+        let pending_lifecycle_events = self
+            .instance_registry
+            .borrow_mut()
+            .take_pending_lifecycle_events();
+        for event in pending_lifecycle_events {
+            match event {
+                InstanceLifecycleEvent::Mount(node) => node.borrow_mut().handle_mount(rtc),
+                InstanceLifecycleEvent::Unmount(node) => node.borrow_mut().handle_unmount(rtc),
+            }
+        }
     }
 
     /// Simple 2D raycasting: the coordinates of the ray represent a
@@ -1117,9 +1403,16 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
         for node in nodes_ordered {
             // pax_runtime_api::log(&(**node).borrow().get_instance_id().to_string())
 
-            if (*node.instance_node)
+            //cheap rejection by bounding box before the node's own (possibly exact) hit test
+            let is_within_bounding_box = (*node.instance_node)
                 .borrow()
-                .ray_cast_test(&ray, &node.tab)
+                .get_bounding_box(&node.tab)
+                .is_some_and(|bounding_box| bounding_box.contains(Point::new(ray.0, ray.1)));
+
+            if is_within_bounding_box
+                && (*node.instance_node)
+                    .borrow()
+                    .ray_cast_test(&ray, &node.tab)
             {
                 //We only care about the topmost node getting hit, and the element
                 //pool is ordered by z-index so we can just resolve the whole
@@ -1169,9 +1462,68 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
 
     /// Called by chassis when viewport size changes, e.g. with native window resizes
     pub fn set_viewport_size(&mut self, new_viewport_size: (f64, f64)) {
+        if self.viewport_tab.bounds != new_viewport_size {
+            self.is_viewport_resized = true;
+            pax_runtime_api::mark_properties_dirty();
+        }
         self.viewport_tab.bounds = new_viewport_size;
     }
 
+    pub fn get_viewport_bounds(&self) -> (f64, f64) {
+        self.viewport_tab.bounds
+    }
+
+    /// Called by chassis when the device pixel ratio changes, e.g. when a window moves to a display
+    /// with a different density.  Fires `resize` handlers on the next tick, as with viewport resizes.
+    pub fn set_device_pixel_ratio(&mut self, device_pixel_ratio: f64) {
+        if self.device_pixel_ratio != device_pixel_ratio {
+            self.is_viewport_resized = true;
+            pax_runtime_api::mark_properties_dirty();
+        }
+        self.device_pixel_ratio = device_pixel_ratio;
+    }
+
+    /// Forces every expression to be re-evaluated on the next tick, rather than only those whose
+    /// dependencies may have changed; see `pax_runtime_api::mark_properties_dirty`
+    pub fn invalidate_properties(&self) {
+        pax_runtime_api::mark_properties_dirty();
+    }
+
+    /// Toggles the debug overlay, which outlines the bounding box (see `RenderNode::get_bounding_box`)
+    /// of every rendered node and labels it with its type, `instance_id` and layer type, for
+    /// inspecting layout and hit-testing
+    pub fn set_debug_overlay_enabled(&mut self, is_debug_overlay_enabled: bool) {
+        self.is_debug_overlay_enabled = is_debug_overlay_enabled;
+    }
+
+    /// Called by chassis on key down, before dispatching to userland: in debug builds, toggles the
+    /// debug overlay if `keyboard` is its shortcut (Ctrl + Shift + D).  Returns whether the event was
+    /// consumed, in which case it should not be dispatched further.  Always `false` in release builds.
+    pub fn handle_debug_overlay_shortcut(&mut self, keyboard: &KeyboardEventArgs) -> bool {
+        let is_shortcut = cfg!(debug_assertions)
+            && !keyboard.is_repeat
+            && keyboard
+                .key
+                .eq_ignore_ascii_case(DEBUG_OVERLAY_SHORTCUT_KEY)
+            && keyboard
+                .modifiers
+                .iter()
+                .any(|modifier| matches!(modifier, ModifierKey::Control))
+            && keyboard
+                .modifiers
+                .iter()
+                .any(|modifier| matches!(modifier, ModifierKey::Shift));
+        if is_shortcut {
+            self.is_debug_overlay_enabled = !self.is_debug_overlay_enabled;
+        }
+        is_shortcut
+    }
+
+    /// Called by chassis before each tick, with the wall-clock seconds elapsed since the engine started
+    pub fn set_seconds_elapsed(&mut self, seconds_elapsed: f64) {
+        self.seconds_elapsed = seconds_elapsed;
+    }
+
     /// Workhorse method to advance rendering and property calculation by one discrete tick
     /// Will be executed synchronously up to 240 times/second.
     pub fn tick(&mut self, rcs: &mut HashMap<String, R>) -> Vec<NativeMessage> {
@@ -1180,6 +1532,7 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
             .reset_repeat_expanded_node_cache();
         let native_render_queue = self.traverse_render_tree(rcs);
         self.frames_elapsed = self.frames_elapsed + 1;
+        self.is_viewport_resized = false;
         native_render_queue
     }
 
@@ -1194,3 +1547,11 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
             .insert(id_chain, (Box::new(image_data), width, height));
     }
 }
+
+/// Returns the smallest rect enclosing both `a` and `b`, either of which may be absent
+fn union_bounding_boxes(a: Option<kurbo::Rect>, b: Option<kurbo::Rect>) -> Option<kurbo::Rect> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.union(b)),
+        (a, b) => a.or(b),
+    }
+}
@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 use piet::RenderContext;
 use std::rc::Rc;
@@ -16,6 +16,13 @@ pub struct PropertyExpression<T: Default> {
     pub id: usize,
     pub cached_value: T,
     pub transition_manager: TransitionManager<T>,
+    /// The properties epoch (see `pax_runtime_api::mark_properties_dirty`) as of this expression's
+    /// last evaluation, and the tick during which it was last computed, whether evaluated or skipped
+    last_evaluation: Cell<Option<(usize, usize)>>,
+    /// Whether this expression has been computed more than once in a single tick, e.g. as a descendant
+    /// of `Repeat`, each time against a different stack frame.  `cached_value` then holds the result
+    /// of whichever evaluation came last, so the expression is never skipped.
+    is_computed_per_stack_frame: Cell<bool>,
 }
 
 impl<T: Default> PropertyExpression<T> {
@@ -24,6 +31,8 @@ impl<T: Default> PropertyExpression<T> {
             id,
             cached_value: Default::default(),
             transition_manager: TransitionManager::new(),
+            last_evaluation: Cell::new(None),
+            is_computed_per_stack_frame: Cell::new(false),
         }
     }
 }
@@ -37,6 +46,10 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyExpression<T> {
         unreachable!()
     }
 
+    fn _get_mut_untracked(&mut self) -> &mut T {
+        &mut self.cached_value
+    }
+
     // fn is_fresh(&self) -> bool {
     //     self.is_fresh
     // }
@@ -49,6 +62,28 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyExpression<T> {
         Some(self.id)
     }
 
+    /// Dirty unless nothing has been marked dirty since this expression's last evaluation.
+    /// Note that the epoch is captured _before_ evaluation, so that an expression which marks
+    /// properties dirty itself (e.g. by reading `$frames_elapsed`) is re-evaluated every tick.
+    fn _get_dirty_vtable_id(&self, frames_elapsed: usize) -> Option<usize> {
+        let epoch = pax_runtime_api::_get_properties_epoch();
+        let is_clean = match self.last_evaluation.get() {
+            Some((last_epoch, last_frame)) => {
+                if last_frame == frames_elapsed {
+                    self.is_computed_per_stack_frame.set(true);
+                }
+                last_epoch == epoch && !self.is_computed_per_stack_frame.get()
+            }
+            None => false,
+        };
+        self.last_evaluation.set(Some((epoch, frames_elapsed)));
+        if is_clean {
+            None
+        } else {
+            Some(self.id)
+        }
+    }
+
     fn set(&mut self, value: T) {
         self.cached_value = value;
     }
@@ -102,3 +137,18 @@ pub struct ExpressionContext<'a, R: 'static + RenderContext> {
     //TODO: is the following the right approach to enabling evaluation of built-ins?
     // pub render_node: RenderNodePtr<R>,
 }
+
+impl<'a, R: 'static + RenderContext> ExpressionContext<'a, R> {
+    /// Reads the `$frames_elapsed` built-in.  Since it changes every tick, marks properties dirty,
+    /// so that this expression and anything derived from it are re-evaluated every tick.
+    pub fn read_frames_elapsed(&self) -> usize {
+        pax_runtime_api::mark_properties_dirty();
+        self.engine.frames_elapsed
+    }
+
+    /// Reads the `$time` built-in, marking properties dirty as with `read_frames_elapsed`
+    pub fn read_seconds_elapsed(&self) -> f64 {
+        pax_runtime_api::mark_properties_dirty();
+        self.engine.seconds_elapsed
+    }
+}
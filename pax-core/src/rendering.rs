@@ -11,7 +11,7 @@ use piet_common::RenderContext;
 
 use pax_runtime_api::{ArgsScroll, Layer, Size};
 
-use crate::{HandlerRegistry, InstanceRegistry, RenderTreeContext};
+use crate::{ConditionalArm, HandlerRegistry, InstanceRegistry, RenderTreeContext};
 
 use pax_runtime_api::PropertyInstance;
 
@@ -41,9 +41,16 @@ pub struct InstantiationArgs<R: 'static + RenderContext> {
     pub repeat_source_expression_vec:
         Option<Box<dyn PropertyInstance<Vec<Rc<PropertiesCoproduct>>>>>,
     pub repeat_source_expression_range: Option<Box<dyn PropertyInstance<std::ops::Range<isize>>>>,
+    ///used by Repeat — optional per-datum key, by which instances are reused across changes to the source
+    pub repeat_key_expression: Option<Box<dyn PropertyInstance<String>>>,
+    ///used by keyed Repeat — instantiates a new copy of the repeated template, so that each key
+    ///renders (and keeps the state of) instances of its own
+    pub repeat_template_factory: Option<Box<dyn Fn() -> RenderNodePtrList<R>>>,
 
-    ///used by Conditional
-    pub conditional_boolean_expression: Option<Box<dyn PropertyInstance<bool>>>,
+    ///used by Conditional — the ordered `if` / `else if` arms, each a condition and the children it renders
+    pub conditional_arms: Option<Vec<ConditionalArm<R>>>,
+    ///used by Conditional — the `else` branch, rendered while no arm's condition is true
+    pub conditional_else_children: Option<RenderNodePtrList<R>>,
 
     ///used by Component instances, specifically to unwrap type-specific PropertiesCoproducts
     ///and recurse into descendant property computation
@@ -164,6 +171,62 @@ impl TransformAndBounds {
 
         true
     }
+
+    /// Maps a point in global (scroller-normalized) space into this node's local,
+    /// pre-transform space, where the node occupies `(0,0)` to `bounds`.
+    pub fn inverse_transform_point(&self, point: &(f64, f64)) -> Point {
+        self.transform.inverse()
+            * Point {
+                x: point.0,
+                y: point.1,
+            }
+    }
+
+    /// Returns the axis-aligned rect, in global (scroller-normalized) coordinates, that encloses
+    /// these bounds (or clipping bounds, if present) after transformation.
+    pub fn get_bounding_box(&self) -> kurbo::Rect {
+        let relevant_bounds = match self.clipping_bounds {
+            None => self.bounds,
+            Some(cp) => cp,
+        };
+        self.transform.transform_rect_bbox(kurbo::Rect::new(
+            0.0,
+            0.0,
+            relevant_bounds.0,
+            relevant_bounds.1,
+        ))
+    }
+
+    /// Determines whether `point` falls within the transformed rectilinear bounding hull
+    /// (or clipping bounds, if present) described by this `TransformAndBounds`.
+    pub fn contains_point(&self, point: &(f64, f64)) -> bool {
+        let local_point = self.inverse_transform_point(point);
+
+        let relevant_bounds = match self.clipping_bounds {
+            None => self.bounds,
+            Some(cp) => cp,
+        };
+
+        local_point.x > 0.0
+            && local_point.y > 0.0
+            && local_point.x < relevant_bounds.0
+            && local_point.y < relevant_bounds.1
+    }
+
+    /// Determines whether `point` falls within the ellipse inscribed in the transformed bounds
+    /// described by this `TransformAndBounds`, rather than within its bounding box.
+    pub fn contains_point_in_ellipse(&self, point: &(f64, f64)) -> bool {
+        let local_point = self.inverse_transform_point(point);
+        let radius_x = self.bounds.0 / 2.0;
+        let radius_y = self.bounds.1 / 2.0;
+        if radius_x <= 0.0 || radius_y <= 0.0 {
+            return false;
+        }
+
+        let normalized_x = (local_point.x - radius_x) / radius_x;
+        let normalized_y = (local_point.y - radius_y) / radius_y;
+        normalized_x * normalized_x + normalized_y * normalized_y < 1.0
+    }
 }
 
 /// The base trait for a RenderNode, representing any node that can
@@ -201,19 +264,18 @@ pub trait RenderNode<R: 'static + RenderContext> {
             return false;
         }
 
-        let inverted_transform = tab.transform.inverse();
-        let transformed_ray = inverted_transform * Point { x: ray.0, y: ray.1 };
-
-        let relevant_bounds = match tab.clipping_bounds {
-            None => tab.bounds,
-            Some(cp) => cp,
-        };
+        //Default implementation: rectilinear bounding hull, after inverse-transforming
+        //the ray through this node's accumulated transform
+        tab.contains_point(ray)
+    }
 
-        //Default implementation: rectilinear bounding hull
-        transformed_ray.x > 0.0
-            && transformed_ray.y > 0.0
-            && transformed_ray.x < relevant_bounds.0
-            && transformed_ray.y < relevant_bounds.1
+    /// Returns this node's on-screen rect in global (scroller-normalized) coordinates, given the
+    /// transform and bounds computed for it during render tree traversal, or `None` if this node
+    /// has no extent of its own (e.g. `Group`).  Used by the engine to short-circuit ray-casting
+    /// and to draw the debug overlay; primitives whose geometry isn't described by their size
+    /// (e.g. the unsized `Path`) should override this.
+    fn get_bounding_box(&self, tab: &TransformAndBounds) -> Option<kurbo::Rect> {
+        self.get_size().map(|_| tab.get_bounding_box())
     }
 
     fn get_common_properties(&self) -> &CommonProperties;
@@ -268,13 +330,17 @@ pub trait RenderNode<R: 'static + RenderContext> {
     }
 
     /// Returns the size of this node in pixels, requiring
-    /// parent bounds for calculation of `Percent` values
+    /// parent bounds for calculation of `Percent` values.
+    /// The result is clamped by any `min_*` / `max_*` common properties.
     fn compute_size_within_bounds(&self, bounds: (f64, f64)) -> (f64, f64) {
         match self.get_size() {
             None => bounds,
-            Some(size_raw) => (
-                size_raw.0.evaluate(bounds, Axis::X),
-                size_raw.1.evaluate(bounds, Axis::Y),
+            Some(size_raw) => self.get_common_properties().clamp_size(
+                (
+                    size_raw.0.evaluate(bounds, Axis::X),
+                    size_raw.1.evaluate(bounds, Axis::Y),
+                ),
+                bounds,
             ),
         }
     }
@@ -362,6 +428,32 @@ pub trait RenderNode<R: 'static + RenderContext> {
         //no-op default implementation
     }
 
+    /// Fires once per instance, when this instance is registered with the `InstanceRegistry`, i.e. when
+    /// it enters the instance tree — for example when created by a `Repeat`.  Unlike `handle_did_mount`,
+    /// does not fire again for each of a `Repeat`'s expansions of a shared node.  Fires before this
+    /// instance's first `handle_did_mount`.
+    /// A use-case: acquire resources held for the lifetime of this instance, like timers or image handles
+    fn handle_mount(&mut self, _rtc: &mut RenderTreeContext<R>) {
+        //no-op default implementation
+    }
+
+    /// Fires once per instance, when this instance is deregistered from the `InstanceRegistry`, i.e. when
+    /// it leaves the instance tree — for example when discarded by a keyed `Repeat` or a `Conditional`.
+    /// Fires before this instance's `handle_will_unmount`.
+    /// A use-case: release resources acquired in `handle_mount`
+    fn handle_unmount(&mut self, _rtc: &mut RenderTreeContext<R>) {
+        //no-op default implementation
+    }
+
+    /// Returns the name of this RenderNode's type, e.g. `Rectangle` for `RectangleInstance`,
+    /// for debugging.  Note that every component instance is named `Component`.
+    fn get_type_name(&self) -> &'static str {
+        let type_name = std::any::type_name::<Self>();
+        let type_name = type_name.split('<').next().unwrap_or(type_name);
+        let type_name = type_name.rsplit("::").next().unwrap_or(type_name);
+        type_name.strip_suffix("Instance").unwrap_or(type_name)
+    }
+
     /// Returns the layer type (`Layer::Native` or `Layer::Canvas`) for this RenderNode.
     /// Default is `Layer::Canvas`, and must be overwritten for native rendering
     fn get_layer_type(&mut self) -> Layer {
@@ -378,6 +470,14 @@ pub trait RenderNode<R: 'static + RenderContext> {
     fn get_scroll_offset(&mut self) -> (f64, f64) {
         (0.0, 0.0)
     }
+
+    /// Invoked by the engine after this node's children have rendered, with the rect enclosing them in
+    /// this node's own coordinate space, before any scroll offset is applied.  Content that a descendant
+    /// clips away is not included.  Not invoked for `Layer::Scroller` nodes, nor for nodes without
+    /// any sized descendants.  A use-case: clamping a scrolling container's scroll offset to its content.
+    fn handle_content_bounds(&mut self, _content_bounds: kurbo::Rect) {
+        //no-op default implementation
+    }
 }
 
 pub trait LifecycleNode {}
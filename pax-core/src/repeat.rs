@@ -1,4 +1,6 @@
 use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use crate::{
@@ -14,11 +16,20 @@ use piet_common::RenderContext;
 /// That is: for a `source_expression` of length `n`, `Repeat` will render its
 /// template `n` times, each with an embedded component context (`RepeatItem`)
 /// with an index `i` and a pointer to that relevant datum `source_expression[i]`
+///
+/// When a `key_expression` is provided (`for item in self.items key={item.id}`), Repeat
+/// diffs its children by key: each key renders its own instances of the template, built by
+/// `repeated_template_factory`, and a datum whose key matches an existing child reuses that child
+/// (preserving its instances and any state held by them), even if it moved within the source.
+/// Keys are only re-evaluated once a property may have changed.
+/// Unkeyed Repeats render the same shared `repeated_template` for every datum.
 pub struct RepeatInstance<R: 'static + RenderContext> {
     pub instance_id: u32,
     pub repeated_template: RenderNodePtrList<R>,
+    pub repeated_template_factory: Option<Box<dyn Fn() -> RenderNodePtrList<R>>>,
     pub source_expression_vec: Option<Box<dyn PropertyInstance<Vec<Rc<PropertiesCoproduct>>>>>,
     pub source_expression_range: Option<Box<dyn PropertyInstance<std::ops::Range<isize>>>>,
+    pub key_expression: Option<Box<dyn PropertyInstance<String>>>,
     pub active_children: RenderNodePtrList<R>,
     pub cleanup_children: RenderNodePtrList<R>,
     pub common_properties: CommonProperties,
@@ -26,6 +37,170 @@ pub struct RepeatInstance<R: 'static + RenderContext> {
     cached_old_value_vec: Option<Vec<Rc<PropertiesCoproduct>>>,
     cached_old_value_range: Option<std::ops::Range<isize>>,
     cached_old_bounds: (f64, f64),
+    /// For keyed Repeats: the key and `RepeatItem` properties of each of `active_children`, in order
+    active_children_keys: Vec<(String, Rc<RefCell<PropertiesCoproduct>>)>,
+    /// For keyed Repeats: the properties epoch (see `pax_runtime_api::mark_properties_dirty`) as of the
+    /// last evaluation of the keys, which are not re-evaluated until a property changes, and the tick
+    /// during which they were last computed, whether evaluated or skipped
+    active_children_keys_evaluation: Option<(usize, usize)>,
+    /// Whether this Repeat has been computed more than once in a single tick, e.g. as a descendant of
+    /// another Repeat, in which case its keys are evaluated every time, since its source differs per stack frame
+    is_computed_per_stack_frame: bool,
+}
+
+impl<R: 'static + RenderContext> RepeatInstance<R> {
+    /// Wraps `properties` (a `RepeatItem`) in a new `ComponentInstance`, registered and marked as mounted,
+    /// rendering `template`
+    fn instantiate_repeat_item(
+        &self,
+        rtc: &RenderTreeContext<R>,
+        forwarded_children: &RenderNodePtrList<R>,
+        template: RenderNodePtrList<R>,
+        properties: Rc<RefCell<PropertiesCoproduct>>,
+    ) -> RenderNodePtr<R> {
+        let mut instance_registry = (*rtc.engine.instance_registry).borrow_mut();
+        let instance_id = instance_registry.mint_id();
+        let common_properties = CommonProperties::default();
+
+        let render_node: RenderNodePtr<R> = Rc::new(RefCell::new(ComponentInstance {
+            instance_id,
+            children: Rc::clone(forwarded_children),
+            template,
+            common_properties,
+            properties,
+            timeline: None,
+            handler_registry: None,
+            compute_properties_fn: Box::new(|_props, _rtc| {
+                //no-op since the Repeat RenderNode handles the necessary calc (see `RepeatInstance::compute_properties`)
+            }),
+        }));
+
+        instance_registry.register(instance_id, Rc::clone(&render_node));
+        instance_registry.mark_mounted(rtc.get_id_chain(instance_id));
+
+        render_node
+    }
+
+    /// Any stated children (repeat template members) of Repeat should be forwarded to the `RepeatItem`-wrapped `ComponentInstance`s
+    /// so that `Slot` works as expected
+    fn get_forwarded_children(rtc: &RenderTreeContext<R>) -> RenderNodePtrList<R> {
+        match (*rtc.runtime).borrow_mut().peek_stack_frame() {
+            Some(frame) => Rc::clone(&(*frame.borrow()).get_unflattened_adoptees()),
+            None => Rc::new(RefCell::new(vec![])),
+        }
+    }
+
+    /// Diffs `active_children` against the new source by key, reusing each child whose key is still present
+    /// (updating its `RepeatItem` in place), instantiating children for new keys, each with a new copy of
+    /// the template, and unmounting the rest.  If more than one datum shares a key, only the first reuses
+    /// the existing child.
+    fn reconcile_keyed_children(
+        &mut self,
+        rtc: &mut RenderTreeContext<R>,
+        normalized_vec_of_props: Vec<Rc<PropertiesCoproduct>>,
+    ) {
+        let key_vtable_id = self.key_expression.as_ref().unwrap()._get_vtable_id();
+
+        //evaluate each datum's key inside a `RepeatItem` stack frame, as seen by that datum's descendants
+        let len = normalized_vec_of_props.len();
+        let new_keys: Vec<String> = normalized_vec_of_props
+            .iter()
+            .enumerate()
+            .map(|(i, datum)| {
+                (*rtc.runtime).borrow_mut().push_stack_frame(
+                    Rc::new(RefCell::new(vec![])),
+                    Rc::new(RefCell::new(PropertiesCoproduct::RepeatItem(
+                        Rc::clone(datum),
+                        i,
+                        len,
+                    ))),
+                    None,
+                );
+                let key = if let Some(TypesCoproduct::String(key)) =
+                    rtc.compute_vtable_value(key_vtable_id)
+                {
+                    key
+                } else {
+                    unreachable!()
+                };
+                (*rtc.runtime).borrow_mut().pop_stack_frame();
+                key
+            })
+            .collect();
+
+        //same keys in the same order: only the data may have changed, so refresh each `RepeatItem` in place
+        if (*self.active_children).borrow().len() == self.active_children_keys.len()
+            && new_keys
+                .iter()
+                .eq(self.active_children_keys.iter().map(|(key, _)| key))
+        {
+            self.active_children_keys
+                .iter()
+                .zip(normalized_vec_of_props.iter())
+                .enumerate()
+                .for_each(|(i, ((_, properties), datum))| {
+                    *properties.borrow_mut() =
+                        PropertiesCoproduct::RepeatItem(Rc::clone(datum), i, len);
+                });
+            return;
+        }
+
+        //index existing children by key; any child without a (unique) key is stale
+        let mut stale_children = vec![];
+        let mut reusable_children = HashMap::new();
+        let mut old_keys = std::mem::take(&mut self.active_children_keys).into_iter();
+        for child in (*self.active_children).borrow().iter() {
+            match old_keys.next() {
+                Some((key, properties)) => match reusable_children.entry(key) {
+                    Entry::Vacant(entry) => {
+                        entry.insert((Rc::clone(child), properties));
+                    }
+                    Entry::Occupied(_) => stale_children.push(Rc::clone(child)),
+                },
+                None => stale_children.push(Rc::clone(child)),
+            }
+        }
+
+        let forwarded_children = Self::get_forwarded_children(rtc);
+
+        let mut active_children = vec![];
+        for (i, (datum, key)) in normalized_vec_of_props.iter().zip(new_keys).enumerate() {
+            let repeat_item = PropertiesCoproduct::RepeatItem(Rc::clone(datum), i, len);
+            let (child, properties) = match reusable_children.remove(&key) {
+                Some((child, properties)) => {
+                    *properties.borrow_mut() = repeat_item;
+                    (child, properties)
+                }
+                None => {
+                    let properties = Rc::new(RefCell::new(repeat_item));
+                    let template = match &self.repeated_template_factory {
+                        Some(repeated_template_factory) => repeated_template_factory(),
+                        None => Rc::clone(&self.repeated_template),
+                    };
+                    let child = self.instantiate_repeat_item(
+                        rtc,
+                        &forwarded_children,
+                        template,
+                        Rc::clone(&properties),
+                    );
+                    (child, properties)
+                }
+            };
+            active_children.push(child);
+            self.active_children_keys.push((key, properties));
+        }
+
+        stale_children.extend(reusable_children.into_values().map(|(child, _)| child));
+        let mut instance_registry = (*rtc.engine.instance_registry).borrow_mut();
+        stale_children.iter().for_each(|child| {
+            let instance_id = (*(*child)).borrow_mut().get_instance_id();
+            instance_registry.deregister(instance_id);
+            instance_registry.mark_for_unmount(instance_id);
+        });
+
+        self.cleanup_children = Rc::new(RefCell::new(stale_children));
+        self.active_children = Rc::new(RefCell::new(active_children));
+    }
 }
 
 impl<R: 'static + RenderContext> RenderNode<R> for RepeatInstance<R> {
@@ -52,11 +227,16 @@ impl<R: 'static + RenderContext> RenderNode<R> for RepeatInstance<R> {
             common_properties: args.common_properties,
             source_expression_vec: args.repeat_source_expression_vec,
             source_expression_range: args.repeat_source_expression_range,
+            key_expression: args.repeat_key_expression,
+            repeated_template_factory: args.repeat_template_factory,
             active_children: Rc::new(RefCell::new(vec![])),
             cleanup_children: Rc::new(RefCell::new(vec![])),
             cached_old_value_vec: None,
             cached_old_value_range: None,
             cached_old_bounds: (0.0, 0.0),
+            active_children_keys: vec![],
+            active_children_keys_evaluation: None,
+            is_computed_per_stack_frame: false,
         }));
 
         instance_registry.register(instance_id, Rc::clone(&ret) as RenderNodePtr<R>);
@@ -64,12 +244,15 @@ impl<R: 'static + RenderContext> RenderNode<R> for RepeatInstance<R> {
     }
 
     fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
+        //captured before evaluating the source, which may itself mark properties dirty
+        let properties_epoch = pax_runtime_api::_get_properties_epoch();
+
         let (is_dirty, normalized_vec_of_props) = if let Some(se) = &self.source_expression_vec {
             //Handle case where the source expression is a Vec<Property<T>>,
             // like `for elem in self.data_list`
             let new_value = if let Some(tc) = rtc.compute_vtable_value(se._get_vtable_id().clone())
             {
-                if let TypesCoproduct::stdCOCOvecCOCOVecLABRstdCOCOrcCOCORcLABRPropertiesCoproductRABRRABR(vec) = tc { vec } else { unreachable!() }
+                if let TypesCoproduct::std_COCOvec_COCOVec_LABRstd_COCOrc_COCORc_LABRPropertiesCoproduct_RABR_RABR(vec) = tc { vec } else { unreachable!() }
             } else {
                 se.get().clone()
             };
@@ -92,7 +275,7 @@ impl<R: 'static + RenderContext> RenderNode<R> for RepeatInstance<R> {
             // like `for i in 0..5`
             let new_value = if let Some(tc) = rtc.compute_vtable_value(se._get_vtable_id().clone())
             {
-                if let TypesCoproduct::stdCOCOopsCOCORangeLABRisizeRABR(vec) = tc {
+                if let TypesCoproduct::std_COCOops_COCORange_LABRisize_RABR(vec) = tc {
                     vec
                 } else {
                     unreachable!()
@@ -123,24 +306,37 @@ impl<R: 'static + RenderContext> RenderNode<R> for RepeatInstance<R> {
             unreachable!()
         };
 
-        if is_dirty {
-            //Any stated children (repeat template members) of Repeat should be forwarded to the `RepeatItem`-wrapped `ComponentInstance`s
-            //so that `Slot` works as expected
-            let forwarded_children = match (*rtc.runtime).borrow_mut().peek_stack_frame() {
-                Some(frame) => Rc::clone(&(*frame.borrow()).get_unflattened_adoptees()),
-                None => Rc::new(RefCell::new(vec![])),
+        if self.key_expression.is_some() {
+            //keyed Repeats diff by key whenever any property has changed, rather than relying on the
+            //cardinality-based dirty check above; until then, neither the keys nor the data can have changed
+            let frames_elapsed = rtc.engine.frames_elapsed;
+            let is_clean = match self.active_children_keys_evaluation {
+                Some((last_epoch, last_frame)) => {
+                    if last_frame == frames_elapsed {
+                        self.is_computed_per_stack_frame = true;
+                    }
+                    last_epoch == properties_epoch && !self.is_computed_per_stack_frame
+                }
+                None => false,
             };
+            self.active_children_keys_evaluation = Some((properties_epoch, frames_elapsed));
+            if !is_clean {
+                self.reconcile_keyed_children(rtc, normalized_vec_of_props);
+            }
+        } else if is_dirty {
+            let forwarded_children = Self::get_forwarded_children(rtc);
 
-            let mut instance_registry = (*rtc.engine.instance_registry).borrow_mut();
-
-            (*self.active_children)
-                .borrow_mut()
-                .iter()
-                .for_each(|child| {
-                    let instance_id = (*(*child)).borrow_mut().get_instance_id();
-                    instance_registry.deregister(instance_id);
-                    instance_registry.mark_for_unmount(instance_id);
-                });
+            {
+                let mut instance_registry = (*rtc.engine.instance_registry).borrow_mut();
+                (*self.active_children)
+                    .borrow_mut()
+                    .iter()
+                    .for_each(|child| {
+                        let instance_id = (*(*child)).borrow_mut().get_instance_id();
+                        instance_registry.deregister(instance_id);
+                        instance_registry.mark_for_unmount(instance_id);
+                    });
+            }
 
             self.cleanup_children = self.active_children.clone();
 
@@ -152,32 +348,16 @@ impl<R: 'static + RenderContext> RenderNode<R> for RepeatInstance<R> {
                     .iter()
                     .enumerate()
                     .map(|(i, datum)| {
-                        let instance_id = instance_registry.mint_id();
-                        let common_properties = CommonProperties::default();
-
-                        let render_node: RenderNodePtr<R> =
-                            Rc::new(RefCell::new(ComponentInstance {
-                                instance_id,
-                                children: Rc::clone(&forwarded_children),
-                                template: Rc::clone(&self.repeated_template),
-                                common_properties,
-                                properties: Rc::new(RefCell::new(PropertiesCoproduct::RepeatItem(
-                                    Rc::clone(datum),
-                                    i,
-                                ))),
-                                timeline: None,
-                                handler_registry: None,
-                                compute_properties_fn: Box::new(|_props, _rtc| {
-                                    //no-op since the Repeat RenderNode handles the necessary calc (see `RepeatInstance::compute_properties`)
-                                }),
-                            }));
-
-                        instance_registry.register(instance_id, Rc::clone(&render_node));
-                        instance_registry.mark_mounted(rtc.get_id_chain(instance_id));
-
-                        // (&*render_node).borrow_mut().mount_recursive(rtc);
-
-                        render_node
+                        self.instantiate_repeat_item(
+                            rtc,
+                            &forwarded_children,
+                            Rc::clone(&self.repeated_template),
+                            Rc::new(RefCell::new(PropertiesCoproduct::RepeatItem(
+                                Rc::clone(datum),
+                                i,
+                                normalized_vec_of_props.len(),
+                            ))),
+                        )
                     })
                     .collect(),
             ));
@@ -211,6 +391,12 @@ impl<R: 'static + RenderContext> RenderNode<R> for RepeatInstance<R> {
         self.cached_old_value_range = None;
         self.cached_old_value_vec = None;
     }
+
+    fn handle_will_unmount(&mut self, _rtc: &mut RenderTreeContext<R>) {
+        //don't reuse keyed children across an unmount; if remounted, this Repeat's children are rebuilt
+        self.active_children_keys.clear();
+        self.active_children_keys_evaluation = None;
+    }
 }
 
 /*
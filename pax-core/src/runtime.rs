@@ -1,6 +1,5 @@
 use std::borrow::Borrow;
 use std::cell::RefCell;
-use std::collections::VecDeque;
 use std::ops::Deref;
 use std::rc::{Rc, Weak};
 
@@ -22,7 +21,10 @@ pub struct Runtime<R: 'static + RenderContext> {
     clipping_stack: Vec<Vec<u32>>,
     /// Similar to clipping stack but for scroller containers
     scroller_stack: Vec<Vec<u32>>,
-    native_message_queue: VecDeque<pax_message::NativeMessage>,
+    /// Frame-level batch of native patches: every message enqueued during a tick is buffered here
+    /// and handed to the chassis as a single `Vec` at the end of that tick, rather than crossing
+    /// the FFI / wasm boundary once per message
+    native_message_queue: Vec<pax_message::NativeMessage>,
 }
 
 impl<R: 'static + RenderContext> Runtime<R> {
@@ -31,7 +33,7 @@ impl<R: 'static + RenderContext> Runtime<R> {
             stack: vec![],
             clipping_stack: vec![],
             scroller_stack: vec![],
-            native_message_queue: VecDeque::new(),
+            native_message_queue: Vec::new(),
         }
     }
 
@@ -42,7 +44,7 @@ impl<R: 'static + RenderContext> Runtime<R> {
         let mut indices: Vec<u32> = vec![];
 
         self.stack.iter().for_each(|frame_wrapped| {
-            if let PropertiesCoproduct::RepeatItem(_datum, i) =
+            if let PropertiesCoproduct::RepeatItem(_datum, i, _len) =
                 &*(*(*(*frame_wrapped).borrow_mut()).borrow().properties).borrow()
             {
                 indices.push(*i as u32)
@@ -52,12 +54,13 @@ impl<R: 'static + RenderContext> Runtime<R> {
     }
 
     //return current state of native message queue, passing in a freshly initialized queue for next frame
-    pub fn take_native_message_queue(&mut self) -> VecDeque<pax_message::NativeMessage> {
+    pub fn take_native_message_queue(&mut self) -> Vec<pax_message::NativeMessage> {
         std::mem::take(&mut self.native_message_queue)
     }
 
+    /// Buffers `msg` into this tick's batch; see `native_message_queue`
     pub fn enqueue_native_message(&mut self, msg: pax_message::NativeMessage) {
-        self.native_message_queue.push_back(msg);
+        self.native_message_queue.push(msg);
     }
 
     /// Return a pointer to the top StackFrame on the stack,
@@ -230,6 +233,12 @@ impl<R: 'static + RenderContext> StackFrame<R> {
         Rc::clone(&self.adoptees)
     }
 
+    /// Alias of [`StackFrame::get_unflattened_adoptees`]; adoptees are flattened before
+    /// being pushed to the stack, so this is the complete list passed to the current component
+    pub fn get_adoptees(&self) -> RenderNodePtrList<R> {
+        Rc::clone(&self.adoptees)
+    }
+
     pub fn nth_adoptee(&self, n: usize) -> Option<RenderNodePtr<R>> {
         match (*self.adoptees).borrow().get(n) {
             Some(i) => Some(Rc::clone(i)),
@@ -19,9 +19,13 @@ use pax_runtime_api::{CommonProperties, Layer, PropertyInstance, Size};
 /// the outside.  Inside Stacker's template, there are a number of Slots — this primitive —
 /// that become the final rendered home of those adoptees.  This same technique
 /// is portable and applicable elsewhere via Slot.
+///
+/// When `index` is `None`, Slot renders _all_ adoptees from the current stack frame
+/// (a "rest" slot), which is useful for layout components that forward their adoptees
+/// wholesale rather than one cell at a time.
 pub struct SlotInstance<R: 'static + RenderContext> {
     pub instance_id: u32,
-    pub index: Box<dyn PropertyInstance<pax_runtime_api::Numeric>>,
+    pub index: Option<Box<dyn PropertyInstance<pax_runtime_api::Numeric>>>,
     pub common_properties: CommonProperties,
     cached_computed_children: RenderNodePtrList<R>,
 }
@@ -43,7 +47,7 @@ impl<R: 'static + RenderContext> RenderNode<R> for SlotInstance<R> {
         let ret = Rc::new(RefCell::new(Self {
             instance_id,
             common_properties: args.common_properties,
-            index: args.slot_index.expect("index required for Slot"),
+            index: args.slot_index,
             cached_computed_children: Rc::new(RefCell::new(vec![])),
         }));
         instance_registry.register(instance_id, Rc::clone(&ret) as RenderNodePtr<R>);
@@ -62,29 +66,36 @@ impl<R: 'static + RenderContext> RenderNode<R> for SlotInstance<R> {
     }
 
     fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
-        if let Some(index) = rtc.compute_vtable_value(self.index._get_vtable_id()) {
-            let new_value = if let TypesCoproduct::Numeric(v) = index {
-                v
-            } else {
-                unreachable!()
-            };
-            self.index.set(new_value);
+        if let Some(index) = self.index.as_mut() {
+            if let Some(new_index) = rtc.compute_vtable_value(index._get_vtable_id()) {
+                let new_value = if let TypesCoproduct::Numeric(v) = new_index {
+                    v
+                } else {
+                    unreachable!()
+                };
+                index.set(new_value);
+            }
         }
 
         // The following sort of children-caching is done by "control flow" primitives
         // (Slot, Repeat, If) —
         self.cached_computed_children = match rtc.runtime.borrow_mut().peek_stack_frame() {
-            Some(stack_frame) => {
+            Some(stack_frame) => match &self.index {
                 // Grab the adoptee from the current stack_frame at Slot's specified `index`
                 // then make it Slot's own child.
-                match stack_frame
+                Some(index) => match stack_frame
                     .borrow()
-                    .nth_adoptee(self.index.get().get_as_int() as usize)
+                    .nth_adoptee(index.get().get_as_int() as usize)
                 {
                     Some(rnp) => Rc::new(RefCell::new(vec![Rc::clone(&rnp)])),
                     None => Rc::new(RefCell::new(vec![])),
-                }
-            }
+                },
+                // No `index`: adopt the full list of adoptees as Slot's own children.
+                // Cloned into a fresh list so that Slot's children are not aliased to the frame's.
+                None => Rc::new(RefCell::new(
+                    (*stack_frame.borrow().get_adoptees()).borrow().clone(),
+                )),
+            },
             None => Rc::new(RefCell::new(vec![])),
         }
     }
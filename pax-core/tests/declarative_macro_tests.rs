@@ -1,4 +1,4 @@
-use pax_core::unsafe_unwrap;
+use pax_core::{try_unwrap, unsafe_unwrap, unsafe_unwrap_ref};
 
 #[derive(Debug, PartialEq, Default)]
 #[repr(C)]
@@ -26,3 +26,40 @@ fn test_unwrap_invalid_size() {
     let fruit = Fruit::Apple("red".to_string());
     let _unwrapped_fruit = unsafe_unwrap!(fruit, Fruit, Fruit);
 }
+
+#[test]
+fn test_unwrap_ref_apple() {
+    let fruit = Fruit::Apple("green".to_string());
+    let expected_color = "green".to_string();
+    let unwrapped_color = unsafe_unwrap_ref!(fruit, Fruit, String);
+    assert_eq!(unwrapped_color, &expected_color);
+    assert_eq!(fruit, Fruit::Apple("green".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "The size_of target_type must be less than the size_of enum_type.")]
+fn test_unwrap_ref_invalid_size() {
+    let fruit = Fruit::Apple("red".to_string());
+    let _unwrapped_fruit = unsafe_unwrap_ref!(fruit, Fruit, Fruit);
+}
+
+#[test]
+fn test_try_unwrap_apple() {
+    let fruit = Fruit::Apple("green".to_string());
+    let unwrapped_color = try_unwrap!(fruit, Fruit, Apple);
+    assert_eq!(unwrapped_color, Some("green".to_string()));
+}
+
+#[test]
+fn test_try_unwrap_mismatched_variant() {
+    let fruit = Fruit::Apple("red".to_string());
+    let unwrapped_color = try_unwrap!(fruit, Fruit, Banana);
+    assert_eq!(unwrapped_color, None);
+}
+
+#[test]
+fn test_try_unwrap_enum_path() {
+    let fruit = Fruit::Apple("green".to_string());
+    let unwrapped_color = try_unwrap!(fruit, crate::Fruit, Apple);
+    assert_eq!(unwrapped_color, Some("green".to_string()));
+}
@@ -0,0 +1,115 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
+use pax_core::{
+    ComponentInstance, ExpressionContext, InstanceRegistry, InstantiationArgs, PaxEngine,
+    PropertyExpression, RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+};
+use pax_runtime_api::{CommonProperties, PropertyInstance, PropertyLiteral};
+use piet::NullRenderContext;
+
+type R = NullRenderContext;
+type ComputePropertiesFn =
+    Box<dyn FnMut(Rc<RefCell<PropertiesCoproduct>>, &mut RenderTreeContext<R>)>;
+type ExpressionTable = HashMap<usize, Box<dyn Fn(ExpressionContext<R>) -> TypesCoproduct>>;
+
+/// Stands in for a nested property type like `Stroke`, whose fields are themselves properties
+#[derive(Default, Clone)]
+struct Nested {
+    width: Box<dyn PropertyInstance<f64>>,
+}
+
+fn instantiate_component(
+    instance_registry: &Rc<RefCell<InstanceRegistry<R>>>,
+    component_template: Option<RenderNodePtrList<R>>,
+    compute_properties_fn: ComputePropertiesFn,
+) -> Rc<RefCell<ComponentInstance<R>>> {
+    ComponentInstance::instantiate(InstantiationArgs {
+        common_properties: CommonProperties::default(),
+        properties: PropertiesCoproduct::None,
+        handler_registry: None,
+        instance_registry: Rc::clone(instance_registry),
+        children: None,
+        component_template,
+        scroller_args: None,
+        slot_index: None,
+        repeat_source_expression_vec: None,
+        repeat_source_expression_range: None,
+        repeat_key_expression: None,
+        repeat_template_factory: None,
+        conditional_arms: None,
+        conditional_else_children: None,
+        compute_properties_fn: Some(compute_properties_fn),
+    })
+}
+
+#[test]
+fn test_clean_nested_expression_is_skipped_on_following_tick() {
+    const WIDTH_VTABLE_ID: usize = 0;
+
+    let evaluation_count = Rc::new(Cell::new(0));
+    let mut expression_table: ExpressionTable = HashMap::new();
+    let counter = Rc::clone(&evaluation_count);
+    expression_table.insert(
+        WIDTH_VTABLE_ID,
+        Box::new(move |_ec| {
+            counter.set(counter.get() + 1);
+            TypesCoproduct::f64(5.0)
+        }),
+    );
+
+    //a literal `stroke`-like property whose nested `width` is bound to an expression, computed the way primitives do
+    let stroke: Rc<RefCell<Box<dyn PropertyInstance<Nested>>>> =
+        Rc::new(RefCell::new(Box::new(PropertyLiteral::new(Nested {
+            width: Box::new(PropertyExpression::new(WIDTH_VTABLE_ID)),
+        }))));
+    let child_stroke = Rc::clone(&stroke);
+
+    let instance_registry = Rc::new(RefCell::new(InstanceRegistry::new()));
+    let child = instantiate_component(
+        &instance_registry,
+        None,
+        Box::new(move |_properties, rtc| {
+            let stroke = &mut *child_stroke.borrow_mut();
+            if let Some(TypesCoproduct::f64(width)) = rtc.compute_vtable_value(
+                stroke
+                    .get()
+                    .width
+                    ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+            ) {
+                stroke._get_mut_untracked().width.set(width);
+            }
+        }),
+    );
+    let root = instantiate_component(
+        &instance_registry,
+        Some(Rc::new(RefCell::new(vec![child as RenderNodePtr<R>]))),
+        Box::new(|_properties, _rtc| {}),
+    );
+
+    extern "C" fn log(_message: *const std::os::raw::c_char) {}
+    let mut engine = PaxEngine::new(
+        root,
+        expression_table,
+        pax_runtime_api::PlatformSpecificLogger::MacOS(log),
+        (100.0, 100.0),
+        instance_registry,
+    );
+
+    let mut rcs = HashMap::new();
+    engine.tick(&mut rcs);
+    assert_eq!(evaluation_count.get(), 1);
+    assert_eq!(*stroke.borrow().get().width.get(), 5.0);
+
+    //nothing has changed since, so the expression is skipped, as on every tick after
+    engine.tick(&mut rcs);
+    engine.tick(&mut rcs);
+    assert_eq!(evaluation_count.get(), 1);
+
+    //until a property is set
+    stroke.borrow_mut().get_mut();
+    engine.tick(&mut rcs);
+    assert_eq!(evaluation_count.get(), 2);
+}
@@ -0,0 +1,39 @@
+use kurbo::Affine;
+use pax_core::TransformAndBounds;
+
+fn rotated_square_tab() -> TransformAndBounds {
+    // A 100x100 square rotated 45° about its center, positioned at (100, 100)
+    let transform = Affine::translate((150.0, 150.0))
+        * Affine::rotate(std::f64::consts::FRAC_PI_4)
+        * Affine::translate((-50.0, -50.0));
+    TransformAndBounds {
+        transform,
+        bounds: (100.0, 100.0),
+        clipping_bounds: None,
+    }
+}
+
+#[test]
+fn test_rotated_rect_center_hits() {
+    let tab = rotated_square_tab();
+    assert!(tab.contains_point(&(150.0, 150.0)));
+}
+
+#[test]
+fn test_rotated_rect_untransformed_corner_misses() {
+    // The top-left corner of the untransformed bounding box lies outside the rotated square
+    let tab = rotated_square_tab();
+    assert!(!tab.contains_point(&(105.0, 105.0)));
+}
+
+#[test]
+fn test_ellipse_bbox_corner_misses() {
+    let tab = TransformAndBounds {
+        transform: Affine::default(),
+        bounds: (100.0, 50.0),
+        clipping_bounds: None,
+    };
+    assert!(tab.contains_point(&(5.0, 5.0)));
+    assert!(!tab.contains_point_in_ellipse(&(5.0, 5.0)));
+    assert!(tab.contains_point_in_ellipse(&(50.0, 25.0)));
+}
@@ -0,0 +1,175 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
+use pax_core::repeat::RepeatInstance;
+use pax_core::{
+    ComponentInstance, ExpressionContext, InstanceRegistry, InstantiationArgs, PaxEngine,
+    PropertyExpression, RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+};
+use pax_runtime_api::CommonProperties;
+use piet::NullRenderContext;
+
+type R = NullRenderContext;
+type ComputePropertiesFn =
+    Box<dyn FnMut(Rc<RefCell<PropertiesCoproduct>>, &mut RenderTreeContext<R>)>;
+type ExpressionTable = HashMap<usize, Box<dyn Fn(ExpressionContext<R>) -> TypesCoproduct>>;
+
+fn instantiate_component(
+    instance_registry: &Rc<RefCell<InstanceRegistry<R>>>,
+    component_template: Option<RenderNodePtrList<R>>,
+    compute_properties_fn: ComputePropertiesFn,
+) -> Rc<RefCell<ComponentInstance<R>>> {
+    ComponentInstance::instantiate(InstantiationArgs {
+        common_properties: CommonProperties::default(),
+        properties: PropertiesCoproduct::None,
+        handler_registry: None,
+        instance_registry: Rc::clone(instance_registry),
+        children: None,
+        component_template,
+        scroller_args: None,
+        slot_index: None,
+        repeat_source_expression_vec: None,
+        repeat_source_expression_range: None,
+        repeat_key_expression: None,
+        repeat_template_factory: None,
+        conditional_arms: None,
+        conditional_else_children: None,
+        compute_properties_fn: Some(compute_properties_fn),
+    })
+}
+
+/// Reads the datum of the `RepeatItem` on top of the stack, i.e. of the nearest enclosing Repeat
+fn peek_repeat_datum(rtc: &RenderTreeContext<R>) -> isize {
+    let stack_frame = (*rtc.runtime).borrow_mut().peek_stack_frame().unwrap();
+    let properties = (*stack_frame).borrow().get_properties();
+    let properties = &*(*properties).borrow();
+    if let PropertiesCoproduct::RepeatItem(datum, _, _) = properties {
+        if let PropertiesCoproduct::isize(datum) = **datum {
+            return datum;
+        }
+    }
+    unreachable!()
+}
+
+#[test]
+fn test_keyed_repeat_preserves_child_state_across_reorders() {
+    const SOURCE_VTABLE_ID: usize = 0;
+    const KEY_VTABLE_ID: usize = 1;
+
+    let data: Rc<RefCell<Vec<isize>>> = Rc::new(RefCell::new(vec![1, 2, 3]));
+    let key_evaluation_count = Rc::new(Cell::new(0));
+
+    let mut expression_table: ExpressionTable = HashMap::new();
+    let source_data = Rc::clone(&data);
+    expression_table.insert(
+        SOURCE_VTABLE_ID,
+        Box::new(move |_ec| {
+            TypesCoproduct::std_COCOvec_COCOVec_LABRstd_COCOrc_COCORc_LABRPropertiesCoproduct_RABR_RABR(
+                source_data
+                    .borrow()
+                    .iter()
+                    .map(|datum| Rc::new(PropertiesCoproduct::isize(*datum)))
+                    .collect(),
+            )
+        }),
+    );
+    let counter = Rc::clone(&key_evaluation_count);
+    expression_table.insert(
+        KEY_VTABLE_ID,
+        Box::new(move |ec| {
+            counter.set(counter.get() + 1);
+            let properties = (*ec.stack_frame).borrow().get_properties();
+            let properties = &*(*properties).borrow();
+            if let PropertiesCoproduct::RepeatItem(datum, _, _) = properties {
+                if let PropertiesCoproduct::isize(datum) = **datum {
+                    return TypesCoproduct::String(datum.to_string());
+                }
+            }
+            unreachable!()
+        }),
+    );
+
+    //each child remembers the datum it was first computed with, and logs it alongside its current datum every tick;
+    //note that Repeat's children are visited starting from the last
+    let log: Rc<RefCell<Vec<(isize, isize)>>> = Rc::new(RefCell::new(vec![]));
+    let instantiation_count = Rc::new(Cell::new(0));
+    let instance_registry = Rc::new(RefCell::new(InstanceRegistry::new()));
+    let factory_registry = Rc::clone(&instance_registry);
+    let factory_log = Rc::clone(&log);
+    let factory_count = Rc::clone(&instantiation_count);
+    let repeat_template_factory = Box::new(move || -> RenderNodePtrList<R> {
+        factory_count.set(factory_count.get() + 1);
+        let log = Rc::clone(&factory_log);
+        let child = instantiate_component(
+            &factory_registry,
+            None,
+            Box::new(move |properties, rtc| {
+                let datum = peek_repeat_datum(rtc);
+                let properties = &mut *properties.borrow_mut();
+                if let PropertiesCoproduct::None = properties {
+                    *properties = PropertiesCoproduct::isize(datum);
+                }
+                if let PropertiesCoproduct::isize(state) = properties {
+                    log.borrow_mut().push((datum, *state));
+                }
+            }),
+        );
+        Rc::new(RefCell::new(vec![child as RenderNodePtr<R>]))
+    });
+
+    let repeat = RepeatInstance::instantiate(InstantiationArgs {
+        common_properties: CommonProperties::default(),
+        properties: PropertiesCoproduct::None,
+        handler_registry: None,
+        instance_registry: Rc::clone(&instance_registry),
+        children: None,
+        component_template: None,
+        scroller_args: None,
+        slot_index: None,
+        repeat_source_expression_vec: Some(Box::new(PropertyExpression::new(SOURCE_VTABLE_ID))),
+        repeat_source_expression_range: None,
+        repeat_key_expression: Some(Box::new(PropertyExpression::new(KEY_VTABLE_ID))),
+        repeat_template_factory: Some(repeat_template_factory),
+        conditional_arms: None,
+        conditional_else_children: None,
+        compute_properties_fn: None,
+    });
+    let root = instantiate_component(
+        &instance_registry,
+        Some(Rc::new(RefCell::new(vec![repeat as RenderNodePtr<R>]))),
+        Box::new(|_properties, _rtc| {}),
+    );
+
+    extern "C" fn log_message(_message: *const std::os::raw::c_char) {}
+    let mut engine = PaxEngine::new(
+        root,
+        expression_table,
+        pax_runtime_api::PlatformSpecificLogger::MacOS(log_message),
+        (100.0, 100.0),
+        instance_registry,
+    );
+
+    let mut rcs = HashMap::new();
+    engine.tick(&mut rcs);
+    assert_eq!(*log.borrow(), vec![(3, 3), (2, 2), (1, 1)]);
+    assert_eq!(instantiation_count.get(), 3);
+    let key_evaluations = key_evaluation_count.get();
+    assert_eq!(key_evaluations, 3);
+
+    //nothing has changed since, so the keys are not re-evaluated
+    log.borrow_mut().clear();
+    engine.tick(&mut rcs);
+    assert_eq!(*log.borrow(), vec![(3, 3), (2, 2), (1, 1)]);
+    assert_eq!(key_evaluation_count.get(), key_evaluations);
+
+    //once reordered, each datum is rendered by the same instances as before, in the new order
+    *data.borrow_mut() = vec![3, 1, 2];
+    pax_runtime_api::mark_properties_dirty();
+    log.borrow_mut().clear();
+    engine.tick(&mut rcs);
+    assert_eq!(*log.borrow(), vec![(2, 2), (1, 1), (3, 3)]);
+    assert_eq!(instantiation_count.get(), 3);
+    assert_eq!(key_evaluation_count.get(), key_evaluations + 3);
+}
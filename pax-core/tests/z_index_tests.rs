@@ -0,0 +1,95 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
+use pax_core::{
+    ComponentInstance, ExpressionContext, InstanceRegistry, InstantiationArgs, PaxEngine,
+    PropertyExpression, RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+};
+use pax_runtime_api::CommonProperties;
+use piet::NullRenderContext;
+
+type R = NullRenderContext;
+type ComputePropertiesFn =
+    Box<dyn FnMut(Rc<RefCell<PropertiesCoproduct>>, &mut RenderTreeContext<R>)>;
+type ExpressionTable = HashMap<usize, Box<dyn Fn(ExpressionContext<R>) -> TypesCoproduct>>;
+
+fn instantiate_component(
+    instance_registry: &Rc<RefCell<InstanceRegistry<R>>>,
+    common_properties: CommonProperties,
+    component_template: Option<RenderNodePtrList<R>>,
+    compute_properties_fn: ComputePropertiesFn,
+) -> Rc<RefCell<ComponentInstance<R>>> {
+    ComponentInstance::instantiate(InstantiationArgs {
+        common_properties,
+        properties: PropertiesCoproduct::None,
+        handler_registry: None,
+        instance_registry: Rc::clone(instance_registry),
+        children: None,
+        component_template,
+        scroller_args: None,
+        slot_index: None,
+        repeat_source_expression_vec: None,
+        repeat_source_expression_range: None,
+        repeat_key_expression: None,
+        repeat_template_factory: None,
+        conditional_arms: None,
+        conditional_else_children: None,
+        compute_properties_fn: Some(compute_properties_fn),
+    })
+}
+
+#[test]
+fn test_expression_bound_z_index_orders_siblings_on_first_tick() {
+    const Z_INDEX_VTABLE_ID: usize = 0;
+
+    let mut expression_table: ExpressionTable = HashMap::new();
+    expression_table.insert(Z_INDEX_VTABLE_ID, Box::new(|_ec| TypesCoproduct::isize(1)));
+
+    //records the order in which siblings are visited, i.e. from back-most to front-most
+    let visited: Rc<RefCell<Vec<&'static str>>> = Rc::new(RefCell::new(vec![]));
+    let instance_registry = Rc::new(RefCell::new(InstanceRegistry::new()));
+    let visited_a = Rc::clone(&visited);
+    let a = instantiate_component(
+        &instance_registry,
+        CommonProperties::default(),
+        None,
+        Box::new(move |_properties, _rtc| visited_a.borrow_mut().push("a")),
+    );
+    //`b` is declared last, so it is in front of `a` unless its `z_index` says otherwise
+    let visited_b = Rc::clone(&visited);
+    let b = instantiate_component(
+        &instance_registry,
+        CommonProperties {
+            z_index: Some(Rc::new(RefCell::new(PropertyExpression::new(
+                Z_INDEX_VTABLE_ID,
+            )))),
+            ..Default::default()
+        },
+        None,
+        Box::new(move |_properties, _rtc| visited_b.borrow_mut().push("b")),
+    );
+    let root = instantiate_component(
+        &instance_registry,
+        CommonProperties::default(),
+        Some(Rc::new(RefCell::new(vec![
+            a as RenderNodePtr<R>,
+            b as RenderNodePtr<R>,
+        ]))),
+        Box::new(|_properties, _rtc| {}),
+    );
+
+    extern "C" fn log(_message: *const std::os::raw::c_char) {}
+    let mut engine = PaxEngine::new(
+        root,
+        expression_table,
+        pax_runtime_api::PlatformSpecificLogger::MacOS(log),
+        (100.0, 100.0),
+        instance_registry,
+    );
+
+    let mut rcs = HashMap::new();
+    engine.tick(&mut rcs);
+    assert_eq!(*visited.borrow(), vec!["a", "b"]);
+}
@@ -1,7 +1,7 @@
 use pax_lang::api::{
     ArgsClick, ArgsJab, ArgsScroll, ArgsTouchStart, ArgsTouchMove, ArgsTouchEnd,
     ArgsKeyDown, ArgsKeyUp, ArgsKeyPress, ArgsDoubleClick, ArgsMouseMove, ArgsWheel,
-    ArgsMouseDown, ArgsMouseUp, ArgsMouseOver, ArgsMouseOut, ArgsContextMenu,
+    ArgsMouseDown, ArgsMouseUp, ArgsMouseOver, ArgsMouseOut, ArgsContextMenu, ArgsResize,
     RuntimeContext,  Property, PropertyLiteral
 };
 use pax_lang::Pax;
@@ -85,4 +85,8 @@ impl Words {
     pub fn handle_context_menu(&mut self, _ctx: RuntimeContext, _args: ArgsContextMenu){
         self.content.set("Context Menu".to_string());
     }
+
+    pub fn handle_resize(&mut self, _ctx: RuntimeContext, args: ArgsResize) {
+        self.content.set(format!("Resize: {}x{}", args.width, args.height));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,34 @@
+use pax_lang::api::{ArgsMouseDown, ArgsMouseMove, ArgsMouseUp, Property, RuntimeContext, Transform2D};
+use pax_lang::Pax;
+use pax_std::primitives::{Frame, Rectangle};
+use pax_std::types::{Color, Fill};
+
+#[derive(Pax)]
+#[file("drag.pax")]
+pub struct Drag {
+    pub box_x: Property<f64>,
+    pub box_y: Property<f64>,
+    pub is_dragging: Property<bool>,
+    pub grab_offset_x: Property<f64>,
+    pub grab_offset_y: Property<f64>,
+}
+
+impl Drag {
+    pub fn handle_mouse_down(&mut self, _ctx: RuntimeContext, args: ArgsMouseDown) {
+        //remember where the box was grabbed, so it doesn't jump to the cursor
+        self.grab_offset_x.set(args.mouse.local_x);
+        self.grab_offset_y.set(args.mouse.local_y);
+        self.is_dragging.set(true);
+    }
+
+    pub fn handle_mouse_move(&mut self, _ctx: RuntimeContext, args: ArgsMouseMove) {
+        if *self.is_dragging.get() {
+            self.box_x.set(args.mouse.x - self.grab_offset_x.get());
+            self.box_y.set(args.mouse.y - self.grab_offset_y.get());
+        }
+    }
+
+    pub fn handle_mouse_up(&mut self, _ctx: RuntimeContext, _args: ArgsMouseUp) {
+        self.is_dragging.set(false);
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(unused_imports)]
 
+pub mod drag;
 pub mod website_desktop;
 pub mod website_mobile;
 
@@ -0,0 +1,74 @@
+use pax_lang::api::{Interpolatable, Property};
+use pax_lang::Pax;
+
+#[derive(Pax)]
+#[custom(Imports)]
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct UserDerivedType {
+    pub label: String,
+    pub count: usize,
+}
+
+#[derive(Pax, Debug, PartialEq)]
+#[custom(Imports)]
+pub struct PaxDerivedType {
+    pub label: String,
+}
+
+#[derive(Pax, Debug, PartialEq)]
+#[custom(Imports)]
+pub struct ReadonlyFieldType {
+    #[pax(readonly)]
+    pub count: usize,
+}
+
+#[derive(Pax)]
+#[custom(Imports)]
+pub struct DefaultedFieldType {
+    #[pax(default = "\"untitled\".to_string()")]
+    pub label: Property<String>,
+    #[pax(default = "3 * 4")]
+    pub count: usize,
+    pub offset: usize,
+}
+
+#[test]
+fn test_user_derives_coexist_with_pax() {
+    let original = UserDerivedType {
+        label: "button".to_string(),
+        count: 3,
+    };
+    assert_eq!(original.clone(), original);
+    assert_eq!(
+        UserDerivedType::default(),
+        UserDerivedType {
+            label: "".to_string(),
+            count: 0,
+        }
+    );
+    assert_eq!(original.interpolate(&original, 0.5), original);
+}
+
+#[test]
+fn test_pax_generates_clone_and_default() {
+    let original = PaxDerivedType {
+        label: "button".to_string(),
+    };
+    assert_eq!(original.clone(), original);
+    assert_eq!(PaxDerivedType::default().label, "");
+}
+
+#[test]
+fn test_readonly_fields_are_left_intact() {
+    let original = ReadonlyFieldType { count: 3 };
+    assert_eq!(original.clone(), original);
+    assert_eq!(ReadonlyFieldType::default().count, 0);
+}
+
+#[test]
+fn test_field_defaults_are_used_by_default() {
+    let defaulted = DefaultedFieldType::default();
+    assert_eq!(defaulted.label.get(), "untitled");
+    assert_eq!(defaulted.count, 12);
+    assert_eq!(defaulted.offset, 0);
+}
@@ -9,7 +9,7 @@ use std::str::FromStr;
 
 use std::fs::File;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, ToTokens};
@@ -35,6 +35,7 @@ fn pax_primitive(
     let _original_tokens = quote! { #input_parsed }.to_string();
     let pascal_identifier = input_parsed.ident.to_string();
 
+    let enum_variant_names = get_enum_variant_names(&input_parsed.data);
     let static_property_definitions =
         get_static_property_definitions_from_tokens(input_parsed.data);
 
@@ -45,6 +46,7 @@ fn pax_primitive(
         args_struct_only_component: None,
         args_full_component: None,
         static_property_definitions,
+        enum_variant_names,
         pascal_identifier,
         include_imports,
         is_custom_interpolatable,
@@ -63,6 +65,7 @@ fn pax_struct_only_component(
 ) -> proc_macro2::TokenStream {
     let pascal_identifier = input_parsed.ident.to_string();
 
+    let enum_variant_names = get_enum_variant_names(&input_parsed.data);
     let static_property_definitions =
         get_static_property_definitions_from_tokens(input_parsed.data);
 
@@ -73,6 +76,7 @@ fn pax_struct_only_component(
 
         pascal_identifier: pascal_identifier.clone(),
         static_property_definitions,
+        enum_variant_names,
         include_imports,
         is_custom_interpolatable,
     }
@@ -234,6 +238,8 @@ fn get_static_property_definitions_from_tokens(data: Data) -> Vec<StaticProperty
                                     root_scoped_resolvable_type,
                                     pascal_identifier,
                                     is_property_wrapped: ty.1,
+                                    is_readonly: is_readonly_field(f),
+                                    default_value: get_field_default(f),
                                 })
                             }
                         };
@@ -264,6 +270,8 @@ fn get_static_property_definitions_from_tokens(data: Data) -> Vec<StaticProperty
                             root_scoped_resolvable_type,
                             pascal_identifier,
                             is_property_wrapped: ty.1,
+                            is_readonly: is_readonly_field(f),
+                            default_value: None,
                         })
                     }
                 })
@@ -280,6 +288,59 @@ fn get_static_property_definitions_from_tokens(data: Data) -> Vec<StaticProperty
     ret
 }
 
+/// The arguments of each `#[pax(...)]` attribute on `f`, like the `readonly` in `#[pax(readonly)]`
+fn get_pax_field_args(f: &Field) -> Vec<syn::NestedMeta> {
+    f.attrs
+        .iter()
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(meta_list)) if meta_list.path.is_ident("pax") => {
+                Some(meta_list.nested.into_iter())
+            }
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+/// Whether `f` is marked `#[pax(readonly)]`, i.e. may only be set by its own component
+fn is_readonly_field(f: &Field) -> bool {
+    get_pax_field_args(f).iter().any(|nested_meta| {
+        matches!(nested_meta, syn::NestedMeta::Meta(Meta::Path(path)) if path.is_ident("readonly"))
+    })
+}
+
+/// The Rust expression given by `#[pax(default = "...")]` on `f`, if any, which replaces `Default::default()`
+/// as the value of `f` when it is not otherwise set
+fn get_field_default(f: &Field) -> Option<String> {
+    get_pax_field_args(f)
+        .into_iter()
+        .find_map(|nested_meta| match nested_meta {
+            syn::NestedMeta::Meta(Meta::NameValue(name_value))
+                if name_value.path.is_ident("default") =>
+            {
+                match name_value.lit {
+                    Lit::Str(default_str) => Some(default_str.value()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+}
+
+/// Names of the variants of `data`, if it describes an enum
+fn get_enum_variant_names(data: &Data) -> Option<Vec<String>> {
+    match data {
+        Data::Enum(data_enum) => Some(
+            data_enum
+                .variants
+                .iter()
+                .map(|variant| variant.ident.to_string())
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
 fn pax_full_component(
     raw_pax: String,
     input_parsed: DeriveInput,
@@ -290,6 +351,7 @@ fn pax_full_component(
 ) -> proc_macro2::TokenStream {
     let pascal_identifier = input_parsed.ident.to_string();
 
+    let enum_variant_names = get_enum_variant_names(&input_parsed.data);
     let static_property_definitions =
         get_static_property_definitions_from_tokens(input_parsed.data);
     let template_dependencies =
@@ -318,6 +380,7 @@ fn pax_full_component(
         pascal_identifier,
         include_imports,
         static_property_definitions,
+        enum_variant_names,
         is_custom_interpolatable,
     }
     .render_once()
@@ -336,7 +399,33 @@ fn pax_full_component(
     .into()
 }
 
-#[proc_macro_derive(Pax, attributes(main, file, inlined, primitive, custom, default))]
+/// Derives the Pax reflection and runtime glue for a component, primitive, or type.
+///
+/// In addition to the parser-only `Reflectable` impl (behind the `parser` feature), Pax generates
+/// the following trait impls for the annotated item, which users must not also implement manually:
+/// - `Clone`, unless `#[custom(Clone)]` is specified or `Clone` is derived alongside `Pax`
+/// - `Default`, unless `#[custom(Default)]` is specified or `Default` is derived alongside `Pax`
+/// - `pax_lang::api::Interpolatable`, unless `#[custom(Interpolatable)]` is specified
+///
+/// To derive `Clone` or `Default` yourself, do so in a separate attribute following `#[derive(Pax)]`,
+/// e.g. `#[derive(Pax)] #[custom(Imports)] #[derive(Clone, Default)]` — a derive macro cannot see
+/// the other traits listed within its own `#[derive(...)]`.  (Note that rustfmt merges adjacent
+/// `#[derive]`s, so separate them with another attribute.)  Other derives and attributes on the
+/// item (e.g. `#[derive(Debug, Serialize)]`) are left intact.
+///
+/// A component's template is either inlined with `#[inlined(...)]`, read from a path relative to `src/`
+/// with `#[file("some-file.pax")]`, or, with a bare `#[file]`, read from a `.pax` file colocated with the
+/// Rust source file, named after the struct or the module: for `struct Foo` in `src/foo.rs`, `src/Foo.pax`
+/// or `src/foo.pax`.  Main components without `#[file(...)]` or `#[inlined(...)]` also use a colocated file.
+///
+/// A property marked `#[pax(readonly)]` is set only by its own component, e.g. from its event handlers;
+/// templates that instantiate the component cannot bind it, inline or from `@settings`.
+///
+/// A property marked e.g. `#[pax(default = "Color::rgb(1.0, 1.0, 1.0)")]` takes the value of that Rust
+/// expression, rather than `Default::default()`, wherever it is not set: both in the generated `Default`
+/// and for instances whose templates don't bind it.  The expression is type-checked against the property's
+/// type, and must only name types and functions in scope for expressions, like the types of properties.
+#[proc_macro_derive(Pax, attributes(main, file, inlined, primitive, custom, default, pax))]
 pub fn pax_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
@@ -346,20 +435,39 @@ pub fn pax_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
     let mut is_main_component = false;
     let mut file_path: Option<String> = None;
+    let mut is_colocated_file = false;
     let mut inlined_contents: Option<String> = None;
     let mut custom_values: Option<Vec<String>> = None;
     let mut primitive_instance_import_path: Option<String> = None;
     let mut is_primitive = false;
+    let mut user_derives: Vec<String> = vec![];
 
     // iterate through `derive macro helper attributes` to gather config & args
     for attr in attrs {
-        if attr.path.is_ident("file") {
+        if attr.path.is_ident("derive") {
+            //Track traits derived alongside `Pax`, so that we don't generate conflicting impls
             if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
-                if let Some(nested_meta) = meta_list.nested.first() {
-                    if let syn::NestedMeta::Lit(Lit::Str(file_str)) = nested_meta {
+                meta_list.nested.iter().for_each(|nested_meta| {
+                    if let syn::NestedMeta::Meta(Meta::Path(path)) = nested_meta {
+                        if let Some(segment) = path.segments.last() {
+                            user_derives.push(segment.ident.to_string());
+                        }
+                    }
+                });
+            }
+        } else if attr.path.is_ident("file") {
+            match attr.parse_meta() {
+                Ok(Meta::List(meta_list)) => {
+                    if let Some(syn::NestedMeta::Lit(Lit::Str(file_str))) = meta_list.nested.first()
+                    {
                         file_path = Some(file_str.value());
                     }
                 }
+                Ok(Meta::Path(_)) => {
+                    //bare `#[file]`: use the `.pax` file colocated with this Rust file
+                    is_colocated_file = true;
+                }
+                _ => {}
             }
         } else if attr.path.is_ident("primitive") {
             if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
@@ -425,18 +533,33 @@ pub fn pax_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         .to_compile_error()
         .into();
     }
-    if let (None, None) = (file_path.as_ref(), inlined_contents.as_ref()) {
-        // &&
-        if is_main_component {
-            return syn::Error::new_spanned(input.ident, "Main (application-root) components must specify either a Pax file or inlined Pax content, e.g. #[file(\"some-file.pax\")] or #[inlined(<SomePax />)]")
-                .to_compile_error()
-                .into();
-        }
+    if is_colocated_file && inlined_contents.is_some() {
+        return syn::Error::new_spanned(
+            input.ident,
+            "`#[file]` and `#[inlined(...)]` attributes cannot be used together",
+        )
+        .to_compile_error()
+        .into();
     }
+    let colocated_file_path = if file_path.is_none()
+        && inlined_contents.is_none()
+        && (is_colocated_file || is_main_component)
+    {
+        match find_colocated_pax_file(name) {
+            Ok(path) => Some(path),
+            Err(message) => {
+                return syn::Error::new_spanned(input.ident, message)
+                    .to_compile_error()
+                    .into();
+            }
+        }
+    } else {
+        None
+    };
     if is_primitive {
         const ERR : &str = "Primitives cannot have attached templates.  Instead, specify a fully qualified Rust import path pointing to the `impl RenderNode` struct for this primitive.";
 
-        if let Some(_) = file_path.as_ref() {
+        if file_path.is_some() || is_colocated_file {
             return syn::Error::new_spanned(input.ident, ERR)
                 .to_compile_error()
                 .into();
@@ -537,13 +660,56 @@ pub fn pax_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         }
     };
 
+    // Parse the field defaults given by `#[pax(default = "...")]`
+    let mut field_default_exprs: Vec<(&Field, syn::Expr)> = vec![];
+    if let Data::Struct(data_struct) = &input.data {
+        for f in data_struct.fields.iter() {
+            if let Some(default_value) = get_field_default(f) {
+                match syn::parse_str::<syn::Expr>(&default_value) {
+                    Ok(expr) => field_default_exprs.push((f, expr)),
+                    Err(err) => {
+                        return syn::Error::new_spanned(
+                            f,
+                            format!("Invalid `#[pax(default = ...)]` expression: {}", err),
+                        )
+                        .to_compile_error()
+                        .into();
+                    }
+                }
+            }
+        }
+    }
+
+    // Type-check each field default against the type of its field, even if `Default` is implemented manually
+    let default_checks = field_default_exprs.iter().filter_map(|(f, expr)| {
+        let (ty, _) = get_field_type(f)?;
+        Some(quote! {
+            const _: () = {
+                #[allow(dead_code)]
+                fn check_default() -> #ty {
+                    #expr
+                }
+            };
+        })
+    });
+    let default_checks = quote! { #(#default_checks)* };
+
     // Implement Default
     let mut default_impl = match &input.data {
         Data::Struct(data_struct) => match &data_struct.fields {
             Fields::Named(fields_named) => {
                 let field_defaults = fields_named.named.iter().map(|f| {
                     let name = &f.ident;
-                    quote! { #name: Default::default() }
+                    match field_default_exprs
+                        .iter()
+                        .find(|(defaulted_field, _)| defaulted_field.ident == f.ident)
+                    {
+                        Some((_, expr)) if matches!(get_field_type(f), Some((_, true))) => {
+                            quote! { #name: Box::new(pax_lang::api::PropertyLiteral::new(#expr)) }
+                        }
+                        Some((_, expr)) => quote! { #name: #expr },
+                        None => quote! { #name: Default::default() },
+                    }
                 });
 
                 quote! {
@@ -602,6 +768,14 @@ pub fn pax_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let mut include_imports = true;
     let mut is_custom_interpolatable = false;
 
+    //wipe out the above derives if the user derives these traits alongside `Pax`
+    if user_derives.contains(&"Default".to_string()) {
+        default_impl = quote! {};
+    }
+    if user_derives.contains(&"Clone".to_string()) {
+        clone_impl = quote! {};
+    }
+
     //wipe out the above derives if `#[custom(...)]` attrs are set
     if let Some(custom) = custom_values {
         if custom.contains(&"Default".to_string()) {
@@ -620,18 +794,23 @@ pub fn pax_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         }
     }
 
-    let is_pax_file = matches!(&file_path, Some(_f));
+    let pax_file_path = file_path
+        .as_ref()
+        .map(|filename| {
+            let current_dir = std::env::current_dir().expect("Unable to get current directory");
+            current_dir.join(Path::new("src").join(Path::new(&filename)))
+        })
+        .or(colocated_file_path);
+    let is_pax_file = matches!(&pax_file_path, Some(_f));
     let is_pax_inlined = matches!(&inlined_contents, Some(_i));
     // let debug = format!("//is_pax_file:  {}, is_pax_inlined: {}\n//inlined_contents: /*{:?}*/", &is_pax_file, &is_pax_inlined, &inlined_contents);
 
     let appended_tokens = if is_pax_file {
-        let filename = if let Some(p) = file_path.as_ref() {
+        let path = if let Some(p) = pax_file_path {
             p
         } else {
             unreachable!()
         };
-        let current_dir = std::env::current_dir().expect("Unable to get current directory");
-        let path = current_dir.join(Path::new("src").join(Path::new(&filename)));
 
         // generate_include to watch for changes in specified file, ensuring macro is re-evaluated when file changes
         let name = Ident::new("PaxFile", Span::call_site());
@@ -679,11 +858,43 @@ pub fn pax_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         #appended_tokens
         #clone_impl
         #default_impl
+        #default_checks
     };
 
     output.into()
 }
 
+/// Finds the `.pax` file colocated with the Rust file declaring `name`, named either after `name`
+/// (`Foo.pax`) or after the Rust file's module (`foo.pax` for `foo.rs`), in that order.
+fn find_colocated_pax_file(name: &Ident) -> Result<PathBuf, String> {
+    let rust_file = proc_macro::Span::call_site()
+        .local_file()
+        .ok_or_else(|| format!("No Pax template found for `{}`: unable to locate its Rust source file; specify one with #[file(\"some-file.pax\")] or #[inlined(<SomePax />)]", name))?;
+    let rust_file = std::env::current_dir()
+        .expect("Unable to get current directory")
+        .join(rust_file);
+    let dir = rust_file.parent().unwrap();
+    let module_name = rust_file.file_stem().unwrap().to_str().unwrap();
+
+    let candidates = [
+        dir.join(format!("{}.pax", name)),
+        dir.join(format!("{}.pax", module_name)),
+    ];
+    candidates
+        .iter()
+        .find(|candidate| candidate.is_file())
+        .cloned()
+        .ok_or_else(|| {
+            format!(
+                "No Pax template found for `{}`: expected {} or {} alongside {}, or specify one with #[file(\"some-file.pax\")] or #[inlined(<SomePax />)]",
+                name,
+                candidates[0].display(),
+                candidates[1].display(),
+                rust_file.display()
+            )
+        })
+}
+
 // Needed because Cargo wouldn't otherwise watch for changes in pax files.
 // By include_str!ing the file contents,
 // (Trick borrowed from Pest: github.com/pest-parser/pest)
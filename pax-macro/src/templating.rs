@@ -10,6 +10,10 @@ pub struct StaticPropertyDefinition {
     pub original_type: String,
     pub pascal_identifier: String,
     pub is_property_wrapped: bool,
+    /// Whether the field is marked `#[pax(readonly)]`
+    pub is_readonly: bool,
+    /// The Rust expression given by `#[pax(default = "...")]` on the field, if any
+    pub default_value: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -39,6 +43,8 @@ pub struct TemplateArgsDerivePax {
 
     /// Shared properties
     pub static_property_definitions: Vec<StaticPropertyDefinition>,
+    /// Names of the variants of the type deriving Pax, if it is an enum
+    pub enum_variant_names: Option<Vec<String>>,
     pub pascal_identifier: String,
     pub include_imports: bool,
     pub is_custom_interpolatable: bool,
@@ -58,8 +58,22 @@ pub struct JabInterruptArgs {
 #[derive(Deserialize)]
 #[repr(C)]
 pub struct ScrollInterruptArgs {
+    /// Position of the pointer, if any, whose topmost element is scrolled.  Without one
+    /// (e.g. for native scrollers), the element at the center of the viewport is scrolled.
+    pub x: Option<f64>,
+    pub y: Option<f64>,
     pub delta_x: f64,
     pub delta_y: f64,
+    pub delta_mode: ScrollDeltaModeMessage,
+    pub modifiers: Vec<ModifierKeyMessage>,
+}
+
+#[derive(Deserialize)]
+#[repr(C)]
+pub enum ScrollDeltaModeMessage {
+    Pixel,
+    Line,
+    Page,
 }
 
 #[derive(Deserialize)]
@@ -112,6 +126,7 @@ pub enum ModifierKeyMessage {
 #[repr(C)]
 pub struct KeyDownInterruptArgs {
     pub key: String,
+    pub code: String,
     pub modifiers: Vec<ModifierKeyMessage>,
     pub is_repeat: bool,
 }
@@ -120,6 +135,7 @@ pub struct KeyDownInterruptArgs {
 #[repr(C)]
 pub struct KeyUpInterruptArgs {
     pub key: String,
+    pub code: String,
     pub modifiers: Vec<ModifierKeyMessage>,
     pub is_repeat: bool,
 }
@@ -128,6 +144,7 @@ pub struct KeyUpInterruptArgs {
 #[repr(C)]
 pub struct KeyPressInterruptArgs {
     pub key: String,
+    pub code: String,
     pub modifiers: Vec<ModifierKeyMessage>,
     pub is_repeat: bool,
 }
@@ -280,6 +297,8 @@ pub struct TextPatch {
     pub size_y: Option<f64>,
     pub style: Option<TextStyleMessage>,
     pub style_link: Option<TextStyleMessage>,
+    /// Opacity accumulated from this node and its ancestors, `1.0` being fully opaque
+    pub opacity: Option<f64>,
 }
 
 #[derive(Default, Serialize)]
@@ -299,6 +318,10 @@ pub struct TextStyleMessage {
 pub struct ImagePatch {
     pub id_chain: Vec<u32>,
     pub path: Option<String>,
+    /// On-screen size of the image, in pixels, so that chassis may decode
+    /// (or downsample) to no more than the needed resolution
+    pub size_x: Option<f64>,
+    pub size_y: Option<f64>,
 }
 
 #[derive(Serialize)]
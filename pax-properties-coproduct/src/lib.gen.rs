@@ -5,7 +5,7 @@ pub enum PropertiesCoproduct {
     //core
     None,
     RepeatList(Vec<Rc<RefCell<PropertiesCoproduct>>>),
-    RepeatItem(Rc<PropertiesCoproduct>, usize),
+    RepeatItem(Rc<PropertiesCoproduct>, usize, usize),//datum, index, length of the iterable
     isize(isize), //used by range for repeat (0..10)
     //generated
     StackerCell(pax_example::pax_reexports::pax_std::types::StackerCell),
@@ -24,7 +24,7 @@ pub enum TypesCoproduct {
     bool(bool),
     isize(isize),
     usize(usize), //used by Slot for index
-    stdCOCOvecCOCOVecLABRstdCOCOrcCOCORcLABRPropertiesCoproductRABRRABR(Vec<Rc<PropertiesCoproduct>>),
+    std_COCOvec_COCOVec_LABRstd_COCOrc_COCORc_LABRPropertiesCoproduct_RABR_RABR(Vec<Rc<PropertiesCoproduct>>),
     String(String),
     //generated
     Transform2D(pax_example::pax_reexports::Transform2D),
@@ -6,7 +6,7 @@ pub enum PropertiesCoproduct {
     //core
     None,
     RepeatList(Vec<Rc<RefCell<PropertiesCoproduct>>>),
-    RepeatItem(Rc<PropertiesCoproduct>, usize),
+    RepeatItem(Rc<PropertiesCoproduct>, usize, usize),//datum, index, length of the iterable
     #[allow(non_camel_case_types)]
     usize(usize),//used by Repeat + numeric ranges, e.g. `for i in 0..5`
     #[allow(non_camel_case_types)]
@@ -29,9 +29,9 @@ pub enum TypesCoproduct {
     usize(usize), //used by Slot for index
 
     #[allow(non_camel_case_types)]
-    stdCOCOvecCOCOVecLABRstdCOCOrcCOCORcLABRPropertiesCoproductRABRRABR(Vec<Rc<PropertiesCoproduct>>),
+    std_COCOvec_COCOVec_LABRstd_COCOrc_COCORc_LABRPropertiesCoproduct_RABR_RABR(Vec<Rc<PropertiesCoproduct>>),
     #[allow(non_camel_case_types)]
-    stdCOCOopsCOCORangeLABRisizeRABR(Range<isize>),
+    std_COCOops_COCORange_LABRisize_RABR(Range<isize>),
     String(String),
     Transform2D(pax_runtime_api::Transform2D),
     SizePixels(pax_runtime_api::SizePixels),
@@ -1,12 +1,12 @@
 pub mod numeric;
 
 use std::borrow::Borrow;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::ffi::CString;
 use std::rc::Rc;
 
-use std::ops::{Add, Deref, Mul, Neg};
+use std::ops::{Add, Deref, Mul, Neg, Sub};
 
 #[macro_use]
 extern crate lazy_static;
@@ -14,7 +14,27 @@ extern crate mut_static;
 
 pub use crate::numeric::Numeric;
 use mut_static::MutStatic;
-use pax_message::{ModifierKeyMessage, MouseButtonMessage, TouchMessage};
+use pax_message::{ModifierKeyMessage, MouseButtonMessage, ScrollDeltaModeMessage, TouchMessage};
+
+thread_local! {
+    /// Incremented whenever a value that expressions may depend on changes; see `mark_properties_dirty`
+    static PROPERTIES_EPOCH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Marks every expression dirty, so that each is re-evaluated the next time its node computes properties.
+/// The engine otherwise skips evaluating expressions whose dependencies can't have changed since their
+/// last evaluation.  Called automatically when a property is `set`, transitioned or borrowed through `get_mut`,
+/// when the viewport resizes, and when an expression reads a per-tick built-in like `$frames_elapsed`.  Call it
+/// manually after mutating a property in a way the engine cannot observe, e.g. through interior mutability.
+pub fn mark_properties_dirty() {
+    PROPERTIES_EPOCH.with(|epoch| epoch.set(epoch.get() + 1));
+}
+
+/// The number of times `mark_properties_dirty` has been called, used by the engine to tell whether
+/// an expression's cached value may be stale
+pub fn _get_properties_epoch() -> usize {
+    PROPERTIES_EPOCH.with(|epoch| epoch.get())
+}
 
 pub struct TransitionQueueEntry<T> {
     pub global_frame_started: Option<usize>,
@@ -29,8 +49,24 @@ pub trait PropertyInstance<T: Default + Clone> {
     fn get(&self) -> &T;
     fn _get_vtable_id(&self) -> Option<usize>;
 
+    /// Used by engine to skip evaluating expressions whose cached value is still current: returns
+    /// this property's vtable id only if its expression must be (re-)evaluated during the tick
+    /// `frames_elapsed`.  Properties that don't track their dependencies are always considered dirty.
+    fn _get_dirty_vtable_id(&self, _frames_elapsed: usize) -> Option<usize> {
+        self._get_vtable_id()
+    }
+
+    /// Borrows this property's value for in-place mutation, e.g. of a nested property.  Because any
+    /// mutation through the returned reference is invisible to the engine, this marks properties dirty
+    /// (see `mark_properties_dirty`) whether or not the value ends up changing; prefer `get` for reads.
     fn get_mut(&mut self) -> &mut T;
 
+    /// Used by engine to write the value of a nested property, e.g. `stroke.width`, once it has evaluated
+    /// that nested property's expression.  Unlike `get_mut`, doesn't mark properties dirty: such a write
+    /// only stores the result of an evaluation, so marking dirty would make every expression, including
+    /// the one just evaluated, dirty again on the following tick.
+    fn _get_mut_untracked(&mut self) -> &mut T;
+
     fn set(&mut self, value: T);
 
     /// Used by engine to gain access to this property's transition queue
@@ -45,6 +81,20 @@ pub trait PropertyInstance<T: Default + Clone> {
     /// transition will be the final value upon completion of the current transition queue.
     fn ease_to_later(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve);
 
+    /// Drive this property through a sequence of `(value, duration_frames, curve)` transitions,
+    /// starting immediately from its current value.  Works for any backing implementation: while
+    /// the animation runs it takes precedence over the property's literal value or expression, and
+    /// once complete, a literal keeps the final value while an expression resumes evaluation.
+    fn animate(&mut self, transitions: Vec<(T, u64, EasingCurve)>) {
+        let mut transitions = transitions.into_iter();
+        if let Some((new_value, duration_frames, curve)) = transitions.next() {
+            self.ease_to(new_value, duration_frames, curve);
+        }
+        for (new_value, duration_frames, curve) in transitions {
+            self.ease_to_later(new_value, duration_frames, curve);
+        }
+    }
+
     //Wishlist:
     // to_default: set back to default value
     // ease_to_default: set back to default value via interpolation
@@ -68,10 +118,12 @@ pub type Property<T> = Box<dyn PropertyInstance<T>>;
 pub struct RuntimeContext {
     /// The current global engine tick count
     pub frames_elapsed: usize,
-    /// The bounds of this element's immediate container (parent) in px
+    /// The bounds of this element's immediate container (parent) in logical px
     pub bounds_parent: (f64, f64),
-    // /// Viewport bounds
-    // pub bounds_viewport: (f64, f64)
+    /// The bounds of the viewport (e.g. the browser window) in logical px
+    pub bounds_viewport: (f64, f64),
+    /// Ratio of physical to logical px on the rendering device, e.g. `2.0` on most retina displays
+    pub device_pixel_ratio: f64,
     // /// The number of adoptees passed to the current component (used by Stacker for auto cell-count calc; might be extended/adjusted for other use-cases)
     // pub adoptee_count: usize,
     // /// Current playhead position(s) for current component
@@ -93,11 +145,33 @@ pub struct ArgsJab {
 /// Scroll occurs when a frame is translated vertically or horizontally
 /// Can be both by touch, mouse or keyboard
 /// The contained `delta_x` and `delta_y` describe the horizontal and vertical translation of
-/// the frame
+/// the frame, in the units described by `delta_mode`.  `modifiers` lists the modifier keys held
+/// during the scroll, e.g. to distinguish a shift+wheel horizontal scroll.
 #[derive(Clone)]
 pub struct ArgsScroll {
     pub delta_x: f64,
     pub delta_y: f64,
+    pub delta_mode: ScrollDeltaMode,
+    pub modifiers: Vec<ModifierKey>,
+}
+
+/// Units of the deltas in `ArgsScroll`.  `Pixel` deltas typically come from
+/// trackpads (precise scrolling), while `Line` deltas typically come from mouse wheels.
+#[derive(Clone, PartialEq)]
+pub enum ScrollDeltaMode {
+    Pixel,
+    Line,
+    Page,
+}
+
+impl From<&ScrollDeltaModeMessage> for ScrollDeltaMode {
+    fn from(value: &ScrollDeltaModeMessage) -> Self {
+        match value {
+            ScrollDeltaModeMessage::Pixel => ScrollDeltaMode::Pixel,
+            ScrollDeltaModeMessage::Line => ScrollDeltaMode::Line,
+            ScrollDeltaModeMessage::Page => ScrollDeltaMode::Page,
+        }
+    }
 }
 
 // Touch Events
@@ -145,12 +219,24 @@ pub struct ArgsTouchEnd {
     pub touches: Vec<Touch>,
 }
 
+/// A Resize occurs when the bounds of the viewport change, e.g. with a browser
+/// window resize, or when the device pixel ratio changes.  `width` and `height`
+/// describe the new viewport bounds in logical px, independent of pixel density.
+#[derive(Clone)]
+pub struct ArgsResize {
+    pub width: f64,
+    pub height: f64,
+}
+
 // Keyboard Events
 
 /// Common properties in keyboard events.
+/// `key` is the produced value (layout-dependent, e.g. `"a"` or `"A"`), while
+/// `code` is the physical key (layout-independent, e.g. `"KeyA"`).
 #[derive(Clone)]
 pub struct KeyboardEventArgs {
     pub key: String,
+    pub code: String,
     pub modifiers: Vec<ModifierKey>,
     pub is_repeat: bool,
 }
@@ -176,10 +262,14 @@ pub struct ArgsKeyPress {
 // Mouse Events
 
 /// Common properties in mouse events.
+/// `x` and `y` are in global (viewport) coordinates, while `local_x` and `local_y` are
+/// relative to the top-left corner of the element handling the event, before its transform.
 #[derive(Clone)]
 pub struct MouseEventArgs {
     pub x: f64,
     pub y: f64,
+    pub local_x: f64,
+    pub local_y: f64,
     pub button: MouseButton,
     pub modifiers: Vec<ModifierKey>,
 }
@@ -320,6 +410,13 @@ impl Add for Size {
     }
 }
 
+impl Sub for Size {
+    type Output = Size;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
 impl Size {
     #[allow(non_snake_case)]
     pub fn ZERO() -> Self {
@@ -338,6 +435,7 @@ impl Size {
     }
 }
 
+#[derive(Clone, Copy)]
 pub enum Axis {
     X,
     Y,
@@ -380,6 +478,15 @@ pub struct CommonProperties {
     pub transform: Rc<RefCell<dyn PropertyInstance<Transform2D>>>,
     pub width: Rc<RefCell<dyn PropertyInstance<Size>>>,
     pub height: Rc<RefCell<dyn PropertyInstance<Size>>>,
+    pub min_width: Option<Rc<RefCell<dyn PropertyInstance<Size>>>>,
+    pub max_width: Option<Rc<RefCell<dyn PropertyInstance<Size>>>>,
+    pub min_height: Option<Rc<RefCell<dyn PropertyInstance<Size>>>>,
+    pub max_height: Option<Rc<RefCell<dyn PropertyInstance<Size>>>>,
+    /// From `0.0` (transparent) to `1.0` (opaque), multiplied into the opacity of every descendant.
+    /// Each descendant is drawn with its own accumulated opacity: a subtree is not composited as a unit,
+    /// so overlapping children of a translucent parent show through one another.
+    pub opacity: Option<Rc<RefCell<dyn PropertyInstance<f64>>>>,
+    pub z_index: Option<Rc<RefCell<dyn PropertyInstance<isize>>>>,
 }
 
 impl CommonProperties {
@@ -418,8 +525,40 @@ impl CommonProperties {
             ("transform".to_string(), "Transform2D".to_string()),
             ("width".to_string(), "Size".to_string()),
             ("height".to_string(), "Size".to_string()),
+            ("min_width".to_string(), "Size".to_string()),
+            ("max_width".to_string(), "Size".to_string()),
+            ("min_height".to_string(), "Size".to_string()),
+            ("max_height".to_string(), "Size".to_string()),
+            ("opacity".to_string(), "f64".to_string()),
+            ("z_index".to_string(), "isize".to_string()),
         ]
     }
+
+    /// Clamps an already-evaluated `(width, height)` pixel size by `min_*` / `max_*`, if set.
+    /// `Percent` constraints are evaluated against `bounds`.  As in CSS, `min_*` wins over `max_*`.
+    pub fn clamp_size(&self, size: (f64, f64), bounds: (f64, f64)) -> (f64, f64) {
+        fn clamp_axis(
+            value: f64,
+            min: &Option<Rc<RefCell<dyn PropertyInstance<Size>>>>,
+            max: &Option<Rc<RefCell<dyn PropertyInstance<Size>>>>,
+            bounds: (f64, f64),
+            axis: Axis,
+        ) -> f64 {
+            let mut value = value;
+            if let Some(max) = max {
+                value = value.min(max.as_ref().borrow().get().evaluate(bounds, axis));
+            }
+            if let Some(min) = min {
+                value = value.max(min.as_ref().borrow().get().evaluate(bounds, axis));
+            }
+            value
+        }
+
+        (
+            clamp_axis(size.0, &self.min_width, &self.max_width, bounds, Axis::X),
+            clamp_axis(size.1, &self.min_height, &self.max_height, bounds, Axis::Y),
+        )
+    }
 }
 
 impl Default for CommonProperties {
@@ -437,6 +576,12 @@ impl Default for CommonProperties {
 
             width: Rc::new(RefCell::new(PropertyLiteral::new(Size::default()))),
             height: Rc::new(RefCell::new(PropertyLiteral::new(Size::default()))),
+            min_width: Default::default(),
+            max_width: Default::default(),
+            min_height: Default::default(),
+            max_height: Default::default(),
+            opacity: Default::default(),
+            z_index: Default::default(),
 
             transform: Transform2D::default_wrapped(),
         }
@@ -492,6 +637,15 @@ impl Into<Rotation> for Size {
     }
 }
 
+impl Interpolatable for Rotation {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        let radians = self.get_as_radians();
+        Rotation::Radians(Numeric::from(
+            radians + (other.get_as_radians() - radians) * t,
+        ))
+    }
+}
+
 impl Default for Rotation {
     fn default() -> Self {
         Self::ZERO()
@@ -674,6 +828,11 @@ impl Mul for Size {
 ///             By default that's the top-left of the element, but `anchor` allows that
 ///             to be offset either by a pixel or percentage-of-element-size
 ///             for each of (x,y)
+///
+/// Within a single `Transform2D`, the anchor offset applies first, then scale (including flips),
+/// skew and rotation, then translation.  Multiplied transforms compose right-to-left onto points:
+/// in `Transform2D::anchor(50%, 50%) * Transform2D::flip(true, false)`, the anchor applies before the
+/// flip, so the element mirrors about its own center (which the anchor places at its position).
 #[derive(Default, Clone)]
 pub struct Transform2D {
     /// Keeps track of a linked list of previous Transform2Ds, assembled e.g. via multiplication
@@ -686,6 +845,35 @@ pub struct Transform2D {
     pub skew: Option<[f64; 2]>,
 }
 
+impl Interpolatable for Transform2D {
+    /// Interpolates component-wise, e.g. between `translate(0px, 0px)` and `translate(100px, 0px)`.
+    /// Components present on only one side jump to `other`'s value.
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        fn interpolate_component<C: Interpolatable>(
+            this: &Option<C>,
+            other: &Option<C>,
+            t: f64,
+        ) -> Option<C> {
+            match (this, other) {
+                (Some(this), Some(other)) => Some(this.interpolate(other, t)),
+                _ => other.clone(),
+            }
+        }
+
+        Transform2D {
+            previous: match (&self.previous, &other.previous) {
+                (Some(this), Some(other)) => Some(Box::new(this.interpolate(other, t))),
+                _ => other.previous.clone(),
+            },
+            rotate: interpolate_component(&self.rotate, &other.rotate, t),
+            translate: interpolate_component(&self.translate, &other.translate, t),
+            anchor: interpolate_component(&self.anchor, &other.anchor, t),
+            scale: interpolate_component(&self.scale, &other.scale, t),
+            skew: interpolate_component(&self.skew, &other.skew, t),
+        }
+    }
+}
+
 impl Mul for Transform2D {
     type Output = Transform2D;
 
@@ -697,12 +885,18 @@ impl Mul for Transform2D {
 }
 
 impl Transform2D {
-    ///Scale coefficients (1.0 == 100%) over x-y plane
+    ///Scale coefficients (1.0 == 100%) over x-y plane; may be non-uniform, e.g. `scale(200%, 100%)`
     pub fn scale(x: Size, y: Size) -> Self {
         let mut ret = Transform2D::default();
         ret.scale = Some([x, y]);
         ret
     }
+    ///Mirrors across the vertical axis (`horizontal`) and/or the horizontal axis (`vertical`), about the anchor.
+    ///Equivalent to a scale of `-100%` on each flipped axis
+    pub fn flip(horizontal: bool, vertical: bool) -> Self {
+        let sign = |is_flipped: bool| Size::Percent(if is_flipped { -100 } else { 100 }.into());
+        Transform2D::scale(sign(horizontal), sign(vertical))
+    }
     ///Rotation over z axis
     pub fn rotate(z: Rotation) -> Self {
         let mut ret = Transform2D::default();
@@ -770,6 +964,11 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyLiteral<T> {
     }
 
     fn get_mut(&mut self) -> &mut T {
+        mark_properties_dirty();
+        &mut self.value
+    }
+
+    fn _get_mut_untracked(&mut self) -> &mut T {
         &mut self.value
     }
 
@@ -779,10 +978,12 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyLiteral<T> {
 
     fn set(&mut self, value: T) {
         self.value = value;
+        mark_properties_dirty();
     }
 
     //FUTURE: when trait fields land in Rust, DRY this implementation vs. other <T: PropertyInstance> implementations
     fn ease_to(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve) {
+        mark_properties_dirty();
         self.transition_manager.value = Some(self.get().clone());
         let _ = &self.transition_manager.queue.clear();
         let _ = &self
@@ -798,6 +999,7 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyLiteral<T> {
     }
 
     fn ease_to_later(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve) {
+        mark_properties_dirty();
         if let None = self.transition_manager.value {
             //handle case where transition queue is empty -- a None value gets skipped, so populate it with Some
             self.transition_manager.value = Some(self.get().clone());
@@ -841,6 +1043,8 @@ pub enum EasingCurve {
     InBack,
     OutBack,
     InOutBack,
+    InOutCubic,
+    InOutElastic,
     Custom(Box<dyn Fn(f64) -> f64>),
 }
 
@@ -883,23 +1087,50 @@ impl EasingEvaluators {
             ((2.0 * t - 2.0).powi(2) * ((C2 + 1.0) * (t * 2.0 - 2.0) + C2) + 2.0) / 2.0
         }
     }
+
+    fn in_out_cubic(t: f64) -> f64 {
+        if t < 0.5 {
+            4.0 * t * t * t
+        } else {
+            1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+        }
+    }
+
+    fn in_out_elastic(t: f64) -> f64 {
+        const C5: f64 = (2.0 * std::f64::consts::PI) / 4.5;
+        if t <= 0.0 {
+            0.0
+        } else if t >= 1.0 {
+            1.0
+        } else if t < 0.5 {
+            -(2.0_f64.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * C5).sin()) / 2.0
+        } else {
+            (2.0_f64.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * C5).sin()) / 2.0 + 1.0
+        }
+    }
 }
 
 impl EasingCurve {
-    //for a time on the unit interval `t ∈ [0,1]`, given a value `t`,
-    // find the interpolated value `vt` between `v0` and `v1` given the self-contained easing curve
-    pub fn interpolate<T: Interpolatable>(&self, v0: &T, v1: &T, t: f64) -> T /*vt*/ {
-        let multiplier = match self {
+    //for a time on the unit interval `t ∈ [0,1]`, returns the eased progress for this curve,
+    // where `0.0` maps to `0.0` and `1.0` maps to `1.0` (values in between may overshoot, e.g. for `OutBack`)
+    pub fn evaluate(&self, t: f64) -> f64 {
+        match self {
             EasingCurve::Linear => EasingEvaluators::linear(t),
             EasingCurve::InQuad => EasingEvaluators::in_quad(t),
             EasingCurve::OutQuad => EasingEvaluators::out_quad(t),
             EasingCurve::InBack => EasingEvaluators::in_back(t),
             EasingCurve::OutBack => EasingEvaluators::out_back(t),
             EasingCurve::InOutBack => EasingEvaluators::in_out_back(t),
+            EasingCurve::InOutCubic => EasingEvaluators::in_out_cubic(t),
+            EasingCurve::InOutElastic => EasingEvaluators::in_out_elastic(t),
             EasingCurve::Custom(evaluator) => (*evaluator)(t),
-        };
+        }
+    }
 
-        v0.interpolate(v1, multiplier)
+    //for a time on the unit interval `t ∈ [0,1]`, given a value `t`,
+    // find the interpolated value `vt` between `v0` and `v1` given the self-contained easing curve
+    pub fn interpolate<T: Interpolatable>(&self, v0: &T, v1: &T, t: f64) -> T /*vt*/ {
+        v0.interpolate(v1, self.evaluate(t))
     }
 }
 
@@ -930,6 +1161,12 @@ impl<I: Interpolatable> Interpolatable for Vec<I> {
     }
 }
 
+impl<I: Interpolatable, const N: usize> Interpolatable for [I; N] {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        std::array::from_fn(|i| self[i].interpolate(&other[i], t))
+    }
+}
+
 impl Interpolatable for f64 {
     fn interpolate(&self, other: &f64, t: f64) -> f64 {
         self + (*other - self) * t
@@ -1066,3 +1303,191 @@ impl ZIndex {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        _get_properties_epoch, Axis, CommonProperties, EasingCurve, Interpolatable,
+        PropertyInstance, PropertyLiteral, Rotation, Size, Transform2D,
+    };
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_combined_evaluate() {
+        let bounds = (200.0, 100.0);
+        let size = Size::Percent(100.into()) - Size::Pixels(20.into());
+
+        assert_eq!(size.evaluate(bounds, Axis::X), 180.0);
+        assert_eq!(size.evaluate(bounds, Axis::Y), 80.0);
+    }
+
+    #[test]
+    fn test_set_marks_properties_dirty() {
+        let mut literal = PropertyLiteral::new(1.0);
+        let epoch = _get_properties_epoch();
+        let _ = literal.get();
+        assert_eq!(_get_properties_epoch(), epoch);
+        literal.set(2.0);
+        assert!(_get_properties_epoch() > epoch);
+        let epoch = _get_properties_epoch();
+        literal.ease_to(3.0, 10, EasingCurve::Linear);
+        assert!(_get_properties_epoch() > epoch);
+    }
+
+    #[test]
+    fn test_nested_get_mut_marks_properties_dirty() {
+        struct Outer {
+            inner: Box<dyn PropertyInstance<f64>>,
+            plain: f64,
+        }
+        impl Default for Outer {
+            fn default() -> Self {
+                Self {
+                    inner: Box::new(PropertyLiteral::new(0.0)),
+                    plain: 0.0,
+                }
+            }
+        }
+        impl Clone for Outer {
+            fn clone(&self) -> Self {
+                Self {
+                    inner: Box::new(PropertyLiteral::new(*self.inner.get())),
+                    plain: self.plain,
+                }
+            }
+        }
+
+        //an expression depending on `outer` is re-evaluated once the epoch moves past its last evaluation
+        let mut outer = PropertyLiteral::new(Outer::default());
+        let epoch = _get_properties_epoch();
+        outer.get_mut().plain = 1.0;
+        assert!(_get_properties_epoch() > epoch);
+        assert_eq!(outer.get().plain, 1.0);
+
+        let epoch = _get_properties_epoch();
+        outer.get_mut().inner.get_mut();
+        assert!(_get_properties_epoch() > epoch);
+
+        let epoch = _get_properties_epoch();
+        let _ = outer.get().inner.get();
+        assert_eq!(_get_properties_epoch(), epoch);
+
+        //writing back the result of evaluating a nested expression leaves the epoch unchanged
+        let epoch = _get_properties_epoch();
+        outer._get_mut_untracked().plain = 2.0;
+        assert_eq!(_get_properties_epoch(), epoch);
+        assert_eq!(outer.get().plain, 2.0);
+    }
+
+    #[test]
+    fn test_plain_evaluate() {
+        let bounds = (200.0, 100.0);
+
+        assert_eq!(Size::Pixels(20.into()).evaluate(bounds, Axis::X), 20.0);
+        assert_eq!(Size::Percent(50.into()).evaluate(bounds, Axis::Y), 50.0);
+    }
+
+    #[test]
+    fn test_clamp_size() {
+        let bounds = (200.0, 100.0);
+        let mut common_properties = CommonProperties::default();
+        common_properties.max_width = Some(Rc::new(RefCell::new(PropertyLiteral::new(
+            Size::Percent(50.into()),
+        ))));
+        common_properties.min_height = Some(Rc::new(RefCell::new(PropertyLiteral::new(
+            Size::Pixels(40.into()),
+        ))));
+
+        assert_eq!(
+            common_properties.clamp_size((150.0, 10.0), bounds),
+            (100.0, 40.0)
+        );
+        assert_eq!(
+            common_properties.clamp_size((80.0, 60.0), bounds),
+            (80.0, 60.0)
+        );
+    }
+
+    #[test]
+    fn test_easing_curve_endpoints() {
+        let curves = vec![
+            EasingCurve::Linear,
+            EasingCurve::InQuad,
+            EasingCurve::OutQuad,
+            EasingCurve::InBack,
+            EasingCurve::OutBack,
+            EasingCurve::InOutBack,
+            EasingCurve::InOutCubic,
+            EasingCurve::InOutElastic,
+        ];
+
+        for curve in curves {
+            assert!(curve.evaluate(0.0).abs() < 1e-9);
+            assert!((curve.evaluate(1.0) - 1.0).abs() < 1e-9);
+        }
+    }
+    #[test]
+    fn test_animate_queues_transitions() {
+        let mut property = PropertyLiteral::new(0.0);
+        assert!(property._get_transition_manager().is_none());
+
+        property.animate(vec![
+            (10.0, 30, EasingCurve::Linear),
+            (5.0, 15, EasingCurve::OutQuad),
+        ]);
+
+        let transition_manager = property._get_transition_manager().unwrap();
+        assert_eq!(transition_manager.value, Some(0.0));
+        let queue = &transition_manager.queue;
+        assert_eq!(queue.len(), 2);
+        assert_eq!(
+            (queue[0].starting_value, queue[0].ending_value),
+            (0.0, 10.0)
+        );
+        assert_eq!(
+            (queue[1].starting_value, queue[1].ending_value),
+            (10.0, 5.0)
+        );
+    }
+
+    #[test]
+    fn test_transform_interpolate() {
+        let from = Transform2D::translate(Size::Pixels(0.into()), Size::Pixels(0.into()))
+            * Transform2D::rotate(Rotation::Radians(0.into()));
+        let to = Transform2D::translate(Size::Pixels(100.into()), Size::Pixels(50.into()))
+            * Transform2D::rotate(Rotation::Degrees(90.into()));
+
+        let halfway = from.interpolate(&to, 0.5);
+
+        let radians = halfway.rotate.as_ref().unwrap().get_as_radians();
+        assert!((radians - std::f64::consts::FRAC_PI_4).abs() < 1e-9);
+        let translate = halfway.previous.unwrap().translate.unwrap();
+        assert_eq!(translate[0].evaluate((0.0, 0.0), Axis::X), 50.0);
+        assert_eq!(translate[1].evaluate((0.0, 0.0), Axis::Y), 25.0);
+
+        //components missing from `from` jump directly to `to`'s value
+        let unrotated = Transform2D::scale(Size::Percent(100.into()), Size::Percent(100.into()));
+        let jumped = unrotated.interpolate(&to, 0.5);
+        assert!(jumped.scale.is_none());
+        let radians = jumped.rotate.unwrap().get_as_radians();
+        assert!((radians - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_transform_flip() {
+        let scale = |transform: Transform2D| {
+            let scale = transform.scale.unwrap();
+            (scale[0].expect_percent(), scale[1].expect_percent())
+        };
+        assert_eq!(scale(Transform2D::flip(true, false)), (-1.0, 1.0));
+        assert_eq!(scale(Transform2D::flip(false, true)), (1.0, -1.0));
+        assert_eq!(scale(Transform2D::flip(false, false)), (1.0, 1.0));
+
+        //composes like any other transform, e.g. after an anchor
+        let flipped = Transform2D::anchor(Size::Percent(50.into()), Size::Percent(50.into()))
+            * Transform2D::flip(true, true);
+        assert_eq!(scale(flipped.clone()), (-1.0, -1.0));
+        assert!(flipped.previous.unwrap().anchor.is_some());
+    }
+}
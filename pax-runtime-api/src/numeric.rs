@@ -1,5 +1,6 @@
 use crate::Interpolatable;
 use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
 use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 
 /// Numeric is a module that wraps numeric literals in Pax
@@ -225,6 +226,15 @@ impl Numeric {
     }
 }
 
+impl Display for Numeric {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Numeric::Integer(value) => value.fmt(f),
+            Numeric::Float(value) => value.fmt(f),
+        }
+    }
+}
+
 impl Add for Numeric {
     type Output = Numeric;
 
@@ -389,6 +399,12 @@ mod tests {
         assert_eq!(a.get_as_float(), b);
     }
 
+    #[test]
+    fn test_display() {
+        assert_eq!(Numeric::from(3_usize).to_string(), "3");
+        assert_eq!(Numeric::from(-2.5).to_string(), "-2.5");
+    }
+
     #[test]
     fn test_float_eq() {
         let a = 100.01;
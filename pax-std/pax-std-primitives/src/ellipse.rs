@@ -1,21 +1,22 @@
-use kurbo::{Ellipse as KurboEllipse, Rect, Shape};
+use kurbo::{Arc, BezPath, Ellipse as KurboEllipse, Point, Rect, Shape, Vec2};
 use piet::RenderContext;
 
 use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
 use pax_core::{
-    unsafe_unwrap, Color, HandlerRegistry, InstantiationArgs, PropertiesComputable, RenderNode,
-    RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+    try_unwrap, unsafe_unwrap, Color, HandlerRegistry, InstantiationArgs, PropertiesComputable,
+    RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext, TransformAndBounds,
 };
 use pax_std::primitives::Ellipse;
 use pax_std::types::ColorVariant;
 
-use pax_runtime_api::CommonProperties;
+use pax_runtime_api::{Axis, CommonProperties};
 
 use std::cell::RefCell;
 use std::rc::Rc;
 
 /// A basic 2D vector ellipse, drawn to fill the bounds specified
-/// by `size`, transformed by `transform`
+/// by `size`, transformed by `transform`.  Partial arcs (pie slices, or donut
+/// segments when `inner_radius` is non-zero) are drawn between `start_angle` and `end_angle`.
 pub struct EllipseInstance<R: 'static + RenderContext> {
     pub handler_registry: Option<Rc<RefCell<HandlerRegistry<R>>>>,
     pub instance_id: u32,
@@ -54,6 +55,11 @@ impl<R: 'static + RenderContext> RenderNode<R> for EllipseInstance<R> {
         ret
     }
 
+    /// Hit-tests against the ellipse equation rather than the bounding box
+    fn ray_cast_test(&self, ray: &(f64, f64), tab: &TransformAndBounds) -> bool {
+        tab.contains_point_in_ellipse(ray)
+    }
+
     fn get_handler_registry(&self) -> Option<Rc<RefCell<HandlerRegistry<R>>>> {
         match &self.handler_registry {
             Some(registry) => Some(Rc::clone(registry)),
@@ -65,28 +71,63 @@ impl<R: 'static + RenderContext> RenderNode<R> for EllipseInstance<R> {
 
         let properties = &mut *self.properties.as_ref().borrow_mut();
 
-        if let Some(stroke_width) =
-            rtc.compute_vtable_value(properties.stroke.get().width._get_vtable_id())
-        {
-            let new_value = if let TypesCoproduct::SizePixels(v) = stroke_width {
-                v
-            } else {
-                unreachable!()
-            };
-            properties.stroke.get_mut().width.set(new_value);
+        if let Some(stroke_width) = rtc.compute_vtable_value(
+            properties
+                .stroke
+                .get()
+                .width
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = try_unwrap!(stroke_width, TypesCoproduct, SizePixels).unwrap();
+            properties.stroke._get_mut_untracked().width.set(new_value);
         }
 
-        if let Some(stroke_color) =
-            rtc.compute_vtable_value(properties.stroke.get().color._get_vtable_id())
-        {
+        if let Some(stroke_color) = rtc.compute_vtable_value(
+            properties
+                .stroke
+                .get()
+                .color
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(stroke_color, TypesCoproduct, pax_std::types::Color);
-            properties.stroke.get_mut().color.set(new_value);
+            properties.stroke._get_mut_untracked().color.set(new_value);
         }
 
-        if let Some(fill) = rtc.compute_vtable_value(properties.fill._get_vtable_id()) {
+        if let Some(fill) = rtc.compute_vtable_value(
+            properties
+                .fill
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(fill, TypesCoproduct, pax_std::types::Color);
             properties.fill.set(new_value);
         }
+
+        if let Some(start_angle) = rtc.compute_vtable_value(
+            properties
+                .start_angle
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = try_unwrap!(start_angle, TypesCoproduct, Rotation).unwrap();
+            properties.start_angle.set(new_value);
+        }
+
+        if let Some(end_angle) = rtc.compute_vtable_value(
+            properties
+                .end_angle
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = try_unwrap!(end_angle, TypesCoproduct, Rotation).unwrap();
+            properties.end_angle.set(new_value);
+        }
+
+        if let Some(inner_radius) = rtc.compute_vtable_value(
+            properties
+                .inner_radius
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = try_unwrap!(inner_radius, TypesCoproduct, Size).unwrap();
+            properties.inner_radius.set(new_value);
+        }
     }
     fn handle_render(&mut self, rtc: &mut RenderTreeContext<R>, rc: &mut R) {
         let transform = rtc.transform_scroller_reset;
@@ -104,25 +145,112 @@ impl<R: 'static + RenderContext> RenderNode<R> for EllipseInstance<R> {
             ColorVariant::Rgb(slice) => Color::rgb(slice[0], slice[1], slice[2]),
         };
 
-        let rect = Rect::from_points((0.0, 0.0), (width, height));
-        let ellipse = KurboEllipse::from_rect(rect);
-        let accuracy = 0.1;
-        let bez_path = ellipse.to_path(accuracy);
+        let bez_path = compute_ellipse_path(&properties, width, height);
 
         let transformed_bez_path = transform * bez_path;
         let duplicate_transformed_bez_path = transformed_bez_path.clone();
 
-        let color = properties.fill.get().to_piet_color();
+        let color = properties
+            .fill
+            .get()
+            .to_piet_color_with_opacity(rtc.opacity);
         rc.fill(transformed_bez_path, &color);
 
-        //hack to address "phantom stroke" bug on Web
-        let width: f64 = *&properties.stroke.get().width.get().into();
-        if width > f64::EPSILON {
+        if let Some(width) = properties.stroke.get().get_visible_width() {
             rc.stroke(
                 duplicate_transformed_bez_path,
-                &properties.stroke.get().color.get().to_piet_color(),
+                &properties
+                    .stroke
+                    .get()
+                    .color
+                    .get()
+                    .to_piet_color_with_opacity(rtc.opacity),
                 width,
             );
         }
     }
 }
+
+/// Builds the path for `properties` within bounds of `width` by `height`: the full ellipse,
+/// unless `start_angle`, `end_angle` or `inner_radius` describe an arc.
+fn compute_ellipse_path(properties: &Ellipse, width: f64, height: f64) -> BezPath {
+    let accuracy = 0.1;
+    let radii = Vec2::new(width / 2.0, height / 2.0);
+    let inner_radii = Vec2::new(
+        properties
+            .inner_radius
+            .get()
+            .evaluate((radii.x, radii.y), Axis::X),
+        properties
+            .inner_radius
+            .get()
+            .evaluate((radii.x, radii.y), Axis::Y),
+    );
+    let start_angle = properties.start_angle.get().get_as_radians();
+    let sweep_angle = properties.end_angle.get().get_as_radians() - start_angle;
+
+    if sweep_angle == 0.0 && inner_radii.x <= 0.0 && inner_radii.y <= 0.0 {
+        let rect = Rect::from_points((0.0, 0.0), (width, height));
+        let ellipse = KurboEllipse::from_rect(rect);
+        ellipse.to_path(accuracy)
+    } else {
+        //An unspecified sweep (equal start & end angles) describes a full revolution
+        let sweep_angle = if sweep_angle == 0.0 {
+            std::f64::consts::TAU
+        } else {
+            sweep_angle.clamp(-std::f64::consts::TAU, std::f64::consts::TAU)
+        };
+        compute_arc_path(radii, inner_radii, start_angle, sweep_angle, accuracy)
+    }
+}
+
+/// Builds a closed path for an elliptical arc centered within the bounds described by `radii`.
+/// Draws a pie slice when `inner_radii` are zero, or a donut segment otherwise.
+fn compute_arc_path(
+    radii: Vec2,
+    inner_radii: Vec2,
+    start_angle: f64,
+    sweep_angle: f64,
+    accuracy: f64,
+) -> BezPath {
+    let center = Point::new(radii.x, radii.y);
+    let outer_arc = Arc::new(center, radii, start_angle, sweep_angle, 0.0);
+
+    let mut bez_path = BezPath::new();
+    let outer_start = center + Vec2::new(radii.x * start_angle.cos(), radii.y * start_angle.sin());
+    if inner_radii.x > 0.0 || inner_radii.y > 0.0 {
+        let end_angle = start_angle + sweep_angle;
+        let inner_arc = Arc::new(center, inner_radii, end_angle, -sweep_angle, 0.0);
+        let inner_start = center
+            + Vec2::new(
+                inner_radii.x * end_angle.cos(),
+                inner_radii.y * end_angle.sin(),
+            );
+        bez_path.move_to(outer_start);
+        bez_path.extend(outer_arc.append_iter(accuracy));
+        bez_path.line_to(inner_start);
+        bez_path.extend(inner_arc.append_iter(accuracy));
+    } else {
+        bez_path.move_to(center);
+        bez_path.line_to(outer_start);
+        bez_path.extend(outer_arc.append_iter(accuracy));
+    }
+    bez_path.close_path();
+    bez_path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compute_ellipse_path;
+    use kurbo::{Ellipse as KurboEllipse, Rect, Shape};
+    use pax_std::primitives::Ellipse;
+
+    #[test]
+    fn test_default_ellipse_is_drawn_in_full() {
+        let expected = KurboEllipse::from_rect(Rect::from_points((0.0, 0.0), (100.0, 50.0)));
+        assert_eq!(
+            compute_ellipse_path(&Ellipse::default(), 100.0, 50.0),
+            expected.to_path(0.1)
+        );
+    }
+}
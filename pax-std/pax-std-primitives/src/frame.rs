@@ -7,12 +7,15 @@ use std::rc::Rc;
 use kurbo::BezPath;
 use piet::RenderContext;
 
+use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
 use pax_core::{
-    HandlerRegistry, InstantiationArgs, PropertiesComputable, RenderNode, RenderNodePtr,
-    RenderNodePtrList, RenderTreeContext,
+    try_unwrap, unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable,
+    RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext,
 };
 use pax_message::{AnyCreatePatch, FramePatch};
-use pax_runtime_api::{CommonProperties, Layer, Size};
+use pax_runtime_api::{ArgsScroll, CommonProperties, Layer, Size};
+use pax_std::primitives::Frame;
+use pax_std::types::FrameOverflow;
 
 /// A primitive that gathers children underneath a single render node with a shared base transform,
 /// like [`Group`], except [`Frame`] has the option of clipping rendering outside
@@ -21,15 +24,72 @@ use pax_runtime_api::{CommonProperties, Layer, Size};
 /// If clipping or the option of clipping is not required,
 /// a [`Group`] will generally be a more performant and otherwise-equivalent
 /// to [`Frame`], since `[Frame]` creates a clipping mask.
+///
+/// With `overflow: FrameOverflow::Scroll`, `@scroll` events reaching a [`Frame`] accumulate into
+/// a scroll position along each enabled axis, by which its children are translated within the clipping mask.
+/// The scroll position is clamped between zero and the extent by which the children overflow the frame.
+/// Unlike [`Scroller`], this scrolling is handled entirely by the engine, without a native scrolling container.
 pub struct FrameInstance<R: 'static + RenderContext> {
     pub instance_id: u32,
     pub children: RenderNodePtrList<R>,
     pub handler_registry: Option<Rc<RefCell<HandlerRegistry<R>>>>,
+    pub properties: Rc<RefCell<Frame>>,
 
     pub common_properties: CommonProperties,
+    pub scroll_x: f64,
+    pub scroll_y: f64,
+    /// Scroll speed, in px per frame, carried between frames when `scroll_momentum` is enabled
+    pub scroll_velocity: (f64, f64),
+    /// Whether `@scroll` events have arrived since the last `compute_properties`, which suspends momentum
+    is_scrolling: bool,
+    /// This frame's size and its children's extent, as of the last render
+    last_bounds: (f64, f64),
+    last_content_bounds: kurbo::Rect,
     last_patches: HashMap<Vec<u32>, FramePatch>,
 }
 
+/// Fraction of its scroll velocity that a [`Frame`] retains from one frame to the next
+const SCROLL_MOMENTUM_DECAY: f64 = 0.92;
+/// Scroll speed, in px per frame, below which momentum scrolling comes to rest
+const SCROLL_MOMENTUM_MIN_VELOCITY: f64 = 0.1;
+
+impl<R: 'static + RenderContext> FrameInstance<R> {
+    fn is_clipping(&self) -> bool {
+        !matches!(
+            self.properties.as_ref().borrow().overflow.get(),
+            FrameOverflow::Visible
+        )
+    }
+
+    /// Clamps the scroll position to `[0, content_size - frame_size]` on each axis, stopping any
+    /// momentum that runs into either end.  Disabled axes are reset to their resting position.
+    fn clamp_scroll(&mut self, properties: &Frame) {
+        let max_x = (self.last_content_bounds.x1 - self.last_bounds.0).max(0.0);
+        let max_y = (self.last_content_bounds.y1 - self.last_bounds.1).max(0.0);
+        let max_x = if *properties.scroll_enabled_x.get() {
+            max_x
+        } else {
+            0.0
+        };
+        let max_y = if *properties.scroll_enabled_y.get() {
+            max_y
+        } else {
+            0.0
+        };
+
+        let clamped_x = self.scroll_x.clamp(0.0, max_x);
+        let clamped_y = self.scroll_y.clamp(0.0, max_y);
+        if clamped_x != self.scroll_x {
+            self.scroll_velocity.0 = 0.0;
+        }
+        if clamped_y != self.scroll_y {
+            self.scroll_velocity.1 = 0.0;
+        }
+        self.scroll_x = clamped_x;
+        self.scroll_y = clamped_y;
+    }
+}
+
 impl<R: 'static + RenderContext> RenderNode<R> for FrameInstance<R> {
     fn get_handler_registry(&self) -> Option<Rc<RefCell<HandlerRegistry<R>>>> {
         match &self.handler_registry {
@@ -49,6 +109,8 @@ impl<R: 'static + RenderContext> RenderNode<R> for FrameInstance<R> {
     where
         Self: Sized,
     {
+        let properties = unsafe_unwrap!(args.properties, PropertiesCoproduct, Frame);
+
         let mut instance_registry = args.instance_registry.borrow_mut();
         let instance_id = instance_registry.mint_id();
         let ret = Rc::new(RefCell::new(Self {
@@ -56,7 +118,14 @@ impl<R: 'static + RenderContext> RenderNode<R> for FrameInstance<R> {
             children: args.children.unwrap(), //Frame expects primitive_children, even if empty Vec
             last_patches: HashMap::new(),
             handler_registry: args.handler_registry,
+            properties: Rc::new(RefCell::new(properties)),
             common_properties: args.common_properties,
+            scroll_x: 0.0,
+            scroll_y: 0.0,
+            scroll_velocity: (0.0, 0.0),
+            is_scrolling: false,
+            last_bounds: (0.0, 0.0),
+            last_content_bounds: kurbo::Rect::ZERO,
         }));
 
         instance_registry.register(instance_id, Rc::clone(&ret) as RenderNodePtr<R>);
@@ -64,13 +133,38 @@ impl<R: 'static + RenderContext> RenderNode<R> for FrameInstance<R> {
     }
 
     fn get_clipping_bounds(&self) -> Option<(Size, Size)> {
-        self.get_size()
+        if self.is_clipping() {
+            self.get_size()
+        } else {
+            None
+        }
     }
 
     fn get_layer_type(&mut self) -> Layer {
         Layer::DontCare
     }
 
+    fn handle_scroll(&mut self, args_scroll: ArgsScroll) {
+        let properties = Rc::clone(&self.properties);
+        let properties = &*properties.as_ref().borrow();
+        if let FrameOverflow::Scroll = properties.overflow.get() {
+            self.scroll_x += args_scroll.delta_x;
+            self.scroll_y += args_scroll.delta_y;
+            self.scroll_velocity = (args_scroll.delta_x, args_scroll.delta_y);
+            self.is_scrolling = true;
+            self.clamp_scroll(properties);
+        }
+    }
+
+    fn get_scroll_offset(&mut self) -> (f64, f64) {
+        //scrolling forward moves content up and to the left
+        (-self.scroll_x, -self.scroll_y)
+    }
+
+    fn handle_content_bounds(&mut self, content_bounds: kurbo::Rect) {
+        self.last_content_bounds = content_bounds;
+    }
+
     fn compute_native_patches(
         &mut self,
         rtc: &mut RenderTreeContext<R>,
@@ -138,6 +232,67 @@ impl<R: 'static + RenderContext> RenderNode<R> for FrameInstance<R> {
     }
 
     fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
+        let properties = Rc::clone(&self.properties);
+        let properties = &mut *properties.as_ref().borrow_mut();
+
+        if let Some(overflow) = rtc.compute_vtable_value(
+            properties
+                .overflow
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = unsafe_unwrap!(overflow, TypesCoproduct, FrameOverflow);
+            properties.overflow.set(new_value);
+        }
+
+        if let Some(scroll_enabled_x) = rtc.compute_vtable_value(
+            properties
+                .scroll_enabled_x
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = try_unwrap!(scroll_enabled_x, TypesCoproduct, bool).unwrap();
+            properties.scroll_enabled_x.set(new_value);
+        }
+
+        if let Some(scroll_enabled_y) = rtc.compute_vtable_value(
+            properties
+                .scroll_enabled_y
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = try_unwrap!(scroll_enabled_y, TypesCoproduct, bool).unwrap();
+            properties.scroll_enabled_y.set(new_value);
+        }
+
+        if let Some(scroll_momentum) = rtc.compute_vtable_value(
+            properties
+                .scroll_momentum
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = try_unwrap!(scroll_momentum, TypesCoproduct, bool).unwrap();
+            properties.scroll_momentum.set(new_value);
+        }
+
+        if !matches!(properties.overflow.get(), FrameOverflow::Scroll) {
+            //a stale offset would otherwise keep translating content once scrolling is turned off
+            self.scroll_x = 0.0;
+            self.scroll_y = 0.0;
+            self.scroll_velocity = (0.0, 0.0);
+        } else if !*properties.scroll_momentum.get() {
+            self.scroll_velocity = (0.0, 0.0);
+        } else if !std::mem::take(&mut self.is_scrolling) {
+            //coast along with the velocity of the last `@scroll` event, decaying until it comes to rest
+            self.scroll_x += self.scroll_velocity.0;
+            self.scroll_y += self.scroll_velocity.1;
+            self.scroll_velocity.0 *= SCROLL_MOMENTUM_DECAY;
+            self.scroll_velocity.1 *= SCROLL_MOMENTUM_DECAY;
+            if self.scroll_velocity.0.abs() < SCROLL_MOMENTUM_MIN_VELOCITY {
+                self.scroll_velocity.0 = 0.0;
+            }
+            if self.scroll_velocity.1.abs() < SCROLL_MOMENTUM_MIN_VELOCITY {
+                self.scroll_velocity.1 = 0.0;
+            }
+        }
+        self.clamp_scroll(properties);
+
         self.common_properties.compute_properties(rtc);
     }
 
@@ -146,6 +301,11 @@ impl<R: 'static + RenderContext> RenderNode<R> for FrameInstance<R> {
         rtc: &mut RenderTreeContext<R>,
         rcs: &mut HashMap<std::string::String, R>,
     ) {
+        self.last_bounds = rtc.bounds;
+        if !self.is_clipping() {
+            return;
+        }
+
         // construct a BezPath of this frame's bounds * its transform,
         // then pass that BezPath into rc.clip() [which pushes a clipping context to a piet-internal stack]
 
@@ -172,6 +332,9 @@ impl<R: 'static + RenderContext> RenderNode<R> for FrameInstance<R> {
         (*rtc.runtime).borrow_mut().push_clipping_stack_id(id_chain);
     }
     fn handle_did_render(&mut self, rtc: &mut RenderTreeContext<R>, _rcs: &mut HashMap<String, R>) {
+        if !self.is_clipping() {
+            return;
+        }
         for (_key, rc) in _rcs.iter_mut() {
             //pop the clipping context from the stack
             rc.restore().unwrap();
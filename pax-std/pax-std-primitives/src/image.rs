@@ -1,18 +1,25 @@
+use kurbo::Shape;
 use pax_std::primitives::Image;
-use piet::{ImageFormat, InterpolationMode, RenderContext};
+use pax_std::types::{Color, ImageFit, ImageInterpolation};
+use piet::{ImageFormat, RenderContext};
 use std::collections::HashMap;
 
 use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
 use pax_core::{
-    unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable, RenderNode,
-    RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+    try_unwrap, unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable,
+    RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext,
 };
 use pax_message::ImagePatch;
 use pax_runtime_api::CommonProperties;
 use std::cell::RefCell;
 use std::rc::Rc;
-/// An Image (decoded by chassis), drawn to the bounds specified
-/// by `size`, transformed by `transform`
+
+/// Appended to an image's `id_chain` to address its `placeholder_path` image, which is loaded
+/// through the chassis alongside (and keyed separately from) the image itself
+const PLACEHOLDER_ID_CHAIN_SUFFIX: u32 = u32::MAX;
+
+/// An Image (decoded by chassis), drawn within the bounds specified
+/// by `size` according to `fit`, transformed by `transform`
 pub struct ImageInstance<R: 'static + RenderContext> {
     pub handler_registry: Option<Rc<RefCell<HandlerRegistry<R>>>>,
     pub instance_id: u32,
@@ -20,6 +27,16 @@ pub struct ImageInstance<R: 'static + RenderContext> {
     pub common_properties: CommonProperties,
     last_patches: HashMap<Vec<u32>, pax_message::ImagePatch>,
     pub image: Option<<R as RenderContext>::Image>,
+    /// Intrinsic (width, height) of `image`, in pixels
+    image_size: (f64, f64),
+    /// Opacity multiplied into the alpha of `image` when it was made
+    image_opacity: f64,
+    /// Decoded `placeholder_path` image, drawn while `image` is still loading
+    placeholder_image: Option<<R as RenderContext>::Image>,
+    /// Intrinsic (width, height) of `placeholder_image`, in pixels
+    placeholder_image_size: (f64, f64),
+    /// Opacity multiplied into the alpha of `placeholder_image` when it was made
+    placeholder_image_opacity: f64,
 }
 
 impl<R: 'static + RenderContext> RenderNode<R> for ImageInstance<R> {
@@ -48,6 +65,11 @@ impl<R: 'static + RenderContext> RenderNode<R> for ImageInstance<R> {
             handler_registry: args.handler_registry,
             last_patches: Default::default(),
             image: None,
+            image_size: (0.0, 0.0),
+            image_opacity: 1.0,
+            placeholder_image: None,
+            placeholder_image_size: (0.0, 0.0),
+            placeholder_image_opacity: 1.0,
         }));
 
         instance_registry.register(instance_id, Rc::clone(&ret) as RenderNodePtr<R>);
@@ -63,53 +85,84 @@ impl<R: 'static + RenderContext> RenderNode<R> for ImageInstance<R> {
     fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
         let properties = &mut *self.properties.as_ref().borrow_mut();
 
-        if let Some(path) = rtc.compute_vtable_value(properties.path._get_vtable_id()) {
-            let new_value = if let TypesCoproduct::String(v) = path {
-                v
-            } else {
-                unreachable!()
-            };
+        if let Some(path) = rtc.compute_vtable_value(
+            properties
+                .path
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = try_unwrap!(path, TypesCoproduct, String).unwrap();
             properties.path.set(new_value);
         }
 
+        if let Some(fit) = rtc.compute_vtable_value(
+            properties
+                .fit
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = unsafe_unwrap!(fit, TypesCoproduct, ImageFit);
+            properties.fit.set(new_value);
+        }
+
+        if let Some(interpolation) = rtc.compute_vtable_value(
+            properties
+                .interpolation
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = unsafe_unwrap!(interpolation, TypesCoproduct, ImageInterpolation);
+            properties.interpolation.set(new_value);
+        }
+
+        if let Some(placeholder_color) = rtc.compute_vtable_value(
+            properties
+                .placeholder_color
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = unsafe_unwrap!(placeholder_color, TypesCoproduct, Option<Color>);
+            properties.placeholder_color.set(new_value);
+        }
+
+        if let Some(placeholder_path) = rtc.compute_vtable_value(
+            properties
+                .placeholder_path
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = try_unwrap!(placeholder_path, TypesCoproduct, String).unwrap();
+            properties.placeholder_path.set(new_value);
+        }
+
         self.common_properties.compute_properties(rtc);
     }
 
     fn compute_native_patches(
         &mut self,
         rtc: &mut RenderTreeContext<R>,
-        _computed_size: (f64, f64),
+        computed_size: (f64, f64),
         _transform_coeffs: Vec<f64>,
         _z_index: u32,
         _subtree_depth: u32,
     ) {
-        let mut new_message: ImagePatch = Default::default();
-        new_message.id_chain = rtc.get_id_chain(self.instance_id);
-        if !self.last_patches.contains_key(&new_message.id_chain) {
-            let mut patch = ImagePatch::default();
-            patch.id_chain = new_message.id_chain.clone();
-            self.last_patches
-                .insert(new_message.id_chain.clone(), patch);
-        }
-        let last_patch = self.last_patches.get_mut(&new_message.id_chain).unwrap();
-        let mut has_any_updates = false;
-
-        let properties = &mut *self.properties.as_ref().borrow_mut();
-        let val = properties.path.get();
-        let is_new_value = match &last_patch.path {
-            Some(cached_value) => !val.eq(cached_value),
-            None => true,
-        };
-        if is_new_value {
-            new_message.path = Some(val.clone());
-            last_patch.path = Some(val.clone());
-            has_any_updates = true;
-        }
+        let id_chain = rtc.get_id_chain(self.instance_id);
+        let properties = &*self.properties.as_ref().borrow();
+        enqueue_image_load_if_changed(
+            &mut self.last_patches,
+            rtc,
+            id_chain.clone(),
+            properties.path.get(),
+            computed_size,
+        );
 
-        if has_any_updates {
-            (*rtc.engine.runtime)
-                .borrow_mut()
-                .enqueue_native_message(pax_message::NativeMessage::ImageLoad(new_message));
+        // The placeholder is only worth loading while the image itself hasn't arrived
+        let placeholder_path = properties.placeholder_path.get();
+        if self.image.is_none() && !placeholder_path.is_empty() {
+            let mut placeholder_id_chain = id_chain;
+            placeholder_id_chain.push(PLACEHOLDER_ID_CHAIN_SUFFIX);
+            enqueue_image_load_if_changed(
+                &mut self.last_patches,
+                rtc,
+                placeholder_id_chain,
+                placeholder_path,
+                computed_size,
+            );
         }
     }
 
@@ -119,23 +172,161 @@ impl<R: 'static + RenderContext> RenderNode<R> for ImageInstance<R> {
         let width = bounding_dimens.0;
         let height = bounding_dimens.1;
 
-        let bounds = kurbo::Rect::new(0.0, 0.0, width, height);
-        let top_left = transform * kurbo::Point::new(bounds.min_x(), bounds.min_y());
-        let bottom_right = transform * kurbo::Point::new(bounds.max_x(), bounds.max_y());
-        let transformed_bounds =
-            kurbo::Rect::new(top_left.x, top_left.y, bottom_right.x, bottom_right.y);
-
-        let _properties = (*self.properties).borrow();
+        let properties = (*self.properties).borrow();
         let id_chain = rtc.get_id_chain(self.instance_id);
-        if rtc.engine.image_map.contains_key(&id_chain) && self.image.is_none() {
+        //piet draws images fully opaque, so `opacity` is baked into their alpha, re-making them when it changes
+        if rtc.engine.image_map.contains_key(&id_chain)
+            && (self.image.is_none() || self.image_opacity != rtc.opacity)
+        {
             let (bytes, width, height) = rtc.engine.image_map.get(&id_chain).unwrap();
-            let image = rc
-                .make_image(*width, *height, &*bytes, ImageFormat::RgbaSeparate)
-                .unwrap();
+            let image = make_image_with_opacity(rc, bytes, (*width, *height), rtc.opacity);
             self.image = Some(image);
+            self.image_size = (*width as f64, *height as f64);
+            self.image_opacity = rtc.opacity;
         }
         if let Some(image) = &self.image {
-            rc.draw_image(&image, transformed_bounds, InterpolationMode::Bilinear);
+            draw_image_fit(
+                rc,
+                image,
+                self.image_size,
+                &properties,
+                transform,
+                (width, height),
+            );
+            return;
+        }
+
+        //still loading: draw whichever placeholders were specified
+        if let Some(color) = properties.placeholder_color.get() {
+            let bounds = transform * kurbo::Rect::new(0.0, 0.0, width, height).to_path(0.1);
+            rc.fill(bounds, &color.to_piet_color_with_opacity(rtc.opacity));
+        }
+        let mut placeholder_id_chain = id_chain;
+        placeholder_id_chain.push(PLACEHOLDER_ID_CHAIN_SUFFIX);
+        if rtc.engine.image_map.contains_key(&placeholder_id_chain)
+            && (self.placeholder_image.is_none() || self.placeholder_image_opacity != rtc.opacity)
+        {
+            let (bytes, width, height) = rtc.engine.image_map.get(&placeholder_id_chain).unwrap();
+            let image = make_image_with_opacity(rc, bytes, (*width, *height), rtc.opacity);
+            self.placeholder_image = Some(image);
+            self.placeholder_image_size = (*width as f64, *height as f64);
+            self.placeholder_image_opacity = rtc.opacity;
         }
+        if let Some(placeholder_image) = &self.placeholder_image {
+            draw_image_fit(
+                rc,
+                placeholder_image,
+                self.placeholder_image_size,
+                &properties,
+                transform,
+                (width, height),
+            );
+        }
+    }
+}
+
+/// Diffs an image load request against the last one sent for `id_chain`, enqueueing an
+/// `ImageLoad` if either the path or the on-screen size has changed
+fn enqueue_image_load_if_changed<R: 'static + RenderContext>(
+    last_patches: &mut HashMap<Vec<u32>, ImagePatch>,
+    rtc: &mut RenderTreeContext<R>,
+    id_chain: Vec<u32>,
+    path: &str,
+    computed_size: (f64, f64),
+) {
+    let mut new_message: ImagePatch = Default::default();
+    new_message.id_chain = id_chain;
+    if !last_patches.contains_key(&new_message.id_chain) {
+        let mut patch = ImagePatch::default();
+        patch.id_chain = new_message.id_chain.clone();
+        last_patches.insert(new_message.id_chain.clone(), patch);
+    }
+    let last_patch = last_patches.get_mut(&new_message.id_chain).unwrap();
+    let mut has_any_updates = false;
+
+    let val = path;
+    let is_new_value = match &last_patch.path {
+        Some(cached_value) => cached_value.as_str() != val,
+        None => true,
+    };
+    if is_new_value {
+        last_patch.path = Some(val.to_string());
+        has_any_updates = true;
+    }
+
+    let val = computed_size.0;
+    let is_new_value = match &last_patch.size_x {
+        Some(cached_value) => !val.eq(cached_value),
+        None => true,
+    };
+    if is_new_value {
+        last_patch.size_x = Some(val);
+        has_any_updates = true;
+    }
+
+    let val = computed_size.1;
+    let is_new_value = match &last_patch.size_y {
+        Some(cached_value) => !val.eq(cached_value),
+        None => true,
+    };
+    if is_new_value {
+        last_patch.size_y = Some(val);
+        has_any_updates = true;
+    }
+
+    if has_any_updates {
+        // Each ImageLoad is a complete load request, so a change to either the path
+        // or the on-screen size re-sends both
+        new_message.path = last_patch.path.clone();
+        new_message.size_x = last_patch.size_x;
+        new_message.size_y = last_patch.size_y;
+        (*rtc.engine.runtime)
+            .borrow_mut()
+            .enqueue_native_message(pax_message::NativeMessage::ImageLoad(new_message));
     }
 }
+
+/// Makes an image from RGBA `bytes` (with separate alpha), multiplying each pixel's alpha by `opacity`
+fn make_image_with_opacity<R: 'static + RenderContext>(
+    rc: &mut R,
+    bytes: &[u8],
+    (width, height): (usize, usize),
+    opacity: f64,
+) -> <R as RenderContext>::Image {
+    if opacity >= 1.0 {
+        return rc
+            .make_image(width, height, bytes, ImageFormat::RgbaSeparate)
+            .unwrap();
+    }
+    let mut bytes = bytes.to_vec();
+    for alpha in bytes.iter_mut().skip(3).step_by(4) {
+        *alpha = (*alpha as f64 * opacity.max(0.0)).round() as u8;
+    }
+    rc.make_image(width, height, &bytes, ImageFormat::RgbaSeparate)
+        .unwrap()
+}
+
+/// Draws `image`, of intrinsic size `image_size`, into the given bounds according to `fit`
+fn draw_image_fit<R: 'static + RenderContext>(
+    rc: &mut R,
+    image: &<R as RenderContext>::Image,
+    image_size: (f64, f64),
+    properties: &Image,
+    transform: kurbo::Affine,
+    (width, height): (f64, f64),
+) {
+    let (source, destination) = properties
+        .fit
+        .get()
+        .get_image_rects(image_size, (width, height));
+    let top_left = transform * kurbo::Point::new(destination.min_x(), destination.min_y());
+    let bottom_right = transform * kurbo::Point::new(destination.max_x(), destination.max_y());
+    let transformed_destination =
+        kurbo::Rect::new(top_left.x, top_left.y, bottom_right.x, bottom_right.y);
+    rc.draw_image_area(
+        image,
+        source,
+        transformed_destination,
+        properties.interpolation.get().to_piet_interpolation_mode(),
+    );
+}
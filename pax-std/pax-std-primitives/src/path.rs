@@ -1,14 +1,14 @@
-use kurbo::BezPath;
-use piet::RenderContext;
+use kurbo::{BezPath, Shape};
+use piet::{LinearGradient, RadialGradient, RenderContext};
 
 use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
 use pax_core::{
     unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable, RenderNode,
-    RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+    RenderNodePtr, RenderNodePtrList, RenderTreeContext, TransformAndBounds,
 };
 use pax_runtime_api::{CommonProperties, Size};
 use pax_std::primitives::Path;
-use pax_std::types::PathSegment;
+use pax_std::types::{Fill, PathSegment};
 
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -64,30 +64,61 @@ impl<R: 'static + RenderContext> RenderNode<R> for PathInstance<R> {
         None
     }
 
+    /// Paths are unsized, so their extent is that of their segments, padded by half the stroke width
+    fn get_bounding_box(&self, tab: &TransformAndBounds) -> Option<kurbo::Rect> {
+        let properties = (*self.properties).borrow();
+        let bez_path = get_bez_path(properties.segments.get());
+        if bez_path.elements().is_empty() {
+            return None;
+        }
+        let stroke_width: f64 = properties.stroke.get().width.get().into();
+        Some(
+            (tab.transform * bez_path)
+                .bounding_box()
+                .inflate(stroke_width / 2.0, stroke_width / 2.0),
+        )
+    }
+
     fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
         let properties = &mut *self.properties.as_ref().borrow_mut();
 
-        if let Some(stroke_width) =
-            rtc.compute_vtable_value(properties.stroke.get().width._get_vtable_id())
-        {
+        if let Some(stroke_width) = rtc.compute_vtable_value(
+            properties
+                .stroke
+                .get()
+                .width
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value =
                 unsafe_unwrap!(stroke_width, TypesCoproduct, pax_runtime_api::SizePixels);
-            properties.stroke.get_mut().width.set(new_value);
+            properties.stroke._get_mut_untracked().width.set(new_value);
         }
 
-        if let Some(stroke_color) =
-            rtc.compute_vtable_value(properties.stroke.get().color._get_vtable_id())
-        {
+        if let Some(stroke_color) = rtc.compute_vtable_value(
+            properties
+                .stroke
+                .get()
+                .color
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(stroke_color, TypesCoproduct, pax_std::types::Color);
-            properties.stroke.get_mut().color.set(new_value);
+            properties.stroke._get_mut_untracked().color.set(new_value);
         }
 
-        if let Some(fill) = rtc.compute_vtable_value(properties.fill._get_vtable_id()) {
-            let new_value = unsafe_unwrap!(fill, TypesCoproduct, pax_std::types::Color);
+        if let Some(fill) = rtc.compute_vtable_value(
+            properties
+                .fill
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = unsafe_unwrap!(fill, TypesCoproduct, Fill);
             properties.fill.set(new_value);
         }
 
-        if let Some(segments) = rtc.compute_vtable_value(properties.segments._get_vtable_id()) {
+        if let Some(segments) = rtc.compute_vtable_value(
+            properties
+                .segments
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(segments, TypesCoproduct, Vec<PathSegment>);
             properties.segments.set(new_value);
         }
@@ -99,31 +130,72 @@ impl<R: 'static + RenderContext> RenderNode<R> for PathInstance<R> {
 
         let properties = (*self.properties).borrow();
 
-        let mut bez_path = BezPath::new();
-
-        for segment in properties.segments.get().iter() {
-            match segment {
-                PathSegment::Empty => { /* no-op */ }
-                PathSegment::LineSegment(data) => {
-                    bez_path.move_to(data.start);
-                    bez_path.line_to(data.end);
-                }
-                PathSegment::CurveSegment(data) => {
-                    bez_path.move_to(data.start);
-                    bez_path.quad_to(data.handle, data.end);
-                }
-            }
-        }
+        let bez_path = get_bez_path(properties.segments.get());
 
         let transformed_bez_path = transform * bez_path;
         let duplicate_transformed_bez_path = transformed_bez_path.clone();
 
-        let color = properties.fill.get().to_piet_color();
-        rc.fill(transformed_bez_path, &color);
-        rc.stroke(
-            duplicate_transformed_bez_path,
-            &properties.stroke.get().color.get().to_piet_color(),
-            *&properties.stroke.get().width.get().into(),
-        );
+        let (width, height) = rtc.bounds;
+        match properties.fill.get() {
+            Fill::Solid(color) => {
+                rc.fill(
+                    transformed_bez_path,
+                    &color.to_piet_color_with_opacity(rtc.opacity),
+                );
+            }
+            Fill::LinearGradient(linear) => {
+                let linear_gradient = LinearGradient::new(
+                    Fill::to_unit_point(linear.start, (width, height)),
+                    Fill::to_unit_point(linear.end, (width, height)),
+                    Fill::to_piet_gradient_stops(linear.stops.clone(), rtc.opacity),
+                );
+                rc.fill(transformed_bez_path, &linear_gradient)
+            }
+            Fill::RadialGradient(radial) => {
+                let origin = Fill::to_unit_point(radial.start, (width, height));
+                let center = Fill::to_unit_point(radial.end, (width, height));
+                let gradient_stops =
+                    Fill::to_piet_gradient_stops(radial.stops.clone(), rtc.opacity);
+                let radial_gradient = RadialGradient::new(radial.radius, gradient_stops)
+                    .with_center(center)
+                    .with_origin(origin);
+                rc.fill(transformed_bez_path, &radial_gradient);
+            }
+            Fill::None => {
+                //stroke-only: skip the fill pass entirely
+            }
+        }
+        if let Some(width) = properties.stroke.get().get_visible_width() {
+            rc.stroke(
+                duplicate_transformed_bez_path,
+                &properties
+                    .stroke
+                    .get()
+                    .color
+                    .get()
+                    .to_piet_color_with_opacity(rtc.opacity),
+                width,
+            );
+        }
+    }
+}
+
+/// Builds the (untransformed) geometry described by a path's `segments`
+fn get_bez_path(segments: &[PathSegment]) -> BezPath {
+    let mut bez_path = BezPath::new();
+
+    for segment in segments.iter() {
+        match segment {
+            PathSegment::Empty => { /* no-op */ }
+            PathSegment::LineSegment(data) => {
+                bez_path.move_to(data.start);
+                bez_path.line_to(data.end);
+            }
+            PathSegment::CurveSegment(data) => {
+                bez_path.move_to(data.start);
+                bez_path.quad_to(data.handle, data.end);
+            }
+        }
     }
+    bez_path
 }
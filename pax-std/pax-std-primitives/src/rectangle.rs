@@ -3,8 +3,8 @@ use piet::{LinearGradient, RadialGradient, RenderContext};
 
 use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
 use pax_core::{
-    unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable, RenderNode,
-    RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+    try_unwrap, unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable,
+    RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext,
 };
 use pax_std::primitives::Rectangle;
 use pax_std::types::{Fill, RectangleCornerRadii};
@@ -64,64 +64,102 @@ impl<R: 'static + RenderContext> RenderNode<R> for RectangleInstance<R> {
     fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
         let properties = &mut *self.properties.as_ref().borrow_mut();
 
-        if let Some(stroke_width) =
-            rtc.compute_vtable_value(properties.stroke.get().width._get_vtable_id())
-        {
-            let new_value = if let TypesCoproduct::SizePixels(v) = stroke_width {
-                v
-            } else {
-                unreachable!()
-            };
-            properties.stroke.get_mut().width.set(new_value);
+        if let Some(stroke_width) = rtc.compute_vtable_value(
+            properties
+                .stroke
+                .get()
+                .width
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = try_unwrap!(stroke_width, TypesCoproduct, SizePixels).unwrap();
+            properties.stroke._get_mut_untracked().width.set(new_value);
         }
 
-        if let Some(stroke_color) =
-            rtc.compute_vtable_value(properties.stroke.get().color._get_vtable_id())
-        {
+        if let Some(stroke_color) = rtc.compute_vtable_value(
+            properties
+                .stroke
+                .get()
+                .color
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(stroke_color, TypesCoproduct, pax_std::types::Color);
-            properties.stroke.get_mut().color.set(new_value);
+            properties.stroke._get_mut_untracked().color.set(new_value);
         }
 
-        if let Some(fill) = rtc.compute_vtable_value(properties.fill._get_vtable_id()) {
+        if let Some(fill) = rtc.compute_vtable_value(
+            properties
+                .fill
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(fill, TypesCoproduct, Fill);
             properties.fill.set(new_value);
         }
 
-        if let Some(top_right) =
-            rtc.compute_vtable_value(properties.corner_radii.get().top_right._get_vtable_id())
-        {
+        if let Some(top_right) = rtc.compute_vtable_value(
+            properties
+                .corner_radii
+                .get()
+                .top_right
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(top_right, TypesCoproduct, f64);
-            properties.corner_radii.get_mut().top_right.set(new_value);
+            properties
+                .corner_radii
+                ._get_mut_untracked()
+                .top_right
+                .set(new_value);
         }
 
-        if let Some(top_left) =
-            rtc.compute_vtable_value(properties.corner_radii.get().top_left._get_vtable_id())
-        {
+        if let Some(top_left) = rtc.compute_vtable_value(
+            properties
+                .corner_radii
+                .get()
+                .top_left
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(top_left, TypesCoproduct, f64);
-            properties.corner_radii.get_mut().top_left.set(new_value);
+            properties
+                .corner_radii
+                ._get_mut_untracked()
+                .top_left
+                .set(new_value);
         }
 
-        if let Some(bottom_right) =
-            rtc.compute_vtable_value(properties.corner_radii.get().bottom_right._get_vtable_id())
-        {
+        if let Some(bottom_right) = rtc.compute_vtable_value(
+            properties
+                .corner_radii
+                .get()
+                .bottom_right
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(bottom_right, TypesCoproduct, f64);
             properties
                 .corner_radii
-                .get_mut()
+                ._get_mut_untracked()
                 .bottom_right
                 .set(new_value);
         }
 
-        if let Some(bottom_left) =
-            rtc.compute_vtable_value(properties.corner_radii.get().bottom_left._get_vtable_id())
-        {
+        if let Some(bottom_left) = rtc.compute_vtable_value(
+            properties
+                .corner_radii
+                .get()
+                .bottom_left
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(bottom_left, TypesCoproduct, f64);
-            properties.corner_radii.get_mut().bottom_left.set(new_value);
+            properties
+                .corner_radii
+                ._get_mut_untracked()
+                .bottom_left
+                .set(new_value);
         }
 
-        if let Some(corner_radii) =
-            rtc.compute_vtable_value(properties.corner_radii._get_vtable_id())
-        {
+        if let Some(corner_radii) = rtc.compute_vtable_value(
+            properties
+                .corner_radii
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(corner_radii, TypesCoproduct, RectangleCornerRadii);
             properties.corner_radii.set(new_value);
         }
@@ -142,37 +180,52 @@ impl<R: 'static + RenderContext> RenderNode<R> for RectangleInstance<R> {
         let bez_path = rect.to_path(0.1);
 
         let transformed_bez_path = transform * bez_path;
-        let duplicate_transformed_bez_path = transformed_bez_path.clone();
+        let stroke_bez_path = properties
+            .stroke
+            .get()
+            .get_aligned_rounded_rect((width, height), properties.corner_radii.get().into())
+            .to_path(0.1);
+        let transformed_stroke_bez_path = transform * stroke_bez_path;
 
         match properties.fill.get() {
             Fill::Solid(color) => {
-                rc.fill(transformed_bez_path, &color.to_piet_color());
+                rc.fill(
+                    transformed_bez_path,
+                    &color.to_piet_color_with_opacity(rtc.opacity),
+                );
             }
             Fill::LinearGradient(linear) => {
                 let linear_gradient = LinearGradient::new(
                     Fill::to_unit_point(linear.start, (width, height)),
                     Fill::to_unit_point(linear.end, (width, height)),
-                    Fill::to_piet_gradient_stops(linear.stops.clone()),
+                    Fill::to_piet_gradient_stops(linear.stops.clone(), rtc.opacity),
                 );
                 rc.fill(transformed_bez_path, &linear_gradient)
             }
             Fill::RadialGradient(radial) => {
                 let origin = Fill::to_unit_point(radial.start, (width, height));
                 let center = Fill::to_unit_point(radial.end, (width, height));
-                let gradient_stops = Fill::to_piet_gradient_stops(radial.stops.clone());
+                let gradient_stops =
+                    Fill::to_piet_gradient_stops(radial.stops.clone(), rtc.opacity);
                 let radial_gradient = RadialGradient::new(radial.radius, gradient_stops)
                     .with_center(center)
                     .with_origin(origin);
                 rc.fill(transformed_bez_path, &radial_gradient);
             }
+            Fill::None => {
+                //stroke-only: skip the fill pass entirely
+            }
         }
 
-        //hack to address "phantom stroke" bug on Web
-        let width: f64 = *&properties.stroke.get().width.get().into();
-        if width > f64::EPSILON {
+        if let Some(width) = properties.stroke.get().get_visible_width() {
             rc.stroke(
-                duplicate_transformed_bez_path,
-                &properties.stroke.get().color.get().to_piet_color(),
+                transformed_stroke_bez_path,
+                &properties
+                    .stroke
+                    .get()
+                    .color
+                    .get()
+                    .to_piet_color_with_opacity(rtc.opacity),
                 width,
             );
         }
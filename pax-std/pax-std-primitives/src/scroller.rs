@@ -9,8 +9,8 @@ use piet::RenderContext;
 
 use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
 use pax_core::{
-    unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable, RenderNode,
-    RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+    try_unwrap, unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable,
+    RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext,
 };
 use pax_message::{AnyCreatePatch, ScrollerPatch};
 use pax_runtime_api::{
@@ -274,47 +274,39 @@ impl<R: 'static + RenderContext> RenderNode<R> for ScrollerInstance<R> {
 
         let properties = &mut *self.properties.as_ref().borrow_mut();
 
-        if let Some(new_size) =
-            rtc.compute_vtable_value(properties.size_inner_pane_x._get_vtable_id())
-        {
-            let new_value = if let TypesCoproduct::Size(v) = new_size {
-                v
-            } else {
-                unreachable!()
-            };
+        if let Some(new_size) = rtc.compute_vtable_value(
+            properties
+                .size_inner_pane_x
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = try_unwrap!(new_size, TypesCoproduct, Size).unwrap();
             properties.size_inner_pane_x.set(new_value);
         }
 
-        if let Some(new_size) =
-            rtc.compute_vtable_value(properties.size_inner_pane_y._get_vtable_id())
-        {
-            let new_value = if let TypesCoproduct::Size(v) = new_size {
-                v
-            } else {
-                unreachable!()
-            };
+        if let Some(new_size) = rtc.compute_vtable_value(
+            properties
+                .size_inner_pane_y
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = try_unwrap!(new_size, TypesCoproduct, Size).unwrap();
             properties.size_inner_pane_y.set(new_value);
         }
 
-        if let Some(scroll_enabled_x) =
-            rtc.compute_vtable_value(properties.scroll_enabled_x._get_vtable_id())
-        {
-            let new_value = if let TypesCoproduct::bool(v) = scroll_enabled_x {
-                v
-            } else {
-                unreachable!()
-            };
+        if let Some(scroll_enabled_x) = rtc.compute_vtable_value(
+            properties
+                .scroll_enabled_x
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = try_unwrap!(scroll_enabled_x, TypesCoproduct, bool).unwrap();
             properties.scroll_enabled_x.set(new_value);
         }
 
-        if let Some(scroll_enabled_y) =
-            rtc.compute_vtable_value(properties.scroll_enabled_y._get_vtable_id())
-        {
-            let new_value = if let TypesCoproduct::bool(v) = scroll_enabled_y {
-                v
-            } else {
-                unreachable!()
-            };
+        if let Some(scroll_enabled_y) = rtc.compute_vtable_value(
+            properties
+                .scroll_enabled_y
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
+            let new_value = try_unwrap!(scroll_enabled_y, TypesCoproduct, bool).unwrap();
             properties.scroll_enabled_y.set(new_value);
         }
 
@@ -63,113 +63,228 @@ impl<R: 'static + RenderContext> RenderNode<R> for TextInstance<R> {
     fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
         let properties = &mut *self.properties.as_ref().borrow_mut();
 
-        if let Some(text) = rtc.compute_vtable_value(properties.text._get_vtable_id()) {
+        if let Some(text) = rtc.compute_vtable_value(
+            properties
+                .text
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(text, TypesCoproduct, String);
             properties.text.set(new_value);
         }
 
-        if let Some(style_font) =
-            rtc.compute_vtable_value(properties.style.get().font._get_vtable_id())
-        {
+        if let Some(style_font) = rtc.compute_vtable_value(
+            properties
+                .style
+                .get()
+                .font
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(style_font, TypesCoproduct, Font);
-            properties.style.get_mut().font.set(new_value);
+            properties.style._get_mut_untracked().font.set(new_value);
         }
 
-        if let Some(style_font_size) =
-            rtc.compute_vtable_value(properties.style.get().font_size._get_vtable_id())
-        {
+        if let Some(style_font_size) = rtc.compute_vtable_value(
+            properties
+                .style
+                .get()
+                .font_size
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(style_font_size, TypesCoproduct, SizePixels);
-            properties.style.get_mut().font_size.set(new_value);
+            properties
+                .style
+                ._get_mut_untracked()
+                .font_size
+                .set(new_value);
         }
 
-        if let Some(style_fill) =
-            rtc.compute_vtable_value(properties.style.get().fill._get_vtable_id())
-        {
+        if let Some(style_fill) = rtc.compute_vtable_value(
+            properties
+                .style
+                .get()
+                .fill
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(style_fill, TypesCoproduct, Color);
-            properties.style.get_mut().fill.set(new_value);
+            properties.style._get_mut_untracked().fill.set(new_value);
         }
 
-        if let Some(style_underline) =
-            rtc.compute_vtable_value(properties.style.get().underline._get_vtable_id())
-        {
+        if let Some(style_underline) = rtc.compute_vtable_value(
+            properties
+                .style
+                .get()
+                .underline
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(style_underline, TypesCoproduct, bool);
-            properties.style.get_mut().underline.set(new_value);
+            properties
+                .style
+                ._get_mut_untracked()
+                .underline
+                .set(new_value);
         }
 
-        if let Some(style_align_multiline) =
-            rtc.compute_vtable_value(properties.style.get().align_multiline._get_vtable_id())
-        {
+        if let Some(style_align_multiline) = rtc.compute_vtable_value(
+            properties
+                .style
+                .get()
+                .align_multiline
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value =
                 unsafe_unwrap!(style_align_multiline, TypesCoproduct, TextAlignHorizontal);
-            properties.style.get_mut().align_multiline.set(new_value);
+            properties
+                .style
+                ._get_mut_untracked()
+                .align_multiline
+                .set(new_value);
         }
 
-        if let Some(style_align_vertical) =
-            rtc.compute_vtable_value(properties.style.get().align_vertical._get_vtable_id())
-        {
+        if let Some(style_align_vertical) = rtc.compute_vtable_value(
+            properties
+                .style
+                .get()
+                .align_vertical
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(style_align_vertical, TypesCoproduct, TextAlignVertical);
-            properties.style.get_mut().align_vertical.set(new_value);
+            properties
+                .style
+                ._get_mut_untracked()
+                .align_vertical
+                .set(new_value);
         }
 
-        if let Some(style_align_horizontal) =
-            rtc.compute_vtable_value(properties.style.get().align_horizontal._get_vtable_id())
-        {
+        if let Some(style_align_horizontal) = rtc.compute_vtable_value(
+            properties
+                .style
+                .get()
+                .align_horizontal
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value =
                 unsafe_unwrap!(style_align_horizontal, TypesCoproduct, TextAlignHorizontal);
-            properties.style.get_mut().align_horizontal.set(new_value);
+            properties
+                .style
+                ._get_mut_untracked()
+                .align_horizontal
+                .set(new_value);
         }
 
-        if let Some(style_link) = rtc.compute_vtable_value(properties.style_link._get_vtable_id()) {
+        if let Some(style_link) = rtc.compute_vtable_value(
+            properties
+                .style_link
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(style_link, TypesCoproduct, TextStyle);
             properties.style_link.set(new_value);
         }
 
-        let style_link = properties.style_link.get_mut();
-        if let Some(style_font) = rtc.compute_vtable_value(style_link.font._get_vtable_id()) {
+        if let Some(style_font) = rtc.compute_vtable_value(
+            properties
+                .style_link
+                .get()
+                .font
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(style_font, TypesCoproduct, Font);
-            style_link.font.set(new_value);
+            properties
+                .style_link
+                ._get_mut_untracked()
+                .font
+                .set(new_value);
         }
 
-        if let Some(style_font_size) =
-            rtc.compute_vtable_value(style_link.font_size._get_vtable_id())
-        {
+        if let Some(style_font_size) = rtc.compute_vtable_value(
+            properties
+                .style_link
+                .get()
+                .font_size
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(style_font_size, TypesCoproduct, SizePixels);
-            style_link.font_size.set(new_value);
+            properties
+                .style_link
+                ._get_mut_untracked()
+                .font_size
+                .set(new_value);
         }
 
-        if let Some(style_fill) = rtc.compute_vtable_value(style_link.fill._get_vtable_id()) {
+        if let Some(style_fill) = rtc.compute_vtable_value(
+            properties
+                .style_link
+                .get()
+                .fill
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(style_fill, TypesCoproduct, Color);
-            style_link.fill.set(new_value);
+            properties
+                .style_link
+                ._get_mut_untracked()
+                .fill
+                .set(new_value);
         }
 
-        if let Some(style_underline) =
-            rtc.compute_vtable_value(style_link.underline._get_vtable_id())
-        {
+        if let Some(style_underline) = rtc.compute_vtable_value(
+            properties
+                .style_link
+                .get()
+                .underline
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(style_underline, TypesCoproduct, bool);
-            style_link.underline.set(new_value);
+            properties
+                .style_link
+                ._get_mut_untracked()
+                .underline
+                .set(new_value);
         }
 
-        if let Some(style_align_multiline) =
-            rtc.compute_vtable_value(style_link.align_multiline._get_vtable_id())
-        {
+        if let Some(style_align_multiline) = rtc.compute_vtable_value(
+            properties
+                .style_link
+                .get()
+                .align_multiline
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value =
                 unsafe_unwrap!(style_align_multiline, TypesCoproduct, TextAlignHorizontal);
-            style_link.align_multiline.set(new_value);
+            properties
+                .style_link
+                ._get_mut_untracked()
+                .align_multiline
+                .set(new_value);
         }
 
-        if let Some(style_align_vertical) =
-            rtc.compute_vtable_value(style_link.align_vertical._get_vtable_id())
-        {
+        if let Some(style_align_vertical) = rtc.compute_vtable_value(
+            properties
+                .style_link
+                .get()
+                .align_vertical
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value = unsafe_unwrap!(style_align_vertical, TypesCoproduct, TextAlignVertical);
-            style_link.align_vertical.set(new_value);
+            properties
+                .style_link
+                ._get_mut_untracked()
+                .align_vertical
+                .set(new_value);
         }
 
-        if let Some(style_align_horizontal) =
-            rtc.compute_vtable_value(style_link.align_horizontal._get_vtable_id())
-        {
+        if let Some(style_align_horizontal) = rtc.compute_vtable_value(
+            properties
+                .style_link
+                .get()
+                .align_horizontal
+                ._get_dirty_vtable_id(rtc.engine.frames_elapsed),
+        ) {
             let new_value =
                 unsafe_unwrap!(style_align_horizontal, TypesCoproduct, TextAlignHorizontal);
-            style_link.align_horizontal.set(new_value);
+            properties
+                .style_link
+                ._get_mut_untracked()
+                .align_horizontal
+                .set(new_value);
         }
 
         self.common_properties.compute_properties(rtc);
@@ -268,6 +383,17 @@ impl<R: 'static + RenderContext> RenderNode<R> for TextInstance<R> {
             has_any_updates = true;
         }
 
+        let val = rtc.opacity;
+        let is_new_value = match &last_patch.opacity {
+            Some(cached_value) => !val.eq(cached_value),
+            None => true,
+        };
+        if is_new_value {
+            new_message.opacity = Some(val);
+            last_patch.opacity = Some(val);
+            has_any_updates = true;
+        }
+
         if has_any_updates {
             (*rtc.engine.runtime)
                 .borrow_mut()
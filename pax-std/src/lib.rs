@@ -9,14 +9,24 @@ pub mod components {
 
 pub mod primitives {
     use pax_lang::Pax;
-    use pax_runtime_api::Size;
+    use pax_runtime_api::{Rotation, Size};
 
     use crate::types::text::TextStyle;
     use crate::types::PathSegment;
 
     #[derive(Pax)]
+    #[custom(Imports)]
     #[primitive("pax_std_primitives::frame::FrameInstance")]
-    pub struct Frame {}
+    pub struct Frame {
+        /// How content extending beyond this frame's bounds is handled.  Defaults to clipping.
+        pub overflow: pax_lang::Property<crate::types::FrameOverflow>,
+        /// Whether content scrolls horizontally, when `overflow` is `FrameOverflow::Scroll`
+        pub scroll_enabled_x: pax_lang::Property<bool>,
+        /// Whether content scrolls vertically, when `overflow` is `FrameOverflow::Scroll`
+        pub scroll_enabled_y: pax_lang::Property<bool>,
+        /// Whether scrolling continues with decaying speed after `@scroll` events stop arriving
+        pub scroll_momentum: pax_lang::Property<bool>,
+    }
 
     #[derive(Pax)]
     #[custom(Imports)]
@@ -48,6 +58,15 @@ pub mod primitives {
     pub struct Ellipse {
         pub stroke: pax_lang::Property<crate::types::Stroke>,
         pub fill: pax_lang::Property<crate::types::Color>,
+        /// Angle at which the drawn arc begins, measured clockwise from the positive x-axis.
+        pub start_angle: pax_lang::Property<Rotation>,
+        /// Angle at which the drawn arc ends.  When equal to `start_angle` (the default),
+        /// the full ellipse is drawn.
+        pub end_angle: pax_lang::Property<Rotation>,
+        /// Radius of the hollow center, evaluated against the ellipse's radii.  When greater
+        /// than zero, arcs are drawn as donut segments rather than pie slices.
+        #[pax(default = "Size::Pixels(0.into())")]
+        pub inner_radius: pax_lang::Property<Size>,
     }
 
     #[derive(Pax)]
@@ -56,7 +75,7 @@ pub mod primitives {
     pub struct Path {
         pub segments: pax_lang::Property<Vec<PathSegment>>,
         pub stroke: pax_lang::Property<crate::types::Stroke>,
-        pub fill: pax_lang::Property<crate::types::Color>,
+        pub fill: pax_lang::Property<crate::types::Fill>,
     }
 
     #[derive(Pax)]
@@ -73,5 +92,15 @@ pub mod primitives {
     #[primitive("pax_std_primitives::image::ImageInstance")]
     pub struct Image {
         pub path: pax_lang::Property<String>,
+        /// How the image is sized within its bounds; stretches to fill them by default
+        pub fit: pax_lang::Property<crate::types::ImageFit>,
+        /// How the image is resampled when scaled; bilinear by default
+        pub interpolation: pax_lang::Property<crate::types::ImageInterpolation>,
+        /// Solid color filling the image's bounds until `path` has loaded, e.g.
+        /// `placeholder_color={Some(Color::rgb(90%, 90%, 90%))}`; nothing is drawn by default
+        pub placeholder_color: pax_lang::Property<Option<crate::types::Color>>,
+        /// Fallback image drawn (over `placeholder_color`, according to `fit`) until `path` has loaded;
+        /// none by default
+        pub placeholder_path: pax_lang::Property<String>,
     }
 }
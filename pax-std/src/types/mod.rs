@@ -1,7 +1,7 @@
 pub mod text;
 
 use crate::primitives::Path;
-use kurbo::{Point, RoundedRectRadii};
+use kurbo::{Point, Rect, RoundedRect, RoundedRectRadii};
 use pax_lang::api::numeric::Numeric;
 pub use pax_lang::api::Size;
 use pax_lang::api::{PropertyLiteral, SizePixels};
@@ -14,6 +14,7 @@ use piet::UnitPoint;
 pub struct Stroke {
     pub color: Property<Color>,
     pub width: Property<SizePixels>,
+    pub align: Property<StrokeAlign>,
 }
 
 impl Default for Stroke {
@@ -21,6 +22,160 @@ impl Default for Stroke {
         Self {
             color: Default::default(),
             width: Box::new(PropertyLiteral::new(SizePixels(0.0.into()))),
+            align: Default::default(),
+        }
+    }
+}
+
+impl Stroke {
+    /// A stroke that is never drawn; equivalent to the default, zero-width stroke
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Returns the width to stroke with, or `None` if this stroke has no width,
+    /// in which case shapes skip their stroke pass entirely rather than drawing a hairline
+    pub fn get_visible_width(&self) -> Option<f64> {
+        let width: f64 = self.width.get().into();
+        (width > f64::EPSILON).then_some(width)
+    }
+
+    /// Returns the rounded rect to stroke for a `width` x `height` rectangle with the given corner `radii`,
+    /// inset or outset by half the stroke width according to `align`.  Radii are adjusted by the same amount
+    /// so that corners stay concentric with the shape's own.
+    pub fn get_aligned_rounded_rect(
+        &self,
+        (width, height): (f64, f64),
+        radii: RoundedRectRadii,
+    ) -> RoundedRect {
+        let stroke_width: f64 = self.width.get().into();
+        let outset = self.align.get().get_geometry_outset(stroke_width);
+        let adjust = |radius: f64| (radius + outset).max(0.0);
+        RoundedRect::new(
+            -outset,
+            -outset,
+            width + outset,
+            height + outset,
+            RoundedRectRadii::new(
+                adjust(radii.top_left),
+                adjust(radii.top_right),
+                adjust(radii.bottom_right),
+                adjust(radii.bottom_left),
+            ),
+        )
+    }
+}
+
+/// Where a stroke is drawn relative to the edge of a shape.
+/// Currently honored by `Rectangle`; other shapes' strokes are always centered.
+#[derive(Pax)]
+#[custom(Imports)]
+pub enum StrokeAlign {
+    /// Drawn entirely within the shape's bounds
+    Inside,
+    /// Straddles the shape's edge, half inside and half outside
+    #[default]
+    Center,
+    /// Drawn entirely outside the shape's bounds
+    Outside,
+}
+
+impl StrokeAlign {
+    /// Distance by which to outset a shape's geometry before stroking it with a stroke of `stroke_width`,
+    /// so that the stroke lands with this alignment.  Negative values inset the geometry.
+    pub fn get_geometry_outset(&self, stroke_width: f64) -> f64 {
+        match self {
+            StrokeAlign::Inside => -stroke_width / 2.0,
+            StrokeAlign::Center => 0.0,
+            StrokeAlign::Outside => stroke_width / 2.0,
+        }
+    }
+}
+
+/// How a `Frame` handles content that extends beyond its bounds.
+#[derive(Pax)]
+#[custom(Imports)]
+pub enum FrameOverflow {
+    /// Content is rendered in full, unclipped
+    Visible,
+    /// Content is clipped to the frame's bounds
+    #[default]
+    Hidden,
+    /// Content is clipped to the frame's bounds and translated by a scroll offset accumulated
+    /// from `@scroll` events, along the axes enabled by `scroll_enabled_x` and `scroll_enabled_y`
+    Scroll,
+}
+
+/// How an `Image` is sized and positioned within its bounds, after CSS's `object-fit`.
+#[derive(Pax)]
+#[custom(Imports)]
+pub enum ImageFit {
+    /// Stretched to exactly cover the bounds, ignoring the image's aspect ratio
+    #[default]
+    Fill,
+    /// Scaled to fit entirely within the bounds, preserving aspect ratio
+    Contain,
+    /// Scaled to cover the bounds, preserving aspect ratio; overflow is cropped
+    Cover,
+    /// Drawn at its intrinsic size, centered; overflow is cropped
+    None,
+    /// The smaller of `None` and `Contain`
+    ScaleDown,
+}
+
+impl ImageFit {
+    /// Computes `(source, destination)` rects for drawing an image of intrinsic `image_size` into `bounds`.
+    /// The source rect is in image pixels; the destination rect is in the (untransformed) space of `bounds`
+    /// and never exceeds it.
+    pub fn get_image_rects(&self, image_size: (f64, f64), bounds: (f64, f64)) -> (Rect, Rect) {
+        let (image_width, image_height) = image_size;
+        let (bounds_width, bounds_height) = bounds;
+        let full_bounds = Rect::new(0.0, 0.0, bounds_width, bounds_height);
+        if image_width <= 0.0 || image_height <= 0.0 {
+            return (Rect::new(0.0, 0.0, image_width, image_height), full_bounds);
+        }
+
+        let contain_scale = (bounds_width / image_width).min(bounds_height / image_height);
+        let scale = match self {
+            ImageFit::Fill => return (Rect::new(0.0, 0.0, image_width, image_height), full_bounds),
+            ImageFit::Contain => contain_scale,
+            ImageFit::Cover => (bounds_width / image_width).max(bounds_height / image_height),
+            ImageFit::None => 1.0,
+            ImageFit::ScaleDown => contain_scale.min(1.0),
+        };
+
+        // The scaled image, centered within the bounds, then clipped to them
+        let scaled = Rect::from_center_size(
+            full_bounds.center(),
+            (image_width * scale, image_height * scale),
+        );
+        let destination = scaled.intersect(full_bounds);
+        let source = Rect::new(
+            (destination.x0 - scaled.x0) / scale,
+            (destination.y0 - scaled.y0) / scale,
+            (destination.x1 - scaled.x0) / scale,
+            (destination.y1 - scaled.y0) / scale,
+        );
+        (source, destination)
+    }
+}
+
+/// How an `Image` is resampled when drawn at a size other than its intrinsic size
+#[derive(Pax)]
+#[custom(Imports)]
+pub enum ImageInterpolation {
+    /// Uses the nearest pixel, keeping edges crisp (e.g. for pixel art)
+    NearestNeighbor,
+    /// Blends neighboring pixels for smooth scaling
+    #[default]
+    Bilinear,
+}
+
+impl ImageInterpolation {
+    pub fn to_piet_interpolation_mode(&self) -> piet::InterpolationMode {
+        match self {
+            ImageInterpolation::NearestNeighbor => piet::InterpolationMode::NearestNeighbor,
+            ImageInterpolation::Bilinear => piet::InterpolationMode::Bilinear,
         }
     }
 }
@@ -56,6 +211,9 @@ pub enum Fill {
     Solid(Color),
     LinearGradient(LinearGradient),
     RadialGradient(RadialGradient),
+    /// No fill: only the stroke is drawn, e.g. for outline-only shapes.
+    /// Unlike a transparent `Solid`, this skips the fill pass entirely.
+    None,
 }
 
 #[derive(Pax)]
@@ -105,12 +263,17 @@ impl Fill {
         let normalized_y = match y {
             Size::Pixels(val) => val.get_as_float() / height,
             Size::Percent(val) => val.get_as_float() / 100.0,
-            Size::Combined(pix, per) => (pix.get_as_float() / width) + (per.get_as_float() / 100.0),
+            Size::Combined(pix, per) => {
+                (pix.get_as_float() / height) + (per.get_as_float() / 100.0)
+            }
         };
         UnitPoint::new(normalized_x, normalized_y)
     }
 
-    pub fn to_piet_gradient_stops(stops: Vec<GradientStop>) -> Vec<piet::GradientStop> {
+    pub fn to_piet_gradient_stops(
+        stops: Vec<GradientStop>,
+        opacity: f64,
+    ) -> Vec<piet::GradientStop> {
         let mut ret = Vec::new();
         for gradient_stop in stops {
             match gradient_stop.position {
@@ -120,7 +283,7 @@ impl Fill {
                 Size::Percent(p) => {
                     ret.push(piet::GradientStop {
                         pos: (p.get_as_float() / 100.0) as f32,
-                        color: gradient_stop.color.to_piet_color(),
+                        color: gradient_stop.color.to_piet_color_with_opacity(opacity),
                     });
                 }
                 Size::Combined(_, _) => {
@@ -193,6 +356,40 @@ impl Color {
             ColorVariant::Rgb(slice) => piet::Color::rgb(slice[0], slice[1], slice[2]),
         }
     }
+
+    /// Returns this color as a `piet::Color`, with its alpha channel multiplied by `opacity`
+    pub fn to_piet_color_with_opacity(&self, opacity: f64) -> piet::Color {
+        let (r, g, b, a) = self.to_piet_color().as_rgba();
+        piet::Color::rgba(r, g, b, a * opacity)
+    }
+
+    /// Returns a copy of this color with its alpha channel set to `a`, e.g. `self.fill.with_alpha(0.5)`
+    /// within an expression
+    pub fn with_alpha(&self, a: Numeric) -> Self {
+        let (r, g, b, _) = self.to_piet_color().as_rgba();
+        Self {
+            color_variant: ColorVariant::Rgba([r, g, b, a.get_as_float()]),
+        }
+    }
+
+    /// Returns a copy of this color mixed toward white by `amount`, from `0` (unchanged) to `1` (white)
+    pub fn lighten(&self, amount: Numeric) -> Self {
+        let amount = amount.get_as_float().clamp(0.0, 1.0);
+        self.map_rgb(|channel| channel + (1.0 - channel) * amount)
+    }
+
+    /// Returns a copy of this color mixed toward black by `amount`, from `0` (unchanged) to `1` (black)
+    pub fn darken(&self, amount: Numeric) -> Self {
+        let amount = amount.get_as_float().clamp(0.0, 1.0);
+        self.map_rgb(|channel| channel * (1.0 - amount))
+    }
+
+    fn map_rgb(&self, f: impl Fn(f64) -> f64) -> Self {
+        let (r, g, b, a) = self.to_piet_color().as_rgba();
+        Self {
+            color_variant: ColorVariant::Rgba([f(r), f(g), f(b), a]),
+        }
+    }
 }
 
 impl Default for Color {
@@ -354,3 +551,134 @@ impl RectangleCornerRadii {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Color, Fill, ImageFit, Stroke, StrokeAlign};
+    use kurbo::{Rect, RoundedRectRadii, Shape};
+    use pax_lang::api::{Numeric, PropertyLiteral, Size, SizePixels};
+
+    fn stroke(width: f64, align: StrokeAlign) -> Stroke {
+        Stroke {
+            color: Default::default(),
+            width: Box::new(PropertyLiteral::new(SizePixels(width.into()))),
+            align: Box::new(PropertyLiteral::new(align)),
+        }
+    }
+
+    /// Visual bounds of a stroke: its geometry, grown by half the stroke width on every side
+    fn visual_bounds(stroke: &Stroke, size: (f64, f64)) -> (f64, f64, f64, f64) {
+        let rect = stroke
+            .get_aligned_rounded_rect(size, RoundedRectRadii::from_single_radius(0.0))
+            .bounding_box();
+        let half_width: f64 = Into::<f64>::into(stroke.width.get()) / 2.0;
+        let visual = rect.inflate(half_width, half_width);
+        (visual.x0, visual.y0, visual.x1, visual.y1)
+    }
+
+    #[test]
+    fn test_fill_to_unit_point_combined() {
+        let combined = || Size::Combined(Numeric::from(10.0), Numeric::from(50.0));
+        let point = Fill::to_unit_point((combined(), combined()), (100.0, 20.0))
+            .resolve(Rect::new(0.0, 0.0, 1.0, 1.0));
+        assert_eq!((point.x, point.y), (0.6, 1.0));
+    }
+
+    #[test]
+    fn test_stroke_align_visual_bounds() {
+        assert_eq!(
+            visual_bounds(&stroke(10.0, StrokeAlign::Inside), (100.0, 100.0)),
+            (0.0, 0.0, 100.0, 100.0)
+        );
+        assert_eq!(
+            visual_bounds(&stroke(10.0, StrokeAlign::Center), (100.0, 100.0)),
+            (-5.0, -5.0, 105.0, 105.0)
+        );
+        assert_eq!(
+            visual_bounds(&stroke(10.0, StrokeAlign::Outside), (100.0, 100.0)),
+            (-10.0, -10.0, 110.0, 110.0)
+        );
+    }
+
+    #[test]
+    fn test_stroke_visible_width() {
+        assert_eq!(Stroke::none().get_visible_width(), None);
+        assert_eq!(stroke(0.0, StrokeAlign::Center).get_visible_width(), None);
+        assert_eq!(
+            stroke(0.5, StrokeAlign::Center).get_visible_width(),
+            Some(0.5)
+        );
+    }
+
+    #[test]
+    fn test_stroke_align_center_matches_geometry() {
+        let radii = RoundedRectRadii::new(1.0, 2.0, 3.0, 4.0);
+        let rect = stroke(10.0, StrokeAlign::Center).get_aligned_rounded_rect((100.0, 50.0), radii);
+        assert_eq!(rect.rect(), kurbo::Rect::new(0.0, 0.0, 100.0, 50.0));
+        assert_eq!(rect.radii(), radii);
+    }
+
+    #[test]
+    fn test_image_fit_rects() {
+        // A 200x100 image drawn into 100x100 bounds
+        let rects = |fit: ImageFit| fit.get_image_rects((200.0, 100.0), (100.0, 100.0));
+
+        assert_eq!(
+            rects(ImageFit::Fill),
+            (
+                Rect::new(0.0, 0.0, 200.0, 100.0),
+                Rect::new(0.0, 0.0, 100.0, 100.0)
+            )
+        );
+        assert_eq!(
+            rects(ImageFit::Contain),
+            (
+                Rect::new(0.0, 0.0, 200.0, 100.0),
+                Rect::new(0.0, 25.0, 100.0, 75.0)
+            )
+        );
+        assert_eq!(
+            rects(ImageFit::Cover),
+            (
+                Rect::new(50.0, 0.0, 150.0, 100.0),
+                Rect::new(0.0, 0.0, 100.0, 100.0)
+            )
+        );
+        assert_eq!(
+            rects(ImageFit::None),
+            (
+                Rect::new(50.0, 0.0, 150.0, 100.0),
+                Rect::new(0.0, 0.0, 100.0, 100.0)
+            )
+        );
+        assert_eq!(rects(ImageFit::ScaleDown), rects(ImageFit::Contain));
+
+        // ScaleDown never enlarges an image smaller than its bounds
+        assert_eq!(
+            ImageFit::ScaleDown.get_image_rects((50.0, 20.0), (100.0, 100.0)),
+            (
+                Rect::new(0.0, 0.0, 50.0, 20.0),
+                Rect::new(25.0, 40.0, 75.0, 60.0)
+            )
+        );
+    }
+
+    #[test]
+    fn test_color_adjustments() {
+        // piet stores 8 bits per channel, so compare channels to two decimal places
+        let rgba = |color: Color| {
+            let (r, g, b, a) = color.to_piet_color().as_rgba();
+            let round = |channel: f64| (channel * 100.0).round() / 100.0;
+            (round(r), round(g), round(b), round(a))
+        };
+        let color = Color::rgb(0.2.into(), 0.4.into(), 1.into());
+
+        assert_eq!(rgba(color.with_alpha(0.5.into())), (0.2, 0.4, 1.0, 0.5));
+        assert_eq!(
+            rgba(color.with_alpha(0.5.into()).lighten(0.5.into())),
+            (0.6, 0.7, 1.0, 0.5)
+        );
+        assert_eq!(rgba(color.darken(0.5.into())), (0.1, 0.2, 0.5, 1.0));
+        assert_eq!(rgba(color.darken(2.into())), (0.0, 0.0, 0.0, 1.0));
+    }
+}